@@ -3,6 +3,9 @@ use crate::error::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// 单次批量更新块请求最多包含的更新操作数
+pub const MAX_UPDATES_PER_REQUEST: usize = 200;
+
 #[derive(Debug, Serialize)]
 pub struct BatchUpdateBlocksRequest {
     pub requests: Vec<UpdateBlockRequest>,
@@ -521,4 +524,38 @@ impl BatchUpdateBlocksApi {
 
         Ok(data)
     }
-}
\ No newline at end of file
+
+    /// 批量更新任意数量的块，超过单次请求上限时按顺序拆分为多次请求
+    ///
+    /// 后续请求以上一次请求返回的文档版本为基准，幂等令牌只用于第一次请求；
+    /// 返回合并后的块列表和最后一次请求的文档版本。
+    pub async fn batch_update_all(
+        &self,
+        document_id: &str,
+        mut requests: Vec<UpdateBlockRequest>,
+        document_revision_id: Option<i32>,
+        client_token: Option<String>,
+        user_id_type: Option<String>,
+    ) -> Result<BatchUpdateBlocksResponse> {
+        let mut response = BatchUpdateBlocksResponse {
+            blocks: Vec::new(),
+            client_token: client_token.clone(),
+            document_revision_id: document_revision_id.unwrap_or(-1),
+        };
+
+        let mut revision_id = document_revision_id;
+        let mut client_token = client_token;
+        while !requests.is_empty() {
+            let rest = requests.split_off(requests.len().min(MAX_UPDATES_PER_REQUEST));
+            let batch = self
+                .batch_update_blocks(document_id, requests, revision_id, client_token.take(), user_id_type.clone())
+                .await?;
+            response.blocks.extend(batch.blocks);
+            response.document_revision_id = batch.document_revision_id;
+            revision_id = Some(batch.document_revision_id);
+            requests = rest;
+        }
+
+        Ok(response)
+    }
+}
@@ -0,0 +1,306 @@
+use super::DescendantBlock;
+use crate::error::{LarkError, Result};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// 单次创建嵌套块请求最多包含的块数
+pub const MAX_BLOCKS_PER_REQUEST: usize = 1000;
+/// 单次创建嵌套块请求中块的最大嵌套层数（一级块为第 1 层）
+pub const MAX_NESTING_DEPTH: usize = 10;
+
+/// 表格（31）和分栏（24）的直接子块必须与父块在同一次请求中创建
+const ATOMIC_BLOCK_TYPES: [i32; 2] = [24, 31];
+
+/// 单次创建嵌套块请求的限制
+#[derive(Debug, Clone, Copy)]
+pub struct BatchLimits {
+    /// 单次请求最多创建的块数
+    pub max_blocks: usize,
+    /// 单次请求中块的最大嵌套层数
+    pub max_depth: usize,
+}
+
+impl Default for BatchLimits {
+    fn default() -> Self {
+        Self {
+            max_blocks: MAX_BLOCKS_PER_REQUEST,
+            max_depth: MAX_NESTING_DEPTH,
+        }
+    }
+}
+
+/// 一次创建请求的父块
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", content = "temporary_block_id", rename_all = "snake_case")]
+pub enum BatchParent {
+    /// 导入的目标父块
+    Target,
+    /// 之前某次请求中创建的块（临时块 ID，执行时替换为实际块 ID）
+    Created(String),
+}
+
+/// 一次创建嵌套块请求
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateBatch {
+    /// 父块
+    pub parent: BatchParent,
+    /// 插入位置
+    pub index: i32,
+    /// 本次请求的一级块临时 ID
+    pub children_id: Vec<String>,
+    /// 本次请求创建的所有块
+    pub descendants: Vec<DescendantBlock>,
+}
+
+/// 需要在后续请求中追加的子块：(父块临时 ID, 子块临时 ID 列表)
+type Deferred = Vec<(String, Vec<String>)>;
+
+/// 正在填充的请求，以及依赖其中块的后续追加任务
+struct PendingBatch {
+    children_id: Vec<String>,
+    descendants: Vec<DescendantBlock>,
+    deferred: Deferred,
+}
+
+impl PendingBatch {
+    fn new() -> Self {
+        Self {
+            children_id: Vec::new(),
+            descendants: Vec::new(),
+            deferred: Vec::new(),
+        }
+    }
+}
+
+/// 块分批规划器
+///
+/// 按一级子树整体打包请求，保证同一棵子树的块不会被拆到父块所在请求之外；
+/// 超过限制的子树先创建父块和能放下的子块，剩余子块在后续请求中追加到已创建的父块下。
+pub struct BlockBatcher {
+    blocks: HashMap<String, DescendantBlock>,
+    /// 每个块的子树块数和子树深度
+    stats: HashMap<String, (usize, usize)>,
+    limits: BatchLimits,
+}
+
+impl BlockBatcher {
+    /// 根据所有待创建的块构建规划器
+    pub fn new(descendants: Vec<DescendantBlock>, limits: BatchLimits) -> Result<Self> {
+        if limits.max_blocks == 0 || limits.max_depth == 0 {
+            return Err(LarkError::ValidationError(
+                "分批限制的块数和嵌套层数必须大于 0".to_string()
+            ));
+        }
+
+        let blocks: HashMap<String, DescendantBlock> = descendants
+            .into_iter()
+            .map(|block| (block.block_id.clone(), block))
+            .collect();
+
+        let mut batcher = Self {
+            blocks,
+            stats: HashMap::new(),
+            limits,
+        };
+
+        let ids: Vec<String> = batcher.blocks.keys().cloned().collect();
+        for id in ids {
+            batcher.compute_stats(&id, &mut HashSet::new())?;
+        }
+
+        Ok(batcher)
+    }
+
+    /// 规划创建请求，`index` 为一级块在目标父块中的起始插入位置
+    pub fn plan(&self, children_id: &[String], index: i32) -> Result<Vec<CreateBatch>> {
+        let mut batches = Vec::new();
+        self.plan_children(BatchParent::Target, children_id, index, &mut batches)?;
+        Ok(batches)
+    }
+
+    fn compute_stats(&mut self, id: &str, visiting: &mut HashSet<String>) -> Result<(usize, usize)> {
+        if let Some(stats) = self.stats.get(id) {
+            return Ok(*stats);
+        }
+
+        if !visiting.insert(id.to_string()) {
+            return Err(LarkError::ValidationError(format!("块 {} 的子块存在循环引用", id)));
+        }
+
+        let mut size = 1;
+        let mut depth = 0;
+        for child in self.children(id) {
+            let (child_size, child_depth) = self.compute_stats(&child, visiting)?;
+            size += child_size;
+            depth = depth.max(child_depth);
+        }
+
+        visiting.remove(id);
+        self.stats.insert(id.to_string(), (size, depth + 1));
+        Ok((size, depth + 1))
+    }
+
+    /// 块的子块 ID（忽略不在待创建列表中的引用）
+    fn children(&self, id: &str) -> Vec<String> {
+        self.blocks
+            .get(id)
+            .and_then(|block| block.children.as_ref())
+            .map(|children| {
+                children
+                    .iter()
+                    .filter(|child| self.blocks.contains_key(*child))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn fits(&self, id: &str, budget: usize, depth_left: usize) -> bool {
+        self.stats
+            .get(id)
+            .is_some_and(|&(size, depth)| size <= budget && depth <= depth_left)
+    }
+
+    fn plan_children(
+        &self,
+        parent: BatchParent,
+        child_ids: &[String],
+        index: i32,
+        batches: &mut Vec<CreateBatch>,
+    ) -> Result<()> {
+        let mut created = 0;
+        let mut current = PendingBatch::new();
+
+        for id in child_ids {
+            if !self.blocks.contains_key(id) {
+                return Err(LarkError::ValidationError(format!("一级块 {} 不在待创建的块列表中", id)));
+            }
+
+            if self.fits(id, self.limits.max_blocks, self.limits.max_depth) {
+                if !self.fits(id, self.limits.max_blocks - current.descendants.len(), self.limits.max_depth) {
+                    self.flush(&parent, index, &mut created, &mut current, batches)?;
+                }
+                current.children_id.push(id.clone());
+                self.collect_subtree(id, &mut current.descendants);
+            } else {
+                // 子树超过单次请求限制，单独开始一个请求并拆分
+                if !current.children_id.is_empty() {
+                    self.flush(&parent, index, &mut created, &mut current, batches)?;
+                }
+                let (blocks, deferred) = self.take(id, self.limits.max_blocks, self.limits.max_depth)?;
+                current.children_id.push(id.clone());
+                current.descendants.extend(blocks);
+                current.deferred.extend(deferred);
+            }
+        }
+
+        if !current.children_id.is_empty() {
+            self.flush(&parent, index, &mut created, &mut current, batches)?;
+        }
+
+        Ok(())
+    }
+
+    /// 输出当前请求，然后规划依赖它的后续追加请求
+    fn flush(
+        &self,
+        parent: &BatchParent,
+        index: i32,
+        created: &mut i32,
+        current: &mut PendingBatch,
+        batches: &mut Vec<CreateBatch>,
+    ) -> Result<()> {
+        let batch = std::mem::replace(current, PendingBatch::new());
+
+        // 非负索引需要随已创建的一级块数后移；负数索引相对末尾，保持不变即可保证顺序
+        let batch_index = if index >= 0 { index + *created } else { index };
+        *created += batch.children_id.len() as i32;
+
+        batches.push(CreateBatch {
+            parent: parent.clone(),
+            index: batch_index,
+            children_id: batch.children_id,
+            descendants: batch.descendants,
+        });
+
+        for (deferred_parent, children) in batch.deferred {
+            self.plan_children(BatchParent::Created(deferred_parent), &children, -1, batches)?;
+        }
+
+        Ok(())
+    }
+
+    /// 按先序收集完整子树
+    fn collect_subtree(&self, id: &str, out: &mut Vec<DescendantBlock>) {
+        if let Some(block) = self.blocks.get(id) {
+            let mut block = block.clone();
+            let children = self.children(id);
+            block.children = Some(children.clone());
+            out.push(block);
+            for child in &children {
+                self.collect_subtree(child, out);
+            }
+        }
+    }
+
+    /// 在块数和层数预算内尽量多地取出子树，返回取出的块和需要后续追加的子块
+    fn take(
+        &self,
+        id: &str,
+        budget: usize,
+        depth_left: usize,
+    ) -> Result<(Vec<DescendantBlock>, Deferred)> {
+        let mut block = self.blocks[id].clone();
+        let children = self.children(id);
+        let atomic = ATOMIC_BLOCK_TYPES.contains(&block.block_type);
+
+        let mut taken = Vec::new();
+        let mut included = Vec::new();
+        let mut deferred = Vec::new();
+        let mut budget = budget - 1;
+
+        if atomic && !children.is_empty() && (depth_left < 2 || budget < children.len()) {
+            return Err(LarkError::ValidationError(format!(
+                "块 {}（类型 {}）有 {} 个直接子块，超过单次请求的块数或层数限制，无法拆分",
+                id,
+                block.block_type,
+                children.len()
+            )));
+        }
+
+        for (i, child) in children.iter().enumerate() {
+            // 表格和分栏需要为后面的兄弟块各预留一个块的预算
+            let reserve = if atomic { children.len() - i - 1 } else { 0 };
+
+            if self.fits(child, budget - reserve, depth_left - 1) {
+                budget -= self.stats[child].0;
+                included.push(child.clone());
+                self.collect_subtree(child, &mut taken);
+            } else if atomic {
+                let (child_blocks, child_deferred) = self.take(child, budget - reserve, depth_left - 1)?;
+                budget -= child_blocks.len();
+                included.push(child.clone());
+                taken.extend(child_blocks);
+                deferred.extend(child_deferred);
+            } else if budget > 0 && depth_left > 1 {
+                // 放不下的子块也先拆分创建一部分，其后的兄弟块延后追加
+                let (child_blocks, child_deferred) = self.take(child, budget, depth_left - 1)?;
+                included.push(child.clone());
+                taken.extend(child_blocks);
+                deferred.extend(child_deferred);
+                if i + 1 < children.len() {
+                    deferred.push((id.to_string(), children[i + 1..].to_vec()));
+                }
+                break;
+            } else {
+                deferred.push((id.to_string(), children[i..].to_vec()));
+                break;
+            }
+        }
+
+        block.children = Some(included);
+        let mut blocks = vec![block];
+        blocks.extend(taken);
+        Ok((blocks, deferred))
+    }
+}
@@ -0,0 +1,750 @@
+use super::DescendantBlock;
+use crate::error::{LarkError, Result};
+use crate::utils::percent_encode;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy)]
+pub enum CalloutType {
+    Info,
+    Warning,
+    Error,
+    Success,
+}
+
+impl CalloutType {
+    /// 预设的背景色、边框色和默认图标
+    pub fn style(self) -> (CalloutBackgroundColor, FontColor, &'static str) {
+        match self {
+            CalloutType::Info => (CalloutBackgroundColor::LightOrange, FontColor::Orange, "sparkles"),
+            CalloutType::Warning => (CalloutBackgroundColor::LightYellow, FontColor::Yellow, "o"),
+            CalloutType::Error => (CalloutBackgroundColor::LightRed, FontColor::Red, "x"),
+            CalloutType::Success => (CalloutBackgroundColor::LightGreen, FontColor::Green, "white_check_mark"),
+        }
+    }
+}
+
+impl std::str::FromStr for CalloutType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "info" => Ok(CalloutType::Info),
+            "warning" | "warn" => Ok(CalloutType::Warning),
+            "error" | "danger" => Ok(CalloutType::Error),
+            "success" | "good" => Ok(CalloutType::Success),
+            _ => Err(format!("无效的高亮块类型: {}。可选值: info, warning, error, success", s)),
+        }
+    }
+}
+
+/// 构建包含一段文本的高亮块，返回高亮块的临时 ID 和所有待创建的块
+pub fn callout_blocks(
+    parent_id: &str,
+    content: &str,
+    callout_type: CalloutType,
+    icon: Option<String>,
+) -> (String, Vec<DescendantBlock>) {
+    // 生成临时块ID
+    let temp_callout_id = format!("temp-callout-{}", uuid::Uuid::new_v4());
+    let temp_text_id = format!("temp-text-{}", uuid::Uuid::new_v4());
+
+    // 根据类型获取颜色配置
+    let (bg_color, border_color, default_icon) = callout_type.style();
+
+    // 构建高亮块（父块）
+    let callout_block = DescendantBlock {
+        block_id: temp_callout_id.clone(),
+        block_type: 19, // BlockType::Callout 的值
+        parent_id: Some(parent_id.to_string()),
+        children: Some(vec![temp_text_id.clone()]), // 包含文本子块
+        callout: Some(json!({
+            "background_color": bg_color as i32,
+            "border_color": border_color as i32,
+            "emoji_id": icon.unwrap_or_else(|| default_icon.to_string())
+        })),
+        ..Default::default()
+    };
+
+    // 构建文本子块
+    let text_block = DescendantBlock {
+        block_id: temp_text_id,
+        block_type: 2, // BlockType::Text 的值
+        parent_id: Some(temp_callout_id.clone()), // 父块是高亮块
+        children: Some(vec![]),
+        text: Some(json!({
+            "elements": [{
+                "text_run": {
+                    "content": content
+                }
+            }]
+        })),
+        ..Default::default()
+    };
+
+    (temp_callout_id, vec![callout_block, text_block])
+}
+
+/// 高亮块背景色
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalloutBackgroundColor {
+    LightRed = 1,
+    LightOrange = 2,
+    LightYellow = 3,
+    LightGreen = 4,
+    LightBlue = 5,
+    LightPurple = 6,
+    MediumGray = 7,
+    MediumRed = 8,
+    MediumOrange = 9,
+    MediumYellow = 10,
+    MediumGreen = 11,
+    MediumBlue = 12,
+    MediumPurple = 13,
+    Gray = 14,
+    LightGray = 15,
+}
+
+impl CalloutBackgroundColor {
+    pub const ALL: [CalloutBackgroundColor; 15] = [
+        CalloutBackgroundColor::LightRed,
+        CalloutBackgroundColor::LightOrange,
+        CalloutBackgroundColor::LightYellow,
+        CalloutBackgroundColor::LightGreen,
+        CalloutBackgroundColor::LightBlue,
+        CalloutBackgroundColor::LightPurple,
+        CalloutBackgroundColor::MediumGray,
+        CalloutBackgroundColor::MediumRed,
+        CalloutBackgroundColor::MediumOrange,
+        CalloutBackgroundColor::MediumYellow,
+        CalloutBackgroundColor::MediumGreen,
+        CalloutBackgroundColor::MediumBlue,
+        CalloutBackgroundColor::MediumPurple,
+        CalloutBackgroundColor::Gray,
+        CalloutBackgroundColor::LightGray,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            CalloutBackgroundColor::LightRed => "light-red",
+            CalloutBackgroundColor::LightOrange => "light-orange",
+            CalloutBackgroundColor::LightYellow => "light-yellow",
+            CalloutBackgroundColor::LightGreen => "light-green",
+            CalloutBackgroundColor::LightBlue => "light-blue",
+            CalloutBackgroundColor::LightPurple => "light-purple",
+            CalloutBackgroundColor::MediumGray => "medium-gray",
+            CalloutBackgroundColor::MediumRed => "medium-red",
+            CalloutBackgroundColor::MediumOrange => "medium-orange",
+            CalloutBackgroundColor::MediumYellow => "medium-yellow",
+            CalloutBackgroundColor::MediumGreen => "medium-green",
+            CalloutBackgroundColor::MediumBlue => "medium-blue",
+            CalloutBackgroundColor::MediumPurple => "medium-purple",
+            CalloutBackgroundColor::Gray => "gray",
+            CalloutBackgroundColor::LightGray => "light-gray",
+        }
+    }
+}
+
+impl std::str::FromStr for CalloutBackgroundColor {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace('_', "-");
+        Self::ALL.into_iter().find(|c| c.name() == name).ok_or_else(|| {
+            let names: Vec<&str> = Self::ALL.iter().map(|c| c.name()).collect();
+            format!("无效的背景色: {}。可选值: {}", s, names.join(", "))
+        })
+    }
+}
+
+/// 字体颜色，也用作高亮块的边框色
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FontColor {
+    Red = 1,
+    Orange = 2,
+    Yellow = 3,
+    Green = 4,
+    Blue = 5,
+    Purple = 6,
+    Gray = 7,
+}
+
+impl FontColor {
+    pub const ALL: [FontColor; 7] = [
+        FontColor::Red,
+        FontColor::Orange,
+        FontColor::Yellow,
+        FontColor::Green,
+        FontColor::Blue,
+        FontColor::Purple,
+        FontColor::Gray,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FontColor::Red => "red",
+            FontColor::Orange => "orange",
+            FontColor::Yellow => "yellow",
+            FontColor::Green => "green",
+            FontColor::Blue => "blue",
+            FontColor::Purple => "purple",
+            FontColor::Gray => "gray",
+        }
+    }
+}
+
+impl std::str::FromStr for FontColor {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase();
+        Self::ALL.into_iter().find(|c| c.name() == name).ok_or_else(|| {
+            let names: Vec<&str> = Self::ALL.iter().map(|c| c.name()).collect();
+            format!("无效的颜色: {}。可选值: {}", s, names.join(", "))
+        })
+    }
+}
+
+/// 内嵌网页（iframe）的来源类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IframeType {
+    Bilibili = 1,
+    Xigua = 2,
+    Youku = 3,
+    Airtable = 4,
+    BaiduMap = 5,
+    Amap = 6,
+    Figma = 8,
+    Modao = 9,
+    Canva = 10,
+    CodePen = 11,
+    FeishuSurvey = 12,
+    Jinshuju = 13,
+}
+
+impl IframeType {
+    pub const ALL: [IframeType; 12] = [
+        IframeType::Bilibili,
+        IframeType::Xigua,
+        IframeType::Youku,
+        IframeType::Airtable,
+        IframeType::BaiduMap,
+        IframeType::Amap,
+        IframeType::Figma,
+        IframeType::Modao,
+        IframeType::Canva,
+        IframeType::CodePen,
+        IframeType::FeishuSurvey,
+        IframeType::Jinshuju,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            IframeType::Bilibili => "bilibili",
+            IframeType::Xigua => "xigua",
+            IframeType::Youku => "youku",
+            IframeType::Airtable => "airtable",
+            IframeType::BaiduMap => "baidu-map",
+            IframeType::Amap => "amap",
+            IframeType::Figma => "figma",
+            IframeType::Modao => "modao",
+            IframeType::Canva => "canva",
+            IframeType::CodePen => "codepen",
+            IframeType::FeishuSurvey => "feishu-survey",
+            IframeType::Jinshuju => "jinshuju",
+        }
+    }
+}
+
+impl std::str::FromStr for IframeType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace('_', "-");
+        Self::ALL.into_iter().find(|t| t.name() == name).ok_or_else(|| {
+            let names: Vec<&str> = Self::ALL.iter().map(|t| t.name()).collect();
+            format!("无效的 iframe 类型: {}。可选值: {}", s, names.join(", "))
+        })
+    }
+}
+
+/// 代码块语言，按接口中的枚举值排列
+const CODE_LANGUAGES: [&str; 75] = [
+    "plaintext", "abap", "ada", "apache", "apex", "assembly", "bash", "csharp", "cpp", "c",
+    "cobol", "css", "coffeescript", "d", "dart", "delphi", "django", "dockerfile", "erlang", "fortran",
+    "foxpro", "go", "groovy", "html", "htmlbars", "http", "haskell", "json", "java", "javascript",
+    "julia", "kotlin", "latex", "lisp", "logo", "lua", "matlab", "makefile", "markdown", "nginx",
+    "objective-c", "openedge-abl", "php", "perl", "postscript", "powershell", "prolog", "protobuf", "python", "r",
+    "rpg", "ruby", "rust", "sas", "scss", "sql", "scala", "scheme", "scratch", "shell",
+    "swift", "thrift", "typescript", "vbscript", "visual-basic", "xml", "yaml", "cmake", "diff", "gherkin",
+    "graphql", "glsl", "properties", "solidity", "toml",
+];
+
+/// 代码块语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CodeLanguage(pub i32);
+
+impl std::str::FromStr for CodeLanguage {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase();
+        let name = match name.as_str() {
+            "" | "text" | "plain" | "txt" => "plaintext",
+            "sh" | "zsh" => "shell",
+            "c#" | "cs" => "csharp",
+            "c++" | "cxx" => "cpp",
+            "js" | "jsx" => "javascript",
+            "ts" | "tsx" => "typescript",
+            "py" => "python",
+            "rb" => "ruby",
+            "rs" => "rust",
+            "yml" => "yaml",
+            "md" => "markdown",
+            "golang" => "go",
+            "kt" => "kotlin",
+            "objc" | "objective_c" => "objective-c",
+            "proto" => "protobuf",
+            "ps1" => "powershell",
+            "tex" => "latex",
+            "docker" => "dockerfile",
+            "vb" => "visual-basic",
+            other => other,
+        };
+        CODE_LANGUAGES
+            .iter()
+            .position(|l| *l == name)
+            .map(|i| CodeLanguage(i as i32 + 1))
+            .ok_or_else(|| format!("不支持的代码语言: {}", s))
+    }
+}
+
+/// 可以通过 add-block 创建的块类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockKind {
+    Text,
+    Heading(u8),
+    Bullet,
+    Ordered,
+    Code,
+    Quote,
+    QuoteContainer,
+    Todo,
+    Callout,
+    Divider,
+    Grid,
+    Column,
+    Equation,
+    Iframe,
+}
+
+impl BlockKind {
+    pub fn name(self) -> String {
+        match self {
+            BlockKind::Text => "text".to_string(),
+            BlockKind::Heading(level) => format!("heading{}", level),
+            BlockKind::Bullet => "bullet".to_string(),
+            BlockKind::Ordered => "ordered".to_string(),
+            BlockKind::Code => "code".to_string(),
+            BlockKind::Quote => "quote".to_string(),
+            BlockKind::QuoteContainer => "quote-container".to_string(),
+            BlockKind::Todo => "todo".to_string(),
+            BlockKind::Callout => "callout".to_string(),
+            BlockKind::Divider => "divider".to_string(),
+            BlockKind::Grid => "grid".to_string(),
+            BlockKind::Column => "column".to_string(),
+            BlockKind::Equation => "equation".to_string(),
+            BlockKind::Iframe => "iframe".to_string(),
+        }
+    }
+
+    /// 能否包含子块
+    fn accepts_children(self) -> bool {
+        matches!(
+            self,
+            BlockKind::Text
+                | BlockKind::Bullet
+                | BlockKind::Ordered
+                | BlockKind::Todo
+                | BlockKind::Callout
+                | BlockKind::QuoteContainer
+                | BlockKind::Grid
+                | BlockKind::Column
+        )
+    }
+}
+
+impl std::str::FromStr for BlockKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace('_', "-");
+        let heading = name.strip_prefix("heading").or_else(|| name.strip_prefix('h'));
+        if let Some(level) = heading.and_then(|l| l.parse::<u8>().ok()) {
+            if (1..=9).contains(&level) {
+                return Ok(BlockKind::Heading(level));
+            }
+        }
+        match name.as_str() {
+            "text" | "paragraph" => Ok(BlockKind::Text),
+            "bullet" => Ok(BlockKind::Bullet),
+            "ordered" => Ok(BlockKind::Ordered),
+            "code" => Ok(BlockKind::Code),
+            "quote" => Ok(BlockKind::Quote),
+            "quote-container" => Ok(BlockKind::QuoteContainer),
+            "todo" => Ok(BlockKind::Todo),
+            "callout" => Ok(BlockKind::Callout),
+            "divider" => Ok(BlockKind::Divider),
+            "grid" => Ok(BlockKind::Grid),
+            "column" | "grid-column" => Ok(BlockKind::Column),
+            "equation" => Ok(BlockKind::Equation),
+            "iframe" => Ok(BlockKind::Iframe),
+            _ => Err(format!(
+                "无效的块类型: {}。可选值: text, heading1-heading9, bullet, ordered, code, quote, \
+                 quote-container, todo, callout, divider, grid, column, equation, iframe",
+                s
+            )),
+        }
+    }
+}
+
+/// 待创建块的描述，可以从 YAML 文件读取，`children` 描述嵌套的子块
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlockSpec {
+    /// 块类型，如 text、code、grid
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// 文本内容；公式块为公式内容
+    #[serde(default)]
+    pub text: Option<String>,
+    /// 代码语言（code）
+    #[serde(default)]
+    pub language: Option<String>,
+    /// 代码是否自动换行（code）
+    #[serde(default)]
+    pub wrap: Option<bool>,
+    /// 是否已完成（todo）
+    #[serde(default)]
+    pub done: Option<bool>,
+    /// 分栏数（grid，未提供 children 时使用）
+    #[serde(default)]
+    pub columns: Option<usize>,
+    /// 各栏宽度百分比，总和为 100（grid）
+    #[serde(default)]
+    pub width_ratios: Option<Vec<i32>>,
+    /// 图标（callout）
+    #[serde(default)]
+    pub emoji: Option<String>,
+    /// 预设样式 info、warning、error、success（callout）
+    #[serde(default)]
+    pub callout_type: Option<String>,
+    /// 背景色（callout）
+    #[serde(default)]
+    pub background_color: Option<String>,
+    /// 边框色（callout）
+    #[serde(default)]
+    pub border_color: Option<String>,
+    /// 文字颜色（callout）
+    #[serde(default)]
+    pub text_color: Option<String>,
+    /// 网页地址（iframe）
+    #[serde(default)]
+    pub url: Option<String>,
+    /// 网页来源类型（iframe）
+    #[serde(default)]
+    pub iframe_type: Option<String>,
+    /// 子块
+    #[serde(default)]
+    pub children: Vec<BlockSpec>,
+}
+
+impl BlockSpec {
+    /// 从 YAML（或 JSON）文件读取块描述，顶层可以是单个块或块列表
+    pub fn load(path: &Path) -> Result<Vec<BlockSpec>> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| LarkError::IoError(format!("读取块描述文件失败 {}: {}", path.display(), e)))?;
+        Self::parse(&content)
+            .map_err(|e| LarkError::ParseError(format!("块描述文件格式错误 {}: {}", path.display(), e)))
+    }
+
+    /// 解析 YAML（或 JSON）块描述，顶层可以是单个块或块列表
+    pub fn parse(content: &str) -> std::result::Result<Vec<BlockSpec>, serde_yaml::Error> {
+        let value: serde_yaml::Value = serde_yaml::from_str(content)?;
+        if value.is_sequence() {
+            serde_yaml::from_value(value)
+        } else {
+            serde_yaml::from_value(value).map(|spec| vec![spec])
+        }
+    }
+
+    /// 只对特定块类型有效的选项，用在其他类型上时报错
+    fn check_options(&self, kind: BlockKind) -> Result<()> {
+        let options = [
+            ("text", self.text.is_some(), !matches!(kind, BlockKind::Divider | BlockKind::Grid | BlockKind::Iframe)),
+            ("language", self.language.is_some(), kind == BlockKind::Code),
+            ("wrap", self.wrap.is_some(), kind == BlockKind::Code),
+            ("done", self.done.is_some(), kind == BlockKind::Todo),
+            ("columns", self.columns.is_some(), kind == BlockKind::Grid),
+            ("width_ratios", self.width_ratios.is_some(), kind == BlockKind::Grid),
+            ("emoji", self.emoji.is_some(), kind == BlockKind::Callout),
+            ("callout_type", self.callout_type.is_some(), kind == BlockKind::Callout),
+            ("background_color", self.background_color.is_some(), kind == BlockKind::Callout),
+            ("border_color", self.border_color.is_some(), kind == BlockKind::Callout),
+            ("text_color", self.text_color.is_some(), kind == BlockKind::Callout),
+            ("url", self.url.is_some(), kind == BlockKind::Iframe),
+            ("iframe_type", self.iframe_type.is_some(), kind == BlockKind::Iframe),
+        ];
+        if let Some((name, ..)) = options.iter().find(|(_, set, allowed)| *set && !*allowed) {
+            return Err(LarkError::ValidationError(format!("{} 块不支持选项 {}", kind.name(), name)));
+        }
+        if !self.children.is_empty() && !kind.accepts_children() {
+            return Err(LarkError::ValidationError(format!("{} 块不能包含子块", kind.name())));
+        }
+        Ok(())
+    }
+}
+
+/// 按块描述构建待创建的块，返回一级块的临时 ID 和所有待创建的块
+pub fn spec_blocks(parent_id: &str, specs: &[BlockSpec]) -> Result<(Vec<String>, Vec<DescendantBlock>)> {
+    if specs.is_empty() {
+        return Err(LarkError::ValidationError("没有要创建的块".to_string()));
+    }
+    let mut builder = SpecBuilder::default();
+    let mut children_id = Vec::new();
+    for spec in specs {
+        children_id.push(builder.build(spec, parent_id, false)?);
+    }
+    Ok((children_id, builder.descendants))
+}
+
+#[derive(Default)]
+struct SpecBuilder {
+    descendants: Vec<DescendantBlock>,
+}
+
+impl SpecBuilder {
+    /// 构建一个块及其子块，父块排在子块之前，返回该块的临时 ID
+    fn build(&mut self, spec: &BlockSpec, parent_id: &str, in_grid: bool) -> Result<String> {
+        let kind: BlockKind = spec.kind.parse().map_err(LarkError::ValidationError)?;
+        spec.check_options(kind)?;
+        if (kind == BlockKind::Column) != in_grid {
+            return Err(LarkError::ValidationError(if in_grid {
+                format!("grid 块的子块必须是 column，而不是 {}", kind.name())
+            } else {
+                "column 块只能作为 grid 的子块".to_string()
+            }));
+        }
+
+        let block_id = format!("temp-{}-{}", kind.name(), uuid::Uuid::new_v4());
+        let slot = self.descendants.len();
+        self.descendants.push(DescendantBlock::default());
+
+        let text = spec.text.as_deref().unwrap_or_default();
+        let mut block = DescendantBlock {
+            block_id: block_id.clone(),
+            parent_id: Some(parent_id.to_string()),
+            ..Default::default()
+        };
+        let mut children = Vec::new();
+
+        match kind {
+            BlockKind::Text => {
+                block.block_type = 2;
+                block.text = Some(json!({ "elements": text_elements(text) }));
+            }
+            BlockKind::Heading(level) => {
+                block.block_type = level as i32 + 2;
+                let payload = Some(json!({ "elements": text_elements(text) }));
+                match level {
+                    1 => block.heading1 = payload,
+                    2 => block.heading2 = payload,
+                    3 => block.heading3 = payload,
+                    4 => block.heading4 = payload,
+                    5 => block.heading5 = payload,
+                    6 => block.heading6 = payload,
+                    7 => block.heading7 = payload,
+                    8 => block.heading8 = payload,
+                    _ => block.heading9 = payload,
+                }
+            }
+            BlockKind::Bullet => {
+                block.block_type = 12;
+                block.bullet = Some(json!({ "elements": text_elements(text) }));
+            }
+            BlockKind::Ordered => {
+                block.block_type = 13;
+                block.ordered = Some(json!({ "elements": text_elements(text) }));
+            }
+            BlockKind::Code => {
+                let language: CodeLanguage = spec
+                    .language
+                    .as_deref()
+                    .unwrap_or("plaintext")
+                    .parse()
+                    .map_err(LarkError::ValidationError)?;
+                block.block_type = 14;
+                block.code = Some(json!({
+                    "style": { "language": language.0, "wrap": spec.wrap.unwrap_or(false) },
+                    "elements": text_elements(text)
+                }));
+            }
+            BlockKind::Quote => {
+                block.block_type = 15;
+                block.quote = Some(json!({ "elements": text_elements(text) }));
+            }
+            BlockKind::Todo => {
+                block.block_type = 17;
+                block.todo = Some(json!({
+                    "style": { "done": spec.done.unwrap_or(false) },
+                    "elements": text_elements(text)
+                }));
+            }
+            BlockKind::Equation => {
+                if text.trim().is_empty() {
+                    return Err(LarkError::ValidationError("equation 块需要公式内容 text".to_string()));
+                }
+                block.block_type = 2;
+                block.text = Some(json!({ "elements": [{ "equation": { "content": text } }] }));
+            }
+            BlockKind::Callout => {
+                let callout_type: CalloutType = spec
+                    .callout_type
+                    .as_deref()
+                    .unwrap_or("info")
+                    .parse()
+                    .map_err(LarkError::ValidationError)?;
+                let (mut background, mut border, emoji) = callout_type.style();
+                if let Some(color) = &spec.background_color {
+                    background = color.parse().map_err(LarkError::ValidationError)?;
+                }
+                if let Some(color) = &spec.border_color {
+                    border = color.parse().map_err(LarkError::ValidationError)?;
+                }
+                let mut callout = json!({
+                    "background_color": background as i32,
+                    "border_color": border as i32,
+                    "emoji_id": spec.emoji.as_deref().unwrap_or(emoji),
+                });
+                if let Some(color) = &spec.text_color {
+                    let color: FontColor = color.parse().map_err(LarkError::ValidationError)?;
+                    callout["text_color"] = json!(color as i32);
+                }
+                block.block_type = 19;
+                block.callout = Some(callout);
+                children = self.container_children(spec, &block_id)?;
+            }
+            BlockKind::QuoteContainer => {
+                block.block_type = 34;
+                block.quote_container = Some(json!({}));
+                children = self.container_children(spec, &block_id)?;
+            }
+            BlockKind::Divider => {
+                block.block_type = 22;
+                block.divider = Some(json!({}));
+            }
+            BlockKind::Grid => {
+                let columns = grid_columns(spec)?;
+                block.block_type = 24;
+                block.grid = Some(json!({ "column_size": columns }));
+                for i in 0..columns {
+                    let column = spec.children.get(i).cloned().unwrap_or_else(|| BlockSpec {
+                        kind: "column".to_string(),
+                        ..Default::default()
+                    });
+                    children.push(self.build(&column, &block_id, true)?);
+                }
+                if let Some(ratios) = &spec.width_ratios {
+                    // 宽度比例写在各个分栏块上
+                    for (column_id, ratio) in children.iter().zip(ratios) {
+                        if let Some(column) = self.descendants.iter_mut().find(|b| &b.block_id == column_id) {
+                            column.grid_column = Some(json!({ "width_ratio": ratio }));
+                        }
+                    }
+                }
+            }
+            BlockKind::Column => {
+                block.block_type = 25;
+                block.grid_column = Some(json!({}));
+                children = self.container_children(spec, &block_id)?;
+            }
+            BlockKind::Iframe => {
+                let url = spec
+                    .url
+                    .as_deref()
+                    .filter(|u| !u.trim().is_empty())
+                    .ok_or_else(|| LarkError::ValidationError("iframe 块需要网页地址 url".to_string()))?;
+                let iframe_type: IframeType = spec
+                    .iframe_type
+                    .as_deref()
+                    .ok_or_else(|| LarkError::ValidationError("iframe 块需要来源类型 iframe_type".to_string()))?
+                    .parse()
+                    .map_err(LarkError::ValidationError)?;
+                block.block_type = 26;
+                block.iframe = Some(json!({
+                    "component": { "type": iframe_type as i32, "url": percent_encode(url) }
+                }));
+            }
+        }
+
+        if matches!(
+            kind,
+            BlockKind::Text | BlockKind::Bullet | BlockKind::Ordered | BlockKind::Todo
+        ) {
+            for child in &spec.children {
+                children.push(self.build(child, &block_id, false)?);
+            }
+        }
+        block.children = Some(children);
+        self.descendants[slot] = block;
+        Ok(block_id)
+    }
+
+    /// 容器块（高亮块、引用容器、分栏）的子块：`text` 作为第一个文本子块，没有内容时放一个空文本块
+    fn container_children(&mut self, spec: &BlockSpec, block_id: &str) -> Result<Vec<String>> {
+        let mut children = Vec::new();
+        if spec.text.is_some() || spec.children.is_empty() {
+            let text = BlockSpec {
+                kind: "text".to_string(),
+                text: spec.text.clone(),
+                ..Default::default()
+            };
+            children.push(self.build(&text, block_id, false)?);
+        }
+        for child in &spec.children {
+            children.push(self.build(child, block_id, false)?);
+        }
+        Ok(children)
+    }
+}
+
+/// 分栏数：提供 children 时为子块数，否则为 columns（默认 2），宽度比例需要与分栏数一致且总和为 100
+fn grid_columns(spec: &BlockSpec) -> Result<usize> {
+    let columns = match (spec.children.len(), spec.columns) {
+        (0, columns) => columns.unwrap_or(2),
+        (n, Some(columns)) if columns != n => {
+            return Err(LarkError::ValidationError(format!(
+                "grid 块的 columns 为 {}，但提供了 {} 个 column 子块",
+                columns, n
+            )));
+        }
+        (n, _) => n,
+    };
+    if !(2..=5).contains(&columns) {
+        return Err(LarkError::ValidationError(format!("grid 块的分栏数必须在 2 到 5 之间，当前为 {}", columns)));
+    }
+    if let Some(ratios) = &spec.width_ratios {
+        if ratios.len() != columns || ratios.iter().any(|r| *r <= 0) || ratios.iter().sum::<i32>() != 100 {
+            return Err(LarkError::ValidationError(format!(
+                "width_ratios 需要 {} 个正整数且总和为 100，当前为 {:?}",
+                columns, ratios
+            )));
+        }
+    }
+    Ok(columns)
+}
+
+fn text_elements(content: &str) -> Value {
+    json!([{ "text_run": { "content": content } }])
+}
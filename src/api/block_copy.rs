@@ -0,0 +1,256 @@
+use super::DescendantBlock;
+use crate::error::{LarkError, Result};
+use super::get_blocks::BlockItem;
+use crate::utils::BlockTree;
+use serde::Serialize;
+use serde_json::Value;
+
+/// 可以按内容复制的块类型：文本、标题、列表、代码、引用、待办、高亮块、分割线、分栏、表格和引用容器
+///
+/// 图片、附件、画板、电子表格等块的内容保存在独立的资源中，无法通过创建块接口复制。
+const COPYABLE_BLOCK_TYPES: [i32; 22] = [
+    2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 17, 19, 22, 24, 25, 31, 32, 34,
+];
+
+/// 复制时去掉的字段：评论和由服务端根据子块生成的表格信息
+const STRIPPED_KEYS: [&str; 3] = ["comment_ids", "cells", "merge_info"];
+
+/// 附件块
+pub const FILE_BLOCK_TYPE: i32 = 23;
+/// 图片块
+pub const IMAGE_BLOCK_TYPE: i32 = 27;
+/// 视图块：附件块的外层容器，创建附件块时由服务端自动生成
+const VIEW_BLOCK_TYPE: i32 = 33;
+
+/// 复制得到的待创建块
+#[derive(Debug, Default, Serialize)]
+pub struct CopiedBlocks {
+    /// 一级块临时 ID
+    pub children_id: Vec<String>,
+    /// 所有待创建的块
+    pub descendants: Vec<DescendantBlock>,
+    /// 无法复制而被跳过的块（连同其子块）
+    pub skipped: Vec<SkippedBlock>,
+    /// 以空块创建、需要重新上传素材的图片和附件块
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub media: Vec<MediaBlock>,
+}
+
+/// 需要重新上传素材的图片或附件块
+#[derive(Debug, Clone, Serialize)]
+pub struct MediaBlock {
+    /// 新块的临时 ID
+    pub temporary_block_id: String,
+    /// 原块 ID
+    pub source_block_id: String,
+    /// 块类型（图片 27 或附件 23）
+    pub block_type: i32,
+    /// 原素材 token
+    pub token: String,
+    /// 附件文件名
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// 图片宽度
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<i32>,
+    /// 图片高度
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<i32>,
+    /// 图片对齐方式
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub align: Option<i32>,
+}
+
+/// 被跳过的块
+#[derive(Debug, Clone, Serialize)]
+pub struct SkippedBlock {
+    /// 原块 ID
+    pub block_id: String,
+    /// 块类型
+    pub block_type: i32,
+}
+
+/// 块类型能否复制
+pub fn is_copyable(block_type: i32) -> bool {
+    COPYABLE_BLOCK_TYPES.contains(&block_type)
+}
+
+/// 将块树中的若干子树转换为可用于创建嵌套块接口的块，使用新的临时 ID
+pub fn copy_blocks(tree: &BlockTree, root_ids: &[String]) -> Result<CopiedBlocks> {
+    copy_subtrees(tree, root_ids, false)
+}
+
+/// 与 [`copy_blocks`] 相同，但图片和附件块以空块复制，记录在 `media` 中，由调用方重新上传素材
+pub fn copy_blocks_with_media(tree: &BlockTree, root_ids: &[String]) -> Result<CopiedBlocks> {
+    copy_subtrees(tree, root_ids, true)
+}
+
+fn copy_subtrees(tree: &BlockTree, root_ids: &[String], with_media: bool) -> Result<CopiedBlocks> {
+    let mut copier = Copier {
+        tree,
+        with_media,
+        copied: CopiedBlocks::default(),
+        next_id: 0,
+    };
+    for block_id in root_ids {
+        let ids = copier.copy_block(block_id)?;
+        copier.copied.children_id.extend(ids);
+    }
+    Ok(copier.copied)
+}
+
+struct Copier<'a> {
+    tree: &'a BlockTree,
+    with_media: bool,
+    copied: CopiedBlocks,
+    next_id: usize,
+}
+
+impl Copier<'_> {
+    fn temp_id(&mut self) -> String {
+        self.next_id += 1;
+        format!("copy_{}", self.next_id)
+    }
+
+    /// 复制一个块，返回新块的临时 ID（跳过时为空，视图块展开为其中的附件块）
+    fn copy_block(&mut self, block_id: &str) -> Result<Vec<String>> {
+        let tree = self.tree;
+        let block = tree
+            .get(block_id)
+            .ok_or_else(|| LarkError::ValidationError(format!("找不到块: {}", block_id)))?;
+
+        if self.with_media {
+            match block.block_type {
+                VIEW_BLOCK_TYPE => {
+                    let mut ids = Vec::new();
+                    for child_id in tree.children(block_id) {
+                        ids.extend(self.copy_block(child_id)?);
+                    }
+                    return Ok(ids);
+                }
+                IMAGE_BLOCK_TYPE | FILE_BLOCK_TYPE => return self.copy_media(block),
+                _ => {}
+            }
+        }
+
+        if !is_copyable(block.block_type) {
+            self.copied.skipped.push(SkippedBlock {
+                block_id: block.block_id.clone(),
+                block_type: block.block_type,
+            });
+            return Ok(Vec::new());
+        }
+
+        let temp_id = self.temp_id();
+        let position = self.copied.descendants.len();
+        self.copied.descendants.push(DescendantBlock::default());
+
+        let mut children = Vec::new();
+        for child_id in tree.children(block_id) {
+            children.extend(self.copy_block(child_id)?);
+        }
+
+        // 表格单元格至少需要一个子块
+        if block.block_type == 32 && children.is_empty() {
+            let text_id = self.temp_id();
+            self.copied.descendants.push(descendant(
+                &text_id,
+                2,
+                Vec::new(),
+                "text",
+                serde_json::json!({ "elements": [{ "text_run": { "content": "" } }] }),
+            )?);
+            children.push(text_id);
+        }
+
+        let mut payload = block.content.clone();
+        if let Some(object) = payload.as_object_mut() {
+            for key in ["block_id", "block_type", "parent_id", "children"] {
+                object.remove(key);
+            }
+        }
+        strip_keys(&mut payload);
+
+        let mut object = payload.as_object().cloned().unwrap_or_default();
+        object.insert("block_id".to_string(), Value::String(temp_id.clone()));
+        object.insert("block_type".to_string(), Value::from(block.block_type));
+        if !children.is_empty() {
+            object.insert("children".to_string(), serde_json::json!(children));
+        }
+        self.copied.descendants[position] = serde_json::from_value(Value::Object(object))
+            .map_err(|e| LarkError::ParseError(format!("无法复制块 {}: {}", block_id, e)))?;
+
+        Ok(vec![temp_id])
+    }
+
+    /// 以空块复制图片或附件，素材 token 无法跨文档使用，需要重新上传
+    fn copy_media(&mut self, block: &BlockItem) -> Result<Vec<String>> {
+        let (key, content) = if block.block_type == IMAGE_BLOCK_TYPE {
+            ("image", serde_json::json!({}))
+        } else {
+            ("file", serde_json::json!({ "token": "" }))
+        };
+        let source = block.content.get(key);
+        let field = |name: &str| source.and_then(|s| s.get(name));
+        let token = field("token").and_then(Value::as_str).unwrap_or_default();
+        if token.is_empty() {
+            self.copied.skipped.push(SkippedBlock {
+                block_id: block.block_id.clone(),
+                block_type: block.block_type,
+            });
+            return Ok(Vec::new());
+        }
+
+        let temp_id = self.temp_id();
+        self.copied
+            .descendants
+            .push(descendant(&temp_id, block.block_type, Vec::new(), key, content)?);
+        let number = |name: &str| field(name).and_then(Value::as_i64).map(|n| n as i32);
+        self.copied.media.push(MediaBlock {
+            temporary_block_id: temp_id.clone(),
+            source_block_id: block.block_id.clone(),
+            block_type: block.block_type,
+            token: token.to_string(),
+            name: field("name").and_then(Value::as_str).map(str::to_string),
+            width: number("width"),
+            height: number("height"),
+            align: number("align"),
+        });
+        Ok(vec![temp_id])
+    }
+}
+
+/// 创建附件块时服务端会生成外层视图块，此时实际的附件块是视图块的子块
+pub fn media_block_id(children: &[Value], block_id: &str) -> String {
+    children
+        .iter()
+        .find(|b| b.get("block_id").and_then(Value::as_str) == Some(block_id))
+        .filter(|b| b.get("block_type").and_then(Value::as_i64) == Some(VIEW_BLOCK_TYPE as i64))
+        .and_then(|b| b.get("children")?.get(0)?.as_str())
+        .unwrap_or(block_id)
+        .to_string()
+}
+
+fn descendant(block_id: &str, block_type: i32, children: Vec<String>, key: &str, content: Value) -> Result<DescendantBlock> {
+    let mut object = serde_json::Map::new();
+    object.insert("block_id".to_string(), Value::String(block_id.to_string()));
+    object.insert("block_type".to_string(), Value::from(block_type));
+    if !children.is_empty() {
+        object.insert("children".to_string(), serde_json::json!(children));
+    }
+    object.insert(key.to_string(), content);
+    Ok(serde_json::from_value(Value::Object(object))?)
+}
+
+fn strip_keys(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            for key in STRIPPED_KEYS {
+                object.remove(key);
+            }
+            object.values_mut().for_each(strip_keys);
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_keys),
+        _ => {}
+    }
+}
@@ -0,0 +1,104 @@
+use super::{ApiClient, DeleteBlocksApi, DocumentApi, GetBlocksApi};
+use crate::error::{LarkError, Result};
+use crate::utils::{selection_ranges, BlockSelector, BlockTree, SelectionRange};
+use serde::Serialize;
+
+/// 按选择器删除块的结果
+#[derive(Debug, Serialize)]
+pub struct SelectionDeleteResult {
+    /// 文档 ID
+    pub document_id: String,
+    /// 选择器
+    pub selector: String,
+    /// 是否只规划、未修改文档
+    pub dry_run: bool,
+    /// 选中的块数（不含已被祖先块包含的块）
+    pub block_count: usize,
+    /// 每个父块下删除的区间
+    pub ranges: Vec<SelectionRange>,
+    /// 删除后的文档版本（dry-run 时为读取时的版本）
+    pub document_revision_id: i32,
+}
+
+/// 块选择 API：在文档块树上求值选择器
+pub struct BlockSelectionApi {
+    client: ApiClient,
+}
+
+impl BlockSelectionApi {
+    pub fn new(client: ApiClient) -> Self {
+        Self { client }
+    }
+
+    /// 读取文档的块树并求值选择器，返回块树、读取时的文档版本和选中的块 ID
+    pub async fn select(
+        &self,
+        document_id: &str,
+        selector: &str,
+        document_revision_id: Option<i32>,
+    ) -> Result<(BlockTree, i32, Vec<String>)> {
+        let selector = BlockSelector::parse(selector)?;
+        let revision_id = match document_revision_id.filter(|&r| r >= 0) {
+            Some(revision_id) => revision_id,
+            None => {
+                DocumentApi::new(self.client.clone())
+                    .get_document(document_id)
+                    .await?
+                    .revision_id
+            }
+        };
+        let blocks = GetBlocksApi::new(self.client.clone())
+            .get_all_document_blocks(document_id, Some(revision_id), None)
+            .await?;
+        let tree = BlockTree::new(blocks);
+        let selected = selector.select(&tree, document_id);
+        Ok((tree, revision_id, selected))
+    }
+
+    /// 删除选择器选中的所有块
+    ///
+    /// 每个父块下的区间从后往前删除，前面区间的位置不受影响；所有删除以读取时的版本为基准依次进行。
+    pub async fn delete(
+        &self,
+        document_id: &str,
+        selector: &str,
+        document_revision_id: Option<i32>,
+    ) -> Result<SelectionDeleteResult> {
+        let (tree, revision_id, selected) = self.select(document_id, selector, document_revision_id).await?;
+        if selected.is_empty() {
+            return Err(LarkError::ValidationError(format!("选择器 {} 没有匹配的块", selector)));
+        }
+        let ranges = selection_ranges(&tree, &selected)?;
+
+        let mut result = SelectionDeleteResult {
+            document_id: document_id.to_string(),
+            selector: selector.to_string(),
+            dry_run: self.client.is_dry_run(),
+            block_count: ranges.iter().map(|r| r.block_ids.len()).sum(),
+            ranges,
+            document_revision_id: revision_id,
+        };
+        if result.dry_run {
+            return Ok(result);
+        }
+
+        let delete_api = DeleteBlocksApi::new(self.client.clone());
+        let mut revision_id = revision_id;
+        for range in result.ranges.iter().rev() {
+            revision_id = delete_api
+                .delete_blocks(
+                    document_id,
+                    &range.parent_id,
+                    range.start_index,
+                    range.end_index,
+                    Some(revision_id),
+                    None,
+                )
+                .await?
+                .document_revision_id;
+        }
+
+        result.document_revision_id = revision_id;
+        Ok(result)
+    }
+}
@@ -17,6 +17,111 @@ pub struct ImportDiagramResponse {
     pub node_id: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagramSyntax {
+    PlantUml,
+    Mermaid,
+}
+
+impl DiagramSyntax {
+    /// 导入接口中的 syntax_type 取值
+    pub fn to_value(self) -> i32 {
+        match self {
+            DiagramSyntax::PlantUml => 1,
+            DiagramSyntax::Mermaid => 2,
+        }
+    }
+}
+
+impl std::str::FromStr for DiagramSyntax {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "plantuml" | "plant-uml" | "puml" => Ok(DiagramSyntax::PlantUml),
+            "mermaid" | "mmd" => Ok(DiagramSyntax::Mermaid),
+            _ => Err(format!("无效的图表语法类型: {}。可选值: plantuml, mermaid", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagramType {
+    Auto,
+    MindMap,
+    Sequence,
+    Activity,
+    Class,
+    ER,
+    Flowchart,
+    UseCase,
+    Component,
+}
+
+impl DiagramType {
+    pub fn to_plantuml_value(self) -> i32 {
+        match self {
+            DiagramType::Auto => 0,
+            DiagramType::MindMap => 1,
+            DiagramType::Sequence => 2,
+            DiagramType::Activity => 3,
+            DiagramType::Class => 4,
+            DiagramType::ER => 5,
+            DiagramType::Flowchart => 6,
+            DiagramType::UseCase => 7,
+            DiagramType::Component => 8,
+        }
+    }
+}
+
+impl std::str::FromStr for DiagramType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "auto" | "0" => Ok(DiagramType::Auto),
+            "mindmap" | "mind-map" | "1" => Ok(DiagramType::MindMap),
+            "sequence" | "时序图" | "2" => Ok(DiagramType::Sequence),
+            "activity" | "活动图" | "3" => Ok(DiagramType::Activity),
+            "class" | "类图" | "4" => Ok(DiagramType::Class),
+            "er" | "实体关系" | "5" => Ok(DiagramType::ER),
+            "flowchart" | "流程图" | "6" => Ok(DiagramType::Flowchart),
+            "usecase" | "用例图" | "7" => Ok(DiagramType::UseCase),
+            "component" | "组件图" | "8" => Ok(DiagramType::Component),
+            _ => Err(format!("无效的图表类型: {}。可选值: auto, mindmap, sequence, activity, class, er, flowchart, usecase, component", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum StyleType {
+    BoardStyle,
+    ClassicStyle,
+}
+
+impl StyleType {
+    pub fn to_value(self) -> i32 {
+        match self {
+            StyleType::BoardStyle => 1,
+            StyleType::ClassicStyle => 2,
+        }
+    }
+}
+
+impl std::str::FromStr for StyleType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "board" | "1" => Ok(StyleType::BoardStyle),
+            "classic" | "2" => Ok(StyleType::ClassicStyle),
+            _ => Err(format!("无效的样式类型: {}。可选值: board(1), classic(2)", s)),
+        }
+    }
+}
+
 pub struct BoardApi {
     client: ApiClient,
 }
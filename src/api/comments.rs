@@ -0,0 +1,302 @@
+use super::{ApiClient, GetUserInfoApi};
+use crate::error::{LarkError, Result};
+use crate::utils::{comment_user_ids, export_comments, CommentExport};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+
+/// 支持评论的云文档类型
+const COMMENT_FILE_TYPES: [&str; 5] = ["doc", "docx", "sheet", "file", "slides"];
+
+/// 分页大小上限
+const COMMENT_PAGE_SIZE: &str = "100";
+
+/// 云文档评论
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Comment {
+    /// 评论 ID
+    pub comment_id: String,
+    /// 评论者 open_id
+    #[serde(default)]
+    pub user_id: String,
+    /// 创建时间（秒级时间戳）
+    #[serde(default)]
+    pub create_time: i64,
+    /// 更新时间（秒级时间戳）
+    #[serde(default)]
+    pub update_time: i64,
+    /// 是否已解决
+    #[serde(default)]
+    pub is_solved: bool,
+    /// 解决时间（秒级时间戳）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solved_time: Option<i64>,
+    /// 解决者 open_id
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solver_user_id: Option<String>,
+    /// 是否为全文评论
+    #[serde(default)]
+    pub is_whole: bool,
+    /// 局部评论引用的文档内容
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote: Option<String>,
+    /// 是否还有更多回复未返回
+    #[serde(default, skip_serializing)]
+    pub has_more: bool,
+    /// 获取剩余回复的分页标记
+    #[serde(default, skip_serializing)]
+    pub page_token: Option<String>,
+    /// 回复列表，第一条为评论本身的内容
+    #[serde(default)]
+    pub reply_list: ReplyList,
+}
+
+/// 评论的回复列表
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ReplyList {
+    #[serde(default)]
+    pub replies: Vec<CommentReply>,
+}
+
+/// 评论回复
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct CommentReply {
+    /// 回复 ID
+    #[serde(default)]
+    pub reply_id: String,
+    /// 回复者 open_id
+    #[serde(default)]
+    pub user_id: String,
+    /// 创建时间（秒级时间戳）
+    #[serde(default)]
+    pub create_time: i64,
+    /// 更新时间（秒级时间戳）
+    #[serde(default)]
+    pub update_time: i64,
+    /// 回复内容
+    #[serde(default)]
+    pub content: ReplyContent,
+}
+
+/// 回复内容
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ReplyContent {
+    #[serde(default)]
+    pub elements: Vec<ReplyElement>,
+}
+
+/// 回复内容元素：文本、文档链接或 @用户
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ReplyElement {
+    /// 元素类型：text_run、docs_link、person
+    #[serde(rename = "type")]
+    pub element_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_run: Option<ReplyTextRun>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs_link: Option<ReplyDocsLink>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub person: Option<ReplyPerson>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ReplyTextRun {
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ReplyDocsLink {
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ReplyPerson {
+    pub user_id: String,
+}
+
+impl ReplyContent {
+    /// 纯文本内容
+    pub fn text(content: &str) -> Self {
+        Self {
+            elements: vec![ReplyElement {
+                element_type: "text_run".to_string(),
+                text_run: Some(ReplyTextRun { text: content.to_string() }),
+                ..Default::default()
+            }],
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Page<T> {
+    #[serde(default = "Vec::new")]
+    items: Vec<T>,
+    #[serde(default)]
+    has_more: bool,
+    #[serde(default)]
+    page_token: Option<String>,
+}
+
+/// 修改评论解决状态的结果
+#[derive(Debug, Serialize)]
+pub struct CommentSolvedResult {
+    pub file_token: String,
+    pub comment_id: String,
+    pub is_solved: bool,
+}
+
+/// 云文档评论 API
+pub struct CommentsApi {
+    client: ApiClient,
+}
+
+impl CommentsApi {
+    pub fn new(client: ApiClient) -> Self {
+        Self { client }
+    }
+
+    /// 获取文档的全部评论，包括每条评论的全部回复
+    ///
+    /// `solved` 为 Some 时只返回已解决或未解决的评论。
+    pub async fn list(&self, file_token: &str, file_type: &str, solved: Option<bool>) -> Result<Vec<Comment>> {
+        check_file_type(file_type)?;
+        let url = format!("https://open.larkoffice.com/open-apis/drive/v1/files/{}/comments", file_token);
+
+        let mut comments = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = base_params(file_type);
+            params.insert("page_size".to_string(), COMMENT_PAGE_SIZE.to_string());
+            if let Some(solved) = solved {
+                params.insert("is_solved".to_string(), solved.to_string());
+            }
+            if let Some(token) = &page_token {
+                params.insert("page_token".to_string(), token.clone());
+            }
+            let page: Page<Comment> = self.client.get(&url, Some(params)).await?;
+            comments.extend(page.items);
+            page_token = page.page_token.filter(|t| !t.is_empty());
+            if !page.has_more || page_token.is_none() {
+                break;
+            }
+        }
+
+        // 回复较多的评论只返回了部分回复，补齐剩余回复
+        for comment in comments.iter_mut().filter(|c| c.has_more) {
+            comment.reply_list.replies = self.replies(file_token, file_type, &comment.comment_id).await?;
+            comment.has_more = false;
+            comment.page_token = None;
+        }
+        Ok(comments)
+    }
+
+    /// 获取全部评论，并通过通讯录查询评论者和被 @用户的名字
+    ///
+    /// 查询失败（如缺少通讯录权限）的用户保留 open_id。
+    pub async fn export(&self, file_token: &str, file_type: &str, solved: Option<bool>) -> Result<CommentExport> {
+        let comments = self.list(file_token, file_type, solved).await?;
+        let user_api = GetUserInfoApi::new(self.client.clone());
+        let mut names = HashMap::new();
+        for user_id in comment_user_ids(&comments) {
+            if let Ok(user) = user_api.get_user_info(&user_id, Some("open_id"), None).await {
+                if let Some(name) = user.name.filter(|n| !n.is_empty()) {
+                    names.insert(user_id, name);
+                }
+            }
+        }
+        Ok(export_comments(file_token, file_type, &comments, &names))
+    }
+
+    /// 获取评论的全部回复
+    pub async fn replies(&self, file_token: &str, file_type: &str, comment_id: &str) -> Result<Vec<CommentReply>> {
+        check_file_type(file_type)?;
+        let url = format!(
+            "https://open.larkoffice.com/open-apis/drive/v1/files/{}/comments/{}/replies",
+            file_token, comment_id
+        );
+
+        let mut replies = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = base_params(file_type);
+            params.insert("page_size".to_string(), COMMENT_PAGE_SIZE.to_string());
+            if let Some(token) = &page_token {
+                params.insert("page_token".to_string(), token.clone());
+            }
+            let page: Page<CommentReply> = self.client.get(&url, Some(params)).await?;
+            replies.extend(page.items);
+            page_token = page.page_token.filter(|t| !t.is_empty());
+            if !page.has_more || page_token.is_none() {
+                break;
+            }
+        }
+        Ok(replies)
+    }
+
+    /// 添加全文评论
+    pub async fn add(&self, file_token: &str, file_type: &str, content: &str) -> Result<Comment> {
+        check_file_type(file_type)?;
+        check_content(content)?;
+        let url = format!("https://open.larkoffice.com/open-apis/drive/v1/files/{}/comments", file_token);
+        let body = json!({ "reply_list": { "replies": [{ "content": ReplyContent::text(content) }] } });
+        self.client.post_with_params(&url, Some(base_params(file_type)), &body).await
+    }
+
+    /// 回复评论
+    pub async fn reply(&self, file_token: &str, file_type: &str, comment_id: &str, content: &str) -> Result<CommentReply> {
+        check_file_type(file_type)?;
+        check_content(content)?;
+        let url = format!(
+            "https://open.larkoffice.com/open-apis/drive/v1/files/{}/comments/{}/replies",
+            file_token, comment_id
+        );
+        let body = json!({ "content": ReplyContent::text(content) });
+        self.client.post_with_params(&url, Some(base_params(file_type)), &body).await
+    }
+
+    /// 解决或重新打开评论
+    pub async fn set_solved(
+        &self,
+        file_token: &str,
+        file_type: &str,
+        comment_id: &str,
+        solved: bool,
+    ) -> Result<CommentSolvedResult> {
+        check_file_type(file_type)?;
+        let url = format!(
+            "https://open.larkoffice.com/open-apis/drive/v1/files/{}/comments/{}",
+            file_token, comment_id
+        );
+        let body = json!({ "is_solved": solved });
+        let _: serde_json::Value = self.client.patch_with_params(&url, Some(base_params(file_type)), &body).await?;
+        Ok(CommentSolvedResult {
+            file_token: file_token.to_string(),
+            comment_id: comment_id.to_string(),
+            is_solved: solved,
+        })
+    }
+}
+
+fn base_params(file_type: &str) -> HashMap<String, String> {
+    let mut params = HashMap::new();
+    params.insert("file_type".to_string(), file_type.to_string());
+    params.insert("user_id_type".to_string(), "open_id".to_string());
+    params
+}
+
+fn check_file_type(file_type: &str) -> Result<()> {
+    if !COMMENT_FILE_TYPES.contains(&file_type) {
+        return Err(LarkError::ValidationError(format!(
+            "文档类型必须是以下值之一：{}",
+            COMMENT_FILE_TYPES.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+fn check_content(content: &str) -> Result<()> {
+    if content.trim().is_empty() {
+        return Err(LarkError::ValidationError("评论内容不能为空".to_string()));
+    }
+    Ok(())
+}
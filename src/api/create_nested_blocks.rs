@@ -1,4 +1,5 @@
 use super::ApiClient;
+use super::block_batcher::{BatchParent, CreateBatch};
 use crate::error::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -122,9 +123,11 @@ pub struct DescendantBlock {
     pub undefined: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub table_cell: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quote_container: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct BlockIdRelation {
     #[serde(rename = "block_id")]
     pub block_id: String,
@@ -225,4 +228,88 @@ impl CreateNestedBlocksApi {
 
         Ok(data)
     }
-}
\ No newline at end of file
+
+    /// 按规划好的分批依次创建块，合并所有请求的块 ID 映射关系
+    ///
+    /// 后续请求的父块为之前请求中创建的块时，根据已返回的映射关系替换为实际块 ID。
+    /// 文档版本号和幂等令牌只用于第一次请求。
+    pub async fn create_batches(
+        &self,
+        document_id: &str,
+        block_id: &str,
+        batches: Vec<CreateBatch>,
+        document_revision_id: Option<i32>,
+        client_token: Option<String>,
+    ) -> Result<CreateNestedBlocksResponse> {
+        let mut relations = Vec::new();
+        self.create_batches_tracked(document_id, block_id, batches, document_revision_id, client_token, &mut relations)
+            .await
+    }
+
+    /// 与 `create_batches` 相同，但每批成功后立即把块 ID 映射关系追加到 `relations`
+    ///
+    /// 中途失败时，调用方可以根据 `relations` 找到已经创建的块并清理。
+    pub async fn create_batches_tracked(
+        &self,
+        document_id: &str,
+        block_id: &str,
+        batches: Vec<CreateBatch>,
+        document_revision_id: Option<i32>,
+        client_token: Option<String>,
+        relations: &mut Vec<BlockIdRelation>,
+    ) -> Result<CreateNestedBlocksResponse> {
+        let mut id_map: HashMap<String, String> = HashMap::new();
+        let mut children = Vec::new();
+        let mut revision_id = document_revision_id.unwrap_or(0);
+
+        for (i, batch) in batches.into_iter().enumerate() {
+            let parent_id = match &batch.parent {
+                BatchParent::Target => block_id.to_string(),
+                BatchParent::Created(temp_id) => id_map.get(temp_id).cloned().ok_or_else(|| {
+                    crate::error::LarkError::ValidationError(format!(
+                        "找不到临时块 {} 对应的实际块 ID", temp_id
+                    ))
+                })?,
+            };
+
+            if std::env::var("DEBUG_CREATE").is_ok() {
+                eprintln!(
+                    "第 {} 批：父块 {}，块数 {}，索引 {}",
+                    i + 1, parent_id, batch.descendants.len(), batch.index
+                );
+            }
+
+            let (batch_revision_id, batch_client_token) = if i == 0 {
+                (document_revision_id, client_token.clone())
+            } else {
+                (None, None)
+            };
+
+            let result = self
+                .create_nested_blocks(
+                    document_id,
+                    &parent_id,
+                    batch.children_id,
+                    batch.descendants,
+                    Some(batch.index),
+                    batch_revision_id,
+                    batch_client_token,
+                )
+                .await?;
+
+            for relation in &result.block_id_relations {
+                id_map.insert(relation.temporary_block_id.clone(), relation.block_id.clone());
+            }
+            relations.extend(result.block_id_relations);
+            children.extend(result.children);
+            revision_id = result.document_revision_id;
+        }
+
+        Ok(CreateNestedBlocksResponse {
+            block_id_relations: relations.clone(),
+            children,
+            client_token,
+            document_revision_id: revision_id,
+        })
+    }
+}
@@ -0,0 +1,207 @@
+use super::DescendantBlock;
+use crate::utils::DiagramFence;
+use serde_json::json;
+
+/// 图表渲染为画板后，原始源码的保留方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DiagramSourceMode {
+    /// 不保留源码
+    #[default]
+    None,
+    /// 在画板后保留原代码块
+    Code,
+    /// 在画板后以行内代码文本作为说明
+    Caption,
+    /// 在画板后保留折叠的代码块：飞书代码块本身不能折叠，放在默认折叠的列表项下
+    Collapsed,
+}
+
+impl std::str::FromStr for DiagramSourceMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(DiagramSourceMode::None),
+            "code" => Ok(DiagramSourceMode::Code),
+            "caption" => Ok(DiagramSourceMode::Caption),
+            "collapsed" => Ok(DiagramSourceMode::Collapsed),
+            _ => Err(format!("无效的图表源码保留方式: {}。可选值: none, code, caption, collapsed", s)),
+        }
+    }
+}
+
+/// 导入时将图表代码块渲染为画板的选项
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DiagramOptions {
+    /// 原始源码的保留方式
+    pub keep_source: DiagramSourceMode,
+}
+
+/// 已替换为画板块的图表
+#[derive(Debug, Clone)]
+pub struct PlannedDiagram {
+    /// 图表代码块信息
+    pub fence: DiagramFence,
+    /// 画板块的临时 ID
+    pub board_block_id: String,
+    /// 画板之后保留的源码块临时 ID（code、caption 或 collapsed 模式）
+    pub source_block_id: Option<String>,
+    /// 导入画板失败时用于恢复的原代码块（源码块不是原代码块时才需要）
+    pub fallback_block: Option<DescendantBlock>,
+}
+
+/// 在转换后的块中，将图表标记段落替换为画板块，并按保留方式处理紧随其后的代码块
+///
+/// 找不到标记段落或其后没有代码块的图表保持为普通代码块。
+pub fn plan_diagram_blocks(
+    children_id: &mut Vec<String>,
+    descendants: &mut Vec<DescendantBlock>,
+    fences: Vec<DiagramFence>,
+    keep_source: DiagramSourceMode,
+) -> Vec<PlannedDiagram> {
+    let mut planned = Vec::new();
+
+    for fence in fences {
+        let Some(marker_index) = descendants
+            .iter()
+            .position(|b| b.block_type == 2 && plain_text(b).trim() == fence.marker)
+        else {
+            continue;
+        };
+        let marker_id = descendants[marker_index].block_id.clone();
+
+        // 标记段落所在的兄弟列表：一级块列表（None）或某个块的子块列表
+        let parent = if children_id.contains(&marker_id) {
+            None
+        } else {
+            match descendants.iter().position(|b| {
+                b.children.as_ref().is_some_and(|children| children.contains(&marker_id))
+            }) {
+                Some(index) => Some(descendants[index].block_id.clone()),
+                None => continue,
+            }
+        };
+        let siblings = siblings(children_id, descendants, parent.as_deref()).to_vec();
+        let position = siblings.iter().position(|id| *id == marker_id).unwrap_or_default();
+
+        let code_id = siblings.get(position + 1).filter(|id| {
+            descendants.iter().any(|b| &b.block_id == *id && b.block_type == 14)
+        });
+        let Some(code_id) = code_id.cloned() else {
+            // 没有对应的代码块，去掉标记段落即可
+            siblings_mut(children_id, descendants, parent.as_deref()).remove(position);
+            descendants.retain(|b| b.block_id != marker_id);
+            continue;
+        };
+        let code_index = descendants
+            .iter()
+            .position(|b| b.block_id == code_id)
+            .unwrap_or_default();
+
+        let (source_block_id, fallback_block) = match keep_source {
+            DiagramSourceMode::None => {
+                siblings_mut(children_id, descendants, parent.as_deref()).remove(position + 1);
+                (None, Some(descendants.remove(code_index)))
+            }
+            DiagramSourceMode::Code => (Some(code_id), None),
+            DiagramSourceMode::Caption => {
+                let code_block = descendants[code_index].clone();
+                let caption = &mut descendants[code_index];
+                caption.block_type = 2;
+                caption.code = None;
+                caption.text = Some(json!({
+                    "elements": [{
+                        "text_run": {
+                            "content": fence.source,
+                            "text_element_style": { "inline_code": true }
+                        }
+                    }]
+                }));
+                (Some(code_id), Some(code_block))
+            }
+            DiagramSourceMode::Collapsed => {
+                let toggle_id = format!("{}_source", code_id);
+                siblings_mut(children_id, descendants, parent.as_deref())[position + 1] = toggle_id.clone();
+                descendants.insert(
+                    code_index,
+                    DescendantBlock {
+                        block_id: toggle_id.clone(),
+                        block_type: 12,
+                        children: Some(vec![code_id]),
+                        bullet: Some(json!({
+                            "elements": [{ "text_run": { "content": "图表源码" } }],
+                            "style": { "folded": true }
+                        })),
+                        ..Default::default()
+                    },
+                );
+                (Some(toggle_id), None)
+            }
+        };
+
+        // 标记段落原地替换为画板块，保持临时 ID 不变
+        let board = descendants
+            .iter_mut()
+            .find(|b| b.block_id == marker_id)
+            .expect("标记段落已在上面找到");
+        board.block_type = 43;
+        board.text = None;
+        board.children = Some(vec![]);
+        board.board = Some(json!({ "align": 2 }));
+
+        planned.push(PlannedDiagram {
+            fence,
+            board_block_id: marker_id,
+            source_block_id,
+            fallback_block,
+        });
+    }
+
+    planned
+}
+
+fn siblings<'a>(
+    children_id: &'a [String],
+    descendants: &'a [DescendantBlock],
+    parent: Option<&str>,
+) -> &'a [String] {
+    match parent {
+        None => children_id,
+        Some(parent) => descendants
+            .iter()
+            .find(|b| b.block_id == parent)
+            .and_then(|b| b.children.as_deref())
+            .unwrap_or(&[]),
+    }
+}
+
+fn siblings_mut<'a>(
+    children_id: &'a mut Vec<String>,
+    descendants: &'a mut [DescendantBlock],
+    parent: Option<&str>,
+) -> &'a mut Vec<String> {
+    match parent {
+        None => children_id,
+        Some(parent) => descendants
+            .iter_mut()
+            .find(|b| b.block_id == parent)
+            .and_then(|b| b.children.as_mut())
+            .expect("父块已在上面找到"),
+    }
+}
+
+/// 文本块中所有文本片段拼接后的内容
+fn plain_text(block: &DescendantBlock) -> String {
+    block
+        .text
+        .as_ref()
+        .and_then(|text| text.get("elements"))
+        .and_then(|elements| elements.as_array())
+        .map(|elements| {
+            elements
+                .iter()
+                .filter_map(|e| e.pointer("/text_run/content").and_then(|c| c.as_str()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
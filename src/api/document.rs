@@ -24,6 +24,11 @@ pub struct DocumentInfo {
     pub url: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct GetDocumentResponse {
+    document: DocumentInfo,
+}
+
 pub struct DocumentApi {
     client: ApiClient,
 }
@@ -64,4 +69,15 @@ impl DocumentApi {
 
         Ok(document_info)
     }
-}
\ No newline at end of file
+
+    /// 获取文档基本信息，包括当前版本号
+    ///
+    /// 文档链接的域名因租户而异，接口也不返回链接，因此 `url` 为 `None`。
+    pub async fn get_document(&self, document_id: &str) -> Result<DocumentInfo> {
+        let url = format!("https://open.larkoffice.com/open-apis/docx/v1/documents/{}", document_id);
+
+        let response: GetDocumentResponse = self.client.get(&url, None).await?;
+
+        Ok(response.document)
+    }
+}
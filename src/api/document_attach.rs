@@ -0,0 +1,289 @@
+use super::batch_update_blocks::{ReplaceFileRequest, UpdateBlockRequest};
+use super::block_copy::{media_block_id, FILE_BLOCK_TYPE, IMAGE_BLOCK_TYPE};
+use super::media::UploadMediaRequest;
+use super::{
+    ApiClient, BatchUpdateBlocksApi, CreateNestedBlocksApi, DeleteBlocksApi, DescendantBlock, GetBlocksApi, MediaApi,
+};
+use crate::error::{LarkError, Result};
+use crate::utils::{detect_mime_type, image_dimensions, is_image_mime_type, BlockTree};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// 单个文件的上传限制
+const MAX_ATTACH_SIZE: u64 = 20 * 1024 * 1024;
+
+/// 插入的单个文件
+#[derive(Debug, Serialize)]
+pub struct AttachedFile {
+    /// 本地路径
+    pub path: String,
+    /// 上传时使用的文件名
+    pub file_name: String,
+    /// 插入为图片块（image）还是附件块（file）
+    pub kind: String,
+    /// 识别出的 MIME 类型
+    pub mime_type: String,
+    /// 文件大小（字节）
+    pub size: u64,
+    /// 图片宽度（从文件头读取）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    /// 图片高度（从文件头读取）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    /// 创建的图片或附件块 ID（dry-run 时为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_id: Option<String>,
+    /// 上传后的素材 token（dry-run 时为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_token: Option<String>,
+    /// 上传或替换失败的原因，失败文件的空块已删除
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 插入文件的结果
+#[derive(Debug, Serialize)]
+pub struct AttachResult {
+    /// 文档 ID
+    pub document_id: String,
+    /// 父块 ID
+    pub parent_block_id: String,
+    /// 是否只规划、未修改文档
+    pub dry_run: bool,
+    /// 按参数顺序排列的文件
+    pub files: Vec<AttachedFile>,
+    /// 插入失败的文件数
+    pub failure_count: usize,
+    /// 写入后的文档版本（dry-run 时为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub document_revision_id: Option<i32>,
+}
+
+/// 读取完成、等待上传的本地文件
+struct LocalFile {
+    info: AttachedFile,
+    content: Vec<u8>,
+}
+
+/// 文档附件 API：将本地图片和文件插入文档
+pub struct DocumentAttachApi {
+    client: ApiClient,
+}
+
+impl DocumentAttachApi {
+    pub fn new(client: ApiClient) -> Self {
+        Self { client }
+    }
+
+    /// 将本地文件依次插入父块（默认文档根）的指定位置
+    ///
+    /// 图片插入为图片块，其他文件插入为附件块：先创建空块，再以块 ID 为上传点上传素材，每个文件上传后立即替换到块中。
+    /// 所有文件都先在本地读取和检查，任何文件不可用时不修改文档；上传或替换失败的文件记录错误并删除其空块。
+    pub async fn attach(
+        &self,
+        document_id: &str,
+        paths: &[PathBuf],
+        parent_block_id: Option<&str>,
+        index: i32,
+    ) -> Result<AttachResult> {
+        if paths.is_empty() {
+            return Err(LarkError::ValidationError("需要至少一个文件".to_string()));
+        }
+        let files = paths.iter().map(|path| read_local_file(path)).collect::<Result<Vec<_>>>()?;
+        let parent_id = parent_block_id.filter(|id| !id.is_empty()).unwrap_or(document_id).to_string();
+
+        let mut result = AttachResult {
+            document_id: document_id.to_string(),
+            parent_block_id: parent_id.clone(),
+            dry_run: self.client.is_dry_run(),
+            files: Vec::new(),
+            failure_count: 0,
+            document_revision_id: None,
+        };
+        if result.dry_run {
+            result.files = files.into_iter().map(|f| f.info).collect();
+            return Ok(result);
+        }
+
+        // 1. 创建空的图片和附件块
+        let temp_ids: Vec<String> = files
+            .iter()
+            .map(|_| format!("temp-attach-{}", uuid::Uuid::new_v4()))
+            .collect();
+        let descendants = files
+            .iter()
+            .zip(&temp_ids)
+            .map(|(file, temp_id)| placeholder_block(temp_id, &file.info.kind))
+            .collect();
+        let response = CreateNestedBlocksApi::new(self.client.clone())
+            .create_nested_blocks(document_id, &parent_id, temp_ids.clone(), descendants, Some(index), None, None)
+            .await?;
+        let id_map: HashMap<&str, &str> = response
+            .block_id_relations
+            .iter()
+            .map(|r| (r.temporary_block_id.as_str(), r.block_id.as_str()))
+            .collect();
+
+        // 2. 以新块为上传点上传素材，3. 立即替换到块中
+        let mut revision_id = response.document_revision_id;
+        let mut unfilled = Vec::new();
+        for (file, temp_id) in files.into_iter().zip(&temp_ids) {
+            let LocalFile { mut info, content } = file;
+            let Some(created_id) = id_map.get(temp_id.as_str()).map(|id| id.to_string()) else {
+                info.error = Some("创建响应中缺少块 ID".to_string());
+                result.files.push(info);
+                continue;
+            };
+            let block_id = media_block_id(&response.children, &created_id);
+            match self.fill_placeholder(document_id, &block_id, &info, content, revision_id).await {
+                Ok((token, revision)) => {
+                    revision_id = revision;
+                    info.block_id = Some(block_id);
+                    info.file_token = Some(token);
+                }
+                Err(e) => {
+                    info.error = Some(e.to_string());
+                    unfilled.push(created_id);
+                }
+            }
+            result.files.push(info);
+        }
+        result.failure_count = result.files.iter().filter(|f| f.error.is_some()).count();
+
+        if !unfilled.is_empty() {
+            revision_id = self.remove_placeholders(document_id, &parent_id, &unfilled).await?;
+        }
+        result.document_revision_id = Some(revision_id);
+        Ok(result)
+    }
+
+    /// 以块 ID 为上传点上传素材并替换到块中，返回素材 token 和替换后的文档版本
+    async fn fill_placeholder(
+        &self,
+        document_id: &str,
+        block_id: &str,
+        info: &AttachedFile,
+        content: Vec<u8>,
+        revision_id: i32,
+    ) -> Result<(String, i32)> {
+        let is_image = info.kind == "image";
+        let request = UploadMediaRequest {
+            file_name: info.file_name.clone(),
+            parent_type: if is_image { "docx_image" } else { "docx_file" }.to_string(),
+            parent_node: block_id.to_string(),
+            size: content.len() as u64,
+            file_content: content,
+            checksum: None,
+            extra: Some(json!({ "drive_route_token": document_id }).to_string()),
+        };
+        let token = MediaApi::new(self.client.clone()).upload_drive_media(request).await?.file_token;
+
+        let request = UpdateBlockRequest::new(block_id.to_string());
+        let update = if is_image {
+            let size = |v: Option<u32>| v.map(|v| v as i32);
+            request.with_replace_image(token.clone(), size(info.width), size(info.height), None)
+        } else {
+            let mut request = request;
+            request.replace_file = Some(ReplaceFileRequest { token: token.clone() });
+            request
+        };
+        let revision_id = BatchUpdateBlocksApi::new(self.client.clone())
+            .batch_update_all(document_id, vec![update], Some(revision_id), None, None)
+            .await?
+            .document_revision_id;
+        Ok((token, revision_id))
+    }
+
+    /// 删除未填充素材的空块，从后往前逐个删除，返回删除后的文档版本
+    async fn remove_placeholders(&self, document_id: &str, parent_id: &str, block_ids: &[String]) -> Result<i32> {
+        let blocks = GetBlocksApi::new(self.client.clone())
+            .get_all_document_blocks(document_id, None, None)
+            .await?;
+        let tree = BlockTree::new(blocks);
+        let mut positions: Vec<i32> = block_ids
+            .iter()
+            .filter_map(|id| tree.child_index(parent_id, id))
+            .map(|index| index as i32)
+            .collect();
+        positions.sort_unstable();
+
+        let delete_api = DeleteBlocksApi::new(self.client.clone());
+        let mut revision_id = None;
+        for position in positions.into_iter().rev() {
+            revision_id = Some(
+                delete_api
+                    .delete_blocks(document_id, parent_id, position, position + 1, revision_id, None)
+                    .await?
+                    .document_revision_id,
+            );
+        }
+        match revision_id {
+            Some(revision_id) => Ok(revision_id),
+            None => Err(LarkError::ValidationError(format!("找不到需要删除的空块: {}", block_ids.join(", ")))),
+        }
+    }
+}
+
+/// 读取本地文件，识别类型并检查大小
+fn read_local_file(path: &Path) -> Result<LocalFile> {
+    if !path.is_file() {
+        return Err(LarkError::ValidationError(format!("文件不存在或不是文件: {}", path.display())));
+    }
+    let content = std::fs::read(path)
+        .map_err(|e| LarkError::IoError(format!("读取文件失败 {}: {}", path.display(), e)))?;
+    let size = content.len() as u64;
+    if size == 0 {
+        return Err(LarkError::ValidationError(format!("文件为空: {}", path.display())));
+    }
+    if size > MAX_ATTACH_SIZE {
+        return Err(LarkError::ValidationError(format!("文件超过 20MB 上传限制: {}", path.display())));
+    }
+
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| LarkError::ValidationError(format!("无法获取文件名: {}", path.display())))?
+        .to_string();
+    let mime_type = detect_mime_type(path, &content);
+    let is_image = is_image_mime_type(mime_type);
+    let (width, height) = match is_image.then(|| image_dimensions(&content)).flatten() {
+        Some((width, height)) => (Some(width), Some(height)),
+        None => (None, None),
+    };
+
+    Ok(LocalFile {
+        info: AttachedFile {
+            path: path.display().to_string(),
+            file_name,
+            kind: if is_image { "image" } else { "file" }.to_string(),
+            mime_type: mime_type.to_string(),
+            size,
+            width,
+            height,
+            block_id: None,
+            file_token: None,
+            error: None,
+        },
+        content,
+    })
+}
+
+/// 空的图片或附件块，素材上传后再替换
+fn placeholder_block(block_id: &str, kind: &str) -> DescendantBlock {
+    let mut block = DescendantBlock {
+        block_id: block_id.to_string(),
+        children: Some(Vec::new()),
+        ..Default::default()
+    };
+    if kind == "image" {
+        block.block_type = IMAGE_BLOCK_TYPE;
+        block.image = Some(json!({}));
+    } else {
+        block.block_type = FILE_BLOCK_TYPE;
+        block.file = Some(json!({ "token": "" }));
+    }
+    block
+}
@@ -0,0 +1,349 @@
+use super::batch_update_blocks::{ReplaceFileRequest, UpdateBlockRequest};
+use super::block_batcher::{BatchLimits, BlockBatcher};
+use super::block_copy::{copy_blocks_with_media, media_block_id, MediaBlock, SkippedBlock, IMAGE_BLOCK_TYPE};
+use super::media::{UploadMediaRequest, MEDIA_UPLOAD_ALL_LIMIT};
+use super::{
+    ApiClient, BatchUpdateBlocksApi, CreateNestedBlocksApi, DeleteBlocksApi, DocumentApi, DownloadMediaApi,
+    DownloadOptions, GetBlocksApi, MediaApi,
+};
+use crate::error::{LarkError, Result};
+use crate::utils::{selection_ranges, BlockSelector, BlockTree, SelectionRange};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// 复制或移动块的结果
+#[derive(Debug, Serialize)]
+pub struct CopyBlocksResult {
+    /// 源文档 ID
+    pub source_document_id: String,
+    /// 源块 ID 或选择器
+    pub source: String,
+    /// 目标文档 ID
+    pub document_id: String,
+    /// 目标父块 ID
+    pub parent_block_id: String,
+    /// 是否只规划、未修改文档
+    pub dry_run: bool,
+    /// 复制后是否删除了源块
+    pub moved: bool,
+    /// 复制的一级源块 ID（按文档顺序，不含已被祖先块包含的块）
+    pub source_block_ids: Vec<String>,
+    /// 新建的一级块 ID（dry-run 时为空）
+    pub block_ids: Vec<String>,
+    /// 创建的块总数
+    pub block_count: usize,
+    /// 重新上传素材的图片和附件块
+    pub media: Vec<MediaBlock>,
+    /// 无法复制而被跳过的块
+    pub skipped_blocks: Vec<SkippedBlock>,
+    /// 目标文档写入后的版本（dry-run 时为读取时的版本）
+    pub document_revision_id: i32,
+    /// 源文档删除后的版本（仅移动时）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_document_revision_id: Option<i32>,
+}
+
+/// 文档复制 API：在文档之间复制或移动块
+pub struct DocumentCopyApi {
+    client: ApiClient,
+}
+
+impl DocumentCopyApi {
+    pub fn new(client: ApiClient) -> Self {
+        Self { client }
+    }
+
+    /// 将源文档中的块（块 ID 或选择器）复制到目标文档的父块（默认文档根）下
+    ///
+    /// 图片和附件先以空块创建，再下载原素材上传到新块并替换；素材复制失败时删除未填充的空块并返回错误。
+    /// `move_blocks` 为 true 时，复制全部成功后删除源块；有无法复制的块时拒绝移动，除非 `force` 为 true。
+    #[allow(clippy::too_many_arguments)]
+    pub async fn copy(
+        &self,
+        source_document_id: &str,
+        source: &str,
+        document_id: &str,
+        parent_block_id: Option<&str>,
+        index: i32,
+        move_blocks: bool,
+        force: bool,
+    ) -> Result<CopyBlocksResult> {
+        let (tree, source_revision_id) = self.load(source_document_id).await?;
+        let source_block_ids = resolve_source(&tree, source_document_id, source)?;
+        let ranges = selection_ranges(&tree, &source_block_ids)?;
+        let root_ids: Vec<String> = ranges.iter().flat_map(|r| r.block_ids.clone()).collect();
+
+        let parent_id = parent_block_id.filter(|id| !id.is_empty()).unwrap_or(document_id).to_string();
+        if move_blocks && source_document_id == document_id {
+            check_move_target(&tree, &root_ids, &parent_id)?;
+        }
+
+        let copied = copy_blocks_with_media(&tree, &root_ids)?;
+        if copied.children_id.is_empty() {
+            return Err(LarkError::ValidationError(format!("{} 选中的块都无法复制", source)));
+        }
+        if move_blocks {
+            check_move_skipped(&copied.skipped, force)?;
+        }
+
+        let revision_id = if source_document_id == document_id {
+            source_revision_id
+        } else {
+            DocumentApi::new(self.client.clone())
+                .get_document(document_id)
+                .await?
+                .revision_id
+        };
+
+        let mut result = CopyBlocksResult {
+            source_document_id: source_document_id.to_string(),
+            source: source.to_string(),
+            document_id: document_id.to_string(),
+            parent_block_id: parent_id.clone(),
+            dry_run: self.client.is_dry_run(),
+            moved: false,
+            source_block_ids: root_ids.clone(),
+            block_ids: Vec::new(),
+            block_count: copied.descendants.len(),
+            media: copied.media.clone(),
+            skipped_blocks: copied.skipped.clone(),
+            document_revision_id: revision_id,
+            source_document_revision_id: None,
+        };
+        if result.dry_run {
+            result.moved = move_blocks;
+            return Ok(result);
+        }
+
+        let batches = BlockBatcher::new(copied.descendants, BatchLimits::default())?.plan(&copied.children_id, index)?;
+        let response = CreateNestedBlocksApi::new(self.client.clone())
+            .create_batches(document_id, &parent_id, batches, Some(revision_id), None)
+            .await?;
+        let id_map: HashMap<&str, &str> = response
+            .block_id_relations
+            .iter()
+            .map(|r| (r.temporary_block_id.as_str(), r.block_id.as_str()))
+            .collect();
+        result.block_ids = copied
+            .children_id
+            .iter()
+            .filter_map(|id| id_map.get(id.as_str()).map(|id| id.to_string()))
+            .collect();
+        result.document_revision_id = response.document_revision_id;
+
+        // 每个素材块：(新建的块 ID，素材块 ID)；附件的新建块是包裹它的视图块
+        let placeholders = copied
+            .media
+            .iter()
+            .map(|media| {
+                id_map
+                    .get(media.temporary_block_id.as_str())
+                    .map(|id| (id.to_string(), media_block_id(&response.children, id)))
+                    .ok_or_else(|| LarkError::ParseError(format!("创建响应中缺少块 {} 的 ID", media.source_block_id)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut updates = Vec::new();
+        let mut failure = None;
+        for (media, (_, block_id)) in copied.media.iter().zip(&placeholders) {
+            match self.reupload(document_id, block_id, media).await {
+                Ok(update) => updates.push(update),
+                Err(error) => {
+                    failure = Some(error);
+                    break;
+                }
+            }
+        }
+        let mut filled = updates.len();
+        if !updates.is_empty() {
+            match BatchUpdateBlocksApi::new(self.client.clone())
+                .batch_update_all(document_id, updates, Some(result.document_revision_id), None, None)
+                .await
+            {
+                Ok(response) => result.document_revision_id = response.document_revision_id,
+                Err(error) => {
+                    filled = 0;
+                    failure.get_or_insert(error);
+                }
+            }
+        }
+        if let Some(error) = failure {
+            // 源块尚未删除；只清理目标文档中没有填充素材的空块，避免留下空图片或空附件
+            let unfilled: Vec<String> = placeholders[filled..].iter().map(|(id, _)| id.clone()).collect();
+            self.remove_placeholders(document_id, &unfilled).await?;
+            return Err(error);
+        }
+
+        if move_blocks {
+            let revision_id = if source_document_id == document_id {
+                // 新块可能插入在源块之前，按块 ID 重新计算删除区间
+                let (tree, revision_id) = self.load(source_document_id).await?;
+                let ranges = selection_ranges(&tree, &root_ids)?;
+                let revision_id = self.delete_ranges(source_document_id, &ranges, revision_id).await?;
+                result.document_revision_id = revision_id;
+                revision_id
+            } else {
+                self.delete_ranges(source_document_id, &ranges, source_revision_id).await?
+            };
+            result.source_document_revision_id = Some(revision_id);
+            result.moved = true;
+        }
+
+        Ok(result)
+    }
+
+    /// 下载原素材，上传到目标文档的新块，返回替换素材的更新请求
+    ///
+    /// 素材下载到随机命名的临时文件，超过 20MB 时从磁盘分片上传，不会整个读入内存。
+    async fn reupload(&self, document_id: &str, block_id: &str, media: &MediaBlock) -> Result<UpdateBlockRequest> {
+        let temp_file = tempfile::Builder::new().prefix("lark-copy-").tempfile()?;
+        let temp_path_str = temp_file.path().to_string_lossy().to_string();
+        let downloaded = DownloadMediaApi::new(self.client.clone())
+            .download_media(&media.token, &temp_path_str, None, &DownloadOptions::default())
+            .await;
+        // 下载的分片文件和续传状态写在临时文件旁边，临时文件删除时不会一并清理
+        for suffix in [".part", ".part.json", ".part.stream.json"] {
+            let _ = std::fs::remove_file(format!("{}{}", temp_path_str, suffix));
+        }
+        let downloaded = downloaded?;
+
+        let is_image = media.block_type == IMAGE_BLOCK_TYPE;
+        let file_name = media
+            .name
+            .clone()
+            .or(downloaded.file_name)
+            .filter(|n| !n.is_empty())
+            .unwrap_or_else(|| media.token.clone());
+        let parent_type = if is_image { "docx_image" } else { "docx_file" };
+        let extra = Some(serde_json::json!({ "drive_route_token": document_id }).to_string());
+        let media_api = MediaApi::new(self.client.clone());
+
+        let token = if downloaded.file_size > MEDIA_UPLOAD_ALL_LIMIT {
+            media_api
+                .upload_media_multipart(temp_file.path(), &file_name, parent_type, block_id, extra)
+                .await?
+                .file_token
+        } else {
+            let file_content = std::fs::read(temp_file.path())
+                .map_err(|e| LarkError::IoError(format!("读取下载的素材失败: {}", e)))?;
+            let request = UploadMediaRequest {
+                file_name,
+                parent_type: parent_type.to_string(),
+                parent_node: block_id.to_string(),
+                size: file_content.len() as u64,
+                file_content,
+                checksum: None,
+                extra,
+            };
+            media_api.upload_drive_media(request).await?.file_token
+        };
+
+        let request = UpdateBlockRequest::new(block_id.to_string());
+        Ok(if is_image {
+            request.with_replace_image(token, media.width, media.height, media.align)
+        } else {
+            let mut request = request;
+            request.replace_file = Some(ReplaceFileRequest { token });
+            request
+        })
+    }
+
+    /// 删除未填充素材的空块：按各自父块从后往前逐个删除
+    async fn remove_placeholders(&self, document_id: &str, block_ids: &[String]) -> Result<()> {
+        let blocks = GetBlocksApi::new(self.client.clone())
+            .get_all_document_blocks(document_id, None, None)
+            .await?;
+        let tree = BlockTree::new(blocks);
+        let mut positions: Vec<(String, i32)> = block_ids
+            .iter()
+            .filter_map(|id| {
+                let parent_id = tree.get(id)?.parent_id.clone();
+                let index = tree.child_index(&parent_id, id)? as i32;
+                Some((parent_id, index))
+            })
+            .collect();
+        positions.sort_unstable();
+
+        let delete_api = DeleteBlocksApi::new(self.client.clone());
+        for (parent_id, index) in positions.into_iter().rev() {
+            delete_api
+                .delete_blocks(document_id, &parent_id, index, index + 1, None, None)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// 从后往前删除各区间，返回删除后的文档版本
+    async fn delete_ranges(&self, document_id: &str, ranges: &[SelectionRange], revision_id: i32) -> Result<i32> {
+        let delete_api = DeleteBlocksApi::new(self.client.clone());
+        let mut revision_id = revision_id;
+        for range in ranges.iter().rev() {
+            revision_id = delete_api
+                .delete_blocks(
+                    document_id,
+                    &range.parent_id,
+                    range.start_index,
+                    range.end_index,
+                    Some(revision_id),
+                    None,
+                )
+                .await?
+                .document_revision_id;
+        }
+        Ok(revision_id)
+    }
+
+    async fn load(&self, document_id: &str) -> Result<(BlockTree, i32)> {
+        let revision_id = DocumentApi::new(self.client.clone())
+            .get_document(document_id)
+            .await?
+            .revision_id;
+        let blocks = GetBlocksApi::new(self.client.clone())
+            .get_all_document_blocks(document_id, Some(revision_id), None)
+            .await?;
+        Ok((BlockTree::new(blocks), revision_id))
+    }
+}
+
+/// 解析源参数：文档中存在的块 ID 直接使用，否则按选择器求值
+pub fn resolve_source(tree: &BlockTree, document_id: &str, source: &str) -> Result<Vec<String>> {
+    if tree.get(source).is_some() {
+        return Ok(vec![source.to_string()]);
+    }
+    let selected = BlockSelector::parse(source)?.select(tree, document_id);
+    if selected.is_empty() {
+        return Err(LarkError::ValidationError(format!("{} 没有匹配的块", source)));
+    }
+    Ok(selected)
+}
+
+/// 移动时被跳过的块会随源块删除且不会重建，除非 `force` 为 true，否则列出这些块并拒绝移动
+pub fn check_move_skipped(skipped: &[SkippedBlock], force: bool) -> Result<()> {
+    if skipped.is_empty() || force {
+        return Ok(());
+    }
+    let blocks: Vec<String> = skipped
+        .iter()
+        .map(|b| format!("{}（类型 {}）", b.block_id, b.block_type))
+        .collect();
+    Err(LarkError::ValidationError(format!(
+        "以下 {} 个块无法复制，移动后会随源块一起删除而丢失: {}。确认丢弃这些块请加 --force",
+        skipped.len(),
+        blocks.join(", ")
+    )))
+}
+
+/// 在同一文档中移动时，目标父块不能是被移动的块或其子孙块
+pub fn check_move_target(tree: &BlockTree, root_ids: &[String], parent_id: &str) -> Result<()> {
+    let mut current = Some(parent_id.to_string());
+    while let Some(id) = current {
+        if root_ids.contains(&id) {
+            return Err(LarkError::ValidationError(format!(
+                "目标父块 {} 位于被移动的块 {} 之中",
+                parent_id, id
+            )));
+        }
+        current = tree.get(&id).map(|b| b.parent_id.clone()).filter(|p| !p.is_empty());
+    }
+    Ok(())
+}
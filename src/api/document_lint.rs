@@ -0,0 +1,274 @@
+use super::batch_update_blocks::{TextElement, UpdateBlockRequest, UpdateTextElementsRequest};
+use super::{ApiClient, BatchUpdateBlocksApi, DeleteBlocksApi, DocumentApi, GetBlocksApi, GetUserInfoApi, WikiApi};
+use crate::error::{LarkError, Result};
+use crate::utils::{
+    document_order, lint_blocks, lint_references, selection_ranges, BlockTree, LintConfig, LintFinding, LintFix,
+    LintRule, LintTarget, Severity,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// 表示对象不存在或无权访问的接口错误码：文档不存在、已删除、无权限，知识库节点不存在、无权限，无权访问用户
+const BROKEN_REFERENCE_CODES: [i32; 6] = [1770002, 1770003, 1770032, 131005, 131006, 41050];
+
+/// 网络错误、限流或服务端异常时的重试间隔
+const REFERENCE_RETRY_DELAYS: [Duration; 2] = [Duration::from_secs(1), Duration::from_secs(3)];
+
+/// 文档检查报告
+#[derive(Debug, Serialize)]
+pub struct LintReport {
+    /// 文档 ID
+    pub document_id: String,
+    /// 检查时的文档版本（修复后为修复后的版本）
+    pub document_revision_id: i32,
+    /// 启用的规则
+    pub rules: Vec<LintRule>,
+    /// 是否只规划修复、未修改文档
+    pub dry_run: bool,
+    /// 错误数
+    pub error_count: usize,
+    /// 警告数
+    pub warning_count: usize,
+    /// 已修复（dry-run 时为可修复）的问题数
+    pub fixed_count: usize,
+    /// 发现的问题，按文档顺序排列
+    pub findings: Vec<LintFinding>,
+    /// 因网络错误、限流或服务端异常而未能检查的引用，不计入问题
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unchecked_references: Vec<UncheckedReference>,
+}
+
+/// 未能检查的引用
+#[derive(Debug, Serialize)]
+pub struct UncheckedReference {
+    /// 产生引用的规则
+    pub rule: LintRule,
+    /// 引用所在的块 ID
+    pub block_id: String,
+    /// 引用内容
+    pub label: String,
+    /// 最后一次请求的错误
+    pub error: String,
+}
+
+/// 引用的检查结果
+#[derive(Debug, Clone)]
+enum ReferenceStatus {
+    Resolved,
+    /// 对象不存在或无权访问
+    Broken(String),
+    /// 重试后仍然失败，无法判断
+    Unchecked(String),
+}
+
+impl LintReport {
+    /// 未修复且严重程度不低于指定级别的问题数
+    pub fn unfixed_count(&self, severity: Severity) -> usize {
+        self.findings.iter().filter(|f| !f.fixed && f.severity >= severity).count()
+    }
+}
+
+/// 文档检查 API：检查文档结构和引用，并自动修复可安全修复的问题
+pub struct DocumentLintApi {
+    client: ApiClient,
+}
+
+impl DocumentLintApi {
+    pub fn new(client: ApiClient) -> Self {
+        Self { client }
+    }
+
+    /// 检查文档，`fix` 为 true 时修复可安全修复的问题
+    ///
+    /// 删除类修复按父块合并为区间后从后往前删除；文本修复通过批量更新完成。
+    pub async fn lint(&self, document_id: &str, config: &LintConfig, fix: bool) -> Result<LintReport> {
+        let revision_id = DocumentApi::new(self.client.clone())
+            .get_document(document_id)
+            .await?
+            .revision_id;
+        let blocks = GetBlocksApi::new(self.client.clone())
+            .get_all_document_blocks(document_id, Some(revision_id), None)
+            .await?;
+        let tree = BlockTree::new(blocks);
+
+        let mut findings = lint_blocks(&tree, document_id, config);
+        let (reference_findings, unchecked_references) = self.check_references(&tree, document_id, config).await;
+        findings.extend(reference_findings);
+
+        let order: HashMap<String, usize> = document_order(&tree, document_id)
+            .into_iter()
+            .enumerate()
+            .map(|(i, id)| (id, i))
+            .collect();
+        findings.sort_by_key(|f| order.get(&f.block_id).copied().unwrap_or(usize::MAX));
+
+        let mut report = LintReport {
+            document_id: document_id.to_string(),
+            document_revision_id: revision_id,
+            rules: config.enabled_rules(),
+            dry_run: self.client.is_dry_run(),
+            error_count: findings.iter().filter(|f| f.severity == Severity::Error).count(),
+            warning_count: findings.iter().filter(|f| f.severity == Severity::Warning).count(),
+            fixed_count: 0,
+            findings,
+            unchecked_references,
+        };
+        if fix {
+            self.fix(&tree, &mut report).await?;
+        }
+        Ok(report)
+    }
+
+    /// 检查 @用户、@文档和文档链接是否可以解析，同一对象只请求一次
+    ///
+    /// 只有对象不存在或无权访问时报告为问题；网络错误、限流和服务端异常重试后仍失败的引用单独返回。
+    async fn check_references(
+        &self,
+        tree: &BlockTree,
+        document_id: &str,
+        config: &LintConfig,
+    ) -> (Vec<LintFinding>, Vec<UncheckedReference>) {
+        let mut resolved: HashMap<LintTarget, ReferenceStatus> = HashMap::new();
+        let mut findings = Vec::new();
+        let mut unchecked = Vec::new();
+
+        for reference in lint_references(tree, document_id, config) {
+            let status = match resolved.get(&reference.target) {
+                Some(status) => status.clone(),
+                None => {
+                    let status = self.check_reference(&reference.target).await;
+                    resolved.insert(reference.target.clone(), status.clone());
+                    status
+                }
+            };
+            let error = match status {
+                ReferenceStatus::Resolved => continue,
+                ReferenceStatus::Unchecked(error) => {
+                    unchecked.push(UncheckedReference {
+                        rule: reference.rule,
+                        block_id: reference.block_id,
+                        label: reference.label,
+                        error,
+                    });
+                    continue;
+                }
+                ReferenceStatus::Broken(error) => error,
+            };
+            let message = match reference.rule {
+                LintRule::BrokenLink => format!("链接指向无法读取的文档 {}: {}", reference.label, error),
+                _ => format!("无法解析的提及 {}: {}", reference.label, error),
+            };
+            findings.push(LintFinding {
+                rule: reference.rule,
+                severity: config.severity(reference.rule),
+                block_id: reference.block_id,
+                message,
+                fix: None,
+                fixed: false,
+            });
+        }
+        (findings, unchecked)
+    }
+
+    /// 解析引用，无法判断的错误按间隔重试
+    async fn check_reference(&self, target: &LintTarget) -> ReferenceStatus {
+        let mut delays = REFERENCE_RETRY_DELAYS.iter();
+        loop {
+            let error = match self.resolve(target).await {
+                Ok(()) => return ReferenceStatus::Resolved,
+                Err(error) if is_broken_reference(&error) => return ReferenceStatus::Broken(error.to_string()),
+                Err(error) => error,
+            };
+            match delays.next() {
+                Some(delay) => tokio::time::sleep(*delay).await,
+                None => return ReferenceStatus::Unchecked(error.to_string()),
+            }
+        }
+    }
+
+    async fn resolve(&self, target: &LintTarget) -> Result<()> {
+        match target {
+            LintTarget::User(user_id) if user_id.is_empty() => {
+                Err(LarkError::ValidationError("缺少用户 ID".to_string()))
+            }
+            LintTarget::User(user_id) => GetUserInfoApi::new(self.client.clone())
+                .get_user_info(user_id, Some("open_id"), None)
+                .await
+                .map(|_| ()),
+            LintTarget::Docx(token) => DocumentApi::new(self.client.clone()).get_document(token).await.map(|_| ()),
+            LintTarget::Wiki(token) => WikiApi::new(self.client.clone())
+                .get_knowledge_space_node(token, Some("wiki"))
+                .await
+                .map(|_| ()),
+        }
+    }
+
+    async fn fix(&self, tree: &BlockTree, report: &mut LintReport) -> Result<()> {
+        let mut updates = Vec::new();
+        let mut deletes = Vec::new();
+        for finding in &report.findings {
+            match &finding.fix {
+                Some(LintFix::Delete) => deletes.push(finding.block_id.clone()),
+                Some(LintFix::UpdateElements { elements }) => {
+                    let elements: Vec<TextElement> = serde_json::from_value(elements.clone())?;
+                    let mut request = UpdateBlockRequest::new(finding.block_id.clone());
+                    request.update_text_elements = Some(UpdateTextElementsRequest { elements });
+                    updates.push(request);
+                }
+                None => {}
+            }
+        }
+        report.fixed_count = updates.len() + deletes.len();
+        if report.dry_run || report.fixed_count == 0 {
+            return Ok(());
+        }
+
+        let mut revision_id = BatchUpdateBlocksApi::new(self.client.clone())
+            .batch_update_all(&report.document_id, updates, Some(report.document_revision_id), None, None)
+            .await?
+            .document_revision_id;
+
+        // 更新不改变块结构，删除区间可以按检查时的块树计算
+        let delete_api = DeleteBlocksApi::new(self.client.clone());
+        for range in selection_ranges(tree, &deletes)?.iter().rev() {
+            revision_id = delete_api
+                .delete_blocks(
+                    &report.document_id,
+                    &range.parent_id,
+                    range.start_index,
+                    range.end_index,
+                    Some(revision_id),
+                    None,
+                )
+                .await?
+                .document_revision_id;
+        }
+
+        for finding in &mut report.findings {
+            finding.fixed = finding.fix.is_some();
+        }
+        report.document_revision_id = revision_id;
+        Ok(())
+    }
+}
+
+/// 错误是否表示被引用的对象不存在或无权访问
+pub fn is_broken_reference(error: &LarkError) -> bool {
+    match error {
+        LarkError::ValidationError(_) => true,
+        LarkError::ApiError { code, .. } => BROKEN_REFERENCE_CODES.contains(code),
+        // HTTP 状态码非 2xx 时，错误信息中带有状态码和响应体
+        LarkError::NetworkError(message) => {
+            let code = message
+                .find('{')
+                .and_then(|start| serde_json::from_str::<serde_json::Value>(&message[start..]).ok())
+                .and_then(|body| body.get("code")?.as_i64());
+            match code {
+                Some(code) => BROKEN_REFERENCE_CODES.contains(&(code as i32)),
+                None => message.contains("status code: 403") || message.contains("status code: 404"),
+            }
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,111 @@
+use super::batch_update_blocks::{TextElement, UpdateBlockRequest, UpdateTextElementsRequest};
+use super::{ApiClient, BatchUpdateBlocksApi, DocumentApi, GetBlocksApi};
+use crate::error::{LarkError, Result};
+use crate::utils::{block_elements, replace_in_elements, TextMatch, TextMatcher};
+use serde::Serialize;
+
+/// 查找替换结果
+#[derive(Debug, Serialize)]
+pub struct ReplaceResult {
+    /// 文档 ID
+    pub document_id: String,
+    /// 查找时的文档版本
+    pub base_revision_id: i32,
+    /// 替换完成后的文档版本（预览时与 base_revision_id 相同）
+    pub document_revision_id: i32,
+    /// 是否只预览、未修改文档
+    pub preview: bool,
+    /// 匹配总数
+    pub match_count: usize,
+    /// 包含匹配的块数
+    pub block_count: usize,
+    /// 每个块中的匹配
+    pub blocks: Vec<BlockMatches>,
+}
+
+/// 单个块中的匹配
+#[derive(Debug, Serialize)]
+pub struct BlockMatches {
+    /// 块 ID
+    pub block_id: String,
+    /// 块类型
+    pub block_type: i32,
+    /// 匹配列表
+    pub matches: Vec<TextMatch>,
+}
+
+/// 文档查找替换 API
+pub struct DocumentReplaceApi {
+    client: ApiClient,
+}
+
+impl DocumentReplaceApi {
+    pub fn new(client: ApiClient) -> Self {
+        Self { client }
+    }
+
+    /// 在文档所有含文本的块（包括标题、列表、高亮块和表格单元格中的块）中查找并替换
+    ///
+    /// `preview` 为 true 或处于 dry-run 模式时只返回匹配，不修改文档。
+    pub async fn replace(
+        &self,
+        document_id: &str,
+        matcher: &TextMatcher,
+        replacement: &str,
+        preview: bool,
+    ) -> Result<ReplaceResult> {
+        let base_revision_id = DocumentApi::new(self.client.clone())
+            .get_document(document_id)
+            .await?
+            .revision_id;
+        let blocks = GetBlocksApi::new(self.client.clone())
+            .get_all_document_blocks(document_id, Some(base_revision_id), None)
+            .await?;
+
+        let mut result = ReplaceResult {
+            document_id: document_id.to_string(),
+            base_revision_id,
+            document_revision_id: base_revision_id,
+            preview: preview || self.client.is_dry_run(),
+            match_count: 0,
+            block_count: 0,
+            blocks: Vec::new(),
+        };
+
+        let mut requests = Vec::new();
+        for block in &blocks {
+            let Some(elements) = block_elements(block) else {
+                continue;
+            };
+            let Some((replaced, matches)) = replace_in_elements(elements, matcher, replacement) else {
+                continue;
+            };
+
+            let elements: Vec<TextElement> = serde_json::from_value(replaced).map_err(|e| {
+                LarkError::ParseError(format!("无法解析块 {} 的文本内容: {}", block.block_id, e))
+            })?;
+            let mut request = UpdateBlockRequest::new(block.block_id.clone());
+            request.update_text_elements = Some(UpdateTextElementsRequest { elements });
+            requests.push(request);
+
+            result.match_count += matches.len();
+            result.blocks.push(BlockMatches {
+                block_id: block.block_id.clone(),
+                block_type: block.block_type,
+                matches,
+            });
+        }
+        result.block_count = result.blocks.len();
+
+        if result.preview || requests.is_empty() {
+            return Ok(result);
+        }
+
+        // 以查找时的版本为基准分批更新，文档在此期间被修改时更新失败
+        result.document_revision_id = BatchUpdateBlocksApi::new(self.client.clone())
+            .batch_update_all(document_id, requests, Some(base_revision_id), None, None)
+            .await?
+            .document_revision_id;
+        Ok(result)
+    }
+}
@@ -0,0 +1,128 @@
+use super::import_plan::ImportPlan;
+use super::{ApiClient, DeleteBlocksApi, DocumentApi, GetBlocksApi, ImportDocumentsApi};
+use crate::error::Result;
+use crate::utils::{find_section, parse_heading_path, BlockTree, DocumentSection, SectionMode};
+use serde::Serialize;
+
+/// 章节写入结果
+#[derive(Debug, Serialize)]
+pub struct SectionEditResult {
+    /// 文档 ID
+    pub document_id: String,
+    /// 标题路径
+    pub heading_path: String,
+    /// 写入方式
+    pub mode: SectionMode,
+    /// 找到的章节
+    pub section: DocumentSection,
+    /// 删除的块 ID（仅 replace）
+    pub deleted_block_ids: Vec<String>,
+    /// 新内容插入的位置
+    pub index: i32,
+    /// 创建的一级块 ID
+    pub created_block_ids: Vec<String>,
+    /// 写入完成后的文档版本
+    pub document_revision_id: i32,
+    /// dry-run 模式下的导入计划
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan: Option<ImportPlan>,
+}
+
+/// 按标题定位章节并写入内容的 API
+pub struct DocumentSectionApi {
+    client: ApiClient,
+}
+
+impl DocumentSectionApi {
+    pub fn new(client: ApiClient) -> Self {
+        Self { client }
+    }
+
+    /// 将内容写入标题路径对应的章节
+    ///
+    /// replace 先删除标题与下一个同级或更高级标题之间的块，再在原位置导入新内容；
+    /// append 和 prepend 分别插入到章节末尾和标题之后。标题本身始终保留。
+    pub async fn write_section(
+        &self,
+        document_id: &str,
+        heading_path: &str,
+        content: &str,
+        content_type: &str,
+        mode: SectionMode,
+    ) -> Result<SectionEditResult> {
+        let path = parse_heading_path(heading_path)?;
+
+        let mut revision_id = DocumentApi::new(self.client.clone())
+            .get_document(document_id)
+            .await?
+            .revision_id;
+        let blocks = GetBlocksApi::new(self.client.clone())
+            .get_all_document_blocks(document_id, Some(revision_id), None)
+            .await?;
+        let tree = BlockTree::new(blocks);
+        let section = find_section(&tree, document_id, &path)?;
+
+        let index = match mode {
+            SectionMode::Replace | SectionMode::Prepend => section.start,
+            SectionMode::Append => section.end,
+        } as i32;
+        let deleted_block_ids = match mode {
+            SectionMode::Replace => tree.children(document_id)[section.start..section.end].to_vec(),
+            SectionMode::Append | SectionMode::Prepend => Vec::new(),
+        };
+
+        let mut result = SectionEditResult {
+            document_id: document_id.to_string(),
+            heading_path: heading_path.to_string(),
+            mode,
+            section: section.clone(),
+            deleted_block_ids,
+            index,
+            created_block_ids: Vec::new(),
+            document_revision_id: revision_id,
+            plan: None,
+        };
+
+        let import_api = ImportDocumentsApi::new(self.client.clone());
+        if self.client.is_dry_run() {
+            result.plan = Some(
+                import_api
+                    .plan_document(document_id, content, content_type, document_id, index, false)
+                    .await?,
+            );
+            return Ok(result);
+        }
+
+        // 1. 删除原有内容
+        if !result.deleted_block_ids.is_empty() {
+            let response = DeleteBlocksApi::new(self.client.clone())
+                .delete_blocks(
+                    document_id,
+                    document_id,
+                    section.start as i32,
+                    section.end as i32,
+                    Some(revision_id),
+                    None,
+                )
+                .await?;
+            revision_id = response.document_revision_id;
+        }
+
+        // 2. 在章节位置导入新内容
+        let (response, created_block_ids) = import_api
+            .import_document_with_roots(
+                document_id,
+                content,
+                content_type,
+                document_id,
+                index,
+                Some(revision_id),
+                None,
+            )
+            .await?;
+
+        result.created_block_ids = created_block_ids;
+        result.document_revision_id = response.document_revision_id;
+        Ok(result)
+    }
+}
@@ -0,0 +1,169 @@
+use super::batch_update_blocks::{TextElement, UpdateBlockRequest, UpdateTextElementsRequest};
+use super::block_batcher::{BatchLimits, BlockBatcher};
+use super::{
+    ApiClient, BatchUpdateBlocksApi, CreateNestedBlocksApi, DeleteBlocksApi, DescendantBlock, DocumentApi,
+    GetBlocksApi, ImportDocumentsApi,
+};
+use crate::error::{LarkError, Result};
+use crate::utils::{diff_blocks, BlockDiff, BlockSummary, BlockTree};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// 同步结果
+#[derive(Debug, Serialize)]
+pub struct SyncResult {
+    /// 文档 ID
+    pub document_id: String,
+    /// 计算差异时的文档版本
+    pub base_revision_id: i32,
+    /// 同步完成后的文档版本（dry-run 时与 base_revision_id 相同）
+    pub document_revision_id: i32,
+    /// 是否只计算差异、未修改文档
+    pub dry_run: bool,
+    /// 保持不变的一级块数
+    pub unchanged: usize,
+    /// 原地更新文本的块数
+    pub updated: usize,
+    /// 删除的一级块数
+    pub deleted: usize,
+    /// 新建的一级块数
+    pub created: usize,
+    /// 具体的修改
+    pub diff: BlockDiff,
+}
+
+/// 文档同步 API：将本地内容单向同步到文档，只修改有差异的块
+pub struct DocumentSyncApi {
+    client: ApiClient,
+}
+
+impl DocumentSyncApi {
+    pub fn new(client: ApiClient) -> Self {
+        Self { client }
+    }
+
+    /// 将内容同步到文档正文
+    ///
+    /// 以读取块时的文档版本作为所有写操作的基准版本，每次写入后使用返回的新版本，
+    /// 文档在同步期间被他人修改时写操作会失败，不会覆盖他人的修改。
+    pub async fn sync(&self, document_id: &str, content: &str, content_type: &str) -> Result<SyncResult> {
+        // 1. 转换本地内容
+        let (children_id, descendants) = ImportDocumentsApi::new(self.client.clone())
+            .prepare_document(document_id, content, content_type)
+            .await?;
+        let local_blocks: HashMap<&str, &DescendantBlock> =
+            descendants.iter().map(|b| (b.block_id.as_str(), b)).collect();
+        let local: Vec<BlockSummary> = children_id
+            .iter()
+            .filter_map(|id| BlockSummary::from_local(id, &local_blocks))
+            .collect();
+
+        // 2. 读取指定版本的文档块
+        let base_revision_id = DocumentApi::new(self.client.clone())
+            .get_document(document_id)
+            .await?
+            .revision_id;
+        let blocks = GetBlocksApi::new(self.client.clone())
+            .get_all_document_blocks(document_id, Some(base_revision_id), None)
+            .await?;
+        let tree = BlockTree::new(blocks);
+        let remote: Vec<BlockSummary> = tree
+            .children(document_id)
+            .iter()
+            .filter_map(|id| BlockSummary::from_remote(id, &tree))
+            .collect();
+
+        // 3. 计算差异
+        let diff = diff_blocks(&remote, &local);
+        let mut result = SyncResult {
+            document_id: document_id.to_string(),
+            base_revision_id,
+            document_revision_id: base_revision_id,
+            dry_run: self.client.is_dry_run(),
+            unchanged: diff.unchanged,
+            updated: diff.updates.len(),
+            deleted: diff.hunks.iter().map(|h| h.delete_count).sum(),
+            created: diff.hunks.iter().map(|h| h.insert.len()).sum(),
+            diff,
+        };
+        if result.dry_run || result.diff.is_empty() {
+            return Ok(result);
+        }
+
+        // 4. 先原地更新文本，不影响块的位置
+        let mut revision_id = base_revision_id;
+        let mut requests = Vec::new();
+        for update in &result.diff.updates {
+            let elements = local_blocks
+                .get(update.local_block_id.as_str())
+                .and_then(|block| text_elements(block))
+                .ok_or_else(|| {
+                    LarkError::ParseError(format!("无法解析块 {} 的文本内容", update.local_block_id))
+                })?;
+            let mut request = UpdateBlockRequest::new(update.block_id.clone());
+            request.update_text_elements = Some(UpdateTextElementsRequest { elements });
+            requests.push(request);
+        }
+
+        revision_id = BatchUpdateBlocksApi::new(self.client.clone())
+            .batch_update_all(document_id, requests, Some(revision_id), None, None)
+            .await
+            .map_err(revision_conflict)?
+            .document_revision_id;
+
+        // 5. 从后往前删除和插入，前面的位置不受影响
+        let delete_api = DeleteBlocksApi::new(self.client.clone());
+        let create_api = CreateNestedBlocksApi::new(self.client.clone());
+        for hunk in result.diff.hunks.iter().rev() {
+            if hunk.delete_count > 0 {
+                let response = delete_api
+                    .delete_blocks(
+                        document_id,
+                        document_id,
+                        hunk.index,
+                        hunk.index + hunk.delete_count as i32,
+                        Some(revision_id),
+                        None,
+                    )
+                    .await
+                    .map_err(revision_conflict)?;
+                revision_id = response.document_revision_id;
+            }
+
+            if !hunk.insert.is_empty() {
+                let batches = BlockBatcher::new(descendants.clone(), BatchLimits::default())?
+                    .plan(&hunk.insert, hunk.index)?;
+                let response = create_api
+                    .create_batches(document_id, document_id, batches, Some(revision_id), None)
+                    .await
+                    .map_err(revision_conflict)?;
+                revision_id = response.document_revision_id;
+            }
+        }
+
+        result.document_revision_id = revision_id;
+        Ok(result)
+    }
+}
+
+/// 本地块的文本片段
+fn text_elements(block: &DescendantBlock) -> Option<Vec<TextElement>> {
+    let payload = serde_json::to_value(block).ok()?;
+    let elements = payload
+        .as_object()?
+        .values()
+        .find_map(|v| v.get("elements"))?
+        .clone();
+    serde_json::from_value(elements).ok()
+}
+
+/// 为写操作失败补充提示：基准版本之后文档可能已被修改
+fn revision_conflict(error: LarkError) -> LarkError {
+    match error {
+        LarkError::ApiError { code, message } => LarkError::ApiError {
+            code,
+            message: format!("{}（文档可能在同步期间被修改，请重新运行 sync）", message),
+        },
+        other => other,
+    }
+}
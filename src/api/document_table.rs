@@ -0,0 +1,327 @@
+use super::batch_update_blocks::{InsertTableRowRequest, TextElement, UpdateBlockRequest, UpdateTextElementsRequest};
+use super::block_batcher::MAX_BLOCKS_PER_REQUEST;
+use super::{
+    ApiClient, BatchUpdateBlocksApi, CreateNestedBlocksApi, DeleteBlocksApi, DescendantBlock, DocumentApi,
+    GetBlocksApi,
+};
+use crate::error::{LarkError, Result};
+use crate::utils::{column_count, pad_rows, table_cell_id, table_cell_ids, table_rows, table_size, BlockTree};
+use serde::Serialize;
+
+/// 文本块类型
+const TEXT_BLOCK_TYPE: i32 = 2;
+
+/// 表格写入结果
+#[derive(Debug, Serialize)]
+pub struct TableWriteResult {
+    /// 文档 ID
+    pub document_id: String,
+    /// 表格块 ID（dry-run 创建表格时为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table_block_id: Option<String>,
+    /// 是否只规划、未修改文档
+    pub dry_run: bool,
+    /// 写入的行数
+    pub row_count: usize,
+    /// 表格列数
+    pub column_count: usize,
+    /// 写入后的文档版本（dry-run 时为写入前的版本）
+    pub document_revision_id: i32,
+}
+
+/// 文档表格 API：导出表格内容，创建表格，追加行和修改单元格
+pub struct DocumentTableApi {
+    client: ApiClient,
+}
+
+impl DocumentTableApi {
+    pub fn new(client: ApiClient) -> Self {
+        Self { client }
+    }
+
+    /// 读取表格的所有单元格文本，按行返回
+    pub async fn export(&self, document_id: &str, table_block_id: &str) -> Result<Vec<Vec<String>>> {
+        let (tree, _) = self.load(document_id).await?;
+        table_rows(&tree, table_block_id)
+    }
+
+    /// 在父块（默认文档根）下创建表格
+    ///
+    /// 单次创建请求放不下的行在表格创建后追加。
+    pub async fn create(
+        &self,
+        document_id: &str,
+        parent_block_id: Option<&str>,
+        index: i32,
+        rows: &[Vec<String>],
+        header_row: bool,
+    ) -> Result<TableWriteResult> {
+        let columns = column_count(rows);
+        if columns == 0 {
+            return Err(LarkError::ValidationError("表格数据不能为空".to_string()));
+        }
+
+        let revision_id = DocumentApi::new(self.client.clone())
+            .get_document(document_id)
+            .await?
+            .revision_id;
+        let mut result = TableWriteResult {
+            document_id: document_id.to_string(),
+            table_block_id: None,
+            dry_run: self.client.is_dry_run(),
+            row_count: rows.len(),
+            column_count: columns,
+            document_revision_id: revision_id,
+        };
+        if result.dry_run {
+            return Ok(result);
+        }
+
+        // 每个单元格包含一个单元格块和一个文本块
+        let initial = rows.len().min(((MAX_BLOCKS_PER_REQUEST - 1) / (2 * columns)).max(1));
+        let (children_id, descendants) = table_blocks(&rows[..initial], columns, header_row);
+        let parent_id = parent_block_id.filter(|id| !id.is_empty()).unwrap_or(document_id);
+        let response = CreateNestedBlocksApi::new(self.client.clone())
+            .create_nested_blocks(
+                document_id,
+                parent_id,
+                children_id,
+                descendants,
+                Some(index),
+                Some(revision_id),
+                None,
+            )
+            .await?;
+
+        let table_block_id = response
+            .block_id_relations
+            .iter()
+            .find(|r| r.temporary_block_id == "table")
+            .map(|r| r.block_id.clone())
+            .ok_or_else(|| LarkError::ParseError("创建表格的响应中缺少表格块 ID".to_string()))?;
+        result.document_revision_id = response.document_revision_id;
+
+        if initial < rows.len() {
+            let appended = self.append_rows(document_id, &table_block_id, &rows[initial..]).await?;
+            result.document_revision_id = appended.document_revision_id;
+        }
+        result.table_block_id = Some(table_block_id);
+        Ok(result)
+    }
+
+    /// 在表格末尾追加行，行的列数不能超过表格列数
+    pub async fn append_rows(
+        &self,
+        document_id: &str,
+        table_block_id: &str,
+        rows: &[Vec<String>],
+    ) -> Result<TableWriteResult> {
+        let (tree, revision_id) = self.load(document_id).await?;
+        let (row_size, columns) = table_size(&tree, table_block_id)?;
+        if column_count(rows) > columns {
+            return Err(LarkError::ValidationError(format!(
+                "数据有 {} 列，超过表格的 {} 列",
+                column_count(rows),
+                columns
+            )));
+        }
+
+        let mut result = TableWriteResult {
+            document_id: document_id.to_string(),
+            table_block_id: Some(table_block_id.to_string()),
+            dry_run: self.client.is_dry_run(),
+            row_count: rows.len(),
+            column_count: columns,
+            document_revision_id: revision_id,
+        };
+        if result.dry_run || rows.is_empty() {
+            return Ok(result);
+        }
+
+        // 先插入空行，再写入新单元格中的文本块
+        let inserts = (0..rows.len())
+            .map(|i| {
+                let mut request = UpdateBlockRequest::new(table_block_id.to_string());
+                request.insert_table_row = Some(InsertTableRowRequest {
+                    row_index: (row_size + i) as i32,
+                });
+                request
+            })
+            .collect();
+        let revision_id = self.batch_update(document_id, inserts, revision_id).await?;
+
+        let (tree, _) = self.load(document_id).await?;
+        let cells = table_cell_ids(&tree, table_block_id)?;
+        let mut updates = Vec::new();
+        for (i, row) in pad_rows(rows, columns).iter().enumerate() {
+            for (column, value) in row.iter().enumerate() {
+                let cell_id = cells.get((row_size + i) * columns + column).ok_or_else(|| {
+                    LarkError::ParseError(format!("找不到新插入的单元格 ({}, {})", row_size + i, column))
+                })?;
+                if value.is_empty() {
+                    continue;
+                }
+                let text_id = first_text_child(&tree, cell_id).ok_or_else(|| {
+                    LarkError::ParseError(format!("新插入的单元格 {} 中没有文本块", cell_id))
+                })?;
+                updates.push(text_update(text_id, value));
+            }
+        }
+
+        result.document_revision_id = self.batch_update(document_id, updates, revision_id).await?;
+        Ok(result)
+    }
+
+    /// 修改单元格内容，行列从 0 开始
+    ///
+    /// 单元格的第一个块为文本块时更新其文本并删除其余块，否则清空单元格后新建文本块。
+    pub async fn set_cell(
+        &self,
+        document_id: &str,
+        table_block_id: &str,
+        row: usize,
+        column: usize,
+        value: &str,
+    ) -> Result<TableWriteResult> {
+        let (tree, revision_id) = self.load(document_id).await?;
+        let cell_id = table_cell_id(&tree, table_block_id, row, column)?;
+        let (_, columns) = table_size(&tree, table_block_id)?;
+
+        let mut result = TableWriteResult {
+            document_id: document_id.to_string(),
+            table_block_id: Some(table_block_id.to_string()),
+            dry_run: self.client.is_dry_run(),
+            row_count: 1,
+            column_count: columns,
+            document_revision_id: revision_id,
+        };
+        if result.dry_run {
+            return Ok(result);
+        }
+
+        let children = tree.children(&cell_id);
+        let delete_api = DeleteBlocksApi::new(self.client.clone());
+        let mut revision_id = revision_id;
+
+        if let Some(text_id) = first_text_child(&tree, &cell_id).filter(|id| children.first() == Some(id)) {
+            revision_id = self
+                .batch_update(document_id, vec![text_update(text_id, value)], revision_id)
+                .await?;
+            if children.len() > 1 {
+                revision_id = delete_api
+                    .delete_blocks(document_id, &cell_id, 1, children.len() as i32, Some(revision_id), None)
+                    .await?
+                    .document_revision_id;
+            }
+        } else {
+            if !children.is_empty() {
+                revision_id = delete_api
+                    .delete_blocks(document_id, &cell_id, 0, children.len() as i32, Some(revision_id), None)
+                    .await?
+                    .document_revision_id;
+            }
+            revision_id = CreateNestedBlocksApi::new(self.client.clone())
+                .create_nested_blocks(
+                    document_id,
+                    &cell_id,
+                    vec!["text".to_string()],
+                    vec![text_block("text", value)],
+                    Some(0),
+                    Some(revision_id),
+                    None,
+                )
+                .await?
+                .document_revision_id;
+        }
+
+        result.document_revision_id = revision_id;
+        Ok(result)
+    }
+
+    async fn load(&self, document_id: &str) -> Result<(BlockTree, i32)> {
+        let revision_id = DocumentApi::new(self.client.clone())
+            .get_document(document_id)
+            .await?
+            .revision_id;
+        let blocks = GetBlocksApi::new(self.client.clone())
+            .get_all_document_blocks(document_id, Some(revision_id), None)
+            .await?;
+        Ok((BlockTree::new(blocks), revision_id))
+    }
+
+    async fn batch_update(
+        &self,
+        document_id: &str,
+        requests: Vec<UpdateBlockRequest>,
+        revision_id: i32,
+    ) -> Result<i32> {
+        Ok(BatchUpdateBlocksApi::new(self.client.clone())
+            .batch_update_all(document_id, requests, Some(revision_id), None, None)
+            .await?
+            .document_revision_id)
+    }
+}
+
+/// 构建创建表格所需的块：表格块（临时 ID `table`）、单元格块和其中的文本块
+pub fn table_blocks(rows: &[Vec<String>], columns: usize, header_row: bool) -> (Vec<String>, Vec<DescendantBlock>) {
+    let mut descendants = Vec::new();
+    let mut cell_ids = Vec::new();
+
+    for (r, row) in pad_rows(rows, columns).iter().enumerate() {
+        for (c, value) in row.iter().enumerate() {
+            let cell_id = format!("cell_{}_{}", r, c);
+            let text_id = format!("text_{}_{}", r, c);
+            descendants.push(DescendantBlock {
+                block_id: cell_id.clone(),
+                block_type: 32,
+                children: Some(vec![text_id.clone()]),
+                table_cell: Some(serde_json::json!({})),
+                ..Default::default()
+            });
+            descendants.push(text_block(&text_id, value));
+            cell_ids.push(cell_id);
+        }
+    }
+
+    descendants.insert(
+        0,
+        DescendantBlock {
+            block_id: "table".to_string(),
+            block_type: 31,
+            children: Some(cell_ids),
+            table: Some(serde_json::json!({
+                "property": {
+                    "row_size": rows.len(),
+                    "column_size": columns,
+                    "header_row": header_row
+                }
+            })),
+            ..Default::default()
+        },
+    );
+
+    (vec!["table".to_string()], descendants)
+}
+
+fn text_block(block_id: &str, content: &str) -> DescendantBlock {
+    DescendantBlock {
+        block_id: block_id.to_string(),
+        block_type: TEXT_BLOCK_TYPE,
+        text: Some(serde_json::json!({ "elements": [{ "text_run": { "content": content } }] })),
+        ..Default::default()
+    }
+}
+
+fn text_update(block_id: &str, content: &str) -> UpdateBlockRequest {
+    let mut request = UpdateBlockRequest::new(block_id.to_string());
+    request.update_text_elements = Some(UpdateTextElementsRequest {
+        elements: vec![TextElement::text_run(content.to_string(), None)],
+    });
+    request
+}
+
+fn first_text_child<'a>(tree: &'a BlockTree, cell_id: &str) -> Option<&'a String> {
+    tree.children(cell_id)
+        .iter()
+        .find(|id| tree.get(id).is_some_and(|b| b.block_type == TEXT_BLOCK_TYPE))
+}
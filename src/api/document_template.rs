@@ -0,0 +1,303 @@
+use super::block_batcher::{BatchLimits, BlockBatcher};
+use super::block_copy::{copy_blocks, SkippedBlock};
+use super::document::DocumentInfo;
+use super::get_blocks::BlockItem;
+use super::{ApiClient, CreateNestedBlocksApi, DocumentApi, GetBlocksApi, ImportDocumentsApi};
+use crate::error::{LarkError, Result};
+use crate::utils::{
+    block_plain_text, block_type_key, is_loop_end, item_variables, loop_items, loop_start, render_template,
+    split_front_matter, BlockTree, FileReader, FrontMatter, FrontMatterCollaborator, TemplateVariables,
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// 模板来源
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateSource {
+    /// 飞书文档，复制其块树
+    Document(String),
+    /// 本地 Markdown 文件
+    File(PathBuf),
+}
+
+impl TemplateSource {
+    /// 存在的文件或以 .md 结尾的路径视为本地模板，否则视为文档 ID
+    pub fn parse(source: &str) -> Self {
+        let path = Path::new(source);
+        let is_markdown = matches!(
+            path.extension().and_then(|e| e.to_str()),
+            Some("md") | Some("markdown")
+        );
+        if path.is_file() || is_markdown {
+            TemplateSource::File(path.to_path_buf())
+        } else {
+            TemplateSource::Document(source.to_string())
+        }
+    }
+}
+
+/// 从模板创建文档的结果
+#[derive(Debug, Serialize)]
+pub struct TemplateResult {
+    /// 模板（文档 ID 或文件路径）
+    pub template: String,
+    /// 新文档标题
+    pub title: Option<String>,
+    /// 是否只渲染模板、未创建文档
+    pub dry_run: bool,
+    /// 创建的文档
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub document: Option<DocumentInfo>,
+    /// 创建（或将要创建）的块数
+    pub block_count: usize,
+    /// 无法复制而被跳过的模板块
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped_blocks: Vec<SkippedBlock>,
+    /// 添加的协作者
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<String>,
+    /// dry-run 模式下渲染后的 Markdown 正文（仅本地模板）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+}
+
+/// 文档模板 API：复制模板文档或导入本地 Markdown 模板，并替换其中的占位符
+pub struct DocumentTemplateApi {
+    client: ApiClient,
+}
+
+impl DocumentTemplateApi {
+    pub fn new(client: ApiClient) -> Self {
+        Self { client }
+    }
+
+    /// 根据模板创建新文档，创建完成后添加协作者
+    ///
+    /// `title` 未指定时使用模板标题（同样替换占位符）。
+    pub async fn create_from_template(
+        &self,
+        source: &TemplateSource,
+        folder_token: Option<String>,
+        title: Option<String>,
+        variables: &TemplateVariables,
+        collaborators: Vec<FrontMatterCollaborator>,
+    ) -> Result<TemplateResult> {
+        match source {
+            TemplateSource::File(path) => {
+                self.create_from_file(path, folder_token, title, variables, collaborators).await
+            }
+            TemplateSource::Document(document_id) => {
+                self.create_from_document(document_id, folder_token, title, variables, collaborators)
+                    .await
+            }
+        }
+    }
+
+    async fn create_from_file(
+        &self,
+        path: &Path,
+        folder_token: Option<String>,
+        title: Option<String>,
+        variables: &TemplateVariables,
+        collaborators: Vec<FrontMatterCollaborator>,
+    ) -> Result<TemplateResult> {
+        let content = FileReader::read_to_string(path)?;
+        let (front_matter, body) = split_front_matter(&content)?;
+        let mut front_matter = front_matter.unwrap_or_default();
+
+        // Front Matter 中的字段作为变量默认值，命令行变量优先
+        let mut all_variables = front_matter.extra.clone();
+        all_variables.extend(variables.clone());
+        let title = match title.or_else(|| front_matter.title.clone()) {
+            Some(title) => Some(render_template(&title, &all_variables)?),
+            None => None,
+        };
+        if let Some(title) = &title {
+            all_variables.entry("title".to_string()).or_insert_with(|| Value::String(title.clone()));
+        }
+        let body = render_template(&body, &all_variables)?;
+
+        let mut result = TemplateResult {
+            template: path.display().to_string(),
+            title: title.clone(),
+            dry_run: self.client.is_dry_run(),
+            document: None,
+            block_count: 0,
+            skipped_blocks: Vec::new(),
+            actions: Vec::new(),
+            content: None,
+        };
+        if result.dry_run {
+            result.content = Some(body);
+            return Ok(result);
+        }
+
+        let document = DocumentApi::new(self.client.clone())
+            .create_document(folder_token, title)
+            .await?;
+        let document_id = document.document_id.clone();
+
+        let import_api = ImportDocumentsApi::new(self.client.clone());
+        front_matter.title = result.title.clone();
+        let (response, _) = import_api
+            .import_prepared(&document_id, Some(&front_matter), &body, "markdown", &document_id, 0, None, None)
+            .await?;
+        result.block_count = response.block_id_relations.len();
+
+        front_matter.collaborators.extend(collaborators);
+        result.actions = self.apply_collaborators(&document_id, front_matter.collaborators).await?;
+        result.document = Some(document);
+        Ok(result)
+    }
+
+    async fn create_from_document(
+        &self,
+        template_id: &str,
+        folder_token: Option<String>,
+        title: Option<String>,
+        variables: &TemplateVariables,
+        collaborators: Vec<FrontMatterCollaborator>,
+    ) -> Result<TemplateResult> {
+        let blocks = GetBlocksApi::new(self.client.clone())
+            .get_all_document_blocks(template_id, None, None)
+            .await?;
+        let tree = BlockTree::new(blocks);
+
+        let template_title = tree.get(template_id).map(block_plain_text).filter(|t| !t.is_empty());
+        let title = match title.or(template_title) {
+            Some(title) => Some(render_template(&title, variables)?),
+            None => None,
+        };
+
+        let (rendered, root_ids) = render_block_template(&tree, template_id, variables)?;
+        let copied = copy_blocks(&rendered, &root_ids)?;
+
+        let mut result = TemplateResult {
+            template: template_id.to_string(),
+            title: title.clone(),
+            dry_run: self.client.is_dry_run(),
+            document: None,
+            block_count: copied.descendants.len(),
+            skipped_blocks: copied.skipped,
+            actions: Vec::new(),
+            content: None,
+        };
+        if result.dry_run {
+            return Ok(result);
+        }
+
+        let document = DocumentApi::new(self.client.clone())
+            .create_document(folder_token, title)
+            .await?;
+        let document_id = document.document_id.clone();
+
+        if !copied.children_id.is_empty() {
+            let batches = BlockBatcher::new(copied.descendants, BatchLimits::default())?
+                .plan(&copied.children_id, 0)?;
+            CreateNestedBlocksApi::new(self.client.clone())
+                .create_batches(&document_id, &document_id, batches, None, None)
+                .await?;
+        }
+
+        result.actions = self.apply_collaborators(&document_id, collaborators).await?;
+        result.document = Some(document);
+        Ok(result)
+    }
+
+    async fn apply_collaborators(
+        &self,
+        document_id: &str,
+        collaborators: Vec<FrontMatterCollaborator>,
+    ) -> Result<Vec<String>> {
+        if collaborators.is_empty() {
+            return Ok(Vec::new());
+        }
+        let front_matter = FrontMatter {
+            collaborators,
+            ..Default::default()
+        };
+        ImportDocumentsApi::new(self.client.clone())
+            .apply_front_matter(document_id, &front_matter, false)
+            .await
+    }
+}
+
+/// 渲染模板文档的块树：替换文本中的占位符，并展开循环
+///
+/// 文本为 `{{#each key}}` 和 `{{/each}}` 的两个同级块之间的块按列表 `key` 的每一项重复，
+/// 标记块本身不保留。返回渲染后的块树和一级块 ID，重复的块 ID 带有 `_<序号>` 后缀。
+pub fn render_block_template(
+    tree: &BlockTree,
+    page_id: &str,
+    variables: &TemplateVariables,
+) -> Result<(BlockTree, Vec<String>)> {
+    let mut blocks = Vec::new();
+    let root_ids = render_children(tree, tree.children(page_id), variables, "", &mut blocks)?;
+    Ok((BlockTree::new(blocks), root_ids))
+}
+
+fn render_children(
+    tree: &BlockTree,
+    child_ids: &[String],
+    variables: &TemplateVariables,
+    suffix: &str,
+    blocks: &mut Vec<BlockItem>,
+) -> Result<Vec<String>> {
+    let mut rendered_ids = Vec::new();
+    let mut i = 0;
+
+    while i < child_ids.len() {
+        let Some(block) = tree.get(&child_ids[i]) else {
+            i += 1;
+            continue;
+        };
+
+        let text = block_plain_text(block);
+        if let Some(key) = loop_start(&text) {
+            let end = (i + 1..child_ids.len())
+                .find(|&j| tree.get(&child_ids[j]).is_some_and(|b| is_loop_end(&block_plain_text(b))))
+                .ok_or_else(|| {
+                    LarkError::ValidationError(format!("循环 {} 缺少结束标记 {{{{/each}}}}", key))
+                })?;
+
+            for (n, item) in loop_items(variables, key)?.iter().enumerate() {
+                let scoped = item_variables(variables, item);
+                let item_suffix = format!("{}_{}", suffix, n + 1);
+                rendered_ids.extend(render_children(tree, &child_ids[i + 1..end], &scoped, &item_suffix, blocks)?);
+            }
+            i = end + 1;
+            continue;
+        }
+
+        let mut rendered = block.clone();
+        rendered.block_id = format!("{}{}", block.block_id, suffix);
+        render_elements(&mut rendered, variables)?;
+        let position = blocks.len();
+        blocks.push(rendered);
+
+        let children = render_children(tree, tree.children(&block.block_id), variables, suffix, blocks)?;
+        blocks[position].children = (!children.is_empty()).then_some(children);
+        rendered_ids.push(blocks[position].block_id.clone());
+        i += 1;
+    }
+
+    Ok(rendered_ids)
+}
+
+/// 渲染块中每个文本片段的内容，样式保持不变
+fn render_elements(block: &mut BlockItem, variables: &TemplateVariables) -> Result<()> {
+    let Some(key) = block_type_key(block.block_type) else {
+        return Ok(());
+    };
+    let Some(elements) = block.content.get_mut(key).and_then(|c| c.get_mut("elements")).and_then(|e| e.as_array_mut()) else {
+        return Ok(());
+    };
+
+    for element in elements {
+        if let Some(Value::String(content)) = element.pointer_mut("/text_run/content") {
+            *content = render_template(content, variables)?;
+        }
+    }
+    Ok(())
+}
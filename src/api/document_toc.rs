@@ -0,0 +1,128 @@
+use super::block_batcher::{BatchLimits, BlockBatcher};
+use super::block_builder::{callout_blocks, CalloutType};
+use super::{ApiClient, CreateNestedBlocksApi, DeleteBlocksApi, DocumentApi, GetBlocksApi};
+use crate::error::{LarkError, Result};
+use crate::utils::{collect_headings, find_toc_marker, toc_blocks, BlockTree};
+use serde::Serialize;
+
+/// 目录标记高亮块的默认图标
+const TOC_ICON: &str = "bookmark_tabs";
+
+/// 生成目录的结果
+#[derive(Debug, Serialize)]
+pub struct TocResult {
+    /// 文档 ID
+    pub document_id: String,
+    /// 目录所在的高亮块 ID（dry-run 新建时为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callout_block_id: Option<String>,
+    /// 是否更新了已有的目录（否则为新建）
+    pub refreshed: bool,
+    /// 是否只规划、未修改文档
+    pub dry_run: bool,
+    /// 目录中的标题数
+    pub heading_count: usize,
+    /// 写入后的文档版本（dry-run 时为读取时的版本）
+    pub document_revision_id: i32,
+}
+
+/// 文档目录 API：根据标题生成带链接的目录
+pub struct DocumentTocApi {
+    client: ApiClient,
+}
+
+impl DocumentTocApi {
+    pub fn new(client: ApiClient) -> Self {
+        Self { client }
+    }
+
+    /// 插入或更新目录
+    ///
+    /// 目录放在第一个子块为 `title` 的高亮块中。文档中已有这样的高亮块时，替换其中标题之后的内容；
+    /// 否则在文档根块的 `index` 位置新建。`base_url` 为文档链接的域名部分，如 `https://example.feishu.cn`，
+    /// 文档信息中的链接域名是固定的，不能用于其他租户。
+    pub async fn generate(
+        &self,
+        document_id: &str,
+        title: &str,
+        max_level: usize,
+        index: i32,
+        base_url: &str,
+    ) -> Result<TocResult> {
+        if title.trim().is_empty() {
+            return Err(LarkError::ValidationError("目录标题不能为空".to_string()));
+        }
+        if !(1..=9).contains(&max_level) {
+            return Err(LarkError::ValidationError("标题级别必须在 1 到 9 之间".to_string()));
+        }
+
+        let document = DocumentApi::new(self.client.clone()).get_document(document_id).await?;
+        let blocks = GetBlocksApi::new(self.client.clone())
+            .get_all_document_blocks(document_id, Some(document.revision_id), None)
+            .await?;
+        let tree = BlockTree::new(blocks);
+
+        let marker_id = find_toc_marker(&tree, document_id, title);
+        let entries = collect_headings(&tree, document_id, max_level, marker_id.as_deref());
+        let document_url = format!("{}/docx/{}", base_url.trim_end_matches('/'), document_id);
+        let (bullet_ids, bullets) = toc_blocks(&entries, &document_url);
+
+        let mut result = TocResult {
+            document_id: document_id.to_string(),
+            callout_block_id: marker_id.clone(),
+            refreshed: marker_id.is_some(),
+            dry_run: self.client.is_dry_run(),
+            heading_count: entries.len(),
+            document_revision_id: document.revision_id,
+        };
+        if result.dry_run {
+            return Ok(result);
+        }
+
+        let create_api = CreateNestedBlocksApi::new(self.client.clone());
+        let revision_id = match marker_id {
+            Some(callout_id) => {
+                // 保留标记文本，替换其后的旧目录
+                let mut revision_id = document.revision_id;
+                let existing = tree.children(&callout_id).len();
+                if existing > 1 {
+                    revision_id = DeleteBlocksApi::new(self.client.clone())
+                        .delete_blocks(document_id, &callout_id, 1, existing as i32, Some(revision_id), None)
+                        .await?
+                        .document_revision_id;
+                }
+                if !bullet_ids.is_empty() {
+                    let batches = BlockBatcher::new(bullets, BatchLimits::default())?.plan(&bullet_ids, 1)?;
+                    revision_id = create_api
+                        .create_batches(document_id, &callout_id, batches, Some(revision_id), None)
+                        .await?
+                        .document_revision_id;
+                }
+                revision_id
+            }
+            None => {
+                let (callout_id, mut descendants) =
+                    callout_blocks(document_id, title, CalloutType::Info, Some(TOC_ICON.to_string()));
+                if let Some(callout) = descendants.iter_mut().find(|b| b.block_id == callout_id) {
+                    callout.children.get_or_insert_with(Vec::new).extend(bullet_ids);
+                }
+                descendants.extend(bullets);
+
+                let batches = BlockBatcher::new(descendants, BatchLimits::default())?
+                    .plan(std::slice::from_ref(&callout_id), index)?;
+                let response = create_api
+                    .create_batches(document_id, document_id, batches, Some(document.revision_id), None)
+                    .await?;
+                result.callout_block_id = response
+                    .block_id_relations
+                    .iter()
+                    .find(|r| r.temporary_block_id == callout_id)
+                    .map(|r| r.block_id.clone());
+                response.document_revision_id
+            }
+        };
+
+        result.document_revision_id = revision_id;
+        Ok(result)
+    }
+}
@@ -1,10 +1,25 @@
 use super::ApiClient;
-use crate::error::Result;
+use crate::error::{LarkError, Result};
+use crate::utils::{
+    format_bytes, parse_content_range, parse_sha256_digest, ByteRange, DownloadState, RemoteVersion, StreamState,
+};
 use serde::Serialize;
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::Write;
-use std::path::Path;
+use sha2::{Digest, Sha256};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 下载请求的超时时间，覆盖客户端默认的 30 秒，大文件中断后可以续传
+pub(crate) const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(2 * 60 * 60);
+
+/// 并行下载时每个分段的大小
+const SEGMENT_SIZE: u64 = 8 * 1024 * 1024;
+
+/// 并行下载数的上限
+pub const MAX_DOWNLOAD_PARALLEL: usize = 16;
 
 /// 下载媒体文件的请求参数
 #[derive(Debug, Serialize)]
@@ -17,6 +32,30 @@ pub struct DownloadMediaRequest {
     pub range: Option<String>,
 }
 
+/// 下载选项
+#[derive(Debug, Clone)]
+pub struct DownloadOptions {
+    /// 只下载指定范围，不续传、不并行
+    pub range: Option<ByteRange>,
+    /// 并行的范围请求数，1 表示单个请求流式下载
+    pub parallel: usize,
+    /// 从上次中断留下的临时文件继续下载
+    pub resume: bool,
+    /// 在标准错误输出中显示进度
+    pub progress: bool,
+}
+
+impl Default for DownloadOptions {
+    fn default() -> Self {
+        Self {
+            range: None,
+            parallel: 1,
+            resume: true,
+            progress: false,
+        }
+    }
+}
+
 /// 下载媒体文件的响应
 #[derive(Debug, Serialize)]
 pub struct DownloadMediaResponse {
@@ -28,6 +67,82 @@ pub struct DownloadMediaResponse {
     pub content_type: String,
     /// 文件名（从响应头获取）
     pub file_name: Option<String>,
+    /// 续传时已存在的字节数
+    pub resumed_bytes: u64,
+    /// 并行的范围请求数
+    pub parallel: usize,
+    /// 已校验的内容摘要算法（服务端提供摘要时）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verified_checksum: Option<String>,
+}
+
+/// 首个响应中的文件信息
+struct ResponseInfo {
+    content_type: String,
+    file_name: Option<String>,
+    sha256: Option<Vec<u8>>,
+}
+
+impl ResponseInfo {
+    fn from_response(response: &reqwest::Response) -> Self {
+        let header = |name: &str| response.headers().get(name).and_then(|v| v.to_str().ok());
+        Self {
+            content_type: header("content-type").unwrap_or("application/octet-stream").to_string(),
+            file_name: header("content-disposition").and_then(content_disposition_file_name),
+            sha256: header("repr-digest").or_else(|| header("digest")).and_then(parse_sha256_digest),
+        }
+    }
+}
+
+/// 下载进度，多个并行请求共享，最多每 200 毫秒刷新一次
+pub(crate) struct DownloadProgress {
+    enabled: bool,
+    total: Option<u64>,
+    done: AtomicU64,
+    last_report: Mutex<Instant>,
+}
+
+impl DownloadProgress {
+    pub(crate) fn new(enabled: bool, total: Option<u64>, done: u64) -> Self {
+        Self {
+            enabled,
+            total,
+            done: AtomicU64::new(done),
+            last_report: Mutex::new(Instant::now()),
+        }
+    }
+
+    pub(crate) fn add(&self, bytes: u64) {
+        self.done.fetch_add(bytes, Ordering::Relaxed);
+        if !self.enabled {
+            return;
+        }
+        let mut last = self.last_report.lock().unwrap();
+        if last.elapsed() >= Duration::from_millis(200) {
+            *last = Instant::now();
+            self.report();
+        }
+    }
+
+    pub(crate) fn finish(&self) {
+        if self.enabled {
+            self.report();
+            eprintln!();
+        }
+    }
+
+    fn report(&self) {
+        let done = self.done.load(Ordering::Relaxed);
+        match self.total.filter(|t| *t > 0) {
+            Some(total) => eprint!(
+                "\r下载中 {} / {} ({:.1}%)   ",
+                format_bytes(done),
+                format_bytes(total),
+                done as f64 * 100.0 / total as f64
+            ),
+            None => eprint!("\r下载中 {}   ", format_bytes(done)),
+        }
+    }
 }
 
 pub struct DownloadMediaApi {
@@ -40,225 +155,549 @@ impl DownloadMediaApi {
     }
 
     /// 下载媒体文件并保存到本地
+    ///
+    /// 内容流式写入 `<output_path>.part`，校验大小和摘要后重命名为目标文件；中断后再次下载时用 Range 从临时文件续传。
+    /// 临时文件对应的素材和远端版本（ETag / Last-Modified）记录在 `<output_path>.part.stream.json`，
+    /// 续传时通过 If-Range 确认远端文件未变化，不一致或无法确认时从头下载。
+    /// `parallel` 大于 1 且服务端支持范围请求时，按 8MB 分段并行下载，已完成的分段记录在 `<output_path>.part.json`。
     pub async fn download_media(
         &self,
         file_token: &str,
         output_path: &str,
         extra: Option<String>,
-        range: Option<String>,
+        options: &DownloadOptions,
     ) -> Result<DownloadMediaResponse> {
         // 验证参数
         if file_token.is_empty() {
-            return Err(crate::error::LarkError::ValidationError(
-                "file_token 参数是必需的".to_string(),
-            ));
+            return Err(LarkError::ValidationError("file_token 参数是必需的".to_string()));
+        }
+        if options.parallel == 0 || options.parallel > MAX_DOWNLOAD_PARALLEL {
+            return Err(LarkError::ValidationError(format!(
+                "并行数必须在 1-{} 之间",
+                MAX_DOWNLOAD_PARALLEL
+            )));
         }
 
-        // 构建请求 URL
-        let url = format!(
-            "https://open.larkoffice.com/open-apis/drive/v1/medias/{}/download",
-            file_token
-        );
+        let request = MediaRequest {
+            client: self.client.clone(),
+            url: format!("https://open.larkoffice.com/open-apis/drive/v1/medias/{}/download", file_token),
+            extra,
+        };
+        let output = PathBuf::from(output_path);
+        ensure_parent_dir(&output)?;
+        let part_path = PathBuf::from(format!("{}.part", output_path));
+        let state_path = PathBuf::from(format!("{}.part.json", output_path));
+        let stream_path = PathBuf::from(format!("{}.part.stream.json", output_path));
+        if !options.resume {
+            let _ = std::fs::remove_file(&part_path);
+            let _ = std::fs::remove_file(&state_path);
+            let _ = std::fs::remove_file(&stream_path);
+        }
 
-        // 构建查询参数
-        let mut params = HashMap::new();
-        if let Some(ref extra) = extra {
-            params.insert("extra".to_string(), extra.clone());
+        let mut download = Download {
+            request,
+            file_token: file_token.to_string(),
+            output,
+            part_path,
+            state_path,
+            stream_path,
+            options: options.clone(),
+            resumed_bytes: 0,
+            parallel: 1,
+        };
+        if let Some(range) = options.range {
+            return download.range(range).await;
         }
 
-        // 获取认证头
-        let auth_header = self.client.auth_manager.get_auth_header().await?;
+        let saved = DownloadState::load(&download.state_path).filter(|s| s.file_token == file_token);
+        if options.parallel > 1 || saved.is_some() {
+            // 探测文件大小和是否支持范围请求
+            let probe = download.request.send(Some("bytes=0-0".to_string())).await?;
+            let probe = check_status(probe)?;
+            let total = header_content_range(&probe).and_then(|r| r.total);
+            match (probe.status().as_u16(), total) {
+                (206, Some(total)) if total > SEGMENT_SIZE => {
+                    let info = ResponseInfo::from_response(&probe);
+                    let version = remote_version(&probe);
+                    // 只有记录了远端版本且与当前版本一致时才续传，否则丢弃已下载的分段
+                    let state = saved.filter(|s| {
+                        s.total == total
+                            && s.segment_size == SEGMENT_SIZE
+                            && s.version.validator().is_some()
+                            && !s.version.conflicts_with(&version)
+                    });
+                    return download.segments(file_token, total, state, version, info).await;
+                }
+                (200, _) => {
+                    // 服务端不支持范围请求，直接使用完整响应
+                    let _ = std::fs::remove_file(&download.state_path);
+                    return download.single_from(probe, 0).await;
+                }
+                _ => {
+                    // 文件较小，单个请求下载；分段下载留下的临时文件可能不连续，不能续传
+                    if saved.is_some() {
+                        let _ = std::fs::remove_file(&download.part_path);
+                        let _ = std::fs::remove_file(&download.state_path);
+                    }
+                }
+            }
+        }
+        download.single().await
+    }
 
-        // 构建请求
-        let mut request = self.client.client.get(&url);
+    /// 下载媒体文件到指定路径
+    /// 如果未指定文件名，将使用 file_token 作为文件名
+    pub async fn download_media_auto_name(
+        &self,
+        file_token: &str,
+        output_dir: &str,
+        extra: Option<String>,
+        options: &DownloadOptions,
+    ) -> Result<DownloadMediaResponse> {
+        let request = MediaRequest {
+            client: self.client.clone(),
+            url: format!("https://open.larkoffice.com/open-apis/drive/v1/medias/{}/download", file_token),
+            extra: extra.clone(),
+        };
 
-        // 添加认证头
-        request = request.header("Authorization", auth_header);
+        // 尝试 HEAD 请求获取文件名
+        let file_name = match request.head().await {
+            Ok(resp) => resp
+                .headers()
+                .get("content-disposition")
+                .and_then(|v| v.to_str().ok())
+                .and_then(content_disposition_file_name),
+            Err(_) => None,
+        };
+
+        // 确定输出文件路径
+        let path = Path::new(output_dir);
+        let output_path = path
+            .join(file_name.as_deref().unwrap_or(file_token))
+            .to_string_lossy()
+            .to_string();
 
-        // 添加 Range 头（分片下载）
-        if let Some(ref range_value) = range {
-            request = request.header("Range", range_value);
+        self.download_media(file_token, &output_path, extra, options).await
+    }
+}
+
+/// 素材下载请求
+#[derive(Clone)]
+struct MediaRequest {
+    client: ApiClient,
+    url: String,
+    extra: Option<String>,
+}
+
+impl MediaRequest {
+    async fn send(&self, range: Option<String>) -> Result<reqwest::Response> {
+        self.send_if_range(range, None).await
+    }
+
+    /// 带 If-Range 的范围请求：远端版本与 `if_range` 不一致时，服务端返回完整文件而不是部分内容
+    async fn send_if_range(&self, range: Option<String>, if_range: Option<&str>) -> Result<reqwest::Response> {
+        let auth_header = self.client.auth_manager.get_auth_header().await?;
+        let mut request = self
+            .client
+            .client
+            .get(&self.url)
+            .header("Authorization", auth_header)
+            .timeout(DOWNLOAD_TIMEOUT);
+        if let Some(ref extra) = self.extra {
+            request = request.query(&[("extra", extra)]);
+        }
+        if let Some(range) = range {
+            request = request.header("Range", range);
+        }
+        if let Some(validator) = if_range {
+            request = request.header("If-Range", validator);
         }
 
-        // 添加查询参数
-        if !params.is_empty() {
-            request = request.query(&params);
+        tracing::debug!("Sending GET request to: {:?}", self.url);
+        let response = request.send().await?;
+        tracing::debug!("Response status: {}", response.status());
+        Ok(response)
+    }
+
+    async fn head(&self) -> Result<reqwest::Response> {
+        let auth_header = self.client.auth_manager.get_auth_header().await?;
+        let mut request = self.client.client.head(&self.url).header("Authorization", auth_header);
+        if let Some(ref extra) = self.extra {
+            request = request.query(&[("extra", extra)]);
         }
+        Ok(request.send().await?)
+    }
+}
 
-        tracing::debug!("Sending GET request to: {:?}", url);
+/// 一次下载的状态
+struct Download {
+    request: MediaRequest,
+    file_token: String,
+    output: PathBuf,
+    part_path: PathBuf,
+    state_path: PathBuf,
+    stream_path: PathBuf,
+    options: DownloadOptions,
+    resumed_bytes: u64,
+    parallel: usize,
+}
 
-        // 发送请求
-        let response = request.send().await?;
+impl Download {
+    /// 只下载指定范围
+    async fn range(&mut self, range: ByteRange) -> Result<DownloadMediaResponse> {
+        // 临时文件将被部分内容覆盖，不能再用于续传
+        let _ = std::fs::remove_file(&self.stream_path);
+        let response = check_status(self.request.send(Some(range.header_value())).await?)?;
+        let info = ResponseInfo {
+            sha256: None,
+            ..ResponseInfo::from_response(&response)
+        };
+        let expected = match header_content_range(&response).and_then(|r| r.range) {
+            Some((start, end)) => Some(end - start + 1),
+            None => response.content_length(),
+        };
+        let mut file = File::create(&self.part_path)?;
+        let progress = DownloadProgress::new(self.options.progress, expected, 0);
+        write_stream(response, &mut file, &progress).await?;
+        progress.finish();
+        drop(file);
+        self.finish(info, expected)
+    }
 
-        let status = response.status();
-        tracing::debug!("Response status: {}", status);
+    /// 单个请求流式下载，已有同一素材的临时文件时从其末尾续传
+    ///
+    /// 只有断点记录中有可用于 If-Range 的远端版本时才续传；服务端返回完整文件或版本不一致时从头下载。
+    async fn single(&mut self) -> Result<DownloadMediaResponse> {
+        let saved = StreamState::load(&self.stream_path).filter(|s| s.file_token == self.file_token);
+        let validator = saved.as_ref().and_then(|s| s.version.validator()).map(str::to_string);
+        let offset = match validator {
+            Some(_) => std::fs::metadata(&self.part_path).map(|m| m.len()).unwrap_or(0),
+            None => 0,
+        };
+        if offset == 0 {
+            let response = check_status(self.request.send(None).await?)?;
+            return self.single_from(response, 0).await;
+        }
 
-        // 检查 HTTP 状态码
-        match status.as_u16() {
-            200 | 206 => {
-                // 成功或部分内容
-            }
-            400 => {
-                return Err(crate::error::LarkError::ApiError {
-                    code: 400,
-                    message: "请求参数错误，对于开启了高级权限的多维表格，需确保已正确添加额外的扩展信息".to_string(),
-                });
-            }
-            403 => {
-                return Err(crate::error::LarkError::ApiError {
-                    code: 403,
-                    message: "没有下载素材的权限，请确保调用身份拥有文档资源权限".to_string(),
-                });
-            }
-            404 => {
-                return Err(crate::error::LarkError::ApiError {
-                    code: 404,
-                    message: "素材 token 不存在或素材被删除".to_string(),
-                });
-            }
-            500 => {
-                return Err(crate::error::LarkError::ApiError {
-                    code: 500,
-                    message: "服务端内部异常，请重试".to_string(),
-                });
+        let range = Some(format!("bytes={}-", offset));
+        let response = self.request.send_if_range(range, validator.as_deref()).await?;
+        let changed = saved.is_some_and(|s| s.version.conflicts_with(&remote_version(&response)));
+
+        if response.status().as_u16() == 416 {
+            // 临时文件已经完整，或者比服务端文件更大（文件已变化），后者重新下载
+            let total = header_content_range(&response).and_then(|r| r.total);
+            if total == Some(offset) && !changed {
+                self.resumed_bytes = offset;
+                let info = ResponseInfo::from_response(&response);
+                return self.finish(info, total);
             }
-            _ => {
-                return Err(crate::error::LarkError::NetworkError(format!(
-                    "HTTP 请求失败，状态码: {}",
-                    status
+            return self.restart().await;
+        }
+        let response = check_status(response)?;
+        if changed && response.status().as_u16() == 206 {
+            // 服务端忽略了 If-Range，返回的部分内容来自已变化的文件
+            return self.restart().await;
+        }
+        self.single_from(response, offset).await
+    }
+
+    /// 丢弃临时文件和断点记录，单个请求从头下载
+    async fn restart(&mut self) -> Result<DownloadMediaResponse> {
+        let _ = std::fs::remove_file(&self.part_path);
+        let _ = std::fs::remove_file(&self.state_path);
+        self.parallel = 1;
+        let response = check_status(self.request.send(None).await?)?;
+        self.single_from(response, 0).await
+    }
+
+    /// 把响应写入临时文件：206 时追加到 `offset` 之后，200 时从头写入
+    async fn single_from(&mut self, response: reqwest::Response, offset: u64) -> Result<DownloadMediaResponse> {
+        let info = ResponseInfo::from_response(&response);
+        let (mut file, offset, total) = if response.status().as_u16() == 206 && offset > 0 {
+            let content_range = header_content_range(&response);
+            if content_range.and_then(|r| r.range).map(|(start, _)| start) != Some(offset) {
+                return Err(LarkError::NetworkError(format!(
+                    "续传响应的范围与临时文件不一致，可以使用 --no-resume 重新下载: {}",
+                    self.part_path.display()
                 )));
             }
-        }
+            let file = OpenOptions::new().append(true).open(&self.part_path)?;
+            (file, offset, content_range.and_then(|r| r.total))
+        } else {
+            (File::create(&self.part_path)?, 0, response.content_length())
+        };
+        self.resumed_bytes = offset;
+        let state = StreamState {
+            file_token: self.file_token.clone(),
+            version: remote_version(&response),
+        };
+        state.save(&self.stream_path)?;
 
-        // 获取 Content-Type
-        let content_type = response
-            .headers()
-            .get("content-type")
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("application/octet-stream")
-            .to_string();
+        let progress = DownloadProgress::new(self.options.progress, total, offset);
+        write_stream(response, &mut file, &progress).await?;
+        progress.finish();
+        drop(file);
+        self.finish(info, total)
+    }
 
-        // 获取文件名（从 Content-Disposition 头）
-        let file_name = response
-            .headers()
-            .get("content-disposition")
-            .and_then(|v| v.to_str().ok())
-            .and_then(|disposition| {
-                // 解析 filename="..." 或 filename=...
-                if let Some(start) = disposition.find("filename=") {
-                    let rest = &disposition[start + 9..];
-                    let filename = if rest.starts_with('"') {
-                        // 带引号的文件名
-                        rest.chars()
-                            .skip(1)
-                            .take_while(|c| *c != '"')
-                            .collect::<String>()
-                    } else {
-                        // 不带引号的文件名，取到空格或分号
-                        rest.chars()
-                            .take_while(|c| *c != ' ' && *c != ';')
-                            .collect::<String>()
-                    };
-                    Some(filename)
-                } else {
-                    None
-                }
-            });
+    /// 分段并行下载，每完成一个分段更新断点记录
+    ///
+    /// 每个分段请求都带 If-Range，下载过程中远端文件变化时丢弃临时文件，改为单个请求从头下载。
+    async fn segments(
+        &mut self,
+        file_token: &str,
+        total: u64,
+        saved: Option<DownloadState>,
+        version: RemoteVersion,
+        info: ResponseInfo,
+    ) -> Result<DownloadMediaResponse> {
+        let _ = std::fs::remove_file(&self.stream_path);
+        let state = match saved.filter(|_| self.part_path.exists()) {
+            Some(state) => state,
+            None => {
+                let file = File::create(&self.part_path)?;
+                file.set_len(total)?;
+                let state = DownloadState::new(file_token, total, SEGMENT_SIZE, version);
+                state.save(&self.state_path)?;
+                state
+            }
+        };
+        self.resumed_bytes = state.completed_bytes();
 
-        // 获取二进制内容
-        let bytes = response.bytes().await?;
-        let file_size = bytes.len() as u64;
+        let pending = state.pending_segments();
+        self.parallel = self.options.parallel.min(pending.len()).max(1);
+        let progress = Arc::new(DownloadProgress::new(self.options.progress, Some(total), self.resumed_bytes));
+        let queue = Arc::new(Mutex::new(pending.into_iter().rev().collect::<Vec<_>>()));
+        let version = state.version.clone();
+        let state = Arc::new(Mutex::new(state));
+        let changed = Arc::new(AtomicBool::new(false));
 
-        // 确保输出目录存在
-        if let Some(parent) = Path::new(output_path).parent() {
-            if !parent.as_os_str().is_empty() && !parent.exists() {
-                std::fs::create_dir_all(parent)?;
+        let mut tasks = tokio::task::JoinSet::new();
+        for _ in 0..self.parallel {
+            let worker = SegmentWorker {
+                request: self.request.clone(),
+                part_path: self.part_path.clone(),
+                state_path: self.state_path.clone(),
+                state: Arc::clone(&state),
+                version: version.clone(),
+                changed: Arc::clone(&changed),
+                queue: Arc::clone(&queue),
+                progress: Arc::clone(&progress),
+            };
+            tasks.spawn(worker.run());
+        }
+        // 任一分段失败时立即返回，已完成的分段保留在断点记录中
+        while let Some(joined) = tasks.join_next().await {
+            let result = joined
+                .map_err(|e| LarkError::IoError(format!("分段下载任务异常退出: {}", e)))
+                .and_then(|result| result);
+            if let Err(error) = result {
+                if !changed.load(Ordering::Relaxed) {
+                    return Err(error);
+                }
+                // 远端文件已变化，已下载的分段不能再用
+                tasks.shutdown().await;
+                progress.finish();
+                return self.restart().await;
             }
         }
+        progress.finish();
+
+        let response = self.finish(info, Some(total))?;
+        let _ = std::fs::remove_file(&self.state_path);
+        Ok(response)
+    }
+
+    /// 校验大小和摘要，把临时文件重命名为目标文件
+    fn finish(&self, info: ResponseInfo, expected_size: Option<u64>) -> Result<DownloadMediaResponse> {
+        let file_size = std::fs::metadata(&self.part_path)?.len();
+        if let Some(expected) = expected_size.filter(|e| *e != file_size) {
+            return Err(LarkError::NetworkError(format!(
+                "下载的文件大小不一致：期望 {} 字节，实际 {} 字节，重新运行可以继续下载",
+                expected, file_size
+            )));
+        }
 
-        // 写入文件
-        let mut file = File::create(output_path)?;
-        file.write_all(&bytes)?;
+        let verified_checksum = match info.sha256 {
+            Some(expected) => {
+                if sha256_file(&self.part_path)? != expected {
+                    let _ = std::fs::remove_file(&self.part_path);
+                    let _ = std::fs::remove_file(&self.state_path);
+                    let _ = std::fs::remove_file(&self.stream_path);
+                    return Err(LarkError::ValidationError(
+                        "下载的文件 SHA-256 摘要与服务端不一致，已删除临时文件".to_string(),
+                    ));
+                }
+                Some("sha-256".to_string())
+            }
+            None => None,
+        };
 
-        tracing::debug!(
-            "File downloaded successfully: {} bytes",
-            file_size
-        );
+        std::fs::rename(&self.part_path, &self.output)?;
+        let _ = std::fs::remove_file(&self.stream_path);
+        tracing::debug!("File downloaded successfully: {} bytes", file_size);
 
         Ok(DownloadMediaResponse {
-            file_path: output_path.to_string(),
+            file_path: self.output.to_string_lossy().to_string(),
             file_size,
-            content_type,
-            file_name,
+            content_type: info.content_type,
+            file_name: info.file_name,
+            resumed_bytes: self.resumed_bytes,
+            parallel: self.parallel,
+            verified_checksum,
         })
     }
+}
 
-    /// 下载媒体文件到指定路径
-    /// 如果未指定文件名，将使用 file_token 作为文件名
-    pub async fn download_media_auto_name(
-        &self,
-        file_token: &str,
-        output_dir: &str,
-        extra: Option<String>,
-        range: Option<String>,
-    ) -> Result<DownloadMediaResponse> {
-        // 先发起请求获取文件名
-        let url = format!(
-            "https://open.larkoffice.com/open-apis/drive/v1/medias/{}/download",
-            file_token
-        );
-
-        let mut params = HashMap::new();
-        if let Some(ref extra) = extra {
-            params.insert("extra".to_string(), extra.clone());
-        }
+/// 并行下载的工作任务：从队列中依次取出分段下载，直到队列为空
+struct SegmentWorker {
+    request: MediaRequest,
+    part_path: PathBuf,
+    state_path: PathBuf,
+    state: Arc<Mutex<DownloadState>>,
+    /// 断点记录中的远端版本，作为 If-Range 发送
+    version: RemoteVersion,
+    /// 发现远端文件已变化
+    changed: Arc<AtomicBool>,
+    queue: Arc<Mutex<Vec<u64>>>,
+    progress: Arc<DownloadProgress>,
+}
 
-        let auth_header = self.client.auth_manager.get_auth_header().await?;
+impl SegmentWorker {
+    async fn run(self) -> Result<()> {
+        let mut file = OpenOptions::new().write(true).open(&self.part_path)?;
+        loop {
+            let Some(seq) = self.queue.lock().unwrap().pop() else {
+                return Ok(());
+            };
+            let (start, end) = self.state.lock().unwrap().segment_range(seq);
+            let range = Some(format!("bytes={}-{}", start, end));
+            let validator = self.version.validator();
+            let response = check_status(self.request.send_if_range(range, validator).await?)?;
+            let status = response.status().as_u16();
+            if validator.is_some() && (status == 200 || self.version.conflicts_with(&remote_version(&response))) {
+                self.changed.store(true, Ordering::Relaxed);
+                return Err(LarkError::NetworkError("远端文件在下载过程中已变化".to_string()));
+            }
+            if status != 206
+                || header_content_range(&response).and_then(|r| r.range) != Some((start, end))
+            {
+                return Err(LarkError::NetworkError(format!(
+                    "服务端未按请求的范围 {}-{} 返回数据",
+                    start, end
+                )));
+            }
 
-        let mut request = self.client.client.head(&url);
-        request = request.header("Authorization", auth_header);
+            file.seek(SeekFrom::Start(start))?;
+            let written = write_stream(response, &mut file, &self.progress).await?;
+            if written != end - start + 1 {
+                return Err(LarkError::NetworkError(format!(
+                    "分段 {}-{} 下载不完整，重新运行可以继续下载",
+                    start, end
+                )));
+            }
+            file.flush()?;
 
-        if !params.is_empty() {
-            request = request.query(&params);
+            let mut state = self.state.lock().unwrap();
+            state.completed.insert(seq);
+            state.save(&self.state_path)?;
         }
+    }
+}
 
-        // 尝试 HEAD 请求获取文件名
-        let file_name = match request.send().await {
-            Ok(resp) => resp
-                .headers()
-                .get("content-disposition")
-                .and_then(|v| v.to_str().ok())
-                .and_then(|disposition| {
-                    if let Some(start) = disposition.find("filename=") {
-                        let rest = &disposition[start + 9..];
-                        let filename = if rest.starts_with('"') {
-                            rest.chars()
-                                .skip(1)
-                                .take_while(|c| *c != '"')
-                                .collect::<String>()
-                        } else {
-                            rest.chars()
-                                .take_while(|c| *c != ' ' && *c != ';')
-                                .collect::<String>()
-                        };
-                        Some(filename)
-                    } else {
-                        None
-                    }
-                }),
-            Err(_) => None,
-        };
+/// 将响应内容逐块写入文件，返回写入的字节数
+pub(crate) async fn write_stream(
+    mut response: reqwest::Response,
+    file: &mut File,
+    progress: &DownloadProgress,
+) -> Result<u64> {
+    let mut written = 0;
+    while let Some(chunk) = response.chunk().await? {
+        file.write_all(&chunk)?;
+        written += chunk.len() as u64;
+        progress.add(chunk.len() as u64);
+    }
+    Ok(written)
+}
 
-        // 确定输出文件路径
-        let output_path = if let Some(name) = file_name {
-            let path = Path::new(output_dir);
-            path.join(name).to_str().unwrap().to_string()
-        } else {
-            let path = Path::new(output_dir);
-            path.join(file_token).to_str().unwrap().to_string()
-        };
+/// 确保输出目录存在
+pub(crate) fn ensure_parent_dir(path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() && !parent.exists() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+    Ok(())
+}
+
+/// 检查下载响应的 HTTP 状态码
+fn check_status(response: reqwest::Response) -> Result<reqwest::Response> {
+    let status = response.status();
+    match status.as_u16() {
+        200 | 206 => Ok(response),
+        400 => Err(LarkError::ApiError {
+            code: 400,
+            message: "请求参数错误，对于开启了高级权限的多维表格，需确保已正确添加额外的扩展信息".to_string(),
+        }),
+        403 => Err(LarkError::ApiError {
+            code: 403,
+            message: "没有下载素材的权限，请确保调用身份拥有文档资源权限".to_string(),
+        }),
+        404 => Err(LarkError::ApiError {
+            code: 404,
+            message: "素材 token 不存在或素材被删除".to_string(),
+        }),
+        416 => Err(LarkError::ValidationError("下载范围超出文件大小".to_string())),
+        500 => Err(LarkError::ApiError {
+            code: 500,
+            message: "服务端内部异常，请重试".to_string(),
+        }),
+        _ => Err(LarkError::NetworkError(format!("HTTP 请求失败，状态码: {}", status))),
+    }
+}
+
+/// 响应头中的远端版本
+fn remote_version(response: &reqwest::Response) -> RemoteVersion {
+    let header = |name: &str| response.headers().get(name).and_then(|v| v.to_str().ok()).map(str::to_string);
+    RemoteVersion {
+        etag: header("etag"),
+        last_modified: header("last-modified"),
+    }
+}
 
-        self.download_media(file_token, &output_path, extra, range)
-            .await
+fn header_content_range(response: &reqwest::Response) -> Option<crate::utils::ContentRange> {
+    response
+        .headers()
+        .get("content-range")
+        .and_then(|v| v.to_str().ok())
+        .and_then(parse_content_range)
+}
+
+/// 解析 Content-Disposition 中的 filename="..." 或 filename=...
+fn content_disposition_file_name(disposition: &str) -> Option<String> {
+    let start = disposition.find("filename=")?;
+    let rest = &disposition[start + 9..];
+    let filename = if let Some(quoted) = rest.strip_prefix('"') {
+        // 带引号的文件名
+        quoted.chars().take_while(|c| *c != '"').collect::<String>()
+    } else {
+        // 不带引号的文件名，取到空格或分号
+        rest.chars().take_while(|c| *c != ' ' && *c != ';').collect::<String>()
+    };
+    Some(filename)
+}
+
+/// 分块读取文件计算 SHA-256
+fn sha256_file(path: &Path) -> Result<Vec<u8>> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
     }
+    Ok(hasher.finalize().to_vec())
 }
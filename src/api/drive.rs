@@ -0,0 +1,423 @@
+use super::{ApiClient, WikiApi};
+use crate::error::{LarkError, Result};
+use crate::utils::{check_drive_file_type, parse_drive_ref, DriveEntry, DriveRef};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+
+/// 列出文件的分页大小上限
+const LIST_PAGE_SIZE: &str = "200";
+
+/// 批量获取元数据的单次上限
+const META_BATCH_SIZE: usize = 200;
+
+/// 异步任务的最长等待次数（每秒一次）
+const TASK_POLL_ATTEMPTS: usize = 60;
+
+#[derive(Debug, Serialize)]
+pub struct CreateFolderRequest {
+    pub name: String,
+    pub folder_token: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateFolderResponse {
+    /// 新建文件夹的 token
+    pub token: String,
+    /// 新建文件夹的访问链接
+    pub url: String,
+}
+
+/// 云空间中的文件或文件夹
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct DriveFile {
+    pub token: String,
+    pub name: String,
+    /// 文件类型：doc、docx、sheet、bitable、mindnote、file、slides、folder、shortcut
+    #[serde(rename = "type")]
+    pub file_type: String,
+    #[serde(default)]
+    pub parent_token: String,
+    #[serde(default)]
+    pub url: String,
+    /// 创建时间（秒级时间戳）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_time: Option<String>,
+    /// 修改时间（秒级时间戳）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified_time: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner_id: Option<String>,
+    /// 快捷方式指向的文件
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shortcut_info: Option<ShortcutInfo>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ShortcutInfo {
+    pub target_type: String,
+    pub target_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct FileListPage {
+    #[serde(default)]
+    files: Vec<DriveFile>,
+    #[serde(default)]
+    has_more: bool,
+    #[serde(default)]
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RootFolderMeta {
+    token: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct TaskResponse {
+    #[serde(default)]
+    task_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TaskStatus {
+    status: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CopyFileResponse {
+    file: DriveFile,
+}
+
+/// 解析后的文件 token 和类型
+#[derive(Debug, Clone, Serialize)]
+pub struct DriveTarget {
+    pub token: String,
+    pub file_type: String,
+}
+
+/// 文件元数据
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DriveMeta {
+    pub doc_token: String,
+    pub doc_type: String,
+    pub title: String,
+    pub owner_id: String,
+    /// 创建时间（秒级时间戳）
+    pub create_time: String,
+    pub latest_modify_user: String,
+    /// 最后修改时间（秒级时间戳）
+    pub latest_modify_time: String,
+    pub url: String,
+}
+
+/// 获取失败的文件
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct FailedMeta {
+    pub token: String,
+    pub code: i32,
+}
+
+/// 批量获取元数据的结果
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct MetaResult {
+    pub metas: Vec<DriveMeta>,
+    pub failed_list: Vec<FailedMeta>,
+}
+
+/// 移动、复制、删除的结果
+#[derive(Debug, Serialize)]
+pub struct DriveOperationResult {
+    /// 操作：move、copy、delete
+    pub action: String,
+    pub token: String,
+    pub file_type: String,
+    /// 是否只规划、未执行
+    pub dry_run: bool,
+    /// 目标文件夹
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub folder_token: Option<String>,
+    /// 副本名称
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// 副本 token
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_token: Option<String>,
+    /// 副本链接
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// 文件夹操作的异步任务 ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+}
+
+impl DriveOperationResult {
+    fn new(action: &str, target: &DriveTarget, dry_run: bool) -> Self {
+        Self {
+            action: action.to_string(),
+            token: target.token.clone(),
+            file_type: target.file_type.clone(),
+            dry_run,
+            folder_token: None,
+            name: None,
+            new_token: None,
+            url: None,
+            task_id: None,
+        }
+    }
+}
+
+/// 云空间 API
+pub struct DriveApi {
+    client: ApiClient,
+}
+
+impl DriveApi {
+    pub fn new(client: ApiClient) -> Self {
+        Self { client }
+    }
+
+    /// 在指定文件夹下创建文件夹
+    pub async fn create_folder(&self, name: &str, folder_token: &str) -> Result<CreateFolderResponse> {
+        if name.is_empty() || name.chars().count() > 256 {
+            return Err(LarkError::ValidationError(
+                "文件夹名称长度必须在1-256个字符之间".to_string()
+            ));
+        }
+
+        let request = CreateFolderRequest {
+            name: name.to_string(),
+            folder_token: folder_token.to_string(),
+        };
+
+        let url = "https://open.larkoffice.com/open-apis/drive/v1/files/create_folder";
+
+        self.client.post(url, &request).await
+    }
+
+    /// 获取当前用户根文件夹的 token
+    pub async fn root_folder_token(&self) -> Result<String> {
+        let url = "https://open.larkoffice.com/open-apis/drive/explorer/v2/root_folder/meta";
+        let response: RootFolderMeta = self.client.get(url, None).await?;
+        Ok(response.token)
+    }
+
+    /// 列出文件夹中的全部文件，自动翻页
+    pub async fn list_files(&self, folder_token: &str) -> Result<Vec<DriveFile>> {
+        let url = "https://open.larkoffice.com/open-apis/drive/v1/files";
+        let mut files = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = HashMap::new();
+            params.insert("folder_token".to_string(), folder_token.to_string());
+            params.insert("page_size".to_string(), LIST_PAGE_SIZE.to_string());
+            if let Some(token) = &page_token {
+                params.insert("page_token".to_string(), token.clone());
+            }
+            let page: FileListPage = self.client.get(url, Some(params)).await?;
+            files.extend(page.files);
+            page_token = page.next_page_token.filter(|t| !t.is_empty());
+            if !page.has_more || page_token.is_none() {
+                break;
+            }
+        }
+        Ok(files)
+    }
+
+    /// 列出文件夹内容，`recursive` 时按先序展开子文件夹，`max_depth` 限制展开的层数
+    pub async fn list_tree(
+        &self,
+        folder_token: &str,
+        recursive: bool,
+        max_depth: Option<usize>,
+    ) -> Result<Vec<DriveEntry>> {
+        let mut entries = Vec::new();
+        let mut pending = child_entries(self.list_files(folder_token).await?, 0, "");
+        while let Some(entry) = pending.pop() {
+            let depth = entry.depth + 1;
+            if recursive && entry.file.file_type == "folder" && max_depth.is_none_or(|max| depth < max) {
+                let children = self.list_files(&entry.file.token).await?;
+                pending.extend(child_entries(children, depth, &entry.path));
+            }
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+
+    /// 将 token 或链接解析为文件 token 和类型，知识库链接解析为节点对应的文档
+    ///
+    /// `file_type` 用于指定无法从 token 判断的类型。
+    pub async fn resolve(&self, input: &str, file_type: Option<&str>) -> Result<DriveTarget> {
+        if let Some(file_type) = file_type {
+            check_drive_file_type(file_type).map_err(LarkError::ValidationError)?;
+        }
+        match parse_drive_ref(input).map_err(LarkError::ValidationError)? {
+            DriveRef::Wiki(node_token) => {
+                let node = WikiApi::new(self.client.clone())
+                    .get_knowledge_space_node(&node_token, None)
+                    .await?;
+                Ok(DriveTarget {
+                    token: node.obj_token,
+                    file_type: node.obj_type,
+                })
+            }
+            DriveRef::File { token, file_type: parsed } => {
+                let file_type = file_type.map(str::to_string).or(parsed).ok_or_else(|| {
+                    LarkError::ValidationError(format!(
+                        "无法判断 {} 的文件类型，请使用文档链接或 --type 指定",
+                        token
+                    ))
+                })?;
+                Ok(DriveTarget { token, file_type })
+            }
+        }
+    }
+
+    /// 解析目标文件夹，未指定时为根文件夹
+    pub async fn resolve_folder(&self, input: Option<&str>) -> Result<String> {
+        let Some(input) = input else {
+            return self.root_folder_token().await;
+        };
+        match parse_drive_ref(input).map_err(LarkError::ValidationError)? {
+            DriveRef::File { token, file_type } if file_type.as_deref().is_none_or(|t| t == "folder") => Ok(token),
+            _ => Err(LarkError::ValidationError(format!("不是文件夹: {}", input))),
+        }
+    }
+
+    /// 移动文件或文件夹到目标文件夹，移动文件夹时等待异步任务完成
+    pub async fn move_file(&self, target: &DriveTarget, folder_token: &str) -> Result<DriveOperationResult> {
+        let mut result = DriveOperationResult::new("move", target, self.client.is_dry_run());
+        result.folder_token = Some(folder_token.to_string());
+        if result.dry_run {
+            return Ok(result);
+        }
+
+        let url = format!("https://open.larkoffice.com/open-apis/drive/v1/files/{}/move", target.token);
+        let body = json!({ "type": target.file_type, "folder_token": folder_token });
+        let response: TaskResponse = self.client.post(&url, &body).await?;
+        result.task_id = self.wait_task(response.task_id).await?;
+        Ok(result)
+    }
+
+    /// 复制文件到目标文件夹，`name` 为空时沿用原文件名加“副本”后缀
+    pub async fn copy_file(
+        &self,
+        target: &DriveTarget,
+        folder_token: &str,
+        name: Option<&str>,
+    ) -> Result<DriveOperationResult> {
+        if target.file_type == "folder" {
+            return Err(LarkError::ValidationError("不支持复制文件夹".to_string()));
+        }
+        let name = match name.filter(|n| !n.is_empty()) {
+            Some(name) => name.to_string(),
+            None => format!("{} 副本", self.title(target).await?),
+        };
+
+        let mut result = DriveOperationResult::new("copy", target, self.client.is_dry_run());
+        result.folder_token = Some(folder_token.to_string());
+        result.name = Some(name.clone());
+        if result.dry_run {
+            return Ok(result);
+        }
+
+        let url = format!("https://open.larkoffice.com/open-apis/drive/v1/files/{}/copy", target.token);
+        let body = json!({ "name": name, "type": target.file_type, "folder_token": folder_token });
+        let response: CopyFileResponse = self.client.post(&url, &body).await?;
+        result.new_token = Some(response.file.token);
+        result.url = Some(response.file.url).filter(|u| !u.is_empty());
+        Ok(result)
+    }
+
+    /// 删除文件或文件夹（移到回收站），删除文件夹时等待异步任务完成
+    pub async fn delete_file(&self, target: &DriveTarget) -> Result<DriveOperationResult> {
+        let mut result = DriveOperationResult::new("delete", target, self.client.is_dry_run());
+        if result.dry_run {
+            return Ok(result);
+        }
+
+        let url = format!("https://open.larkoffice.com/open-apis/drive/v1/files/{}", target.token);
+        let mut params = HashMap::new();
+        params.insert("type".to_string(), target.file_type.clone());
+        let response: TaskResponse = self.client.delete_with_params(&url, Some(params), &json!({})).await?;
+        result.task_id = self.wait_task(response.task_id).await?;
+        Ok(result)
+    }
+
+    /// 批量获取文件元数据，每次请求最多 200 个
+    pub async fn batch_meta(&self, targets: &[DriveTarget]) -> Result<MetaResult> {
+        let url = "https://open.larkoffice.com/open-apis/drive/v1/metas/batch_query";
+        let mut params = HashMap::new();
+        params.insert("user_id_type".to_string(), "open_id".to_string());
+
+        let mut result = MetaResult::default();
+        for chunk in targets.chunks(META_BATCH_SIZE) {
+            let request_docs: Vec<_> = chunk
+                .iter()
+                .map(|t| json!({ "doc_token": t.token, "doc_type": t.file_type }))
+                .collect();
+            let body = json!({ "request_docs": request_docs, "with_url": true });
+            let response: MetaResult = self.client.post_with_params(url, Some(params.clone()), &body).await?;
+            result.metas.extend(response.metas);
+            result.failed_list.extend(response.failed_list);
+        }
+        Ok(result)
+    }
+
+    /// 文件标题，查询失败时返回 token
+    pub async fn title(&self, target: &DriveTarget) -> Result<String> {
+        let meta = self.batch_meta(std::slice::from_ref(target)).await?;
+        Ok(meta
+            .metas
+            .into_iter()
+            .next()
+            .map(|m| m.title)
+            .filter(|t| !t.is_empty())
+            .unwrap_or_else(|| target.token.clone()))
+    }
+
+    /// 等待文件夹移动、删除等异步任务完成
+    async fn wait_task(&self, task_id: Option<String>) -> Result<Option<String>> {
+        let Some(task_id) = task_id.filter(|id| !id.is_empty()) else {
+            return Ok(None);
+        };
+        let url = "https://open.larkoffice.com/open-apis/drive/v1/files/task_check";
+        for _ in 0..TASK_POLL_ATTEMPTS {
+            let mut params = HashMap::new();
+            params.insert("task_id".to_string(), task_id.clone());
+            let response: TaskStatus = self.client.get(url, Some(params)).await?;
+            match response.status.as_str() {
+                "success" => return Ok(Some(task_id)),
+                "fail" => {
+                    return Err(LarkError::ApiError {
+                        code: -1,
+                        message: format!("异步任务 {} 执行失败", task_id),
+                    })
+                }
+                _ => tokio::time::sleep(std::time::Duration::from_secs(1)).await,
+            }
+        }
+        Err(LarkError::NetworkError(format!("等待异步任务 {} 超时", task_id)))
+    }
+}
+
+/// 子项转换为目录树项，逆序排列以便按栈弹出
+fn child_entries(files: Vec<DriveFile>, depth: usize, parent_path: &str) -> Vec<DriveEntry> {
+    files
+        .into_iter()
+        .rev()
+        .map(|file| DriveEntry {
+            depth,
+            path: if parent_path.is_empty() {
+                file.name.clone()
+            } else {
+                format!("{}/{}", parent_path, file.name)
+            },
+            file,
+        })
+        .collect()
+}
@@ -0,0 +1,308 @@
+use super::multipart::MultipartForm;
+use super::ApiClient;
+use crate::error::{LarkError, Result};
+use crate::utils::{adler32, UploadSource, UploadState};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// 不超过该大小的文件一次上传，更大的文件分片上传
+const UPLOAD_ALL_LIMIT: u64 = 20 * 1024 * 1024;
+
+/// 服务端默认的分片大小，仅用于 dry-run 时估算分片数
+const DEFAULT_BLOCK_SIZE: u64 = 4 * 1024 * 1024;
+
+/// 并发上传分片数的上限
+pub const MAX_UPLOAD_CONCURRENCY: usize = 16;
+
+#[derive(Debug, Deserialize)]
+struct UploadFileResponse {
+    file_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct UploadPrepareResponse {
+    upload_id: String,
+    block_size: u64,
+    block_num: u32,
+}
+
+/// 上传结果
+#[derive(Debug, Serialize)]
+pub struct DriveUploadResult {
+    /// 本地路径
+    pub path: String,
+    /// 上传后的文件名
+    pub file_name: String,
+    /// 目标文件夹
+    pub folder_token: String,
+    /// 文件大小（字节）
+    pub size: u64,
+    /// 上传方式：upload_all（一次上传）或 multipart（分片上传）
+    pub mode: String,
+    /// 是否只规划、未上传
+    pub dry_run: bool,
+    /// 整个文件的 Adler-32 校验和（一次上传时）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+    /// 分片上传的 upload_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upload_id: Option<String>,
+    /// 分片大小（字节）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_size: Option<u64>,
+    /// 分片数量
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_num: Option<u32>,
+    /// 续传时跳过的已上传分片数
+    pub resumed_parts: usize,
+    /// 本次上传的分片数
+    pub uploaded_parts: usize,
+    /// 上传后的文件 token（dry-run 时为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_token: Option<String>,
+}
+
+/// 云空间文件上传 API：小文件一次上传，大文件分片并发上传并支持断点续传
+pub struct DriveUploadApi {
+    client: ApiClient,
+}
+
+impl DriveUploadApi {
+    pub fn new(client: ApiClient) -> Self {
+        Self { client }
+    }
+
+    /// 上传本地文件到文件夹
+    ///
+    /// 大文件的 upload_id 和已完成的分片记录在 `state_dir` 中，中断后再次上传同一文件时从记录继续；
+    /// `resume` 为 false 时忽略已有记录重新开始。
+    pub async fn upload(
+        &self,
+        path: &Path,
+        folder_token: &str,
+        file_name: Option<&str>,
+        concurrency: usize,
+        resume: bool,
+        state_dir: &Path,
+    ) -> Result<DriveUploadResult> {
+        if !path.is_file() {
+            return Err(LarkError::ValidationError(format!("文件不存在或不是文件: {}", path.display())));
+        }
+        if concurrency == 0 || concurrency > MAX_UPLOAD_CONCURRENCY {
+            return Err(LarkError::ValidationError(format!(
+                "并发数必须在 1-{} 之间",
+                MAX_UPLOAD_CONCURRENCY
+            )));
+        }
+        let file_name = match file_name.filter(|n| !n.is_empty()) {
+            Some(name) => name.to_string(),
+            None => path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .ok_or_else(|| LarkError::ValidationError(format!("无法获取文件名: {}", path.display())))?
+                .to_string(),
+        };
+        if file_name.chars().count() > 250 {
+            return Err(LarkError::ValidationError("文件名不能超过250个字符".to_string()));
+        }
+
+        let source = UploadSource::from_file(path, &file_name, folder_token)?;
+        if source.size == 0 {
+            return Err(LarkError::ValidationError(format!("文件为空: {}", path.display())));
+        }
+        let mut result = DriveUploadResult {
+            path: path.display().to_string(),
+            file_name,
+            folder_token: folder_token.to_string(),
+            size: source.size,
+            mode: if source.size <= UPLOAD_ALL_LIMIT { "upload_all" } else { "multipart" }.to_string(),
+            dry_run: self.client.is_dry_run(),
+            checksum: None,
+            upload_id: None,
+            block_size: None,
+            block_num: None,
+            resumed_parts: 0,
+            uploaded_parts: 0,
+            file_token: None,
+        };
+
+        if source.size <= UPLOAD_ALL_LIMIT {
+            self.upload_all(path, &mut result).await?;
+        } else {
+            self.upload_multipart(path, source, concurrency, resume, state_dir, &mut result)
+                .await?;
+        }
+        Ok(result)
+    }
+
+    /// 一次上传整个文件
+    async fn upload_all(&self, path: &Path, result: &mut DriveUploadResult) -> Result<()> {
+        let content = std::fs::read(path)
+            .map_err(|e| LarkError::IoError(format!("读取文件失败 {}: {}", path.display(), e)))?;
+        let checksum = adler32(&content).to_string();
+        result.checksum = Some(checksum.clone());
+        if result.dry_run {
+            return Ok(());
+        }
+
+        let (body, headers) = MultipartForm::new()
+            .text("file_name", &result.file_name)
+            .text("parent_type", "explorer")
+            .text("parent_node", &result.folder_token)
+            .text("size", &content.len().to_string())
+            .text("checksum", &checksum)
+            .file("file", &result.file_name, &content)
+            .finish();
+        let response: UploadFileResponse = self
+            .client
+            .post_form_data("https://open.larkoffice.com/open-apis/drive/v1/files/upload_all", body, headers)
+            .await?;
+        result.file_token = Some(response.file_token);
+        Ok(())
+    }
+
+    /// 分片上传：预上传获取 upload_id，并发上传未完成的分片，最后完成上传
+    async fn upload_multipart(
+        &self,
+        path: &Path,
+        source: UploadSource,
+        concurrency: usize,
+        resume: bool,
+        state_dir: &Path,
+        result: &mut DriveUploadResult,
+    ) -> Result<()> {
+        let state_path = UploadState::state_path(state_dir, &source);
+        let now = chrono::Utc::now().timestamp();
+        let saved = if resume { UploadState::load(&state_path) } else { None };
+        let saved = saved.filter(|state| state.can_resume(&source, now));
+
+        if result.dry_run {
+            match saved {
+                Some(state) => {
+                    result.resumed_parts = state.completed.len();
+                    result.upload_id = Some(state.upload_id);
+                    result.block_size = Some(state.block_size);
+                    result.block_num = Some(state.block_num);
+                }
+                None => {
+                    result.block_size = Some(DEFAULT_BLOCK_SIZE);
+                    result.block_num = Some(source.size.div_ceil(DEFAULT_BLOCK_SIZE) as u32);
+                }
+            }
+            return Ok(());
+        }
+
+        let state = match saved {
+            Some(state) => state,
+            None => {
+                let body = json!({
+                    "file_name": source.file_name,
+                    "parent_type": "explorer",
+                    "parent_node": source.parent_node,
+                    "size": source.size,
+                });
+                let prepared: UploadPrepareResponse = self
+                    .client
+                    .post("https://open.larkoffice.com/open-apis/drive/v1/files/upload_prepare", &body)
+                    .await?;
+                let state = UploadState::new(source, prepared.upload_id, prepared.block_size, prepared.block_num);
+                state.save(&state_path)?;
+                state
+            }
+        };
+        result.resumed_parts = state.completed.len();
+        result.upload_id = Some(state.upload_id.clone());
+        result.block_size = Some(state.block_size);
+        result.block_num = Some(state.block_num);
+
+        let pending = state.pending_parts();
+        result.uploaded_parts = pending.len();
+        let (upload_id, block_num) = (state.upload_id.clone(), state.block_num);
+        self.upload_parts(path, state, &state_path, pending, concurrency).await?;
+
+        let body = json!({ "upload_id": upload_id, "block_num": block_num });
+        let response: UploadFileResponse = self
+            .client
+            .post("https://open.larkoffice.com/open-apis/drive/v1/files/upload_finish", &body)
+            .await?;
+        result.file_token = Some(response.file_token);
+        // 上传完成后断点记录不再需要，删除失败不影响结果
+        let _ = std::fs::remove_file(&state_path);
+        Ok(())
+    }
+
+    /// 并发上传分片，每完成一个分片更新断点记录
+    async fn upload_parts(
+        &self,
+        path: &Path,
+        state: UploadState,
+        state_path: &Path,
+        pending: Vec<u32>,
+        concurrency: usize,
+    ) -> Result<()> {
+        let workers = concurrency.min(pending.len());
+        let queue = Arc::new(Mutex::new(pending.into_iter().rev().collect::<Vec<_>>()));
+        let state = Arc::new(Mutex::new(state));
+
+        let mut tasks = tokio::task::JoinSet::new();
+        for _ in 0..workers {
+            let client = self.client.clone();
+            let queue = Arc::clone(&queue);
+            let state = Arc::clone(&state);
+            let path = path.to_path_buf();
+            let state_path = state_path.to_path_buf();
+            tasks.spawn(async move { upload_worker(client, path, state, state_path, queue).await });
+        }
+
+        // 任一分片失败时立即返回，未完成的任务随 JoinSet 一起取消，已完成的分片保留在断点记录中
+        while let Some(joined) = tasks.join_next().await {
+            joined.map_err(|e| LarkError::IoError(format!("分片上传任务异常退出: {}", e)))??;
+        }
+        Ok(())
+    }
+}
+
+/// 从队列中依次取出分片上传，直到队列为空
+async fn upload_worker(
+    client: ApiClient,
+    path: PathBuf,
+    state: Arc<Mutex<UploadState>>,
+    state_path: PathBuf,
+    queue: Arc<Mutex<Vec<u32>>>,
+) -> Result<()> {
+    let (upload_id, block_size, size) = {
+        let state = state.lock().unwrap();
+        (state.upload_id.clone(), state.block_size, state.source.size)
+    };
+    let mut file = std::fs::File::open(&path)
+        .map_err(|e| LarkError::IoError(format!("读取文件失败 {}: {}", path.display(), e)))?;
+
+    loop {
+        let Some(seq) = queue.lock().unwrap().pop() else {
+            return Ok(());
+        };
+        let offset = u64::from(seq) * block_size;
+        let mut chunk = vec![0u8; block_size.min(size.saturating_sub(offset)) as usize];
+        file.seek(SeekFrom::Start(offset))
+            .and_then(|_| file.read_exact(&mut chunk))
+            .map_err(|e| LarkError::IoError(format!("读取分片 {} 失败: {}", seq, e)))?;
+
+        let (body, headers) = MultipartForm::new()
+            .text("upload_id", &upload_id)
+            .text("seq", &seq.to_string())
+            .text("size", &chunk.len().to_string())
+            .text("checksum", &adler32(&chunk).to_string())
+            .file("file", &format!("part-{}", seq), &chunk)
+            .finish();
+        let _: serde_json::Value = client
+            .post_form_data("https://open.larkoffice.com/open-apis/drive/v1/files/upload_part", body, headers)
+            .await?;
+
+        let mut state = state.lock().unwrap();
+        state.completed.insert(seq);
+        state.save(&state_path)?;
+    }
+}
@@ -16,7 +16,7 @@ pub struct GetBlocksRequest {
     pub user_id_type: String,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct BlockItem {
     #[serde(rename = "block_id")]
     pub block_id: String,
@@ -1,8 +1,8 @@
+use super::download_media::{ensure_parent_dir, write_stream, DownloadProgress, DOWNLOAD_TIMEOUT};
 use super::ApiClient;
 use crate::error::Result;
 use serde::Serialize;
 use std::fs::File;
-use std::io::Write;
 use std::path::Path;
 
 /// 获取画板图片的响应
@@ -54,6 +54,7 @@ impl GetBoardImageApi {
             .client
             .get(&url)
             .header("Authorization", auth_header)
+            .timeout(DOWNLOAD_TIMEOUT)
             .send()
             .await?;
 
@@ -136,10 +137,6 @@ impl GetBoardImageApi {
             _ => "png", // 默认使用 png
         };
 
-        // 获取二进制内容
-        let bytes = response.bytes().await?;
-        let file_size = bytes.len() as u64;
-
         // 如果 output_path 是目录，则自动添加文件名
         let final_output_path = if output_path.ends_with('/') || output_path.ends_with('\\') {
             let path = Path::new(output_path);
@@ -150,15 +147,14 @@ impl GetBoardImageApi {
         };
 
         // 确保输出目录存在
-        if let Some(parent) = Path::new(&final_output_path).parent() {
-            if !parent.as_os_str().is_empty() && !parent.exists() {
-                std::fs::create_dir_all(parent)?;
-            }
-        }
-
-        // 写入文件
-        let mut file = File::create(&final_output_path)?;
-        file.write_all(&bytes)?;
+        ensure_parent_dir(Path::new(&final_output_path))?;
+
+        // 流式写入临时文件，完成后重命名，避免中断时留下不完整的图片
+        let part_path = format!("{}.part", final_output_path);
+        let mut file = File::create(&part_path)?;
+        let file_size = write_stream(response, &mut file, &DownloadProgress::new(false, None, 0)).await?;
+        drop(file);
+        std::fs::rename(&part_path, &final_output_path)?;
 
         tracing::debug!(
             "Board image downloaded successfully: {} bytes",
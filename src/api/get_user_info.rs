@@ -355,6 +355,23 @@ pub struct DepartmentPath {
     pub department_path_name: Option<DepartmentPathName>,
 }
 
+/// 通过邮箱批量查询用户 ID 的响应
+#[derive(Debug, Deserialize)]
+struct BatchGetIdResponse {
+    #[serde(default)]
+    user_list: Vec<UserContactInfo>,
+}
+
+/// 邮箱对应的用户
+#[derive(Debug, Deserialize)]
+struct UserContactInfo {
+    user_id: Option<String>,
+    email: Option<String>,
+}
+
+/// 单次批量查询的邮箱数量上限
+const BATCH_GET_ID_LIMIT: usize = 50;
+
 pub struct GetUserInfoApi {
     client: ApiClient,
 }
@@ -425,4 +442,25 @@ impl GetUserInfoApi {
 
         Ok(data.user)
     }
+
+    /// 通过邮箱批量查询用户的 open_id
+    ///
+    /// 返回邮箱到 open_id 的映射，找不到的邮箱不在结果中。
+    pub async fn open_ids_by_email(&self, emails: &[String]) -> Result<HashMap<String, String>> {
+        let url = "https://open.larkoffice.com/open-apis/contact/v3/users/batch_get_id";
+        let mut params = HashMap::new();
+        params.insert("user_id_type".to_string(), "open_id".to_string());
+
+        let mut open_ids = HashMap::new();
+        for chunk in emails.chunks(BATCH_GET_ID_LIMIT) {
+            let body = serde_json::json!({ "emails": chunk });
+            let data: BatchGetIdResponse = self.client.post_with_params(url, Some(params.clone()), &body).await?;
+            for user in data.user_list {
+                if let (Some(email), Some(user_id)) = (user.email, user.user_id) {
+                    open_ids.insert(email, user_id);
+                }
+            }
+        }
+        Ok(open_ids)
+    }
 }
@@ -1,20 +1,50 @@
-use super::{ApiClient, ConvertBlocksApi, CreateNestedBlocksApi, DescendantBlock};
+use super::{ApiClient, ConvertBlocksApi, CreateNestedBlocksApi, DeleteBlocksApi, DescendantBlock, GetBlocksApi};
 use crate::api::convert_blocks::ConvertBlocksResponse;
-use crate::api::create_nested_blocks::CreateNestedBlocksResponse;
-use crate::error::Result;
-use crate::utils::{ImportRequest, ImportResult, BatchImportResult};
+use crate::api::create_nested_blocks::{BlockIdRelation, CreateNestedBlocksResponse};
+use crate::error::{LarkError, Result};
+use crate::utils::{
+    apply_mentions, content_hash, index_ranges, mark_diagram_fences, mark_mentions, mention_emails, split_front_matter, BatchImportResult, BlockTree,
+    ExistingFilePolicy, FrontMatter, ImportManifest, ImportRequest, ImportResult, ManifestStatus,
+};
+use super::batch_update_blocks::{TextElement, UpdateBlockRequest};
+use super::block_batcher::{BatchLimits, BlockBatcher};
+use super::block_builder::{callout_blocks, CalloutType};
+use super::import_plan::{collaborator_request, BatchImportPlan, FileImportPlan, ImportPlan};
+use super::board::{ImportDiagramRequest, StyleType};
+use super::diagram_blocks::{plan_diagram_blocks, DiagramOptions, PlannedDiagram};
+use super::{BatchUpdateBlocksApi, BoardApi, GetUserInfoApi, PermissionApi};
+use std::collections::HashMap;
 use super::block_converter::BlockConverter;
 
+/// 转换完成、等待创建的块
+struct PreparedBlocks {
+    children_id: Vec<String>,
+    descendants: Vec<DescendantBlock>,
+    diagrams: Vec<PlannedDiagram>,
+}
+
 /// 文档导入 API，封装了转换和创建两个步骤
 #[derive(Clone)]
 pub struct ImportDocumentsApi {
     client: ApiClient,
+    diagrams: Option<DiagramOptions>,
 }
 
 impl ImportDocumentsApi {
     /// 创建新的文档导入 API 实例
     pub fn new(client: ApiClient) -> Self {
-        Self { client }
+        Self { client, diagrams: None }
+    }
+
+    /// 是否处于 dry-run 模式，此时应使用 plan_* 方法生成请求计划
+    pub fn is_dry_run(&self) -> bool {
+        self.client.is_dry_run()
+    }
+
+    /// 导入 Markdown 时将 mermaid / plantuml 代码块渲染为画板
+    pub fn with_diagrams(mut self, options: DiagramOptions) -> Self {
+        self.diagrams = Some(options);
+        self
     }
 
     /// 导入单个文档内容
@@ -29,141 +59,394 @@ impl ImportDocumentsApi {
         document_revision_id: Option<i32>,
         client_token: Option<String>,
     ) -> Result<CreateNestedBlocksResponse> {
-        // 1. 调用 convert_blocks 转换内容
-        let convert_api = ConvertBlocksApi::new(self.client.clone());
-        let convert_result = convert_api
-            .convert_content_to_blocks(content, content_type)
+        let (response, _) = self
+            .import_document_with_roots(
+                document_id,
+                content,
+                content_type,
+                block_id,
+                index,
+                document_revision_id,
+                client_token,
+            )
             .await?;
+        Ok(response)
+    }
 
-        // 调试：打印 convert_blocks 的结果
-        if std::env::var("DEBUG_CONVERT").is_ok() {
-            eprintln!("ConvertBlocks Response: {}", serde_json::to_string_pretty(&convert_result)?);
+    /// 导入单个文档内容，同时返回创建的一级块 ID
+    #[allow(clippy::too_many_arguments)]
+    pub async fn import_document_with_roots(
+        &self,
+        document_id: &str,
+        content: &str,
+        content_type: &str,
+        block_id: &str,
+        index: i32,
+        document_revision_id: Option<i32>,
+        client_token: Option<String>,
+    ) -> Result<(CreateNestedBlocksResponse, Vec<String>)> {
+        // Markdown 开头的 Front Matter 不作为正文导入
+        let (front_matter, body) = if content_type == "markdown" {
+            split_front_matter(content)?
+        } else {
+            (None, content.to_string())
+        };
+
+        self.import_prepared(
+            document_id,
+            front_matter.as_ref(),
+            &body,
+            content_type,
+            block_id,
+            index,
+            document_revision_id,
+            client_token,
+        )
+        .await
+    }
+
+    /// 导入已拆分出 Front Matter 的正文，按 Front Matter 渲染正文并在开头插入高亮块
+    #[allow(clippy::too_many_arguments)]
+    pub async fn import_prepared(
+        &self,
+        document_id: &str,
+        front_matter: Option<&FrontMatter>,
+        body: &str,
+        content_type: &str,
+        block_id: &str,
+        index: i32,
+        document_revision_id: Option<i32>,
+        client_token: Option<String>,
+    ) -> Result<(CreateNestedBlocksResponse, Vec<String>)> {
+        let prepared = self
+            .prepare_blocks(document_id, front_matter, body, content_type, block_id)
+            .await?;
+        let PreparedBlocks { children_id, descendants, diagrams } = prepared;
+
+        // 3. 按子树规划创建请求，超过单次请求限制时自动分批
+        let batches = BlockBatcher::new(descendants, BatchLimits::default())?
+            .plan(&children_id, index)?;
+
+        // 调试：打印请求数据
+        if std::env::var("DEBUG_CREATE").is_ok() {
+            eprintln!("CreateNestedBlocks Request:");
+            eprintln!("  document_id: {}", document_id);
+            eprintln!("  block_id: {}", block_id);
+            eprintln!("  index: {:?}", Some(index));
+            eprintln!("  children_id: {:?}", children_id);
+            eprintln!("  batches: {}", batches.len());
         }
 
-        // 2. 转换数据格式
-        let (children_id, descendants) = Self::convert_response_to_descendants(convert_result)?;
+        // 4. 调用 create_nested_blocks 创建块
+        let create_api = CreateNestedBlocksApi::new(self.client.clone());
+        let mut created = Vec::new();
+        let result = match create_api
+            .create_batches_tracked(
+                document_id,
+                block_id,
+                batches,
+                document_revision_id,
+                client_token,
+                &mut created,
+            )
+            .await
+        {
+            Ok(result) => result,
+            Err(e) => {
+                // 分批创建中途失败时，已创建的块不会记入导入清单，先删除，避免重新运行时重复导入
+                let root_block_ids = Self::resolve_root_block_ids(&children_id, &created);
+                if !root_block_ids.is_empty() {
+                    if let Err(cleanup) = self.remove_root_blocks(document_id, block_id, &root_block_ids).await {
+                        eprintln!("删除已创建的块失败 {}: {}", root_block_ids.join(", "), cleanup);
+                    }
+                }
+                return Err(e);
+            }
+        };
 
-        // 调试：打印转换结果
-        if std::env::var("DEBUG_CONVERT").is_ok() {
-            eprintln!("转换后的 descendants 数量: {}", descendants.len());
-            eprintln!("children_id 数量: {}", children_id.len());
+        // 5. 将图表源码导入画板，内容已创建，失败不影响导入结果
+        if !diagrams.is_empty() {
+            if let Err(e) = self.render_diagrams(document_id, &diagrams, &result.block_id_relations).await {
+                eprintln!("渲染图表失败: {}", e);
+            }
         }
 
-        // 3. 检查块数量，如果超过1000需要分批处理
-        const MAX_BLOCKS_PER_REQUEST: usize = 1000;
+        let root_block_ids = Self::resolve_root_block_ids(&children_id, &result.block_id_relations);
+        Ok((result, root_block_ids))
+    }
 
-        if descendants.len() > MAX_BLOCKS_PER_REQUEST {
-            // 分批处理
-            let mut all_block_relations = Vec::new();
-            let mut current_index = index;
+    /// 生成导入单个内容的请求计划，只调用转换接口，不创建任何块
+    ///
+    /// `set_title` 为 true 时计划中包含 Front Matter 中的标题。
+    #[allow(clippy::too_many_arguments)]
+    pub async fn plan_document(
+        &self,
+        document_id: &str,
+        content: &str,
+        content_type: &str,
+        block_id: &str,
+        index: i32,
+        set_title: bool,
+    ) -> Result<ImportPlan> {
+        let (front_matter, body) = if content_type == "markdown" {
+            split_front_matter(content)?
+        } else {
+            (None, content.to_string())
+        };
 
-            // 调试：打印分批信息
-            if std::env::var("DEBUG_CREATE").is_ok() {
-                eprintln!("分批处理：总块数 {}，分成 {} 批", descendants.len(),
-                    descendants.len().div_ceil(MAX_BLOCKS_PER_REQUEST));
-            }
+        let prepared = self
+            .prepare_blocks(document_id, front_matter.as_ref(), &body, content_type, block_id)
+            .await?;
+        let parent_id = if block_id.is_empty() { document_id } else { block_id };
 
-            for (i, chunk) in descendants.chunks(MAX_BLOCKS_PER_REQUEST).enumerate() {
-                let chunk_children_id: Vec<String> = chunk
-                    .iter()
-                    .filter(|block| children_id.contains(&block.block_id))
-                    .map(|block| block.block_id.clone())
-                    .collect();
-
-                // 调试：打印每批的信息
-                if std::env::var("DEBUG_CREATE").is_ok() {
-                    eprintln!("第 {} 批：块数 {}，索引 {}", i + 1, chunk.len(), current_index);
-                    eprintln!("chunk_children_id: {:?}", chunk_children_id);
-                }
+        ImportPlan::new(
+            document_id,
+            parent_id,
+            index,
+            prepared.children_id,
+            prepared.descendants,
+            &prepared.diagrams,
+            front_matter.as_ref(),
+            set_title,
+        )
+    }
 
-                let create_api = CreateNestedBlocksApi::new(self.client.clone());
-                let result = create_api
-                    .create_nested_blocks(
-                        document_id,
-                        block_id,
-                        chunk_children_id,
-                        chunk.to_vec(),
-                        Some(current_index),
-                        document_revision_id,
-                        client_token.clone(),
-                    )
-                    .await?;
+    /// 按导入时的方式转换内容（包括 Front Matter 渲染和高亮块），返回一级块临时 ID 和所有块，不创建任何块
+    pub async fn prepare_document(
+        &self,
+        document_id: &str,
+        content: &str,
+        content_type: &str,
+    ) -> Result<(Vec<String>, Vec<DescendantBlock>)> {
+        let (front_matter, body) = if content_type == "markdown" {
+            split_front_matter(content)?
+        } else {
+            (None, content.to_string())
+        };
+
+        let prepared = self
+            .prepare_blocks(document_id, front_matter.as_ref(), &body, content_type, "")
+            .await?;
+        Ok((prepared.children_id, prepared.descendants))
+    }
 
-                all_block_relations.extend(result.block_id_relations);
-                current_index += chunk.len() as i32;
+    /// 渲染正文并转换为待创建的块：插入 Front Matter 高亮块，并按需把图表代码块替换为画板块
+    async fn prepare_blocks(
+        &self,
+        document_id: &str,
+        front_matter: Option<&FrontMatter>,
+        body: &str,
+        content_type: &str,
+        block_id: &str,
+    ) -> Result<PreparedBlocks> {
+        let mut content = match front_matter {
+            Some(front_matter) => front_matter.render_body(body),
+            None => body.to_string(),
+        };
+
+        // 在图表代码块前插入标记段落，转换后据此替换为画板块
+        let fences = match self.diagrams {
+            Some(_) if content_type == "markdown" => {
+                let (marked, fences) = mark_diagram_fences(&content);
+                content = marked;
+                fences
             }
+            _ => Vec::new(),
+        };
 
-            // 返回合并的结果
-            Ok(CreateNestedBlocksResponse {
-                block_id_relations: all_block_relations,
-                children: vec![],
-                client_token,
-                document_revision_id: document_revision_id.unwrap_or(0),
-            })
+        // @提及替换为占位符，转换后再替换为提及元素
+        let mentions = if content_type == "markdown" {
+            let (marked, mentions) = mark_mentions(&content);
+            content = marked;
+            mentions
         } else {
-            // 3. 调用 create_nested_blocks 创建块
-            let create_api = CreateNestedBlocksApi::new(self.client.clone());
-
-            // 调试：打印请求数据
-            if std::env::var("DEBUG_CREATE").is_ok() {
-                eprintln!("CreateNestedBlocks Request:");
-                eprintln!("  document_id: {}", document_id);
-                eprintln!("  block_id: {}", block_id);
-                eprintln!("  index: {:?}", Some(index));
-                eprintln!("  children_id: {:?}", children_id);
-                eprintln!("  descendants count: {}", descendants.len());
-                eprintln!("  descendants: {}", serde_json::to_string_pretty(&descendants)?);
+            Vec::new()
+        };
+
+        // 1. 调用 convert_blocks 转换内容
+        let convert_api = ConvertBlocksApi::new(self.client.clone());
+        let convert_result = convert_api
+            .convert_content_to_blocks(&content, content_type)
+            .await?;
+
+        // 调试：打印 convert_blocks 的结果
+        if std::env::var("DEBUG_CONVERT").is_ok() {
+            eprintln!("ConvertBlocks Response: {}", serde_json::to_string_pretty(&convert_result)?);
+        }
+
+        // 2. 转换数据格式
+        let (mut children_id, mut descendants) = Self::convert_response_to_descendants(convert_result)?;
+
+        if !mentions.is_empty() {
+            let emails = mention_emails(&mentions);
+            let open_ids = if emails.is_empty() {
+                HashMap::new()
+            } else {
+                GetUserInfoApi::new(self.client.clone())
+                    .open_ids_by_email(&emails)
+                    .await
+                    .unwrap_or_else(|e| {
+                        eprintln!("警告: 查询提及的用户失败: {}", e);
+                        HashMap::new()
+                    })
+            };
+            for warning in apply_mentions(&mut descendants, &mentions, &open_ids) {
+                eprintln!("警告: {} {}", warning.mention, warning.message);
             }
+        }
 
-            let result = create_api
-                .create_nested_blocks(
-                    document_id,
-                    block_id,
-                    children_id,
-                    descendants,
-                    Some(index),
-                    document_revision_id,
-                    client_token,
-                )
-                .await?;
+        // Front Matter 中的高亮块放在内容最前面
+        if let Some(callout) = front_matter.and_then(|f| f.callout.as_ref()) {
+            let (content, callout_type, icon) = callout.parts();
+            let callout_type: CalloutType = callout_type.parse().map_err(LarkError::ValidationError)?;
+            let parent_id = if block_id.is_empty() { document_id } else { block_id };
+            let (callout_id, callout_blocks) =
+                callout_blocks(parent_id, content, callout_type, icon.map(|i| i.to_string()));
+            children_id.insert(0, callout_id);
+            descendants.extend(callout_blocks);
+        }
 
-            Ok(result)
+        // 调试：打印转换结果
+        if std::env::var("DEBUG_CONVERT").is_ok() {
+            eprintln!("转换后的 descendants 数量: {}", descendants.len());
+            eprintln!("children_id 数量: {}", children_id.len());
         }
+
+        let diagrams = match self.diagrams {
+            Some(options) if !fences.is_empty() => plan_diagram_blocks(
+                &mut children_id,
+                &mut descendants,
+                fences,
+                options.keep_source,
+            ),
+            _ => Vec::new(),
+        };
+
+        Ok(PreparedBlocks { children_id, descendants, diagrams })
     }
 
     /// 批量导入文档
+    ///
+    /// 提供导入清单时，每个文件导入成功后立即写入清单，中断后重新运行即可从断点继续；
+    /// `policy` 控制对清单中已有记录的文件是跳过还是替换。
     pub async fn import_batch(
         &self,
         document_id: &str,
         requests: Vec<ImportRequest>,
         _batch_size: usize,
         document_revision_id: Option<i32>,
+        mut manifest: Option<&mut ImportManifest>,
+        policy: ExistingFilePolicy,
     ) -> Result<BatchImportResult> {
         let mut results = Vec::new();
         let mut success_count = 0;
         let mut failure_count = 0;
+        let mut skipped_count = 0;
 
         // 串行处理每个请求
         for request in requests {
-            // 处理单个文件
-            let result = self.import_document(
-                document_id,
-                &request.content,
-                &request.content_type,
-                &request.block_id,
-                request.index,
-                document_revision_id,
-                None,
-            ).await;
+            let key = request
+                .relative_path
+                .clone()
+                .unwrap_or_else(|| request.file_path.display().to_string());
+            let hash = content_hash(&request.content);
+
+            let status = manifest
+                .as_deref()
+                .map(|m| m.status(&key, &hash))
+                .unwrap_or(ManifestStatus::New);
+
+            // 内容已变化但未指定替换时跳过，避免重复导入后清单丢失上次导入的块
+            let changed = matches!(status, ManifestStatus::Changed(_)) && !policy.replace_changed;
+            if changed || (status == ManifestStatus::Unchanged && policy.skip_unchanged) {
+                skipped_count += 1;
+                results.push(ImportResult {
+                    file_path: request.file_path,
+                    success: true,
+                    skipped: true,
+                    changed,
+                    error: None,
+                    block_ids: None,
+                });
+                continue;
+            }
+
+            // 内容已变化时，先删除上次导入的块，并在原位置重新导入
+            let result = match &status {
+                ManifestStatus::Changed(entry) if policy.replace_changed => {
+                    match self
+                        .remove_root_blocks(document_id, &request.block_id, &entry.root_block_ids)
+                        .await
+                    {
+                        Ok(position) => {
+                            self.import_document_with_roots(
+                                document_id,
+                                &request.content,
+                                &request.content_type,
+                                &request.block_id,
+                                position.unwrap_or(request.index),
+                                document_revision_id,
+                                None,
+                            )
+                            .await
+                        }
+                        Err(e) => Err(e),
+                    }
+                }
+                _ => {
+                    self.import_document_with_roots(
+                        document_id,
+                        &request.content,
+                        &request.content_type,
+                        &request.block_id,
+                        request.index,
+                        document_revision_id,
+                        None,
+                    )
+                    .await
+                }
+            };
 
             match result {
-                Ok(_) => {
+                Ok((response, root_block_ids)) => {
                     success_count += 1;
+
+                    // 多个文件导入同一文档，只应用协作者，不修改文档标题
+                    let front_matter = if request.content_type == "markdown" {
+                        split_front_matter(&request.content).ok().and_then(|(f, _)| f)
+                    } else {
+                        None
+                    };
+                    if let Some(front_matter) = front_matter {
+                        if let Err(e) = self.apply_front_matter(document_id, &front_matter, false).await {
+                            eprintln!("应用 Front Matter 失败 {}: {}", request.file_path.display(), e);
+                        }
+                    }
+
+                    if let Some(manifest) = manifest.as_deref_mut() {
+                        // 未删除旧块而再次导入时，保留上次导入的块 ID，之后替换时一并删除
+                        let mut recorded_ids = match (&status, manifest.files.get(&key)) {
+                            (ManifestStatus::Unchanged, Some(entry)) => entry.root_block_ids.clone(),
+                            _ => Vec::new(),
+                        };
+                        recorded_ids.extend(root_block_ids.iter().cloned());
+                        manifest.record(
+                            &key,
+                            &request.file_path,
+                            hash,
+                            recorded_ids,
+                            response.document_revision_id,
+                        );
+                        manifest.save()?;
+                    }
+
                     results.push(ImportResult {
                         file_path: request.file_path,
                         success: true,
+                        skipped: false,
+                        changed: false,
                         error: None,
-                        block_ids: None,
+                        block_ids: Some(root_block_ids),
                     });
                 }
                 Err(e) => {
@@ -171,6 +454,8 @@ impl ImportDocumentsApi {
                     results.push(ImportResult {
                         file_path: request.file_path,
                         success: false,
+                        skipped: false,
+                        changed: false,
                         error: Some(format!("导入失败: {}", e)),
                         block_ids: None,
                     });
@@ -181,11 +466,286 @@ impl ImportDocumentsApi {
         Ok(BatchImportResult {
             success_count,
             failure_count,
-            skipped_count: 0,
+            skipped_count,
             results,
         })
     }
 
+    /// 生成批量导入的请求计划，按导入清单判断每个文件是跳过、替换还是新导入
+    ///
+    /// 只读取文档和调用转换接口，不创建或删除任何块。
+    pub async fn plan_batch(
+        &self,
+        document_id: &str,
+        requests: Vec<ImportRequest>,
+        manifest: Option<&ImportManifest>,
+        policy: ExistingFilePolicy,
+    ) -> Result<BatchImportPlan> {
+        let mut files = Vec::new();
+
+        for request in requests {
+            let key = request
+                .relative_path
+                .clone()
+                .unwrap_or_else(|| request.file_path.display().to_string());
+            let status = manifest
+                .map(|m| m.status(&key, &content_hash(&request.content)))
+                .unwrap_or(ManifestStatus::New);
+
+            let mut file = FileImportPlan {
+                file_path: request.file_path.clone(),
+                skipped: false,
+                changed: false,
+                delete_ranges: Vec::new(),
+                plan: None,
+                error: None,
+            };
+
+            let changed = matches!(status, ManifestStatus::Changed(_)) && !policy.replace_changed;
+            if changed || (status == ManifestStatus::Unchanged && policy.skip_unchanged) {
+                file.skipped = true;
+                file.changed = changed;
+                files.push(file);
+                continue;
+            }
+
+            // 内容已变化时，先删除上次导入的块，并在原位置重新导入
+            let mut index = request.index;
+            if let ManifestStatus::Changed(entry) = &status {
+                if policy.replace_changed {
+                    let parent_id = if request.block_id.is_empty() { document_id } else { &request.block_id };
+                    match self.root_block_ranges(document_id, parent_id, &entry.root_block_ids).await {
+                        Ok(ranges) => {
+                            if let Some((start, _)) = ranges.first() {
+                                index = *start;
+                            }
+                            file.delete_ranges = ranges;
+                        }
+                        Err(e) => {
+                            file.error = Some(format!("读取文档失败: {}", e));
+                            files.push(file);
+                            continue;
+                        }
+                    }
+                }
+            }
+
+            match self
+                .plan_document(document_id, &request.content, &request.content_type, &request.block_id, index, false)
+                .await
+            {
+                Ok(plan) => file.plan = Some(plan),
+                Err(e) => file.error = Some(format!("规划失败: {}", e)),
+            }
+            files.push(file);
+        }
+
+        Ok(BatchImportPlan {
+            document_id: document_id.to_string(),
+            files,
+        })
+    }
+
+    /// 将 Front Matter 中作用于整篇文档的字段应用到目标文档，返回执行的操作说明
+    ///
+    /// `set_title` 为 true 时用 `title` 更新文档标题；`collaborators` 中的每个成员都会添加为协作者。
+    pub async fn apply_front_matter(
+        &self,
+        document_id: &str,
+        front_matter: &FrontMatter,
+        set_title: bool,
+    ) -> Result<Vec<String>> {
+        let mut actions = Vec::new();
+
+        if let (true, Some(title)) = (set_title, &front_matter.title) {
+            // 文档标题即页面块（块 ID 与文档 ID 相同）的文本内容
+            let request = UpdateBlockRequest::new(document_id.to_string())
+                .with_text_elements(vec![TextElement::text_run(title.clone(), None)]);
+            BatchUpdateBlocksApi::new(self.client.clone())
+                .batch_update_blocks(document_id, vec![request], None, None, None)
+                .await?;
+            actions.push(format!("设置文档标题: {}", title));
+        }
+
+        let permission_api = PermissionApi::new(self.client.clone());
+        for collaborator in &front_matter.collaborators {
+            let request = collaborator_request(collaborator);
+            let action = format!("添加协作者: {} ({})", request.member_id, request.perm);
+            permission_api
+                .add_permission_member(document_id, "docx", request, Some(false))
+                .await?;
+            actions.push(action);
+        }
+
+        Ok(actions)
+    }
+
+    /// 将图表源码导入对应的画板，导入失败的图表恢复为原代码块
+    async fn render_diagrams(
+        &self,
+        document_id: &str,
+        diagrams: &[PlannedDiagram],
+        relations: &[BlockIdRelation],
+    ) -> Result<()> {
+        let id_map: HashMap<&str, &str> = relations
+            .iter()
+            .map(|r| (r.temporary_block_id.as_str(), r.block_id.as_str()))
+            .collect();
+
+        // 画板 token 只能在创建后从文档块中获取
+        let blocks = GetBlocksApi::new(self.client.clone())
+            .get_all_document_blocks(document_id, None, None)
+            .await?;
+        let tree = BlockTree::new(blocks);
+
+        let board_api = BoardApi::new(self.client.clone());
+        let mut failed = Vec::new();
+        for diagram in diagrams {
+            let Some(board_id) = id_map.get(diagram.board_block_id.as_str()) else {
+                continue;
+            };
+
+            let token = tree
+                .get(board_id)
+                .and_then(|block| block.content.pointer("/board/token"))
+                .and_then(|token| token.as_str());
+            let result = match token {
+                Some(token) => {
+                    let request = ImportDiagramRequest {
+                        plant_uml_code: diagram.fence.source.clone(),
+                        style_type: Some(StyleType::BoardStyle.to_value()),
+                        syntax_type: diagram.fence.syntax.to_value(),
+                        diagram_type: Some(diagram.fence.diagram_type.to_plantuml_value()),
+                    };
+                    board_api.import_diagram(token, request).await.map(|_| ())
+                }
+                None => Err(LarkError::ParseError(format!("找不到画板块 {} 的画板 token", board_id))),
+            };
+
+            if let Err(e) = result {
+                eprintln!("图表导入画板失败，恢复为代码块: {}", e);
+                failed.push((diagram, board_id.to_string()));
+            }
+        }
+
+        for (diagram, board_id) in failed {
+            let source_id = diagram
+                .source_block_id
+                .as_deref()
+                .and_then(|id| id_map.get(id))
+                .map(|id| id.to_string());
+            self.restore_diagram_code(document_id, diagram, &board_id, source_id.as_deref())
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// 删除导入失败的画板（以及 caption 模式下的说明文本），在原位置恢复代码块
+    async fn restore_diagram_code(
+        &self,
+        document_id: &str,
+        diagram: &PlannedDiagram,
+        board_id: &str,
+        source_id: Option<&str>,
+    ) -> Result<()> {
+        // 前面的恢复会改变位置，每次都重新获取块树
+        let blocks = GetBlocksApi::new(self.client.clone())
+            .get_all_document_blocks(document_id, None, None)
+            .await?;
+        let tree = BlockTree::new(blocks);
+
+        let parent_id = tree
+            .get(board_id)
+            .map(|block| block.parent_id.clone())
+            .ok_or_else(|| LarkError::ValidationError(format!("找不到画板块 {}", board_id)))?;
+        let Some(position) = tree.child_index(&parent_id, board_id) else {
+            return Ok(());
+        };
+        let mut position = position as i32;
+
+        let mut remove = 1;
+        if let Some(code_block) = &diagram.fallback_block {
+            let mut code_block = code_block.clone();
+            code_block.parent_id = Some(parent_id.clone());
+            CreateNestedBlocksApi::new(self.client.clone())
+                .create_nested_blocks(
+                    document_id,
+                    &parent_id,
+                    vec![code_block.block_id.clone()],
+                    vec![code_block],
+                    Some(position),
+                    None,
+                    None,
+                )
+                .await?;
+            position += 1;
+
+            // caption 模式下画板后的说明文本也一并删除
+            if source_id.is_some_and(|id| tree.child_index(&parent_id, id).is_some()) {
+                remove += 1;
+            }
+        }
+
+        DeleteBlocksApi::new(self.client.clone())
+            .delete_blocks(document_id, &parent_id, position, position + remove, None, None)
+            .await?;
+
+        Ok(())
+    }
+
+    /// 删除父块下指定的一级块，返回被删除块中最靠前的位置
+    async fn remove_root_blocks(
+        &self,
+        document_id: &str,
+        block_id: &str,
+        root_block_ids: &[String],
+    ) -> Result<Option<i32>> {
+        let parent_id = if block_id.is_empty() { document_id } else { block_id };
+        let ranges = self.root_block_ranges(document_id, parent_id, root_block_ids).await?;
+
+        // 从后往前删除，避免前面的删除影响后面区间的索引
+        let delete_api = DeleteBlocksApi::new(self.client.clone());
+        for (start, end) in ranges.iter().rev() {
+            delete_api
+                .delete_blocks(document_id, parent_id, *start, *end, None, None)
+                .await?;
+        }
+
+        Ok(ranges.first().map(|(start, _)| *start))
+    }
+
+    /// 读取文档，计算指定一级块在父块中占据的 [start, end) 区间
+    async fn root_block_ranges(
+        &self,
+        document_id: &str,
+        parent_id: &str,
+        root_block_ids: &[String],
+    ) -> Result<Vec<(i32, i32)>> {
+        let blocks = GetBlocksApi::new(self.client.clone())
+            .get_all_document_blocks(document_id, None, None)
+            .await?;
+        let tree = BlockTree::new(blocks);
+
+        let positions: Vec<usize> = root_block_ids
+            .iter()
+            .filter_map(|id| tree.child_index(parent_id, id))
+            .collect();
+        Ok(index_ranges(&positions))
+    }
+
+    /// 根据块 ID 映射关系，将临时一级块 ID 转换为实际创建的块 ID
+    fn resolve_root_block_ids(children_id: &[String], relations: &[BlockIdRelation]) -> Vec<String> {
+        children_id
+            .iter()
+            .filter_map(|temp_id| {
+                relations
+                    .iter()
+                    .find(|relation| &relation.temporary_block_id == temp_id)
+                    .map(|relation| relation.block_id.clone())
+            })
+            .collect()
+    }
 
     /// 将 convert_blocks 的响应转换为 create_nested_blocks 的请求格式
     fn convert_response_to_descendants(
@@ -0,0 +1,157 @@
+use super::block_batcher::{BatchLimits, BatchParent, BlockBatcher, CreateBatch};
+use super::board::{DiagramSyntax, DiagramType};
+use super::diagram_blocks::PlannedDiagram;
+use super::permission::AddPermissionRequest;
+use super::DescendantBlock;
+use crate::error::Result;
+use crate::utils::FrontMatter;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// dry-run 模式下导入单个内容的请求计划
+#[derive(Debug, Serialize)]
+pub struct ImportPlan {
+    /// 目标文档 ID
+    pub document_id: String,
+    /// 插入内容的父块 ID
+    pub parent_block_id: String,
+    /// 在父块中的插入位置（-1 表示末尾）
+    pub index: i32,
+    /// 插入的一级块临时 ID
+    pub children_id: Vec<String>,
+    /// 转换后的完整块树
+    pub descendants: Vec<DescendantBlock>,
+    /// 分批后的创建嵌套块请求
+    pub create_calls: Vec<PlannedCreateCall>,
+    /// 创建完成后导入画板的图表
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub diagram_imports: Vec<PlannedDiagramImport>,
+    /// 将要设置的文档标题
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// 将要添加的协作者权限
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub permissions: Vec<AddPermissionRequest>,
+}
+
+/// 一次创建嵌套块请求的概要
+#[derive(Debug, Serialize)]
+pub struct PlannedCreateCall {
+    /// 父块
+    pub parent: BatchParent,
+    /// 插入位置
+    pub index: i32,
+    /// 本次请求的一级块临时 ID
+    pub children_id: Vec<String>,
+    /// 本次请求创建的块数
+    pub block_count: usize,
+}
+
+/// 一个将要导入画板的图表
+#[derive(Debug, Serialize)]
+pub struct PlannedDiagramImport {
+    /// 画板块的临时 ID
+    pub board_block_id: String,
+    /// 图表语法
+    pub syntax: DiagramSyntax,
+    /// 图表类型
+    pub diagram_type: DiagramType,
+}
+
+/// dry-run 模式下批量导入目录的请求计划
+#[derive(Debug, Serialize)]
+pub struct BatchImportPlan {
+    /// 目标文档 ID
+    pub document_id: String,
+    /// 每个文件的计划
+    pub files: Vec<FileImportPlan>,
+}
+
+/// 单个文件的导入计划
+#[derive(Debug, Serialize)]
+pub struct FileImportPlan {
+    /// 文件路径
+    pub file_path: PathBuf,
+    /// 将被跳过（内容未变化，或内容已变化但未指定替换）
+    pub skipped: bool,
+    /// 内容自上次导入后已变化，未指定 --replace-changed 而跳过
+    pub changed: bool,
+    /// 重新导入前删除的上次导入块区间 [start, end)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub delete_ranges: Vec<(i32, i32)>,
+    /// 导入计划
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan: Option<ImportPlan>,
+    /// 规划失败的原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ImportPlan {
+    /// 根据转换后的块生成导入计划，分批方式与实际导入完全一致
+    ///
+    /// `set_title` 为 true 时计划中包含 Front Matter 的标题；协作者总是计入权限变更。
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        document_id: &str,
+        parent_block_id: &str,
+        index: i32,
+        children_id: Vec<String>,
+        descendants: Vec<DescendantBlock>,
+        diagrams: &[PlannedDiagram],
+        front_matter: Option<&FrontMatter>,
+        set_title: bool,
+    ) -> Result<Self> {
+        let batches = BlockBatcher::new(descendants.clone(), BatchLimits::default())?
+            .plan(&children_id, index)?;
+
+        let title = front_matter
+            .filter(|_| set_title)
+            .and_then(|f| f.title.clone());
+        let permissions = front_matter
+            .map(|f| f.collaborators.iter().map(collaborator_request).collect())
+            .unwrap_or_default();
+
+        Ok(Self {
+            document_id: document_id.to_string(),
+            parent_block_id: parent_block_id.to_string(),
+            index,
+            children_id,
+            descendants,
+            create_calls: batches.iter().map(PlannedCreateCall::from).collect(),
+            diagram_imports: diagrams
+                .iter()
+                .map(|d| PlannedDiagramImport {
+                    board_block_id: d.board_block_id.clone(),
+                    syntax: d.fence.syntax,
+                    diagram_type: d.fence.diagram_type,
+                })
+                .collect(),
+            title,
+            permissions,
+        })
+    }
+}
+
+impl From<&CreateBatch> for PlannedCreateCall {
+    fn from(batch: &CreateBatch) -> Self {
+        Self {
+            parent: batch.parent.clone(),
+            index: batch.index,
+            children_id: batch.children_id.clone(),
+            block_count: batch.descendants.len(),
+        }
+    }
+}
+
+/// Front Matter 协作者对应的添加权限请求
+pub fn collaborator_request(collaborator: &crate::utils::FrontMatterCollaborator) -> AddPermissionRequest {
+    let (member_type, member_id, perm) = collaborator.member();
+    AddPermissionRequest {
+        member_type: member_type.to_string(),
+        member_id: member_id.to_string(),
+        perm: perm.to_string(),
+        perm_type: None,
+        collaborator_type: Some("user".to_string()),
+    }
+}
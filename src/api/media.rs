@@ -1,9 +1,16 @@
+use super::multipart::MultipartForm;
 use super::ApiClient;
 use crate::error::Result;
+use crate::utils::adler32;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use std::fs;
+use std::io::Read;
 use std::path::Path;
 
+/// 一次上传素材的大小上限，更大的文件需要分片上传
+pub const MEDIA_UPLOAD_ALL_LIMIT: u64 = 20 * 1024 * 1024;
+
 #[derive(Debug, Serialize)]
 pub struct UploadMediaRequest {
     pub file_name: String,
@@ -20,6 +27,13 @@ pub struct UploadMediaResponse {
     pub file_token: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct MediaUploadPrepareResponse {
+    upload_id: String,
+    block_size: u64,
+    block_num: u32,
+}
+
 pub struct MediaApi {
     client: ApiClient,
 }
@@ -31,6 +45,17 @@ impl MediaApi {
 
     /// 上传媒体文件
     pub async fn upload_media(&self, request: UploadMediaRequest) -> Result<UploadMediaResponse> {
+        self.upload_form("https://open.larkoffice.com/open-apis/im/v1/files", request).await
+    }
+
+    /// 通过云文档素材接口一次上传素材（不超过 20MB），素材挂在 `parent_node` 指定的文档块上
+    ///
+    /// 用于复制块和插入附件时填充新建的图片、附件块。
+    pub async fn upload_drive_media(&self, request: UploadMediaRequest) -> Result<UploadMediaResponse> {
+        self.upload_form("https://open.larkoffice.com/open-apis/drive/v1/medias/upload_all", request).await
+    }
+
+    async fn upload_form(&self, url: &str, request: UploadMediaRequest) -> Result<UploadMediaResponse> {
         // 验证参数
         if request.file_name.is_empty() {
             return Err(crate::error::LarkError::ValidationError(
@@ -57,7 +82,7 @@ impl MediaApi {
             ));
         }
 
-        if request.size > 20_971_520 { // 20MB限制
+        if request.size > MEDIA_UPLOAD_ALL_LIMIT { // 20MB限制
             return Err(crate::error::LarkError::ValidationError(
                 "文件大小超过20MB限制".to_string()
             ));
@@ -69,55 +94,23 @@ impl MediaApi {
             ));
         }
 
-        // 构建multipart/form-data请求
-        let boundary = "----7MA4YWxkTrZu0gW";
-
-        // 构建表单数据
-        let mut body_lines = Vec::new();
-
-        // 先添加参数字段（文件字段必须在最后）
-        let mut fields = std::collections::HashMap::new();
-        fields.insert("file_name", request.file_name.clone());
-        fields.insert("parent_type", request.parent_type.clone());
-        fields.insert("parent_node", request.parent_node.clone());
-        fields.insert("size", request.file_content.len().to_string());
-
+        // 构建multipart/form-data请求，参数字段在前，文件字段必须在最后
+        let mut form = MultipartForm::new()
+            .text("file_name", &request.file_name)
+            .text("parent_type", &request.parent_type)
+            .text("parent_node", &request.parent_node)
+            .text("size", &request.file_content.len().to_string());
         if let Some(ref checksum) = request.checksum {
-            fields.insert("checksum", checksum.clone());
+            form = form.text("checksum", checksum);
         }
         if let Some(ref extra) = request.extra {
-            fields.insert("extra", extra.clone());
-        }
-
-        // 添加参数字段
-        for (name, value) in fields {
-            body_lines.push(format!("--{}", boundary));
-            body_lines.push(format!(r#"Content-Disposition: form-data; name="{}""#, name));
-            body_lines.push("".to_string());
-            body_lines.push(value);
+            form = form.text("extra", extra);
         }
-
-        // 添加文件字段（必须在最后）
-        body_lines.push(format!("--{}", boundary));
-        body_lines.push(format!(r#"Content-Disposition: form-data; name="file"; filename="{}""#, request.file_name));
-        body_lines.push("Content-Type: application/octet-stream".to_string());
-        body_lines.push("".to_string());
-
-        // 构建请求体
-        let text_part = body_lines.join("\r\n") + "\r\n";
-        let mut body = text_part.into_bytes();
-        body.extend_from_slice(&request.file_content);
-
-        let end_boundary = format!("\r\n--{}--\r\n", boundary);
-        body.extend_from_slice(end_boundary.as_bytes());
-
-        // 设置请求头
-        let mut headers = std::collections::HashMap::new();
-        headers.insert("Content-Type".to_string(), format!("multipart/form-data; boundary={}", boundary));
+        let (body, headers) = form.file("file", &request.file_name, &request.file_content).finish();
 
         // 发送请求
         let response = self.client
-            .post_form_data("https://open.larkoffice.com/open-apis/im/v1/files", body, headers)
+            .post_form_data(url, body, headers)
             .await?;
 
         // 解析响应
@@ -176,4 +169,57 @@ impl MediaApi {
 
         self.upload_media(request).await
     }
-}
\ No newline at end of file
+
+    /// 分片上传本地文件作为素材，每次只读取一个分片，用于超过 20MB 的文件
+    pub async fn upload_media_multipart(
+        &self,
+        path: &Path,
+        file_name: &str,
+        parent_type: &str,
+        parent_node: &str,
+        extra: Option<String>,
+    ) -> Result<UploadMediaResponse> {
+        let size = fs::metadata(path)
+            .map_err(|e| crate::error::LarkError::IoError(format!("读取文件失败: {}", e)))?
+            .len();
+        let mut body = json!({
+            "file_name": file_name,
+            "parent_type": parent_type,
+            "parent_node": parent_node,
+            "size": size,
+        });
+        if let Some(extra) = extra {
+            body["extra"] = json!(extra);
+        }
+        let prepared: MediaUploadPrepareResponse = self
+            .client
+            .post("https://open.larkoffice.com/open-apis/drive/v1/medias/upload_prepare", &body)
+            .await?;
+
+        let mut file = fs::File::open(path)
+            .map_err(|e| crate::error::LarkError::IoError(format!("读取文件失败: {}", e)))?;
+        for seq in 0..prepared.block_num {
+            let offset = u64::from(seq) * prepared.block_size;
+            let mut chunk = vec![0u8; prepared.block_size.min(size.saturating_sub(offset)) as usize];
+            file.read_exact(&mut chunk)
+                .map_err(|e| crate::error::LarkError::IoError(format!("读取分片 {} 失败: {}", seq, e)))?;
+
+            let (body, headers) = MultipartForm::new()
+                .text("upload_id", &prepared.upload_id)
+                .text("seq", &seq.to_string())
+                .text("size", &chunk.len().to_string())
+                .text("checksum", &adler32(&chunk).to_string())
+                .file("file", &format!("part-{}", seq), &chunk)
+                .finish();
+            let _: serde_json::Value = self
+                .client
+                .post_form_data("https://open.larkoffice.com/open-apis/drive/v1/medias/upload_part", body, headers)
+                .await?;
+        }
+
+        let body = json!({ "upload_id": prepared.upload_id, "block_num": prepared.block_num });
+        self.client
+            .post("https://open.larkoffice.com/open-apis/drive/v1/medias/upload_finish", &body)
+            .await
+    }
+}
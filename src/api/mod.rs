@@ -21,9 +21,29 @@ pub mod search_chats;
 pub mod get_message_history;
 pub mod import_documents;
 pub mod block_converter;
+pub mod block_batcher;
+pub mod block_builder;
+pub mod diagram_blocks;
+pub mod import_plan;
 pub mod board;
 pub mod create_board_notes;
 pub mod get_user_info;
+pub mod drive;
+pub mod document_sync;
+pub mod document_replace;
+pub mod document_sections;
+pub mod block_copy;
+pub mod document_template;
+pub mod document_table;
+pub mod update_request_builder;
+pub mod block_selection;
+pub mod document_copy;
+pub mod document_lint;
+pub mod document_toc;
+pub mod document_attach;
+pub mod comments;
+pub mod multipart;
+pub mod drive_upload;
 
 pub use wiki::WikiApi;
 pub use docx::DocxApi;
@@ -36,7 +56,7 @@ pub use batch_update_blocks::BatchUpdateBlocksApi;
 pub use delete_blocks::DeleteBlocksApi;
 pub use file::FileApi;
 pub use media::MediaApi;
-pub use download_media::DownloadMediaApi;
+pub use download_media::{DownloadMediaApi, DownloadOptions};
 pub use get_board_image::GetBoardImageApi;
 pub use message::MessageApi;
 pub use search_chats::SearchChatsApi;
@@ -45,6 +65,19 @@ pub use import_documents::ImportDocumentsApi;
 pub use board::BoardApi;
 pub use create_board_notes::CreateBoardNotesApi;
 pub use get_user_info::GetUserInfoApi;
+pub use drive::DriveApi;
+pub use document_sync::DocumentSyncApi;
+pub use document_replace::DocumentReplaceApi;
+pub use document_sections::DocumentSectionApi;
+pub use document_template::DocumentTemplateApi;
+pub use document_table::DocumentTableApi;
+pub use block_selection::BlockSelectionApi;
+pub use document_copy::DocumentCopyApi;
+pub use document_lint::DocumentLintApi;
+pub use document_toc::DocumentTocApi;
+pub use document_attach::DocumentAttachApi;
+pub use comments::CommentsApi;
+pub use drive_upload::DriveUploadApi;
 
 #[derive(Debug, Deserialize)]
 pub struct ApiResponse<T> {
@@ -57,8 +90,16 @@ pub struct ApiResponse<T> {
 pub struct ApiClient {
     pub(crate) auth_manager: AuthManager,
     pub(crate) client: reqwest::Client,
+    pub(crate) dry_run: bool,
 }
 
+/// 使用 POST 但不修改数据的接口，dry-run 模式下仍然发送
+const READ_ONLY_POST_URLS: [&str; 3] = [
+    "https://open.larkoffice.com/open-apis/docx/v1/documents/blocks/convert",
+    "https://open.larkoffice.com/open-apis/contact/v3/users/batch_get_id",
+    "https://open.larkoffice.com/open-apis/drive/v1/metas/batch_query",
+];
+
 impl ApiClient {
     pub fn new(auth_manager: AuthManager) -> Self {
         let client = reqwest::Client::builder()
@@ -69,7 +110,30 @@ impl ApiClient {
         Self {
             auth_manager,
             client,
+            dry_run: false,
+        }
+    }
+
+    /// 设置 dry-run 模式：只规划请求，不发送任何写操作
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// 是否处于 dry-run 模式
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// dry-run 模式下拒绝发送写请求，防止没有规划输出的命令误修改数据
+    fn ensure_writable(&self, method: &str, url: &str) -> Result<()> {
+        if self.dry_run && !READ_ONLY_POST_URLS.contains(&url) {
+            return Err(LarkError::DryRunError(format!(
+                "该命令不支持输出请求计划，已拦截写请求 {} {}",
+                method, url
+            )));
         }
+        Ok(())
     }
 
     /// Send GET request
@@ -124,6 +188,7 @@ impl ApiClient {
         T: for<'de> Deserialize<'de>,
         B: serde::Serialize,
     {
+        self.ensure_writable("POST", url)?;
         let auth_header = self.auth_manager.get_auth_header().await?;
 
         tracing::debug!("Sending POST request to: {}", url);
@@ -173,6 +238,7 @@ impl ApiClient {
         T: for<'de> Deserialize<'de>,
         B: serde::Serialize,
     {
+        self.ensure_writable("POST", url)?;
         let auth_header = self.auth_manager.get_auth_header().await?;
 
         let mut request = self.client
@@ -226,6 +292,7 @@ impl ApiClient {
         T: for<'de> Deserialize<'de>,
         B: serde::Serialize,
     {
+        self.ensure_writable("PATCH", url)?;
         let auth_header = self.auth_manager.get_auth_header().await?;
 
         let mut request = self.client
@@ -278,6 +345,7 @@ impl ApiClient {
     where
         T: for<'de> Deserialize<'de>,
     {
+        self.ensure_writable("POST", url)?;
         let auth_header = self.auth_manager.get_auth_header().await?;
 
         tracing::debug!("Sending POST multipart request to: {}", url);
@@ -331,6 +399,7 @@ impl ApiClient {
         T: for<'de> Deserialize<'de>,
         B: serde::Serialize,
     {
+        self.ensure_writable("DELETE", url)?;
         let auth_header = self.auth_manager.get_auth_header().await?;
 
         let mut request = self.client
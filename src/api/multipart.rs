@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+/// multipart/form-data 请求体，每个请求使用随机分隔符，避免与文件内容冲突
+pub struct MultipartForm {
+    boundary: String,
+    body: Vec<u8>,
+}
+
+impl Default for MultipartForm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MultipartForm {
+    pub fn new() -> Self {
+        Self {
+            boundary: format!("----LarkCliBoundary{}", uuid::Uuid::new_v4().simple()),
+            body: Vec::new(),
+        }
+    }
+
+    /// 添加文本字段
+    pub fn text(mut self, name: &str, value: &str) -> Self {
+        self.body.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                self.boundary, name, value
+            )
+            .as_bytes(),
+        );
+        self
+    }
+
+    /// 添加文件字段（飞书上传接口要求文件字段放在最后）
+    pub fn file(mut self, name: &str, file_name: &str, content: &[u8]) -> Self {
+        self.body.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+                self.boundary,
+                name,
+                file_name.replace('"', "%22")
+            )
+            .as_bytes(),
+        );
+        self.body.extend_from_slice(content);
+        self.body.extend_from_slice(b"\r\n");
+        self
+    }
+
+    /// 结束请求体，返回请求体和 Content-Type 请求头
+    pub fn finish(mut self) -> (Vec<u8>, HashMap<String, String>) {
+        self.body.extend_from_slice(format!("--{}--\r\n", self.boundary).as_bytes());
+        let mut headers = HashMap::new();
+        headers.insert(
+            "Content-Type".to_string(),
+            format!("multipart/form-data; boundary={}", self.boundary),
+        );
+        (self.body, headers)
+    }
+}
@@ -0,0 +1,288 @@
+use super::batch_update_blocks::{
+    DeleteGridColumnRequest, DeleteTableColumnsRequest, DeleteTableRowsRequest, InsertGridColumnRequest,
+    InsertTableColumnRequest, InsertTableRowRequest, MergeTableCellsRequest, ReplaceFileRequest, ReplaceImageRequest,
+    TextElement, UnmergeTableCellsRequest, UpdateBlockRequest, UpdateGridColumnWidthRatioRequest,
+    UpdateTablePropertyRequest, UpdateTaskRequest, UpdateTextRequest, UpdateTextStyleRequest,
+};
+use crate::error::{LarkError, Result};
+use crate::utils::{BlockSelector, BlockTree};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+/// 更新操作字段，每个请求必须且只能包含其中一个
+pub const UPDATE_OPERATIONS: [&str; 16] = [
+    "update_text_elements",
+    "update_text_style",
+    "update_table_property",
+    "insert_table_row",
+    "insert_table_column",
+    "delete_table_rows",
+    "delete_table_columns",
+    "merge_table_cells",
+    "unmerge_table_cells",
+    "insert_grid_column",
+    "delete_grid_column",
+    "update_grid_column_width_ratio",
+    "replace_image",
+    "replace_file",
+    "update_text",
+    "update_task",
+];
+
+/// 简写字段及其展开后的操作
+const SHORTHANDS: [(&str, &str); 5] = [
+    ("text", "update_text_elements"),
+    ("insert_row", "insert_table_row"),
+    ("insert_column", "insert_table_column"),
+    ("delete_rows", "delete_table_rows"),
+    ("delete_columns", "delete_table_columns"),
+];
+
+/// 解析 JSON 或 YAML 格式的更新请求，得到未校验的值
+///
+/// 内容可以是请求列表，也可以是包含 `requests` 字段的对象。
+pub fn parse_update_value(content: &str) -> Result<Value> {
+    match serde_json::from_str(content) {
+        Ok(value) => Ok(value),
+        Err(_) => serde_yaml::from_str(content)
+            .map_err(|e| LarkError::ParseError(format!("更新请求不是有效的 JSON 或 YAML: {}", e))),
+    }
+}
+
+/// 是否有请求使用 `select` 选择器代替 `block_id`
+pub fn uses_selectors(value: &Value) -> bool {
+    request_items(value).is_some_and(|items| items.iter().any(|item| item.get("select").is_some()))
+}
+
+/// 将使用 `select` 的请求展开为每个选中块各一个请求
+///
+/// 选择器没有匹配任何块时报错，避免静默跳过。
+pub fn expand_selectors(value: &Value, tree: &BlockTree, root_id: &str) -> Result<Value> {
+    let Some(items) = request_items(value) else {
+        return Ok(value.clone());
+    };
+
+    let mut expanded = Vec::with_capacity(items.len());
+    for (i, item) in items.iter().enumerate() {
+        let Some(select) = item.get("select") else {
+            expanded.push(item.clone());
+            continue;
+        };
+        let path = format!("requests[{}]", i);
+        if item.get("block_id").is_some() {
+            return Err(invalid(&path, "select 和 block_id 不能同时使用"));
+        }
+        let selector = select
+            .as_str()
+            .ok_or_else(|| invalid(&format!("{}.select", path), "必须是字符串"))?;
+
+        let block_ids = BlockSelector::parse(selector)?.select(tree, root_id);
+        if block_ids.is_empty() {
+            return Err(invalid(&format!("{}.select", path), &format!("选择器 {} 没有匹配的块", selector)));
+        }
+        for block_id in block_ids {
+            let mut request = item.clone();
+            if let Some(object) = request.as_object_mut() {
+                object.remove("select");
+                object.insert("block_id".to_string(), Value::String(block_id));
+            }
+            expanded.push(request);
+        }
+    }
+    Ok(Value::Array(expanded))
+}
+
+fn request_items(value: &Value) -> Option<&Vec<Value>> {
+    match value {
+        Value::Array(items) => Some(items),
+        Value::Object(object) => object.get("requests").and_then(|r| r.as_array()),
+        _ => None,
+    }
+}
+
+/// 校验并构建更新请求：展开简写，按类型校验每个操作，报错时指出出错的位置
+pub fn build_update_requests(value: &Value) -> Result<Vec<UpdateBlockRequest>> {
+    let items = request_items(value).ok_or_else(|| {
+        LarkError::ValidationError("更新请求必须是列表，或包含 requests 列表的对象".to_string())
+    })?;
+    if items.is_empty() {
+        return Err(LarkError::ValidationError("更新请求不能为空".to_string()));
+    }
+
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| build_request(item, &format!("requests[{}]", i)))
+        .collect()
+}
+
+fn build_request(item: &Value, path: &str) -> Result<UpdateBlockRequest> {
+    let object = item
+        .as_object()
+        .ok_or_else(|| invalid(path, "每个请求必须是对象"))?;
+
+    let block_id = match object.get("block_id") {
+        Some(Value::String(id)) if !id.is_empty() => id.clone(),
+        Some(_) => return Err(invalid(&format!("{}.block_id", path), "必须是非空字符串")),
+        None => return Err(invalid(path, "缺少 block_id")),
+    };
+
+    let mut operations = Map::new();
+    for (key, value) in object {
+        if key == "block_id" {
+            continue;
+        }
+        let field_path = format!("{}.{}", path, key);
+        let (operation, value) = if let Some((_, operation)) = SHORTHANDS.iter().find(|(s, _)| s == key) {
+            (operation.to_string(), expand_shorthand(key, value, &field_path)?)
+        } else if UPDATE_OPERATIONS.contains(&key.as_str()) {
+            (key.clone(), value.clone())
+        } else {
+            return Err(invalid(
+                &field_path,
+                &format!(
+                    "未知的字段。可用的操作: {}；简写: {}",
+                    UPDATE_OPERATIONS.join(", "),
+                    SHORTHANDS.map(|(s, _)| s).join(", ")
+                ),
+            ));
+        };
+        if operations.insert(operation.clone(), value).is_some() {
+            return Err(invalid(path, &format!("操作 {} 重复出现（简写与完整写法不能同时使用）", operation)));
+        }
+    }
+
+    let operation = match operations.len() {
+        0 => return Err(invalid(path, "缺少更新操作")),
+        1 => operations.keys().next().cloned().unwrap_or_default(),
+        _ => {
+            return Err(invalid(
+                path,
+                &format!(
+                    "每个请求只能包含一个更新操作，当前包含: {}",
+                    operations.keys().cloned().collect::<Vec<_>>().join(", ")
+                ),
+            ));
+        }
+    };
+    let value = &operations[&operation];
+    validate_operation(&operation, value, &format!("{}.{}", path, operation))?;
+
+    let mut request = Map::new();
+    request.insert("block_id".to_string(), Value::String(block_id));
+    request.insert(operation, value.clone());
+    serde_json::from_value(Value::Object(request)).map_err(|e| invalid(path, &e.to_string()))
+}
+
+/// 展开简写字段
+fn expand_shorthand(key: &str, value: &Value, path: &str) -> Result<Value> {
+    match key {
+        "text" => {
+            let text = value.as_str().ok_or_else(|| invalid(path, "text 简写必须是字符串"))?;
+            Ok(json!({ "elements": [{ "text_run": { "content": text } }] }))
+        }
+        "insert_row" => Ok(json!({ "row_index": index_value(value, path)? })),
+        "insert_column" => Ok(json!({ "column_index": index_value(value, path)? })),
+        "delete_rows" => {
+            let (start, end) = range_value(value, path)?;
+            Ok(json!({ "row_start_index": start, "row_end_index": end }))
+        }
+        "delete_columns" => {
+            let (start, end) = range_value(value, path)?;
+            Ok(json!({ "column_start_index": start, "column_end_index": end }))
+        }
+        _ => Err(invalid(path, "未知的简写")),
+    }
+}
+
+fn index_value(value: &Value, path: &str) -> Result<i64> {
+    value.as_i64().ok_or_else(|| invalid(path, "必须是整数索引"))
+}
+
+fn range_value(value: &Value, path: &str) -> Result<(i64, i64)> {
+    match value.as_array().map(Vec::as_slice) {
+        Some([start, end]) => match (start.as_i64(), end.as_i64()) {
+            (Some(start), Some(end)) if start < end => Ok((start, end)),
+            (Some(_), Some(_)) => Err(invalid(path, "起始索引必须小于结束索引")),
+            _ => Err(invalid(path, "必须是两个整数 [起始, 结束)")),
+        },
+        _ => Err(invalid(path, "必须是两个整数 [起始, 结束)")),
+    }
+}
+
+/// 按操作对应的类型校验内容
+fn validate_operation(operation: &str, value: &Value, path: &str) -> Result<()> {
+    match operation {
+        "update_text_elements" | "update_text" => {
+            let elements = value
+                .get("elements")
+                .ok_or_else(|| invalid(path, "缺少 elements"))?
+                .as_array()
+                .ok_or_else(|| invalid(&format!("{}.elements", path), "必须是列表"))?;
+            for (i, element) in elements.iter().enumerate() {
+                let element_path = format!("{}.elements[{}]", path, i);
+                if element.as_object().is_some_and(|e| e.is_empty()) {
+                    return Err(invalid(&element_path, "缺少文本元素类型，如 text_run"));
+                }
+                check::<TextElement>(element, &element_path)?;
+            }
+            check::<UpdateTextRequest>(value, path)
+        }
+        "update_text_style" => check::<UpdateTextStyleRequest>(value, path),
+        "update_table_property" => check::<UpdateTablePropertyRequest>(value, path),
+        "insert_table_row" => check::<InsertTableRowRequest>(value, path),
+        "insert_table_column" => check::<InsertTableColumnRequest>(value, path),
+        "delete_table_rows" => check::<DeleteTableRowsRequest>(value, path),
+        "delete_table_columns" => check::<DeleteTableColumnsRequest>(value, path),
+        "merge_table_cells" => check::<MergeTableCellsRequest>(value, path),
+        "unmerge_table_cells" => check::<UnmergeTableCellsRequest>(value, path),
+        "insert_grid_column" => check::<InsertGridColumnRequest>(value, path),
+        "delete_grid_column" => check::<DeleteGridColumnRequest>(value, path),
+        "update_grid_column_width_ratio" => check::<UpdateGridColumnWidthRatioRequest>(value, path),
+        "replace_image" => check::<ReplaceImageRequest>(value, path),
+        "replace_file" => check::<ReplaceFileRequest>(value, path),
+        "update_task" => check::<UpdateTaskRequest>(value, path),
+        _ => Err(invalid(path, "未知的更新操作")),
+    }
+}
+
+/// 反序列化为指定类型，并检查是否有类型中不存在的字段（通常是拼写错误）
+fn check<T: DeserializeOwned + Serialize>(value: &Value, path: &str) -> Result<()> {
+    let typed: T = serde_json::from_value(value.clone()).map_err(|e| invalid(path, &e.to_string()))?;
+    let normalized = serde_json::to_value(&typed)?;
+    match find_unknown_field(value, &normalized, path) {
+        Some(field) => Err(invalid(&field, "未知的字段")),
+        None => Ok(()),
+    }
+}
+
+/// 对比原始内容和类型化后的内容，返回第一个被丢弃的字段路径
+fn find_unknown_field(original: &Value, normalized: &Value, path: &str) -> Option<String> {
+    match (original, normalized) {
+        (Value::Object(original), Value::Object(normalized)) => original.iter().find_map(|(key, value)| {
+            let field_path = format!("{}.{}", path, key);
+            match normalized.get(key) {
+                Some(normalized) => find_unknown_field(value, normalized, &field_path),
+                None if value.is_null() => None,
+                None => Some(field_path),
+            }
+        }),
+        (Value::Object(original), _) => original
+            .iter()
+            .find(|(_, value)| !value.is_null())
+            .map(|(key, _)| format!("{}.{}", path, key)),
+        (Value::Array(original), Value::Array(normalized)) => original
+            .iter()
+            .zip(normalized)
+            .enumerate()
+            .find_map(|(i, (original, normalized))| {
+                find_unknown_field(original, normalized, &format!("{}[{}]", path, i))
+            }),
+        _ => None,
+    }
+}
+
+fn invalid(path: &str, message: &str) -> LarkError {
+    LarkError::ValidationError(format!("{}: {}", path, message))
+}
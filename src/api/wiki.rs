@@ -2,7 +2,8 @@ use serde::{Deserialize, Serialize};
 use crate::api::ApiClient;
 use crate::error::Result;
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
 pub struct KnowledgeSpaceNode {
     pub creator: String,
     pub has_child: bool,
@@ -27,6 +28,15 @@ struct WikiNodeResponse {
     node: KnowledgeSpaceNode,
 }
 
+#[derive(Debug, Serialize)]
+struct CreateWikiNodeRequest {
+    obj_type: String,
+    node_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_node_token: Option<String>,
+    title: String,
+}
+
 
 pub struct WikiApi {
     client: ApiClient,
@@ -91,4 +101,28 @@ impl WikiApi {
             crate::error::LarkError::ParseError("Response data field is empty".to_string())
         })
     }
-}
\ No newline at end of file
+
+    /// 在知识空间中创建节点，未指定父节点时创建为一级节点
+    pub async fn create_node(
+        &self,
+        space_id: &str,
+        parent_node_token: Option<&str>,
+        obj_type: &str,
+        title: &str,
+    ) -> Result<KnowledgeSpaceNode> {
+        let request = CreateWikiNodeRequest {
+            obj_type: obj_type.to_string(),
+            node_type: "origin".to_string(),
+            parent_node_token: parent_node_token.map(|t| t.to_string()),
+            title: title.to_string(),
+        };
+
+        let url = format!(
+            "https://open.larkoffice.com/open-apis/wiki/v2/spaces/{}/nodes",
+            space_id
+        );
+
+        let response: WikiNodeResponse = self.client.post(&url, &request).await?;
+        Ok(response.node)
+    }
+}
@@ -0,0 +1,51 @@
+use crate::api::block_builder::{spec_blocks, BlockSpec};
+use crate::api::{ApiClient, CreateNestedBlocksApi};
+use crate::error::Result;
+use crate::output::{OutputFormat, format_output};
+
+pub async fn handle_add_block(
+    api_client: ApiClient,
+    document_id: String,
+    specs: Vec<BlockSpec>,
+    parent_id: Option<String>,
+    index: Option<i32>,
+    output_format: OutputFormat,
+) -> Result<()> {
+    // 父块默认为文档根
+    let effective_parent_id = parent_id.as_deref()
+        .filter(|id| !id.is_empty())
+        .unwrap_or(&document_id)
+        .to_string();
+
+    let (children_id, descendants) = spec_blocks(&effective_parent_id, &specs)?;
+
+    // dry-run 时输出将要创建的块
+    if api_client.is_dry_run() {
+        let plan = serde_json::json!({
+            "document_id": document_id,
+            "parent_block_id": effective_parent_id,
+            "index": index,
+            "children_id": children_id,
+            "descendants": descendants,
+        });
+        let output = format_output(&plan, output_format)?;
+        println!("{}", output);
+        return Ok(());
+    }
+
+    let result = CreateNestedBlocksApi::new(api_client)
+        .create_nested_blocks(
+            &document_id,
+            &effective_parent_id,
+            children_id,
+            descendants,
+            index,
+            None,
+            None,
+        )
+        .await?;
+
+    let output = format_output(&result, output_format)?;
+    println!("{}", output);
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use crate::api::{ApiClient, DocumentAttachApi};
+use crate::error::Result;
+use crate::output::OutputFormat;
+use crate::output::format_output;
+use std::path::PathBuf;
+
+pub async fn handle_attach(
+    api_client: ApiClient,
+    document_id: String,
+    paths: Vec<PathBuf>,
+    parent_block_id: Option<String>,
+    index: i32,
+    verbose: bool,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let attach_api = DocumentAttachApi::new(api_client);
+    let result = attach_api
+        .attach(&document_id, &paths, parent_block_id.as_deref(), index)
+        .await?;
+
+    if verbose {
+        let images = result.files.iter().filter(|f| f.kind == "image").count();
+        eprintln!("插入 {} 个文件（{} 张图片，{} 个附件）", result.files.len(), images, result.files.len() - images);
+    }
+
+    if result.failure_count > 0 {
+        eprintln!("{} 个文件插入失败，已删除其空块，详见 error 字段", result.failure_count);
+    }
+
+    let output = format_output(&result, output_format)?;
+    println!("{}", output);
+    Ok(())
+}
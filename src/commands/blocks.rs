@@ -1,11 +1,17 @@
 use crate::api::{
     ApiClient, ConvertBlocksApi, GetBlocksApi,
-    BatchUpdateBlocksApi, DeleteBlocksApi
+    BatchUpdateBlocksApi, BlockSelectionApi, DeleteBlocksApi
+};
+use crate::api::batch_update_blocks::MAX_UPDATES_PER_REQUEST;
+use crate::api::update_request_builder::{
+    build_update_requests, expand_selectors, parse_update_value, uses_selectors,
 };
 use crate::error::{Result, LarkError};
 use crate::output::OutputFormat;
 use crate::output::format_output;
+use crate::utils::{BlockTree, FileReader};
 use serde_json;
+use std::path::Path;
 
 #[allow(dead_code)]
 pub async fn handle_convert_blocks(
@@ -31,8 +37,26 @@ pub async fn handle_get_blocks(
     document_revision_id: Option<i32>,
     user_id_type: String,
     all: bool,
+    select: Option<String>,
     output_format: OutputFormat,
 ) -> Result<()> {
+    // 使用选择器时读取所有块，只输出选中的块
+    if let Some(selector) = select {
+        let (tree, _, selected) = BlockSelectionApi::new(api_client)
+            .select(&document_id, &selector, document_revision_id)
+            .await?;
+        let items: Vec<_> = selected.iter().filter_map(|id| tree.get(id)).collect();
+        let result = serde_json::json!({
+            "items": items,
+            "total_count": items.len(),
+            "has_more": false,
+            "page_token": null
+        });
+        let output = format_output(&result, output_format)?;
+        println!("{}", output);
+        return Ok(());
+    }
+
     let blocks_api = GetBlocksApi::new(api_client);
 
     let result = if all {
@@ -73,22 +97,56 @@ pub async fn handle_get_blocks(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_batch_update_blocks(
     api_client: ApiClient,
     document_id: String,
-    requests: String,
+    requests: Option<String>,
+    file: Option<String>,
     document_revision_id: Option<i32>,
     client_token: Option<String>,
     user_id_type: String,
     output_format: OutputFormat,
 ) -> Result<()> {
-    let batch_update_api = BatchUpdateBlocksApi::new(api_client);
+    let dry_run = api_client.is_dry_run();
+    let batch_update_api = BatchUpdateBlocksApi::new(api_client.clone());
+
+    // 请求可以直接作为参数传入，也可以从 JSON/YAML 文件读取
+    let content = match (requests, file) {
+        (Some(requests), None) => requests,
+        (None, Some(file)) => FileReader::read_to_string(Path::new(&file))?,
+        (Some(_), Some(_)) => {
+            return Err(LarkError::ValidationError("requests 参数和 --file 不能同时使用".to_string()));
+        }
+        (None, None) => {
+            return Err(LarkError::ValidationError("需要提供 requests 参数或 --file".to_string()));
+        }
+    };
+    let mut value = parse_update_value(&content)?;
+
+    // 使用 select 的请求展开为每个选中块各一个请求
+    if uses_selectors(&value) {
+        let blocks = GetBlocksApi::new(api_client.clone())
+            .get_all_document_blocks(&document_id, document_revision_id, None)
+            .await?;
+        value = expand_selectors(&value, &BlockTree::new(blocks), &document_id)?;
+    }
+    let requests_list = build_update_requests(&value)?;
 
-    // 解析 JSON 参数
-    let requests_list: Vec<crate::api::batch_update_blocks::UpdateBlockRequest> = serde_json::from_str(&requests)
-        .map_err(|e| LarkError::ParseError(format!("requests JSON 解析失败: {}", e)))?;
+    if dry_run {
+        let plan = serde_json::json!({
+            "document_id": document_id,
+            "document_revision_id": document_revision_id.unwrap_or(-1),
+            "request_count": requests_list.len(),
+            "batch_count": requests_list.len().div_ceil(MAX_UPDATES_PER_REQUEST),
+            "requests": requests_list,
+        });
+        let output = format_output(&plan, output_format)?;
+        println!("{}", output);
+        return Ok(());
+    }
 
-    let result = batch_update_api.batch_update_blocks(
+    let result = batch_update_api.batch_update_all(
         &document_id,
         requests_list,
         document_revision_id,
@@ -105,13 +163,42 @@ pub async fn handle_batch_update_blocks(
 pub async fn handle_delete_blocks(
     api_client: ApiClient,
     document_id: String,
-    block_id: String,
-    start_index: i32,
-    end_index: i32,
+    block_id: Option<String>,
+    start_index: Option<i32>,
+    end_index: Option<i32>,
+    select: Option<String>,
     document_revision_id: Option<i32>,
     client_token: Option<String>,
     output_format: OutputFormat,
 ) -> Result<()> {
+    // 使用选择器时按父块分组删除选中的块
+    if let Some(selector) = select {
+        if block_id.is_some() || start_index.is_some() || end_index.is_some() {
+            return Err(LarkError::ValidationError(
+                "--select 不能与 block_id、start_index、end_index 同时使用".to_string()
+            ));
+        }
+        let result = BlockSelectionApi::new(api_client)
+            .delete(&document_id, &selector, document_revision_id)
+            .await?;
+        let output = format_output(&result, output_format)?;
+        println!("{}", output);
+        return Ok(());
+    }
+
+    let (Some(block_id), Some(start_index), Some(end_index)) = (block_id, start_index, end_index) else {
+        return Err(LarkError::ValidationError(
+            "需要提供 block_id、start_index 和 end_index，或使用 --select".to_string()
+        ));
+    };
+
+    if api_client.is_dry_run() {
+        let plan = plan_delete_blocks(api_client, &document_id, &block_id, start_index, end_index).await?;
+        let output = format_output(&plan, output_format)?;
+        println!("{}", output);
+        return Ok(());
+    }
+
     let delete_blocks_api = DeleteBlocksApi::new(api_client);
 
     let result = delete_blocks_api.delete_blocks(
@@ -126,4 +213,54 @@ pub async fn handle_delete_blocks(
     let output = format_output(&result, output_format)?;
     println!("{}", output);
     Ok(())
-}
\ No newline at end of file
+}
+/// 读取父块的子块，列出 [start_index, end_index) 区间内将被删除的块
+async fn plan_delete_blocks(
+    api_client: ApiClient,
+    document_id: &str,
+    block_id: &str,
+    start_index: i32,
+    end_index: i32,
+) -> Result<serde_json::Value> {
+    if start_index < 0 || start_index >= end_index {
+        return Err(LarkError::ValidationError(
+            "start_index 必须大于等于 0 且小于 end_index".to_string()
+        ));
+    }
+
+    let blocks = GetBlocksApi::new(api_client)
+        .get_all_document_blocks(document_id, None, None)
+        .await?;
+    let tree = BlockTree::new(blocks);
+    if tree.get(block_id).is_none() {
+        return Err(LarkError::ValidationError(format!("找不到块 {}", block_id)));
+    }
+
+    let children = tree.children(block_id);
+    if end_index as usize > children.len() {
+        return Err(LarkError::ValidationError(format!(
+            "end_index {} 超出块 {} 的子块数量 {}",
+            end_index,
+            block_id,
+            children.len()
+        )));
+    }
+
+    let deleted: Vec<serde_json::Value> = children[start_index as usize..end_index as usize]
+        .iter()
+        .filter_map(|id| tree.get(id))
+        .map(|block| serde_json::json!({
+            "block_id": block.block_id,
+            "block_type": block.block_type,
+            "child_count": block.children.as_ref().map(|c| c.len()).unwrap_or(0),
+        }))
+        .collect();
+
+    Ok(serde_json::json!({
+        "document_id": document_id,
+        "block_id": block_id,
+        "start_index": start_index,
+        "end_index": end_index,
+        "blocks": deleted,
+    }))
+}
@@ -0,0 +1,104 @@
+use crate::api::{ApiClient, CommentsApi};
+use crate::error::{LarkError, Result};
+use crate::output::OutputFormat;
+use crate::output::format_output;
+use crate::utils::{render_comments_markdown, CommentExportFormat};
+
+pub async fn handle_comments_list(
+    api_client: ApiClient,
+    file_token: String,
+    file_type: String,
+    solved: Option<bool>,
+    verbose: bool,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let comments_api = CommentsApi::new(api_client);
+    let comments = comments_api.list(&file_token, &file_type, solved).await?;
+
+    if verbose {
+        let replies: usize = comments.iter().map(|c| c.reply_list.replies.len()).sum();
+        eprintln!("共 {} 条评论，{} 条回复", comments.len(), replies);
+    }
+
+    let output = format_output(&comments, output_format)?;
+    println!("{}", output);
+    Ok(())
+}
+
+pub async fn handle_comments_add(
+    api_client: ApiClient,
+    file_token: String,
+    file_type: String,
+    content: String,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let comments_api = CommentsApi::new(api_client);
+    let comment = comments_api.add(&file_token, &file_type, &content).await?;
+
+    let output = format_output(&comment, output_format)?;
+    println!("{}", output);
+    Ok(())
+}
+
+pub async fn handle_comments_reply(
+    api_client: ApiClient,
+    file_token: String,
+    file_type: String,
+    comment_id: String,
+    content: String,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let comments_api = CommentsApi::new(api_client);
+    let reply = comments_api.reply(&file_token, &file_type, &comment_id, &content).await?;
+
+    let output = format_output(&reply, output_format)?;
+    println!("{}", output);
+    Ok(())
+}
+
+pub async fn handle_comments_set_solved(
+    api_client: ApiClient,
+    file_token: String,
+    file_type: String,
+    comment_id: String,
+    solved: bool,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let comments_api = CommentsApi::new(api_client);
+    let result = comments_api.set_solved(&file_token, &file_type, &comment_id, solved).await?;
+
+    let output = format_output(&result, output_format)?;
+    println!("{}", output);
+    Ok(())
+}
+
+pub async fn handle_comments_export(
+    api_client: ApiClient,
+    file_token: String,
+    file_type: String,
+    to: String,
+    solved: Option<bool>,
+    output: Option<String>,
+    verbose: bool,
+) -> Result<()> {
+    let format: CommentExportFormat = to.parse().map_err(LarkError::ParseError)?;
+
+    let comments_api = CommentsApi::new(api_client);
+    let export = comments_api.export(&file_token, &file_type, solved).await?;
+    let content = match format {
+        CommentExportFormat::Markdown => render_comments_markdown(&export),
+        CommentExportFormat::Json => serde_json::to_string_pretty(&export)? + "\n",
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(&path, &content)
+                .map_err(|e| LarkError::IoError(format!("写入文件失败 {}: {}", path, e)))?;
+            if verbose {
+                eprintln!("已导出 {} 条评论到 {}", export.comment_count, path);
+            }
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use crate::api::{ApiClient, DocumentCopyApi};
+use crate::error::Result;
+use crate::output::OutputFormat;
+use crate::output::format_output;
+
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_copy_blocks(
+    api_client: ApiClient,
+    source_document_id: String,
+    source: String,
+    document_id: String,
+    parent_block_id: Option<String>,
+    index: i32,
+    move_blocks: bool,
+    force: bool,
+    verbose: bool,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let copy_api = DocumentCopyApi::new(api_client);
+    let result = copy_api
+        .copy(
+            &source_document_id,
+            &source,
+            &document_id,
+            parent_block_id.as_deref(),
+            index,
+            move_blocks,
+            force,
+        )
+        .await?;
+
+    if verbose {
+        eprintln!(
+            "复制 {} 个块（其中 {} 个图片/附件重新上传），跳过 {} 个块",
+            result.block_count,
+            result.media.len(),
+            result.skipped_blocks.len()
+        );
+    }
+
+    let output = format_output(&result, output_format)?;
+    println!("{}", output);
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use crate::api::{ApiClient, DocumentApi, GetBlocksApi};
+use crate::error::{LarkError, Result};
+use crate::output::OutputFormat;
+use crate::output::format_output;
+use crate::utils::{diff_revisions, render_document, unified_diff, BlockTree};
+
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_diff(
+    api_client: ApiClient,
+    document_id: String,
+    from: i32,
+    to: String,
+    unified: bool,
+    context: usize,
+    verbose: bool,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let to_revision = resolve_revision(&api_client, &document_id, &to).await?;
+    if verbose {
+        println!("比较文档 {} 的版本 {} 与 {}", document_id, from, to_revision);
+    }
+
+    let blocks_api = GetBlocksApi::new(api_client);
+    let from_tree = BlockTree::new(blocks_api.get_all_document_blocks(&document_id, Some(from), None).await?);
+    let to_tree = BlockTree::new(blocks_api.get_all_document_blocks(&document_id, Some(to_revision), None).await?);
+
+    if unified {
+        let diff = unified_diff(
+            &render_document(&from_tree, &document_id),
+            &render_document(&to_tree, &document_id),
+            &format!("{}@{}", document_id, from),
+            &format!("{}@{}", document_id, to_revision),
+            context,
+        );
+        if diff.is_empty() {
+            println!("两个版本的内容相同");
+        } else {
+            print!("{}", diff);
+        }
+        return Ok(());
+    }
+
+    let diff = diff_revisions(&from_tree, &to_tree, &document_id, from, to_revision);
+    if verbose && diff.is_empty() {
+        println!("两个版本的内容相同");
+    } else if verbose {
+        println!("=== 差异统计 ===");
+        println!("  新增: {}", diff.added.len());
+        println!("  删除: {}", diff.removed.len());
+        println!("  移动: {}", diff.moved.len());
+        println!("  修改: {}", diff.modified.len());
+    }
+
+    let output = format_output(&diff, output_format)?;
+    println!("{}", output);
+    Ok(())
+}
+
+/// 解析目标版本，`latest` 表示文档当前版本
+async fn resolve_revision(api_client: &ApiClient, document_id: &str, revision: &str) -> Result<i32> {
+    if revision == "latest" {
+        let document = DocumentApi::new(api_client.clone()).get_document(document_id).await?;
+        return Ok(document.revision_id);
+    }
+
+    revision.parse().map_err(|_| {
+        LarkError::ValidationError(format!("无效的版本号: {}（应为数字或 latest）", revision))
+    })
+}
@@ -1,17 +1,77 @@
-use crate::api::{ApiClient, DocumentApi};
-use crate::error::Result;
+use crate::api::document_template::TemplateSource;
+use crate::api::{ApiClient, DocumentApi, DocumentTemplateApi};
+use crate::error::{LarkError, Result};
 use crate::output::OutputFormat;
 use crate::output::format_output;
+use crate::utils::{load_vars_file, parse_var_assignments, FrontMatterCollaborator};
+use std::path::Path;
 
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_create_document(
     api_client: ApiClient,
     folder_token: Option<String>,
     title: Option<String>,
+    template: Option<String>,
+    vars: Vec<String>,
+    vars_file: Option<String>,
+    collaborators: Vec<String>,
     output_format: OutputFormat,
 ) -> Result<()> {
-    let document_api = DocumentApi::new(api_client);
-    let document = document_api.create_document(folder_token, title).await?;
-    let output = format_output(&document, output_format)?;
+    let Some(template) = template else {
+        if !vars.is_empty() || vars_file.is_some() || !collaborators.is_empty() {
+            return Err(LarkError::ValidationError(
+                "--var、--vars 和 --collaborator 需要与 --template 一起使用".to_string(),
+            ));
+        }
+
+        let document_api = DocumentApi::new(api_client);
+        let document = document_api.create_document(folder_token, title).await?;
+        let output = format_output(&document, output_format)?;
+        println!("{}", output);
+        return Ok(());
+    };
+
+    // 变量文件中的值作为默认值，--var 优先
+    let mut variables = match vars_file {
+        Some(path) => load_vars_file(Path::new(&path))?,
+        None => Default::default(),
+    };
+    variables.extend(parse_var_assignments(&vars)?);
+
+    let collaborators = collaborators
+        .iter()
+        .map(|c| parse_collaborator(c))
+        .collect::<Result<Vec<_>>>()?;
+
+    let template_api = DocumentTemplateApi::new(api_client);
+    let result = template_api
+        .create_from_template(
+            &TemplateSource::parse(&template),
+            folder_token,
+            title,
+            &variables,
+            collaborators,
+        )
+        .await?;
+
+    let output = format_output(&result, output_format)?;
     println!("{}", output);
     Ok(())
-}
\ No newline at end of file
+}
+
+/// 解析 `email` 或 `email:perm` 形式的协作者，默认授予阅读权限
+fn parse_collaborator(value: &str) -> Result<FrontMatterCollaborator> {
+    let (email, perm) = value.split_once(':').unwrap_or((value, "view"));
+    if !matches!(perm, "view" | "edit" | "full_access") {
+        return Err(LarkError::ValidationError(format!(
+            "无效的协作者权限: {}。可选值: view, edit, full_access",
+            perm
+        )));
+    }
+
+    Ok(FrontMatterCollaborator::Member {
+        member_id: email.to_string(),
+        member_type: "email".to_string(),
+        perm: perm.to_string(),
+    })
+}
@@ -1,26 +1,41 @@
-use crate::api::DownloadMediaApi;
-use crate::error::Result;
+use crate::api::{DownloadMediaApi, DownloadOptions};
+use crate::error::{LarkError, Result};
 use crate::output::OutputFormat;
 use crate::output::format_output;
+use crate::utils::ByteRange;
+use std::io::IsTerminal;
 
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_download_media(
     api_client: crate::api::ApiClient,
     file_token: String,
     output_path: String,
     extra: Option<String>,
     range: Option<String>,
+    parallel: usize,
+    no_resume: bool,
     output_format: OutputFormat,
 ) -> Result<()> {
+    let range = range
+        .map(|r| r.parse::<ByteRange>())
+        .transpose()
+        .map_err(LarkError::ParseError)?;
+    let options = DownloadOptions {
+        range,
+        parallel,
+        resume: !no_resume,
+        progress: std::io::stderr().is_terminal(),
+    };
     let download_api = DownloadMediaApi::new(api_client);
 
     // 如果 output_path 是目录，使用自动命名
     let result = if output_path.ends_with('/') || output_path.ends_with('\\') {
         download_api
-            .download_media_auto_name(&file_token, &output_path, extra, range)
+            .download_media_auto_name(&file_token, &output_path, extra, &options)
             .await?
     } else {
         download_api
-            .download_media(&file_token, &output_path, extra, range)
+            .download_media(&file_token, &output_path, extra, &options)
             .await?
     };
 
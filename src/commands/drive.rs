@@ -0,0 +1,200 @@
+use crate::api::drive::DriveTarget;
+use crate::api::{ApiClient, DriveApi, DriveUploadApi};
+use crate::error::{LarkError, Result};
+use crate::output::OutputFormat;
+use crate::output::format_output;
+use crate::utils::{check_drive_file_type, filter_entries, render_drive_tree, UploadState};
+use std::path::PathBuf;
+
+pub async fn handle_drive_ls(
+    api_client: ApiClient,
+    folder: Option<String>,
+    recursive: bool,
+    depth: Option<usize>,
+    types: Vec<String>,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let types = parse_types(&types)?;
+    let drive_api = DriveApi::new(api_client);
+    let folder_token = drive_api.resolve_folder(folder.as_deref()).await?;
+    let entries = drive_api.list_tree(&folder_token, recursive || depth.is_some(), depth).await?;
+    let entries = filter_entries(entries, &types);
+
+    match output_format {
+        OutputFormat::Text => print!("{}", render_drive_tree(&folder_token, &entries)),
+        OutputFormat::Json => {
+            let listing = serde_json::json!({
+                "folder_token": folder_token,
+                "count": entries.len(),
+                "entries": entries,
+            });
+            println!("{}", format_output(&listing, output_format)?);
+        }
+    }
+    Ok(())
+}
+
+pub async fn handle_drive_mkdir(
+    api_client: ApiClient,
+    name: String,
+    parent: Option<String>,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let dry_run = api_client.is_dry_run();
+    let drive_api = DriveApi::new(api_client);
+    let folder_token = drive_api.resolve_folder(parent.as_deref()).await?;
+
+    if dry_run {
+        let plan = serde_json::json!({
+            "action": "create_folder",
+            "name": name,
+            "folder_token": folder_token,
+            "dry_run": true,
+        });
+        println!("{}", format_output(&plan, output_format)?);
+        return Ok(());
+    }
+
+    let folder = drive_api.create_folder(&name, &folder_token).await?;
+    println!("{}", format_output(&folder, output_format)?);
+    Ok(())
+}
+
+pub async fn handle_drive_mv(
+    api_client: ApiClient,
+    source: String,
+    folder: String,
+    file_type: Option<String>,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let drive_api = DriveApi::new(api_client);
+    let target = drive_api.resolve(&source, file_type.as_deref()).await?;
+    let folder_token = drive_api.resolve_folder(Some(&folder)).await?;
+    let result = drive_api.move_file(&target, &folder_token).await?;
+
+    println!("{}", format_output(&result, output_format)?);
+    Ok(())
+}
+
+pub async fn handle_drive_cp(
+    api_client: ApiClient,
+    source: String,
+    folder: String,
+    name: Option<String>,
+    file_type: Option<String>,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let drive_api = DriveApi::new(api_client);
+    let target = drive_api.resolve(&source, file_type.as_deref()).await?;
+    let folder_token = drive_api.resolve_folder(Some(&folder)).await?;
+    let result = drive_api.copy_file(&target, &folder_token, name.as_deref()).await?;
+
+    println!("{}", format_output(&result, output_format)?);
+    Ok(())
+}
+
+pub async fn handle_drive_rm(
+    api_client: ApiClient,
+    sources: Vec<String>,
+    file_type: Option<String>,
+    yes: bool,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let dry_run = api_client.is_dry_run();
+    let drive_api = DriveApi::new(api_client);
+    let targets = resolve_all(&drive_api, &sources, file_type.as_deref()).await?;
+
+    if !dry_run && !yes {
+        eprintln!("将删除以下 {} 个文件（移到回收站）:", targets.len());
+        for target in &targets {
+            let title = drive_api.title(target).await.unwrap_or_else(|_| target.token.clone());
+            eprintln!("  {} [{}] {}", title, target.file_type, target.token);
+        }
+        if !confirm_delete()? {
+            eprintln!("已取消删除");
+            return Ok(());
+        }
+    }
+
+    let mut results = Vec::new();
+    for target in &targets {
+        results.push(drive_api.delete_file(target).await?);
+    }
+    println!("{}", format_output(&results, output_format)?);
+    Ok(())
+}
+
+pub async fn handle_drive_meta(
+    api_client: ApiClient,
+    sources: Vec<String>,
+    file_type: Option<String>,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let drive_api = DriveApi::new(api_client);
+    let targets = resolve_all(&drive_api, &sources, file_type.as_deref()).await?;
+    let result = drive_api.batch_meta(&targets).await?;
+
+    println!("{}", format_output(&result, output_format)?);
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_drive_upload(
+    api_client: ApiClient,
+    path: PathBuf,
+    folder: Option<String>,
+    name: Option<String>,
+    concurrency: usize,
+    no_resume: bool,
+    verbose: bool,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let drive_api = DriveApi::new(api_client.clone());
+    let folder_token = drive_api.resolve_folder(folder.as_deref()).await?;
+    let upload_api = DriveUploadApi::new(api_client);
+    let result = upload_api
+        .upload(&path, &folder_token, name.as_deref(), concurrency, !no_resume, &UploadState::default_dir()?)
+        .await?;
+
+    if verbose && result.resumed_parts > 0 {
+        eprintln!(
+            "从断点继续上传：跳过 {} 个已上传分片，本次上传 {} 个",
+            result.resumed_parts, result.uploaded_parts
+        );
+    }
+    println!("{}", format_output(&result, output_format)?);
+    Ok(())
+}
+
+async fn resolve_all(drive_api: &DriveApi, sources: &[String], file_type: Option<&str>) -> Result<Vec<DriveTarget>> {
+    let mut targets = Vec::new();
+    for source in sources {
+        targets.push(drive_api.resolve(source, file_type).await?);
+    }
+    Ok(targets)
+}
+
+/// 解析逗号分隔或重复指定的文件类型
+fn parse_types(values: &[String]) -> Result<Vec<String>> {
+    values
+        .iter()
+        .flat_map(|v| v.split(','))
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            check_drive_file_type(v).map_err(LarkError::ValidationError)?;
+            Ok(v.to_string())
+        })
+        .collect()
+}
+
+fn confirm_delete() -> Result<bool> {
+    eprintln!("确认删除？(y/N)");
+
+    let mut input = String::new();
+    std::io::stdin()
+        .read_line(&mut input)
+        .map_err(|e| LarkError::IoError(format!("读取输入失败: {}", e)))?;
+
+    Ok(input.trim().eq_ignore_ascii_case("y"))
+}
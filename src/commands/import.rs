@@ -1,9 +1,13 @@
 use crate::api::{ApiClient, ImportDocumentsApi};
+use crate::api::diagram_blocks::DiagramOptions;
 use crate::error::{Result, LarkError};
 use crate::output::OutputFormat;
 use crate::output::format_output;
-use crate::utils::{FileScanner, FileReader};
-use std::path::Path;
+use crate::utils::{
+    split_front_matter, ExistingFilePolicy, FileReader, FileScanner, ImportManifest,
+    DEFAULT_MANIFEST_FILE,
+};
+use std::path::{Path, PathBuf};
 
 #[allow(clippy::too_many_arguments)]
 pub async fn handle_add_content(
@@ -17,14 +21,33 @@ pub async fn handle_add_content(
     recursive: bool,
     pattern: Option<String>,
     batch_size: usize,
-    _skip_existing: bool,
+    skip_existing: bool,
+    manifest: Option<String>,
+    replace_changed: bool,
+    diagrams: Option<DiagramOptions>,
     verbose: bool,
     output_format: OutputFormat,
 ) -> Result<()> {
-    let import_api = ImportDocumentsApi::new(api_client);
+    let mut import_api = ImportDocumentsApi::new(api_client);
+    if let Some(options) = diagrams {
+        import_api = import_api.with_diagrams(options);
+    }
 
     match source_type.as_str() {
         "content" => {
+            if import_api.is_dry_run() {
+                let plan = import_api.plan_document(
+                    &document_id,
+                    &source,
+                    &content_type,
+                    &block_id,
+                    index,
+                    true,
+                ).await?;
+                println!("{}", format_output(&plan, output_format)?);
+                return Ok(());
+            }
+
             // 直接导入内容
             let result = import_api.import_document(
                 &document_id,
@@ -35,6 +58,7 @@ pub async fn handle_add_content(
                 None,
                 None,
             ).await?;
+            apply_front_matter(&import_api, &document_id, &source, &content_type).await?;
             let output = format_output(&result, output_format)?;
             println!("{}", output);
         }
@@ -44,6 +68,19 @@ pub async fn handle_add_content(
             let content = FileReader::read_to_string(file_path)?;
             let inferred_type = FileReader::infer_content_type(file_path);
 
+            if import_api.is_dry_run() {
+                let plan = import_api.plan_document(
+                    &document_id,
+                    &content,
+                    inferred_type,
+                    &block_id,
+                    index,
+                    true,
+                ).await?;
+                println!("{}", format_output(&plan, output_format)?);
+                return Ok(());
+            }
+
             let result = import_api.import_document(
                 &document_id,
                 &content,
@@ -53,6 +90,7 @@ pub async fn handle_add_content(
                 None,
                 None,
             ).await?;
+            apply_front_matter(&import_api, &document_id, &content, inferred_type).await?;
             let output = format_output(&result, output_format)?;
             println!("{}", output);
         }
@@ -61,8 +99,23 @@ pub async fn handle_add_content(
             let dir_path = Path::new(&source);
             let scanner = FileScanner::new(pattern.as_deref())?;
 
-            // 扫描文件
-            let files = scanner.scan_directory(dir_path, recursive)?;
+            // 需要跳过或替换已导入文件时，使用导入清单记录每个文件的导入状态
+            let manifest_path = if skip_existing || replace_changed || manifest.is_some() {
+                Some(manifest.map(PathBuf::from).unwrap_or_else(|| dir_path.join(DEFAULT_MANIFEST_FILE)))
+            } else {
+                None
+            };
+            let mut import_manifest = match &manifest_path {
+                Some(path) => Some(ImportManifest::load_or_create(path, &document_id)?),
+                None => None,
+            };
+
+            // 扫描文件（排除清单文件本身）
+            let mut files = scanner.scan_directory(dir_path, recursive)?;
+            if let Some(path) = &manifest_path {
+                let manifest_file = path.canonicalize().unwrap_or_else(|_| path.clone());
+                files.retain(|f| f.canonicalize().unwrap_or_else(|_| f.clone()) != manifest_file);
+            }
             if files.is_empty() {
                 println!("未找到匹配的文件");
                 return Ok(());
@@ -84,6 +137,10 @@ pub async fn handle_add_content(
                 };
 
                 let file_type = FileReader::infer_content_type(&file_path);
+                let relative_path = file_path
+                    .strip_prefix(dir_path)
+                    .ok()
+                    .map(|p| p.to_string_lossy().replace('\\', "/"));
 
                 import_requests.push(crate::utils::ImportRequest {
                     file_path: file_path.clone(),
@@ -91,7 +148,7 @@ pub async fn handle_add_content(
                     content_type: file_type.to_string(),
                     block_id: block_id.clone(),
                     index,
-                    relative_path: None,
+                    relative_path,
                 });
             }
 
@@ -100,12 +157,30 @@ pub async fn handle_add_content(
                 return Ok(());
             }
 
+            let policy = ExistingFilePolicy {
+                skip_unchanged: skip_existing,
+                replace_changed,
+            };
+
+            if import_api.is_dry_run() {
+                let plan = import_api.plan_batch(
+                    &document_id,
+                    import_requests,
+                    import_manifest.as_ref(),
+                    policy,
+                ).await?;
+                println!("{}", format_output(&plan, output_format)?);
+                return Ok(());
+            }
+
             // 执行批量导入
             let result = import_api.import_batch(
                 &document_id,
                 import_requests,
                 batch_size,
                 None,
+                import_manifest.as_mut(),
+                policy,
             ).await?;
 
             // 显示详细结果（如果启用了详细模式）
@@ -117,7 +192,11 @@ pub async fn handle_add_content(
                         .and_then(|n| n.to_str())
                         .unwrap_or("?");
 
-                    if import_result.success {
+                    if import_result.changed {
+                        println!("  - {} (内容已变化，已跳过；使用 --replace-changed 重新导入)", file_name);
+                    } else if import_result.skipped {
+                        println!("  - {} (内容未变化，已跳过)", file_name);
+                    } else if import_result.success {
                         println!("  ✓ {}", file_name);
                     } else {
                         println!("  ✗ {} - {}",
@@ -134,6 +213,11 @@ pub async fn handle_add_content(
             println!("  成功:     {} ({:.1}%)",
                 result.success_count,
                 (result.success_count as f64 / total as f64) * 100.0);
+            if result.skipped_count > 0 {
+                println!("  跳过:     {} ({:.1}%)",
+                    result.skipped_count,
+                    (result.skipped_count as f64 / total as f64) * 100.0);
+            }
             if result.failure_count > 0 {
                 println!("  导入失败: {} ({:.1}%)",
                     result.failure_count,
@@ -144,6 +228,9 @@ pub async fn handle_add_content(
                     failed_count,
                     (failed_count as f64 / total as f64) * 100.0);
             }
+            if let Some(manifest) = &import_manifest {
+                println!("  导入清单: {}", manifest.path().display());
+            }
 
             // 输出结果
             let output = format_output(&result, output_format)?;
@@ -156,4 +243,23 @@ pub async fn handle_add_content(
         }
     }
     Ok(())
-}
\ No newline at end of file
+}
+
+/// 将 Markdown Front Matter 中的标题和协作者应用到目标文档
+async fn apply_front_matter(
+    import_api: &ImportDocumentsApi,
+    document_id: &str,
+    content: &str,
+    content_type: &str,
+) -> Result<()> {
+    if content_type != "markdown" {
+        return Ok(());
+    }
+
+    if let (Some(front_matter), _) = split_front_matter(content)? {
+        for action in import_api.apply_front_matter(document_id, &front_matter, true).await? {
+            println!("{}", action);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,268 @@
+use crate::api::{ApiClient, DocumentApi, DriveApi, ImportDocumentsApi, WikiApi};
+use crate::config::Config;
+use crate::error::{LarkError, Result};
+use crate::output::OutputFormat;
+use crate::output::format_output;
+use crate::utils::{
+    collect_dirs, document_title, parent_dir, rewrite_links, split_front_matter, FileReader,
+    FileScanner, FrontMatter, HierarchyDocument, HierarchyFolder, HierarchyImportResult,
+};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// 目录层级的创建位置
+enum HierarchyTarget {
+    /// 云空间文件夹
+    Drive { folder_token: String },
+    /// 知识空间节点
+    Wiki { space_id: String, parent_node: Option<String> },
+}
+
+/// 待导入的文件
+struct SourceFile {
+    file_path: PathBuf,
+    relative_path: String,
+    content_type: &'static str,
+    title: String,
+    body: String,
+    front_matter: Option<FrontMatter>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_import_tree(
+    api_client: ApiClient,
+    source: String,
+    folder_token: Option<String>,
+    wiki_space: Option<String>,
+    parent_node: Option<String>,
+    recursive: bool,
+    pattern: Option<String>,
+    base_url: Option<String>,
+    verbose: bool,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let target = match (folder_token, wiki_space) {
+        (Some(folder_token), None) => {
+            if parent_node.is_some() {
+                return Err(LarkError::ValidationError(
+                    "--parent-node 只能与 --wiki-space 一起使用".to_string()
+                ));
+            }
+            HierarchyTarget::Drive { folder_token }
+        }
+        (None, Some(space_id)) => HierarchyTarget::Wiki { space_id, parent_node },
+        _ => {
+            return Err(LarkError::ValidationError(
+                "必须且只能指定 --folder-token 或 --wiki-space 之一".to_string()
+            ));
+        }
+    };
+
+    // 文件之间的链接改写为文档链接，需要租户的域名
+    let base_url = Config::resolve_base_url(base_url)?;
+
+    // 扫描并读取文件
+    let dir_path = Path::new(&source);
+    let scanner = FileScanner::new(pattern.as_deref())?;
+    let files = scanner.scan_directory(dir_path, recursive)?;
+    if files.is_empty() {
+        println!("未找到匹配的文件");
+        return Ok(());
+    }
+
+    let mut sources = Vec::new();
+    for file_path in files {
+        let content = FileReader::read_to_string(&file_path)?;
+        let content_type = FileReader::infer_content_type(&file_path);
+        let relative_path = file_path
+            .strip_prefix(dir_path)
+            .unwrap_or(&file_path)
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        // Front Matter 中的 title 优先于一级标题和文件名
+        let (front_matter, content) = if content_type == "markdown" {
+            split_front_matter(&content)?
+        } else {
+            (None, content)
+        };
+        let (title, body) = match front_matter.as_ref().and_then(|f| f.title.clone()) {
+            Some(title) => (title, content),
+            None => document_title(&file_path, &content, content_type),
+        };
+
+        sources.push(SourceFile {
+            file_path,
+            relative_path,
+            content_type,
+            title,
+            body,
+            front_matter,
+        });
+    }
+
+    println!("找到 {} 个文件，开始创建文档层级...", sources.len());
+
+    let document_api = DocumentApi::new(api_client.clone());
+    let drive_api = DriveApi::new(api_client.clone());
+    let wiki_api = WikiApi::new(api_client.clone());
+    let import_api = ImportDocumentsApi::new(api_client);
+
+    let mut result = HierarchyImportResult::default();
+
+    // 第一步：按目录结构创建文件夹或知识库节点（父目录先于子目录）
+    let root_token = match &target {
+        HierarchyTarget::Drive { folder_token } => Some(folder_token.clone()),
+        HierarchyTarget::Wiki { parent_node, .. } => parent_node.clone(),
+    };
+    let mut containers: HashMap<String, Option<String>> = HashMap::new();
+    containers.insert(String::new(), root_token);
+
+    for dir in collect_dirs(sources.iter().map(|s| s.relative_path.as_str())) {
+        let name = dir.rsplit('/').next().unwrap_or(&dir);
+        let parent = containers[parent_dir(&dir)].clone();
+
+        let folder = match &target {
+            HierarchyTarget::Drive { .. } => {
+                let folder = drive_api
+                    .create_folder(name, parent.as_deref().unwrap_or_default())
+                    .await?;
+                HierarchyFolder {
+                    relative_path: dir.clone(),
+                    token: folder.token,
+                    url: folder.url,
+                }
+            }
+            HierarchyTarget::Wiki { space_id, .. } => {
+                // 知识库没有文件夹，目录以同名文档节点表示
+                let node = wiki_api
+                    .create_node(space_id, parent.as_deref(), "docx", name)
+                    .await?;
+                HierarchyFolder {
+                    relative_path: dir.clone(),
+                    url: document_url(&base_url, "wiki", &node.node_token),
+                    token: node.node_token,
+                }
+            }
+        };
+
+        if verbose {
+            println!("  + {}/ -> {}", dir, folder.url);
+        }
+        containers.insert(dir, Some(folder.token.clone()));
+        result.folders.push(folder);
+    }
+
+    // 第二步：为每个文件创建空文档，得到所有文档链接后再导入内容
+    let mut links: HashMap<String, String> = HashMap::new();
+    for source in &sources {
+        let parent = containers[parent_dir(&source.relative_path)].clone();
+
+        let created = match &target {
+            HierarchyTarget::Drive { .. } => document_api
+                .create_document(parent, Some(source.title.clone()))
+                .await
+                .map(|doc| {
+                    let url = document_url(&base_url, "docx", &doc.document_id);
+                    (doc.document_id, None, url)
+                }),
+            HierarchyTarget::Wiki { space_id, .. } => wiki_api
+                .create_node(space_id, parent.as_deref(), "docx", &source.title)
+                .await
+                .map(|node| (node.obj_token, Some(node.node_token.clone()), document_url(&base_url, "wiki", &node.node_token))),
+        };
+
+        let document = match created {
+            Ok((document_id, node_token, url)) => {
+                links.insert(source.relative_path.clone(), url.clone());
+                HierarchyDocument {
+                    relative_path: source.relative_path.clone(),
+                    title: source.title.clone(),
+                    document_id: Some(document_id),
+                    node_token,
+                    url: Some(url),
+                    success: true,
+                    error: None,
+                }
+            }
+            Err(e) => HierarchyDocument {
+                relative_path: source.relative_path.clone(),
+                title: source.title.clone(),
+                document_id: None,
+                node_token: None,
+                url: None,
+                success: false,
+                error: Some(format!("创建文档失败: {}", e)),
+            },
+        };
+        result.documents.push(document);
+    }
+
+    // 第三步：改写文件之间的相对链接并导入内容
+    for (source, document) in sources.iter().zip(result.documents.iter_mut()) {
+        let Some(document_id) = document.document_id.clone() else {
+            continue;
+        };
+
+        let body = if source.content_type == "markdown" {
+            rewrite_links(&source.body, &source.relative_path, &links)
+        } else {
+            source.body.clone()
+        };
+
+        let has_callout = source.front_matter.as_ref().is_some_and(|f| f.callout.is_some());
+        if !body.trim().is_empty() || has_callout {
+            if let Err(e) = import_api
+                .import_prepared(
+                    &document_id,
+                    source.front_matter.as_ref(),
+                    &body,
+                    source.content_type,
+                    "",
+                    -1,
+                    None,
+                    None,
+                )
+                .await
+            {
+                document.success = false;
+                document.error = Some(format!("导入内容失败: {}", e));
+            }
+        }
+
+        // 标题已在创建文档时设置，这里只添加协作者
+        if let (true, Some(front_matter)) = (document.success, &source.front_matter) {
+            if let Err(e) = import_api.apply_front_matter(&document_id, front_matter, false).await {
+                document.success = false;
+                document.error = Some(format!("应用 Front Matter 失败: {}", e));
+            }
+        }
+
+        if verbose {
+            match &document.error {
+                None => println!("  ✓ {} -> {}", source.file_path.display(), document.url.as_deref().unwrap_or("")),
+                Some(error) => println!("  ✗ {} - {}", source.file_path.display(), error),
+            }
+        }
+    }
+
+    result.success_count = result.documents.iter().filter(|d| d.success).count();
+    result.failure_count = result.documents.len() - result.success_count;
+
+    println!("\n=== 导入统计 ===");
+    println!("  目录:     {}", result.folders.len());
+    println!("  文档:     {}", result.documents.len());
+    println!("  成功:     {}", result.success_count);
+    if result.failure_count > 0 {
+        println!("  失败:     {}", result.failure_count);
+    }
+
+    let output = format_output(&result, output_format)?;
+    println!("{}", output);
+    Ok(())
+}
+
+/// 文档或知识库节点的访问链接，如 `https://example.feishu.cn/wiki/<token>`
+fn document_url(base_url: &str, kind: &str, token: &str) -> String {
+    format!("{}/{}/{}", base_url, kind, token)
+}
@@ -0,0 +1,113 @@
+use crate::api::document_lint::LintReport;
+use crate::api::{ApiClient, DocumentLintApi};
+use crate::error::{LarkError, Result};
+use crate::output::OutputFormat;
+use crate::output::format_output;
+use crate::utils::{LintConfig, LintRule, Severity};
+use std::path::Path;
+
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_lint(
+    api_client: ApiClient,
+    document_id: String,
+    config_path: Option<String>,
+    rules: Vec<String>,
+    skip: Vec<String>,
+    offline: bool,
+    fix: bool,
+    fail_on: String,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let mut config = match config_path {
+        Some(path) => LintConfig::load(Path::new(&path))?,
+        None => LintConfig::default(),
+    };
+    if !rules.is_empty() {
+        config.rules = parse_rules(&rules)?;
+    }
+    config.skip.extend(parse_rules(&skip)?);
+    if offline {
+        config.skip.extend(LintRule::ALL.into_iter().filter(LintRule::is_online));
+    }
+    let fail_on = match fail_on.as_str() {
+        "never" => None,
+        level => Some(level.parse::<Severity>().map_err(LarkError::ParseError)?),
+    };
+
+    let lint_api = DocumentLintApi::new(api_client);
+    let report = lint_api.lint(&document_id, &config, fix).await?;
+
+    match output_format {
+        OutputFormat::Text => print_report(&report),
+        OutputFormat::Json => println!("{}", format_output(&report, output_format)?),
+    }
+
+    if !report.unchecked_references.is_empty() {
+        eprintln!(
+            "{} 个引用因网络错误、限流或服务端异常未能检查，不计入问题",
+            report.unchecked_references.len()
+        );
+    }
+
+    if let Some(level) = fail_on {
+        let count = report.unfixed_count(level);
+        if count > 0 {
+            return Err(LarkError::ValidationError(format!("文档检查发现 {} 个未修复的问题", count)));
+        }
+    }
+    Ok(())
+}
+
+/// 解析逗号分隔或重复指定的规则名
+fn parse_rules(values: &[String]) -> Result<Vec<LintRule>> {
+    values
+        .iter()
+        .flat_map(|v| v.split(','))
+        .filter(|v| !v.trim().is_empty())
+        .map(|v| v.parse().map_err(LarkError::ParseError))
+        .collect()
+}
+
+/// 打印可读的检查报告
+fn print_report(report: &LintReport) {
+    println!("=== 文档检查: {} ===", report.document_id);
+    for finding in &report.findings {
+        let level = match finding.severity {
+            Severity::Error => "错误",
+            Severity::Warning => "警告",
+        };
+        let status = if finding.fixed {
+            "（已修复）"
+        } else if finding.fix.is_some() {
+            "（可自动修复）"
+        } else {
+            ""
+        };
+        println!(
+            "  {} [{}] {} - {}{}",
+            level,
+            finding.rule.name(),
+            finding.block_id,
+            finding.message,
+            status
+        );
+    }
+
+    for reference in &report.unchecked_references {
+        println!(
+            "  未检查 [{}] {} - {}: {}",
+            reference.rule.name(),
+            reference.block_id,
+            reference.label,
+            reference.error
+        );
+    }
+
+    println!();
+    println!("  错误:     {}", report.error_count);
+    println!("  警告:     {}", report.warning_count);
+    if report.fixed_count > 0 {
+        let label = if report.dry_run { "可修复" } else { "已修复" };
+        println!("  {}:   {}", label, report.fixed_count);
+    }
+}
@@ -4,13 +4,27 @@ pub mod blocks;
 pub mod file;
 pub mod message;
 pub mod import;
+pub mod import_tree;
+pub mod sync;
+pub mod diff;
+pub mod replace;
+pub mod section;
+pub mod publish;
 pub mod permission;
 pub mod document;
 pub mod add_callout;
+pub mod add_block;
 pub mod import_diagram;
 pub mod add_board;
 pub mod update;
 pub mod download_media;
 pub mod get_board_image;
 pub mod create_board_notes;
-pub mod get_user_info;
\ No newline at end of file
+pub mod get_user_info;
+pub mod table;
+pub mod copy_blocks;
+pub mod lint;
+pub mod toc;
+pub mod attach;
+pub mod comments;
+pub mod drive;
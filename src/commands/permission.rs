@@ -16,6 +16,7 @@ pub async fn handle_add_permission(
     notification: bool,
     output_format: OutputFormat,
 ) -> Result<()> {
+    let dry_run = api_client.is_dry_run();
     let permission_api = PermissionApi::new(api_client);
 
     // 根据文档类型决定是否使用perm_type
@@ -33,6 +34,18 @@ pub async fn handle_add_permission(
         collaborator_type: Some(collaborator_type),
     };
 
+    if dry_run {
+        let plan = serde_json::json!({
+            "token": token,
+            "doc_type": doc_type,
+            "need_notification": notification,
+            "member": request,
+        });
+        let output = format_output(&plan, output_format)?;
+        println!("{}", output);
+        return Ok(());
+    }
+
     let result = permission_api.add_permission_member(
         &token,
         &doc_type,
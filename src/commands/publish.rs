@@ -0,0 +1,186 @@
+use crate::api::{ApiClient, DocumentSyncApi, GetBlocksApi, WikiApi};
+use crate::error::{LarkError, Result};
+use crate::output::OutputFormat;
+use crate::output::format_output;
+use crate::utils::{
+    content_hash, document_content_hash, BlockTree, DriftStatus, FileReader, ProjectDocument, ProjectLock,
+    ProjectManifest, PublishReport, PublishedFile, DEFAULT_PROJECT_FILE,
+};
+use std::path::Path;
+
+pub async fn handle_publish(
+    api_client: ApiClient,
+    config: Option<String>,
+    check: bool,
+    force: bool,
+    verbose: bool,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let manifest_path = config.unwrap_or_else(|| DEFAULT_PROJECT_FILE.to_string());
+    let manifest = ProjectManifest::load(Path::new(&manifest_path))?;
+    let mut lock = ProjectLock::load_or_default(&manifest.lock_path())?;
+    let dry_run = api_client.is_dry_run();
+
+    let wiki_api = WikiApi::new(api_client.clone());
+    let blocks_api = GetBlocksApi::new(api_client.clone());
+    let sync_api = DocumentSyncApi::new(api_client);
+
+    let mut report = PublishReport {
+        check,
+        ..Default::default()
+    };
+
+    for document in &manifest.documents {
+        let key = document.key();
+        let mut file = PublishedFile {
+            path: key.clone(),
+            document_id: None,
+            status: None,
+            published: false,
+            document_revision_id: None,
+            error: None,
+        };
+
+        let result = async {
+            // 读取本地文件和飞书文档当前内容
+            let file_path = manifest.file_path(document);
+            let content = FileReader::read_to_string(&file_path)?;
+            let content_type = FileReader::infer_content_type(&file_path);
+            let local_hash = content_hash(&content);
+
+            let document_id = resolve_document_id(&wiki_api, document).await?;
+            file.document_id = Some(document_id.clone());
+
+            let tree = BlockTree::new(blocks_api.get_all_document_blocks(&document_id, None, None).await?);
+            let status = DriftStatus::detect(
+                lock.files.get(&key),
+                &document_id,
+                &local_hash,
+                &document_content_hash(&tree, &document_id),
+            );
+            file.status = Some(status);
+
+            // 只推送本地有变化的文件；飞书文档单独被修改时需要 --force 才覆盖
+            let should_publish = match status {
+                DriftStatus::InSync => force,
+                DriftStatus::RemoteChanged => force,
+                DriftStatus::NotPublished | DriftStatus::LocalChanged | DriftStatus::BothChanged => true,
+            };
+            if check || !should_publish {
+                return Ok::<_, LarkError>(());
+            }
+
+            let result = sync_api.sync(&document_id, &content, content_type).await?;
+            if dry_run {
+                return Ok(());
+            }
+
+            // 重新读取发布后的文档，记录内容哈希，之后的检查以此为准
+            let tree = BlockTree::new(blocks_api.get_all_document_blocks(&document_id, None, None).await?);
+            lock.record(
+                &key,
+                &document_id,
+                local_hash,
+                document_content_hash(&tree, &document_id),
+                result.document_revision_id,
+            );
+            lock.save()?;
+
+            file.published = true;
+            file.document_revision_id = Some(result.document_revision_id);
+            Ok(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            file.error = Some(e.to_string());
+        }
+
+        if file.published {
+            report.published_count += 1;
+        } else if file.error.is_some() || (check && file.status != Some(DriftStatus::InSync)) {
+            report.drifted_count += 1;
+        }
+        report.files.push(file);
+    }
+
+    print_report(&report, &lock, verbose);
+    let output = format_output(&report, output_format)?;
+    println!("{}", output);
+
+    if report.drifted_count > 0 {
+        let message = if check {
+            format!("{} 个文件与飞书文档不一致", report.drifted_count)
+        } else {
+            format!("{} 个文件发布失败", report.drifted_count)
+        };
+        return Err(LarkError::ValidationError(message));
+    }
+    Ok(())
+}
+
+/// 文档映射对应的文档 ID，知识库节点解析为其挂载的文档
+async fn resolve_document_id(wiki_api: &WikiApi, document: &ProjectDocument) -> Result<String> {
+    match (&document.document_id, &document.wiki_node) {
+        (Some(document_id), _) => Ok(document_id.clone()),
+        (None, Some(node_token)) => {
+            let node = wiki_api.get_knowledge_space_node(node_token, Some("wiki")).await?;
+            if node.obj_type != "docx" {
+                return Err(LarkError::ValidationError(format!(
+                    "知识库节点 {} 不是文档（类型: {}）",
+                    node_token, node.obj_type
+                )));
+            }
+            Ok(node.obj_token)
+        }
+        (None, None) => Err(LarkError::ValidationError(format!(
+            "{}: 缺少 document_id 或 wiki_node",
+            document.path
+        ))),
+    }
+}
+
+/// 打印可读的一致性报告
+fn print_report(report: &PublishReport, lock: &ProjectLock, verbose: bool) {
+    println!("=== {} ===", if report.check { "发布检查" } else { "发布结果" });
+    for file in &report.files {
+        let line = match (&file.error, file.status) {
+            (Some(error), _) => format!("  ✗ {} - {}", file.path, error),
+            (None, _) if file.published => format!("  ↑ {} - 已发布", file.path),
+            (None, Some(DriftStatus::InSync)) => {
+                if !verbose {
+                    continue;
+                }
+                format!("  ✓ {} - {}", file.path, DriftStatus::InSync.description())
+            }
+            (None, Some(status)) if report.check => format!("  ✗ {} - {}", file.path, status.description()),
+            (None, Some(DriftStatus::RemoteChanged)) => format!(
+                "  - {} - {}，已跳过（使用 --force 覆盖）",
+                file.path,
+                DriftStatus::RemoteChanged.description()
+            ),
+            // dry-run 模式下只计算了差异
+            (None, Some(status)) => format!("  ↑ {} - {}，将发布", file.path, status.description()),
+            (None, None) => continue,
+        };
+        println!("{}", line);
+
+        // 飞书文档被修改时显示上次发布时的版本，便于在历史记录中查找
+        if let (false, Some(DriftStatus::RemoteChanged | DriftStatus::BothChanged), Some(entry)) =
+            (file.published, file.status, lock.files.get(&file.path))
+        {
+            println!("      上次发布: 版本 {}，{}", entry.document_revision_id, entry.published_at);
+        }
+    }
+
+    println!();
+    println!("  文件:     {}", report.files.len());
+    if report.check {
+        println!("  不一致:   {}", report.drifted_count);
+    } else {
+        println!("  已发布:   {}", report.published_count);
+        if report.drifted_count > 0 {
+            println!("  失败:     {}", report.drifted_count);
+        }
+    }
+}
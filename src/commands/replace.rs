@@ -0,0 +1,42 @@
+use crate::api::{ApiClient, DocumentReplaceApi};
+use crate::error::Result;
+use crate::output::OutputFormat;
+use crate::output::format_output;
+use crate::utils::TextMatcher;
+
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_replace(
+    api_client: ApiClient,
+    document_id: String,
+    pattern: String,
+    replacement: String,
+    regex: bool,
+    ignore_case: bool,
+    preview: bool,
+    verbose: bool,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let matcher = TextMatcher::new(&pattern, regex, ignore_case)?;
+    let replace_api = DocumentReplaceApi::new(api_client);
+    let result = replace_api.replace(&document_id, &matcher, &replacement, preview).await?;
+
+    // 预览时逐条列出匹配及其上下文
+    if result.preview || verbose {
+        println!(
+            "=== {} ===",
+            if result.preview { "匹配预览" } else { "替换结果" }
+        );
+        for block in &result.blocks {
+            for m in &block.matches {
+                println!("  [{}] {}", block.block_id, m.context);
+                println!("      {} → {}", m.matched, m.replacement);
+            }
+        }
+        println!();
+        println!("  匹配: {} 处，涉及 {} 个块", result.match_count, result.block_count);
+    }
+
+    let output = format_output(&result, output_format)?;
+    println!("{}", output);
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use crate::api::{ApiClient, DocumentSectionApi};
+use crate::error::{LarkError, Result};
+use crate::output::OutputFormat;
+use crate::output::format_output;
+use crate::utils::{FileReader, SectionMode};
+use std::path::Path;
+
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_edit_section(
+    api_client: ApiClient,
+    document_id: String,
+    heading_path: String,
+    source: String,
+    source_type: String,
+    content_type: String,
+    mode: String,
+    verbose: bool,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let mode: SectionMode = mode.parse().map_err(LarkError::ParseError)?;
+
+    let (content, content_type) = match source_type.as_str() {
+        "file" => {
+            let file_path = Path::new(&source);
+            let content = FileReader::read_to_string(file_path)?;
+            (content, FileReader::infer_content_type(file_path).to_string())
+        }
+        "content" => (source, content_type),
+        _ => {
+            return Err(LarkError::ValidationError(format!(
+                "无效的源类型: {}。可选值: file, content",
+                source_type
+            )));
+        }
+    };
+
+    let section_api = DocumentSectionApi::new(api_client);
+    let result = section_api
+        .write_section(&document_id, &heading_path, &content, &content_type, mode)
+        .await?;
+
+    if verbose {
+        println!("=== 章节写入 ===");
+        println!("  标题块:   {}", result.section.heading_block_id);
+        println!("  原有内容: 第 {} - {} 个块", result.section.start, result.section.end);
+        println!("  删除:     {}", result.deleted_block_ids.len());
+        println!("  新建:     {}", result.created_block_ids.len());
+    }
+
+    let output = format_output(&result, output_format)?;
+    println!("{}", output);
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use crate::api::{ApiClient, DocumentSyncApi};
+use crate::error::Result;
+use crate::output::OutputFormat;
+use crate::output::format_output;
+use crate::utils::FileReader;
+use std::path::Path;
+
+pub async fn handle_sync(
+    api_client: ApiClient,
+    document_id: String,
+    file: String,
+    verbose: bool,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let file_path = Path::new(&file);
+    let content = FileReader::read_to_string(file_path)?;
+    let content_type = FileReader::infer_content_type(file_path);
+
+    let sync_api = DocumentSyncApi::new(api_client);
+    let result = sync_api.sync(&document_id, &content, content_type).await?;
+
+    if verbose {
+        println!("=== 同步统计 ===");
+        println!("  未变化:   {}", result.unchanged);
+        println!("  更新文本: {}", result.updated);
+        println!("  删除:     {}", result.deleted);
+        println!("  新建:     {}", result.created);
+    }
+
+    let output = format_output(&result, output_format)?;
+    println!("{}", output);
+    Ok(())
+}
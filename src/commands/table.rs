@@ -0,0 +1,117 @@
+use crate::api::{ApiClient, DocumentTableApi};
+use crate::error::{LarkError, Result};
+use crate::output::OutputFormat;
+use crate::output::format_output;
+use crate::utils::{format_table, parse_csv, FileReader, TableFormat};
+use std::io::Read;
+use std::path::Path;
+
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_table_export(
+    api_client: ApiClient,
+    document_id: String,
+    table_block_id: String,
+    to: String,
+    no_header: bool,
+    output: Option<String>,
+    verbose: bool,
+) -> Result<()> {
+    let format: TableFormat = to.parse().map_err(LarkError::ParseError)?;
+
+    let table_api = DocumentTableApi::new(api_client);
+    let rows = table_api.export(&document_id, &table_block_id).await?;
+    let content = format_table(&rows, format, !no_header)?;
+
+    match output {
+        Some(path) => {
+            std::fs::write(&path, &content)
+                .map_err(|e| LarkError::IoError(format!("写入文件失败 {}: {}", path, e)))?;
+            if verbose {
+                eprintln!("已导出 {} 行到 {}", rows.len(), path);
+            }
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_table_create(
+    api_client: ApiClient,
+    document_id: String,
+    from: String,
+    parent_block_id: Option<String>,
+    index: i32,
+    no_header: bool,
+    verbose: bool,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let rows = read_csv_source(&from)?;
+    if verbose {
+        eprintln!("读取到 {} 行数据", rows.len());
+    }
+
+    let table_api = DocumentTableApi::new(api_client);
+    let result = table_api
+        .create(&document_id, parent_block_id.as_deref(), index, &rows, !no_header)
+        .await?;
+
+    let output = format_output(&result, output_format)?;
+    println!("{}", output);
+    Ok(())
+}
+
+pub async fn handle_table_append_rows(
+    api_client: ApiClient,
+    document_id: String,
+    table_block_id: String,
+    from: String,
+    verbose: bool,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let rows = read_csv_source(&from)?;
+    if verbose {
+        eprintln!("读取到 {} 行数据", rows.len());
+    }
+
+    let table_api = DocumentTableApi::new(api_client);
+    let result = table_api.append_rows(&document_id, &table_block_id, &rows).await?;
+
+    let output = format_output(&result, output_format)?;
+    println!("{}", output);
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_table_set_cell(
+    api_client: ApiClient,
+    document_id: String,
+    table_block_id: String,
+    row: usize,
+    column: usize,
+    value: String,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let table_api = DocumentTableApi::new(api_client);
+    let result = table_api
+        .set_cell(&document_id, &table_block_id, row, column, &value)
+        .await?;
+
+    let output = format_output(&result, output_format)?;
+    println!("{}", output);
+    Ok(())
+}
+
+/// 读取 CSV 文件，`-` 表示从标准输入读取
+fn read_csv_source(from: &str) -> Result<Vec<Vec<String>>> {
+    let content = if from == "-" {
+        let mut content = String::new();
+        std::io::stdin()
+            .read_to_string(&mut content)
+            .map_err(|e| LarkError::IoError(format!("读取标准输入失败: {}", e)))?;
+        content
+    } else {
+        FileReader::read_to_string(Path::new(from))?
+    };
+    parse_csv(&content)
+}
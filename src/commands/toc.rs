@@ -0,0 +1,23 @@
+use crate::api::{ApiClient, DocumentTocApi};
+use crate::config::Config;
+use crate::error::Result;
+use crate::output::OutputFormat;
+use crate::output::format_output;
+
+pub async fn handle_toc(
+    api_client: ApiClient,
+    document_id: String,
+    title: String,
+    max_level: usize,
+    index: i32,
+    base_url: Option<String>,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let base_url = Config::resolve_base_url(base_url)?;
+    let toc_api = DocumentTocApi::new(api_client);
+    let result = toc_api.generate(&document_id, &title, max_level, index, &base_url).await?;
+
+    let output = format_output(&result, output_format)?;
+    println!("{}", output);
+    Ok(())
+}
@@ -9,6 +9,8 @@ const ENV_FILE_NAME: &str = ".env";
 const ENV_APP_ID: &str = "APP_ID";
 /// 应用密钥环境变量名
 const ENV_APP_SECRET: &str = "APP_SECRET";
+/// 文档链接域名环境变量名（可选）
+const ENV_BASE_URL: &str = "LARK_BASE_URL";
 
 #[derive(Clone)]
 pub struct Config {
@@ -100,6 +102,35 @@ impl Config {
         Ok(Config { app_id, app_secret })
     }
 
+    /// 文档链接的域名，如 `https://example.feishu.cn`，来自 .env 中的 LARK_BASE_URL，未设置时为 None
+    pub fn base_url() -> Option<String> {
+        std::env::var(ENV_BASE_URL)
+            .ok()
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty())
+    }
+
+    /// 生成文档链接使用的域名：优先使用命令行指定的值，其次是 LARK_BASE_URL，两者都没有时报错
+    pub fn resolve_base_url(base_url: Option<String>) -> Result<String> {
+        let base_url = base_url
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty())
+            .or_else(Self::base_url)
+            .ok_or_else(|| {
+                LarkError::ConfigError(format!(
+                    "生成文档链接需要文档域名：请使用 --base-url 指定，或在 .env 中设置 {}",
+                    ENV_BASE_URL
+                ))
+            })?;
+        if !base_url.starts_with("http://") && !base_url.starts_with("https://") {
+            return Err(LarkError::ValidationError(format!(
+                "文档域名必须以 http:// 或 https:// 开头: {}",
+                base_url
+            )));
+        }
+        Ok(base_url)
+    }
+
     /// Get the path to the environment file
     #[allow(dead_code)]
     pub fn env_file_path() -> Result<PathBuf> {
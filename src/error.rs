@@ -11,6 +11,7 @@ pub enum LarkError {
     ParseError(String),
     HttpError(reqwest::Error),
     ValidationError(String),
+    DryRunError(String),
 }
 
 impl fmt::Display for LarkError {
@@ -26,6 +27,7 @@ impl fmt::Display for LarkError {
             LarkError::ParseError(msg) => write!(f, "解析错误: {}", msg),
             LarkError::HttpError(err) => write!(f, "HTTP错误: {}", err),
             LarkError::ValidationError(msg) => write!(f, "验证错误: {}", msg),
+            LarkError::DryRunError(msg) => write!(f, "dry-run 模式: {}", msg),
         }
     }
 }
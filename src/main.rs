@@ -11,6 +11,7 @@ mod utils;
 use config::Config;
 use auth::AuthManager;
 use api::ApiClient;
+use api::block_builder::BlockSpec;
 use output::OutputFormat;
 use error::Result;
 
@@ -37,6 +38,16 @@ struct Cli {
     #[arg(long, default_value = "json")]
     format: String,
 
+    /// 只输出请求计划，不发送任何写操作
+    ///
+    /// 规划所需的读取（如获取文档块、转换 Markdown、比较差异）仍会执行。
+    /// 以下命令输出各自的修改计划或预览后正常结束：add-content、add-block、batch-update-blocks、
+    /// delete-blocks、add-permission、create-document --template、import、sync、
+    /// edit-section、replace、table、copy-blocks、lint --fix、toc、attach、publish 以及 drive 的写操作。
+    /// 其余会修改数据的命令在发送第一个写请求前被拦截并报错。
+    #[arg(long, global = true)]
+    dry_run: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -94,7 +105,7 @@ enum Commands {
     },
     #[command(
         about = "创建云文档",
-        long_about = "创建一个新的云文档\n\n示例:\n  lark create-document\n  lark create-document --title \"My Document\"\n  lark create-document --folder-token foldcnz1abcdefg123456789 --title \"Team Doc\"\n\n参数说明:\n  - folder_token: 可选，指定创建文档的文件夹token\n  - title: 可选，文档标题，1-800字符\n\n返回结构体字段说明:\n  - document_id: 文档唯一标识符\n  - revision_id: 文档版本号\n  - title: 文档标题\n  - url: 文档URL地址"
+        long_about = "创建一个新的云文档，或根据模板创建文档\n\n示例:\n  lark create-document\n  lark create-document --title \"My Document\"\n  lark create-document --folder-token foldcnz1abcdefg123456789 --title \"Team Doc\"\n\n  # 复制模板文档并替换占位符\n  lark create-document --template doccnTemplate123 --var service=payment --var date=2024-06-01\n\n  # 使用本地 Markdown 模板和变量文件，并添加协作者\n  lark create-document --template ./incident.md --vars incident.yaml --collaborator alice@example.com:edit\n\n参数说明:\n  - folder_token: 可选，指定创建文档的文件夹token\n  - title: 可选，文档标题，1-800字符；使用模板时默认为模板标题\n  - template: 可选，模板文档ID或本地 Markdown 文件路径\n  - var: 模板变量 key=value，可重复\n  - vars: YAML/JSON 变量文件，可以包含列表；--var 优先\n  - collaborator: 创建后添加的协作者 email[:view|edit|full_access]，可重复\n\n模板语法:\n  - {{key}}: 替换为变量值，未定义的占位符保持不变\n  - {{#each key}} ... {{/each}}: 按列表变量的每一项重复其中的内容，项内以 {{this}} 或 {{this.字段}} 引用\n  - 模板文档中，循环标记需各自独占一个块；本地模板中 Front Matter 字段作为变量默认值\n\n返回结构体字段说明（未使用模板）:\n  - document_id: 文档唯一标识符\n  - revision_id: 文档版本号\n  - title: 文档标题\n  - url: 文档URL地址\n\n返回结构体字段说明（使用模板）:\n  - template: 模板\n  - title: 新文档标题\n  - dry_run: 是否只渲染模板\n  - document: 创建的文档（字段同上）\n  - block_count: 创建的块数\n  - skipped_blocks: 无法复制而被跳过的模板块（图片、附件、画板等）\n  - actions: 添加的协作者\n  - content: dry-run 时渲染后的 Markdown 正文（仅本地模板）"
     )]
     CreateDocument {
         /// 文件夹 token (可选)
@@ -103,6 +114,18 @@ enum Commands {
         /// 文档标题 (可选，1-800字符)
         #[arg(long)]
         title: Option<String>,
+        /// 模板文档 ID 或本地 Markdown 文件路径
+        #[arg(long)]
+        template: Option<String>,
+        /// 模板变量 key=value（可重复）
+        #[arg(long = "var")]
+        vars: Vec<String>,
+        /// 模板变量文件（YAML 或 JSON）
+        #[arg(long = "vars")]
+        vars_file: Option<String>,
+        /// 创建后添加的协作者 email[:perm]（可重复）
+        #[arg(long = "collaborator")]
+        collaborators: Vec<String>,
     },
     #[command(
         about = "添加高亮块到文档",
@@ -126,6 +149,65 @@ enum Commands {
         #[arg(long)]
         icon: Option<String>,
     },
+    #[command(
+        about = "添加任意类型的块到文档",
+        long_about = "按块类型在飞书文档中创建块，支持代码块、引用容器、分栏、待办、公式、分割线、内嵌网页等，也可以从 YAML 文件创建嵌套的块\n\n示例:\n  # 添加 Rust 代码块\n  lark add-block doccnz1abcdefg123456789 --type code --language rust --text \"fn main() {}\"\n\n  # 添加三栏分栏，宽度 20/30/50\n  lark add-block doccnz1abcdefg123456789 --type grid --columns 3 --width-ratios 20,30,50\n\n  # 添加自定义颜色的高亮块\n  lark add-block doccnz1abcdefg123456789 --type callout --text \"注意\" --emoji fire --background-color light-blue --border-color blue\n\n  # 从 YAML 文件创建嵌套的块\n  lark add-block doccnz1abcdefg123456789 --spec blocks.yaml\n\n参数说明:\n  - document_id: 文档ID\n  - type: 块类型（text、heading1-heading9、bullet、ordered、code、quote、quote-container、todo、callout、divider、grid、equation、iframe）\n  - text: 文本内容，公式块为公式内容\n  - parent_id: 父块ID，空字符串表示在根级别创建\n  - index: 插入位置索引，默认-1表示末尾\n  - language: 代码语言（code），如 rust、python、js\n  - wrap: 代码自动换行（code）\n  - done: 标记为已完成（todo）\n  - columns: 分栏数 2-5（grid），默认2\n  - width-ratios: 各栏宽度百分比，逗号分隔，总和为100（grid）\n  - emoji: 图标（callout）\n  - callout-type: 预设样式 info、warning、error、success（callout），默认info\n  - background-color: 背景色，如 light-blue、medium-green（callout）\n  - border-color: 边框色，如 blue、green（callout）\n  - text-color: 文字颜色（callout）\n  - url: 网页地址（iframe）\n  - iframe-type: 网页来源，如 figma、bilibili、codepen（iframe）\n  - spec: YAML/JSON 块描述文件，单个块或块列表，字段与以上选项相同（使用下划线），children 描述子块；与 --type 不能同时使用\n\n返回结构体字段说明:\n  - block_id_relations: 块ID映射关系数组\n    * block_id: 创建的块ID\n    * temporary_block_id: 临时块ID\n  - children: 创建的块信息数组\n  - document_revision_id: 文档版本号"
+    )]
+    AddBlock {
+        /// 文档 ID
+        document_id: String,
+        /// 块类型，如 code、grid、todo、callout
+        #[arg(long = "type")]
+        block_type: Option<String>,
+        /// 文本内容（公式块为公式内容）
+        #[arg(long)]
+        text: Option<String>,
+        /// 父块 ID（可选，空字符串表示在根级别创建）
+        #[arg(long, default_value = "")]
+        parent_id: Option<String>,
+        /// 插入位置索引（默认: -1 表示末尾）
+        #[arg(long, default_value = "-1")]
+        index: Option<i32>,
+        /// 代码语言（code）
+        #[arg(long)]
+        language: Option<String>,
+        /// 代码自动换行（code）
+        #[arg(long)]
+        wrap: bool,
+        /// 标记为已完成（todo）
+        #[arg(long)]
+        done: bool,
+        /// 分栏数（grid）
+        #[arg(long)]
+        columns: Option<usize>,
+        /// 各栏宽度百分比，逗号分隔（grid）
+        #[arg(long, value_delimiter = ',')]
+        width_ratios: Option<Vec<i32>>,
+        /// 图标（callout）
+        #[arg(long)]
+        emoji: Option<String>,
+        /// 预设样式 info、warning、error、success（callout）
+        #[arg(long)]
+        callout_type: Option<String>,
+        /// 背景色（callout）
+        #[arg(long)]
+        background_color: Option<String>,
+        /// 边框色（callout）
+        #[arg(long)]
+        border_color: Option<String>,
+        /// 文字颜色（callout）
+        #[arg(long)]
+        text_color: Option<String>,
+        /// 网页地址（iframe）
+        #[arg(long)]
+        url: Option<String>,
+        /// 网页来源类型（iframe）
+        #[arg(long)]
+        iframe_type: Option<String>,
+        /// YAML/JSON 块描述文件
+        #[arg(long)]
+        spec: Option<String>,
+    },
     #[command(
         about = "添加画板到文档",
         long_about = "在飞书文档中添加画板（绘图画布）\n\n示例:\n  lark add-board doccnz1abcdefg123456789\n  lark add-board doccnz1abcdefg123456789 --parent-id block_123 --index 0\n\n参数说明:\n  - document_id: 文档ID\n  - parent_id: 父块ID，空字符串表示在根级别创建\n  - index: 插入位置索引，默认-1表示末尾\n\n返回结构体字段说明:\n  - block_id_relations: 块ID映射关系数组\n    * block_id: 创建的块ID\n    * temporary_block_id: 临时块ID\n  - children: 子块信息数组\n    * block_id: 块ID\n    * block_type: 块类型\n    * board: 画板信息\n      - token: 画板唯一标识符（画板ID）\n      - align: 对齐方式\n    * parent_id: 父块ID\n  - client_token: 客户端令牌\n  - document_revision_id: 文档版本号"
@@ -152,7 +234,7 @@ enum Commands {
     },
     #[command(
         about = "添加内容到文档（支持从文件、目录或直接内容添加）",
-        long_about = "将内容添加到飞书文档中，支持多种导入方式\n\n示例:\n  # 从文件导入\n  lark add-content doccnz1abcdefg123456789 ./content.md\n\n  # 从目录批量导入\n  lark add-content doccnz1abcdefg123456789 ./docs --source-type dir --recursive\n\n  # 直接添加内容\n  lark add-content doccnz1abcdefg123456789 \"# 标题\\n\\n内容\" --source-type content\n\n  # 指定插入位置和父块\n  lark add-content doccnz1abcdefg123456789 ./content.md --block-id block_123 --index 0\n\n字段说明:\n  - document_id: 目标文档ID\n  - source: 导入源，根据source_type不同含义不同\n  - source_type: 源类型\n    * file: 单个文件路径\n    * dir: 目录路径\n    * content: 直接内容字符串\n  - content_type: 内容格式，支持markdown和html\n  - block_id: 父块ID，空字符串表示文档根级别\n  - index: 插入位置，-1表示末尾，0表示开头\n  - recursive: 是否递归处理子目录\n  - pattern: 文件匹配模式，如\"*.md\", \"*.txt\"\n  - batch_size: 并发数，建议3-5个\n  - skip_existing: 跳过已存在的文件\n\n返回结构体字段说明:\n  - block_id_relations: 块ID映射关系数组\n    * block_id: 实际创建的块ID\n    * temporary_block_id: 临时块ID（用于关联）\n  - document_revision_id: 文档版本号\n  - client_token: 客户端令牌（可选）\n  - children: 子块信息数组\n\n批量导入结果说明:\n  - success_count: 成功导入的文件数\n  - failure_count: 失败的文件数\n  - skipped_count: 跳过的文件数\n  - results: 详细结果列表\n    * file_path: 文件路径\n    * success: 是否成功\n    * error: 错误信息（如果失败）\n    * block_ids: 创建的块ID列表（如果成功）"
+        long_about = "将内容添加到飞书文档中，支持多种导入方式\n\n示例:\n  # 从文件导入\n  lark add-content doccnz1abcdefg123456789 ./content.md\n\n  # 从目录批量导入\n  lark add-content doccnz1abcdefg123456789 ./docs --source-type dir --recursive\n\n  # 直接添加内容\n  lark add-content doccnz1abcdefg123456789 \"# 标题\\n\\n内容\" --source-type content\n\n  # 指定插入位置和父块\n  lark add-content doccnz1abcdefg123456789 ./content.md --block-id block_123 --index 0\n\n字段说明:\n  - document_id: 目标文档ID\n  - source: 导入源，根据source_type不同含义不同\n  - source_type: 源类型\n    * file: 单个文件路径\n    * dir: 目录路径\n    * content: 直接内容字符串\n  - content_type: 内容格式，支持markdown和html\n  - block_id: 父块ID，空字符串表示文档根级别\n  - index: 插入位置，-1表示末尾，0表示开头\n  - recursive: 是否递归处理子目录\n  - pattern: 文件匹配模式，如\"*.md\", \"*.txt\"\n  - batch_size: 并发数，建议3-5个\n  - skip_existing: 跳过已导入且内容未变化的文件（目录模式）\n  - manifest: 导入清单路径，默认为目录下的 .lark-import.json\n    记录每个文件的内容哈希、创建的一级块ID和文档版本号，中断后重新运行可从断点继续\n  - replace_changed: 内容已变化的文件先删除上次导入的块，再在原位置重新导入；未指定时跳过内容已变化的文件\n  - render_diagrams: 将 mermaid / plantuml 代码块渲染为画板，自动识别图表类型，导入画板失败时保留为代码块\n  - diagram_source: 画板后源码的保留方式\n    * none: 不保留（默认）\n    * code: 在画板后保留原代码块\n    * caption: 在画板后以行内代码文本保留源码\n    * collapsed: 在画板后保留折叠的代码块（放在默认折叠的列表项「图表源码」下）\n\n返回结构体字段说明:\n  - block_id_relations: 块ID映射关系数组\n    * block_id: 实际创建的块ID\n    * temporary_block_id: 临时块ID（用于关联）\n  - document_revision_id: 文档版本号\n  - client_token: 客户端令牌（可选）\n  - children: 子块信息数组\n\n批量导入结果说明:\n  - success_count: 成功导入的文件数\n  - failure_count: 失败的文件数\n  - skipped_count: 跳过的文件数\n  - results: 详细结果列表\n    * file_path: 文件路径\n    * success: 是否成功\n    * skipped: 是否跳过（内容未变化，或内容已变化但未指定 --replace-changed）\n    * changed: 是否因内容已变化且未指定 --replace-changed 而跳过\n    * error: 错误信息（如果失败）\n    * block_ids: 创建的一级块ID列表（如果成功）"
     )]
     AddContent {
         /// 文档 ID
@@ -180,13 +262,249 @@ enum Commands {
         /// 批处理的并发数（默认: 3）
         #[arg(long, default_value = "3")]
         batch_size: usize,
-        /// 跳过已存在的文件
+        /// 跳过已导入且内容未变化的文件（基于导入清单）
         #[arg(long)]
         skip_existing: bool,
+        /// 导入清单文件路径（默认: <目录>/.lark-import.json）
+        #[arg(long)]
+        manifest: Option<String>,
+        /// 内容已变化的文件先删除上次导入的块，再在原位置重新导入
+        #[arg(long)]
+        replace_changed: bool,
+        /// 将 mermaid / plantuml 代码块渲染为画板
+        #[arg(long)]
+        render_diagrams: bool,
+        /// 渲染为画板后源码的保留方式：none、code（保留代码块）、caption（行内代码说明）、collapsed（折叠的代码块）
+        #[arg(long, default_value = "none")]
+        diagram_source: String,
+    },
+    #[command(
+        about = "将目录导入为文档层级（每个文件一篇文档）",
+        long_about = "将本地目录导入为飞书文档层级：每个文件创建一篇独立文档，目录结构对应为云空间文件夹或知识库节点\n\n示例:\n  # 导入到云空间文件夹\n  lark import-tree ./docs --folder-token fldcnz1abcdefg123456789 --recursive\n\n  # 导入到知识空间，挂在指定节点下\n  lark import-tree ./docs --wiki-space 7034502641455497244 --parent-node wikcnz1abcdefg123456789 --recursive\n\n参数说明:\n  - source: 本地目录路径\n  - folder_token: 云空间目标文件夹token，子目录创建为同名文件夹\n  - wiki_space: 知识空间ID，子目录创建为同名文档节点\n  - parent_node: 知识库父节点token（可选，默认创建为一级节点）\n  - recursive: 是否递归处理子目录\n  - pattern: 文件匹配模式，默认\"*.md\"\n  - base-url: 文档链接的域名，如 https://example.feishu.cn；不指定时使用 .env 中的 LARK_BASE_URL，两者都没有时报错\n\n导入规则:\n  - 文档标题使用第一个一级标题（# 标题），该标题行不再重复导入正文；没有一级标题时使用文件名\n  - 文件之间的相对链接（如 [安装](../guide/install.md)）会改写为对应飞书文档的链接\n  - 目录创建失败会中止导入；单个文件失败不影响其他文件\n\n返回结构体字段说明:\n  - success_count: 成功导入的文件数\n  - failure_count: 失败的文件数\n  - folders: 创建的目录节点数组\n    * relative_path: 相对目录路径\n    * token: 文件夹token或知识库节点token\n    * url: 访问链接\n  - documents: 文件导入结果数组\n    * relative_path: 相对文件路径\n    * title: 文档标题\n    * document_id: 文档ID\n    * node_token: 知识库节点token（仅知识库模式）\n    * url: 文档链接\n    * success: 是否成功\n    * error: 错误信息（如果失败）"
+    )]
+    ImportTree {
+        /// 本地目录路径
+        source: String,
+        /// 云空间目标文件夹 token
+        #[arg(long)]
+        folder_token: Option<String>,
+        /// 知识空间 ID
+        #[arg(long)]
+        wiki_space: Option<String>,
+        /// 知识库父节点 token（可选）
+        #[arg(long)]
+        parent_node: Option<String>,
+        /// 是否递归处理子目录
+        #[arg(long)]
+        recursive: bool,
+        /// 文件匹配模式（默认: "*.md"）
+        #[arg(long, default_value = "*.md")]
+        pattern: String,
+        /// 文档链接的域名，如 https://example.feishu.cn（默认: .env 中的 LARK_BASE_URL）
+        #[arg(long)]
+        base_url: Option<String>,
+    },
+    #[command(
+        about = "将本地 Markdown 文件单向同步到文档",
+        long_about = "将本地文件单向同步到飞书文档正文，只修改有差异的块，保留未变化块的块ID、评论和行内编辑\n\n示例:\n  lark sync doccnz1abcdefg123456789 ./design.md\n\n  # 只查看差异，不修改文档\n  lark --dry-run sync doccnz1abcdefg123456789 ./design.md\n\n参数说明:\n  - document_id: 文档ID\n  - file: 本地文件路径（.md 或 .html）\n\n同步规则:\n  - 本地文件按 add-content 的方式转换为块（支持 Front Matter），与文档当前的一级块逐块比较\n  - 内容完全相同的块保持不变；同类型且内容相似的块只更新文本；其余块删除后在原位置重新创建\n  - 以读取时的文档版本作为写操作的基准版本，文档在同步期间被他人修改时写操作失败\n\n返回结构体字段说明:\n  - document_id: 文档ID\n  - base_revision_id: 计算差异时的文档版本\n  - document_revision_id: 同步完成后的文档版本\n  - dry_run: 是否只计算差异\n  - unchanged: 保持不变的一级块数\n  - updated: 原地更新文本的块数\n  - deleted: 删除的一级块数\n  - created: 新建的一级块数\n  - diff: 具体修改\n    * updates: 原地更新的块（block_id、local_block_id）\n    * hunks: 删除和插入（index、delete_count、insert）"
+    )]
+    Sync {
+        /// 文档 ID
+        document_id: String,
+        /// 本地文件路径
+        file: String,
+    },
+    #[command(
+        about = "比较文档的两个版本",
+        long_about = "比较同一文档两个版本的块树，列出新增、删除、移动和修改的块，或输出 Markdown 渲染结果的统一格式差异\n\n示例:\n  # 结构差异（与文档当前版本比较）\n  lark diff doccnz1abcdefg123456789 --from 12\n\n  # 两个历史版本之间的结构差异，JSON 格式\n  lark --format json diff doccnz1abcdefg123456789 --from 12 --to 20\n\n  # 统一格式差异，保留 5 行上下文\n  lark diff doccnz1abcdefg123456789 --from 12 --unified --context 5\n\n参数说明:\n  - document_id: 文档ID\n  - from: 起始版本号\n  - to: 目标版本号，默认 latest（文档当前版本）\n  - unified: 输出两个版本渲染为 Markdown 后的统一格式差异\n  - context: 统一格式差异中每段修改前后的上下文行数，默认 3\n\n比较规则:\n  - 块ID在版本之间保持不变，只在一个版本中出现的块为新增或删除\n  - 父块变化，或与兄弟块的相对顺序变化的块为移动\n  - 去掉子块和默认值后内容或属性不同的块为修改\n\n返回结构体字段说明:\n  - from_revision: 起始版本\n  - to_revision: 目标版本\n  - added / removed: 新增 / 删除的块\n    * block_id: 块ID\n    * block_type: 块类型\n    * parent_id: 父块ID\n    * index: 在父块中的位置\n    * text: 块的 Markdown 表示（不含子块）\n  - moved: 移动的块\n    * block_id、block_type、text\n    * from_parent_id、from_index: 原父块和位置\n    * to_parent_id、to_index: 新父块和位置\n  - modified: 修改的块\n    * block_id、block_type\n    * before / after: 修改前后的 Markdown 表示"
+    )]
+    Diff {
+        /// 文档 ID
+        document_id: String,
+        /// 起始版本号
+        #[arg(long)]
+        from: i32,
+        /// 目标版本号或 latest
+        #[arg(long, default_value = "latest")]
+        to: String,
+        /// 输出 Markdown 渲染结果的统一格式差异
+        #[arg(long)]
+        unified: bool,
+        /// 统一格式差异的上下文行数
+        #[arg(long, default_value = "3")]
+        context: usize,
+    },
+    #[command(
+        about = "替换或追加指定标题下的章节内容",
+        long_about = "按标题路径定位文档中的章节，替换、追加或前置插入内容，文档其他部分保持不变\n\n示例:\n  # 用文件内容替换“Status”下“Metrics”章节的内容\n  lark edit-section doccnz1abcdefg123456789 \"Status / Metrics\" ./metrics.md\n\n  # 追加到章节末尾\n  lark edit-section doccnz1abcdefg123456789 \"## Metrics\" \"- 新增指标\" --source-type content --mode append\n\n参数说明:\n  - document_id: 文档ID\n  - heading_path: 标题路径，以 / 分隔各级标题；可以用 # 前缀限定级别，如 \"## Metrics\"\n  - source: 文件路径或直接内容\n  - source_type: 源类型，file（文件，默认）或 content（直接内容）\n  - content_type: 直接内容的类型，markdown（默认）或 html；文件按扩展名识别\n  - mode: 写入方式，replace（替换，默认）、append（追加到章节末尾）、prepend（插入到标题之后）\n\n章节范围:\n  - 章节从标题之后开始，到下一个同级或更高级标题之前结束，标题本身始终保留\n  - 路径的每一级在上一级章节内查找；同一范围内有多个同名标题时报错\n\n返回结构体字段说明:\n  - document_id: 文档ID\n  - heading_path: 标题路径\n  - mode: 写入方式\n  - section: 找到的章节\n    * heading_block_id: 标题块ID\n    * heading_level: 标题级别\n    * parent_id: 父块ID\n    * start / end: 章节内容在父块中的位置范围 [start, end)\n  - deleted_block_ids: 删除的块ID（仅 replace）\n  - index: 新内容插入的位置\n  - created_block_ids: 创建的一级块ID\n  - document_revision_id: 写入完成后的文档版本"
+    )]
+    EditSection {
+        /// 文档 ID
+        document_id: String,
+        /// 标题路径，如 "Status / Metrics"
+        heading_path: String,
+        /// 文件路径或直接内容
+        source: String,
+        /// 源类型：file（文件）、content（内容）
+        #[arg(long, default_value = "file")]
+        source_type: String,
+        /// 内容类型：markdown 或 html
+        #[arg(long, default_value = "markdown")]
+        content_type: String,
+        /// 写入方式：replace、append、prepend
+        #[arg(long, default_value = "replace")]
+        mode: String,
+    },
+    #[command(
+        about = "在文档中查找并替换文本",
+        long_about = "在文档所有含文本的块中查找并替换文本，包括标题、列表、待办、引用、高亮块和表格单元格，替换时保留文字样式、链接和提及\n\n示例:\n  # 预览所有匹配及其上下文\n  lark replace doccnz1abcdefg123456789 \"旧产品名\" \"新产品名\" --preview\n\n  # 字面量替换\n  lark replace doccnz1abcdefg123456789 \"旧产品名\" \"新产品名\"\n\n  # 正则替换，替换内容中可以使用捕获组\n  lark replace doccnz1abcdefg123456789 \"v(\\d+)\\.0\" \"v$1.1\" --regex\n\n参数说明:\n  - document_id: 文档ID\n  - pattern: 查找内容，默认按字面量匹配\n  - replacement: 替换内容；正则模式下 $1、${name} 引用捕获组\n  - regex: 按正则表达式匹配\n  - ignore_case: 忽略大小写\n  - preview: 只列出匹配，不修改文档（--dry-run 时同样只预览）\n\n替换规则:\n  - 相邻的文本片段拼接后整体匹配，跨越多种样式的内容也能匹配\n  - 替换后的文本使用匹配开始处的样式，提及、公式等非文本内容保持不变\n  - 所有修改以查找时的文档版本为基准，分批通过批量更新块接口写入\n\n返回结构体字段说明:\n  - document_id: 文档ID\n  - base_revision_id: 查找时的文档版本\n  - document_revision_id: 替换完成后的文档版本\n  - preview: 是否只预览\n  - match_count: 匹配总数\n  - block_count: 包含匹配的块数\n  - blocks: 每个块中的匹配\n    * block_id: 块ID\n    * block_type: 块类型\n    * matches: 匹配列表（matched、replacement、context）"
+    )]
+    Replace {
+        /// 文档 ID
+        document_id: String,
+        /// 查找内容
+        pattern: String,
+        /// 替换内容
+        replacement: String,
+        /// 按正则表达式匹配
+        #[arg(long)]
+        regex: bool,
+        /// 忽略大小写
+        #[arg(long)]
+        ignore_case: bool,
+        /// 只列出匹配，不修改文档
+        #[arg(long)]
+        preview: bool,
+    },
+    #[command(
+        about = "表格操作：导出、创建、追加行和修改单元格",
+        long_about = "以 CSV 为主的文档表格操作，适合把仪表盘等表格数据推送到文档中\n\n示例:\n  # 导出表格为 CSV / Markdown / JSON\n  lark table export doccnz1abcdefg123456789 doxcnTable123\n  lark table export doccnz1abcdefg123456789 doxcnTable123 --to markdown\n  lark table export doccnz1abcdefg123456789 doxcnTable123 --to json --output metrics.json\n\n  # 从 CSV 文件创建表格（第一行为表头）\n  lark table create doccnz1abcdefg123456789 --from data.csv\n\n  # 在表格末尾追加行，- 表示从标准输入读取\n  cat new_rows.csv | lark table append-rows doccnz1abcdefg123456789 doxcnTable123 --from -\n\n  # 修改第 2 行第 3 列的单元格（从 0 开始计数）\n  lark table set-cell doccnz1abcdefg123456789 doxcnTable123 2 3 \"99.9%\"\n\n子命令:\n  - export: 导出表格内容；单元格中的多个块以换行连接\n  - create: 从 CSV 创建表格；超出单次请求上限的行在创建后追加\n  - append-rows: 在表格末尾追加行，数据列数不能超过表格列数\n  - set-cell: 修改单个单元格的文本\n\n返回结构体字段说明（create、append-rows、set-cell）:\n  - document_id: 文档ID\n  - table_block_id: 表格块ID\n  - dry_run: 是否只规划、未修改文档\n  - row_count: 写入的行数\n  - column_count: 表格列数\n  - document_revision_id: 写入后的文档版本"
+    )]
+    Table {
+        #[command(subcommand)]
+        command: TableCommands,
+    },
+    #[command(
+        about = "在文档之间复制或移动块",
+        long_about = "将源文档中的块（连同子块）复制到目标文档，图片和附件会重新上传到目标文档\n\n示例:\n  # 复制单个块到目标文档末尾\n  lark copy-blocks doccnSource123 doxcnBlock123 doccnTarget456\n\n  # 复制某个二级标题之后的所有同级块，插入到目标文档开头\n  lark copy-blocks doccnSource123 'heading2:contains(\"接口设计\") ~ *' doccnTarget456 --index 0\n\n  # 移动到目标文档的某个父块下，复制成功后删除源块\n  lark copy-blocks doccnSource123 'callout' doccnTarget456 --parent doxcnParent789 --move\n\n参数说明:\n  - source_document_id: 源文档ID\n  - source: 源块ID或块选择器（语法见 delete-blocks --select）；选中块的子孙块随之复制\n  - document_id: 目标文档ID，可以与源文档相同\n  - parent: 目标父块ID，默认为目标文档根块\n  - index: 插入位置，默认-1表示末尾\n  - move: 复制全部成功后删除源块\n  - force: 移动时丢弃无法复制的块；未指定时，有无法复制的块则拒绝移动并列出这些块\n\n说明:\n  - 评论等只读字段不会复制\n  - 图片和附件先以空块创建，再下载原素材上传到新块\n  - 画板、电子表格、多维表格等内容保存在独立资源中的块会被跳过；--move 时这些块会随源块删除，因此需要 --force 确认\n\n返回结构体字段说明:\n  - source_document_id: 源文档ID\n  - source: 源块ID或选择器\n  - document_id: 目标文档ID\n  - parent_block_id: 目标父块ID\n  - dry_run: 是否只规划、未修改文档\n  - moved: 是否删除了源块\n  - source_block_ids: 复制的一级源块ID\n  - block_ids: 新建的一级块ID\n  - block_count: 创建的块总数\n  - media: 重新上传素材的图片和附件块\n  - skipped_blocks: 被跳过的块（block_id、block_type）\n  - document_revision_id: 目标文档写入后的版本\n  - source_document_revision_id: 源文档删除后的版本（仅 --move）"
+    )]
+    CopyBlocks {
+        /// 源文档 ID
+        source_document_id: String,
+        /// 源块 ID 或块选择器
+        source: String,
+        /// 目标文档 ID
+        document_id: String,
+        /// 目标父块 ID（默认: 文档根块）
+        #[arg(long = "parent")]
+        parent_block_id: Option<String>,
+        /// 插入位置（默认: -1 表示末尾）
+        #[arg(long, default_value = "-1")]
+        index: i32,
+        /// 复制成功后删除源块
+        #[arg(long = "move")]
+        move_blocks: bool,
+        /// 移动时丢弃无法复制的块（画板、电子表格等）
+        #[arg(long, requires = "move_blocks")]
+        force: bool,
+    },
+    #[command(
+        about = "检查文档结构和链接",
+        long_about = "检查文档的标题结构、空内容、提及和链接，适合在发布前或 CI 中运行\n\n示例:\n  lark lint doccnz1abcdefg123456789\n\n  # 只检查链接和提及，输出 JSON 供 CI 解析\n  lark lint doccnz1abcdefg123456789 --rule broken-link,unresolved-mention --format json\n\n  # 自动修复空标题、多余的空段落和标题首尾空白\n  lark lint doccnz1abcdefg123456789 --fix\n\n  # 使用配置文件，只有错误时才以非零状态退出\n  lark lint doccnz1abcdefg123456789 --config lint.toml --fail-on error\n\n规则:\n  - heading-level-jump: 标题跳级，如 H1 后直接出现 H3（错误）\n  - empty-heading: 空标题（错误，可修复：删除）\n  - empty-paragraph: 连续的空段落，或位于开头、结尾的空段落（警告，可修复：删除）\n  - duplicate-heading: 同级标题文本重复（警告）\n  - heading-whitespace: 标题首尾有空白字符（警告，可修复：去掉空白）\n  - unresolved-mention: 无法解析的 @用户 或 @文档（错误）\n  - broken-link: 指向无法读取的飞书文档的链接（错误）\n  - image-caption: 图片缺少说明文字（警告）\n\n配置文件 (TOML):\n  rules = [\"heading-level-jump\", \"broken-link\"]   # 只启用这些规则，默认全部\n  skip = [\"image-caption\"]                        # 跳过的规则\n\n  [severity]\n  empty-paragraph = \"error\"                       # 覆盖严重程度\n\n参数说明:\n  - document_id: 文档ID\n  - config: 检查配置文件\n  - rule: 只启用这些规则，可重复或逗号分隔，覆盖配置文件中的 rules\n  - skip: 跳过的规则，可重复或逗号分隔\n  - offline: 跳过需要调用接口的规则（unresolved-mention、broken-link）\n  - fix: 自动修复可安全修复的问题\n  - fail-on: 存在未修复的该级别及以上问题时以非零状态退出（warning/error/never，默认 error）\n\n返回结构体字段说明:\n  - document_id: 文档ID\n  - document_revision_id: 检查时（修复后）的文档版本\n  - rules: 启用的规则\n  - dry_run: 是否只规划修复\n  - error_count: 错误数\n  - warning_count: 警告数\n  - fixed_count: 已修复（dry-run 时为可修复）的问题数\n  - findings: 发现的问题\n    * rule: 规则\n    * severity: 严重程度（error/warning）\n    * block_id: 问题所在的块ID\n    * message: 问题说明\n    * fix: 自动修复方式（delete 或 update_elements）\n    * fixed: 是否已修复\n  - unchecked_references: 因网络错误、限流或服务端异常重试后仍未能检查的引用（rule、block_id、label、error），不计入问题"
+    )]
+    Lint {
+        /// 文档 ID
+        document_id: String,
+        /// 检查配置文件（TOML）
+        #[arg(long)]
+        config: Option<String>,
+        /// 只启用这些规则（可重复或逗号分隔）
+        #[arg(long = "rule")]
+        rules: Vec<String>,
+        /// 跳过的规则（可重复或逗号分隔）
+        #[arg(long)]
+        skip: Vec<String>,
+        /// 跳过需要调用接口的规则
+        #[arg(long)]
+        offline: bool,
+        /// 自动修复可安全修复的问题
+        #[arg(long)]
+        fix: bool,
+        /// 存在该级别及以上的问题时以非零状态退出 (warning/error/never)
+        #[arg(long, default_value = "error")]
+        fail_on: String,
+    },
+    #[command(
+        about = "生成或更新文档目录",
+        long_about = "根据文档标题生成带链接的嵌套目录，放在一个高亮块中；再次运行时原地更新已有目录\n\n示例:\n  # 在文档开头插入目录（链接域名来自 .env 中的 LARK_BASE_URL）\n  lark toc doccnz1abcdefg123456789\n\n  # 只包含 H1-H2，使用企业域名生成链接\n  lark toc doccnz1abcdefg123456789 --max-level 2 --base-url https://example.feishu.cn\n\n  # 使用英文标题作为标记\n  lark toc doccnz1abcdefg123456789 --title \"Contents\"\n\n参数说明:\n  - document_id: 文档ID\n  - title: 目录标题，默认“目录”；第一个子块为该文本的高亮块被视为已有目录\n  - max-level: 包含的最深标题级别，默认3\n  - index: 新建目录时在文档中的插入位置，默认0（开头）；更新已有目录时忽略\n  - base-url: 文档链接的域名，如 https://example.feishu.cn；不指定时使用 .env 中的 LARK_BASE_URL，两者都没有时报错\n\n说明:\n  - 目录中的每一项链接到对应标题块\n  - 更新时保留高亮块及其标题文本，只替换其后的列表\n\n返回结构体字段说明:\n  - document_id: 文档ID\n  - callout_block_id: 目录所在的高亮块ID\n  - refreshed: 是否更新了已有目录（否则为新建）\n  - dry_run: 是否只规划、未修改文档\n  - heading_count: 目录中的标题数\n  - document_revision_id: 写入后的文档版本"
+    )]
+    Toc {
+        /// 文档 ID
+        document_id: String,
+        /// 目录标题，同时用于查找已有目录
+        #[arg(long, default_value = "目录")]
+        title: String,
+        /// 包含的最深标题级别
+        #[arg(long, default_value = "3")]
+        max_level: usize,
+        /// 新建目录时的插入位置（默认: 0 表示开头）
+        #[arg(long, default_value = "0")]
+        index: i32,
+        /// 文档链接的域名，如 https://example.feishu.cn（默认: .env 中的 LARK_BASE_URL）
+        #[arg(long)]
+        base_url: Option<String>,
+    },
+    #[command(
+        about = "将本地图片和文件插入文档",
+        long_about = "将本地文件插入文档：图片插入为图片块，其他文件插入为附件块，自动完成创建空块、上传素材和替换素材\n\n示例:\n  # 在文档末尾插入截图\n  lark attach doccnz1abcdefg123456789 ./screenshot.png\n\n  # 在某个父块开头依次插入多个文件\n  lark attach doccnz1abcdefg123456789 ./arch.png ./spec.pdf --parent doxcnParent789 --index 0\n\n参数说明:\n  - document_id: 文档ID\n  - paths: 一个或多个本地文件路径，按顺序插入\n  - parent: 父块ID，默认为文档根块\n  - index: 插入位置，默认-1表示末尾\n\n说明:\n  - 按文件头和扩展名识别 MIME 类型，PNG、JPEG、GIF、BMP、WebP、TIFF、HEIC 插入为图片，其他为附件\n  - 图片宽高从本地文件读取（支持 PNG、JPEG、GIF、BMP、WebP）\n  - 单个文件不超过 20MB；所有文件先在本地检查，任何文件不可用时不修改文档\n\n返回结构体字段说明:\n  - document_id: 文档ID\n  - parent_block_id: 父块ID\n  - dry_run: 是否只规划、未修改文档\n  - files: 插入的文件\n    * path: 本地路径\n    * file_name: 文件名\n    * kind: image（图片块）或 file（附件块）\n    * mime_type: MIME 类型\n    * size: 文件大小（字节）\n    * width / height: 图片宽高\n    * block_id: 创建的块ID\n    * file_token: 上传后的素材 token\n    * error: 上传或替换失败的原因（失败文件的空块已删除）\n  - failure_count: 插入失败的文件数\n  - document_revision_id: 写入后的文档版本"
+    )]
+    Attach {
+        /// 文档 ID
+        document_id: String,
+        /// 本地文件路径
+        #[arg(required = true)]
+        paths: Vec<std::path::PathBuf>,
+        /// 父块 ID（默认: 文档根块）
+        #[arg(long = "parent")]
+        parent_block_id: Option<String>,
+        /// 插入位置（默认: -1 表示末尾）
+        #[arg(long, default_value = "-1")]
+        index: i32,
+    },
+    #[command(
+        about = "云文档评论：列出、添加、回复、解决和导出",
+        long_about = "管理云文档的评论，适合在评审后汇总反馈或批量处理评论\n\n示例:\n  # 列出文档的全部评论（含全部回复）\n  lark comments list doccnz1abcdefg123456789\n\n  # 只列出未解决的评论\n  lark comments list doccnz1abcdefg123456789 --unsolved\n\n  # 添加全文评论、回复评论\n  lark comments add doccnz1abcdefg123456789 \"请补充接口说明\"\n  lark comments reply doccnz1abcdefg123456789 6916106822734512356 \"已补充\"\n\n  # 解决或重新打开评论\n  lark comments resolve doccnz1abcdefg123456789 6916106822734512356\n  lark comments unresolve doccnz1abcdefg123456789 6916106822734512356\n\n  # 导出评论为 Markdown 或 JSON\n  lark comments export doccnz1abcdefg123456789 --output review.md\n  lark comments export shtcnAbCdEf --type sheet --to json\n\n子命令:\n  - list: 列出评论，自动翻页并补齐每条评论的全部回复\n  - add: 添加全文评论\n  - reply: 回复评论\n  - resolve / unresolve: 解决或重新打开评论\n  - export: 导出评论，包含引用的文档内容，评论者名字通过通讯录查询\n\n说明:\n  - --type 为文档类型：doc、docx（默认）、sheet、file、slides\n  - 回复列表的第一条为评论本身的内容\n\n返回结构体字段说明（list）:\n  - comment_id: 评论ID\n  - user_id: 评论者 open_id\n  - create_time / update_time: 创建和更新时间（秒级时间戳）\n  - is_solved: 是否已解决\n  - solved_time / solver_user_id: 解决时间和解决者\n  - is_whole: 是否为全文评论\n  - quote: 局部评论引用的文档内容\n  - reply_list.replies: 回复列表（reply_id、user_id、create_time、content.elements）"
+    )]
+    Comments {
+        #[command(subcommand)]
+        command: CommentsCommands,
+    },
+    #[command(
+        about = "云空间文件管理：列出、新建文件夹、移动、复制、删除和查看元数据",
+        long_about = "浏览和管理云空间中的文件夹和文件，所有参数都可以是 token 或文档链接\n\n示例:\n  # 以目录树列出根文件夹，只显示新版文档和电子表格\n  lark drive ls --recursive --type docx,sheet\n\n  # 列出某个文件夹的两层内容\n  lark drive ls https://example.feishu.cn/drive/folder/fldcnAbCdEf --depth 2\n\n  # 新建文件夹\n  lark drive mkdir 周报 --parent fldcnAbCdEf\n\n  # 移动、复制文件\n  lark drive mv https://example.feishu.cn/docx/doxcnAbCdEf fldcnTarget\n  lark drive cp https://example.feishu.cn/sheets/shtcnAbCdEf fldcnTarget --name \"数据备份\"\n\n  # 删除文件（需要确认，--yes 跳过确认）\n  lark drive rm https://example.feishu.cn/docx/doxcnAbCdEf\n\n  # 批量查看元数据\n  lark drive meta https://example.feishu.cn/docx/doxcnAbCdEf https://example.feishu.cn/wiki/wikcnAbCdEf\n\n子命令:\n  - ls: 列出文件夹内容，自动翻页，可递归显示目录树并按类型过滤\n  - mkdir: 新建文件夹，默认在根文件夹下\n  - mv: 移动文件或文件夹\n  - cp: 复制文件（不支持文件夹）\n  - rm: 删除文件或文件夹（移到回收站），执行前确认\n  - meta: 批量获取标题、所有者、修改时间和链接\n  - upload: 上传本地文件，大文件分片并发上传并支持断点续传\n\n说明:\n  - 文件类型从链接路径判断（drive/folder、docx、docs、sheets、base、mindnotes、file、slides）；知识库链接解析为节点对应的文档\n  - 纯 token 只能按旧版前缀判断类型，无法判断时需要用 --type 指定\n  - 文件类型：doc、docx、sheet、bitable、mindnote、file、slides、folder、shortcut"
+    )]
+    Drive {
+        #[command(subcommand)]
+        command: DriveCommands,
+    },
+    #[command(
+        about = "按项目清单将本地文档发布到飞书",
+        long_about = "按 .lark.toml 项目清单将本地 Markdown 文件发布到对应的飞书文档，或检查飞书文档是否与仓库一致\n\n示例:\n  # 发布所有有变化的文件\n  lark publish\n\n  # CI 中检查飞书文档是否与仓库一致，不一致时以非零状态退出\n  lark publish --check\n\n  # 使用指定的清单文件，并覆盖飞书中的修改\n  lark publish --config docs/.lark.toml --force\n\n清单格式 (.lark.toml):\n  lockfile = \".lark.lock\"      # 可选，默认 .lark.lock\n\n  [[documents]]\n  path = \"docs/design.md\"\n  document_id = \"doxcnAbCdEf\"\n\n  [[documents]]\n  path = \"docs/guide.md\"\n  wiki_node = \"wikcnAbCdEf\"\n\n参数说明:\n  - config: 项目清单路径，默认 .lark.toml；文件路径和锁文件相对于清单所在目录\n  - check: 只检查不发布，存在不一致的文件时以非零状态退出\n  - force: 同时发布未变化的文件，并覆盖飞书文档中的修改\n\n一致性状态:\n  - in_sync: 本地和飞书文档都与上次发布一致\n  - not_published: 从未发布过\n  - local_changed: 本地有未发布的修改\n  - remote_changed: 飞书文档在上次发布后被修改（发布时默认跳过）\n  - both_changed: 本地和飞书文档都有修改\n\n返回结构体字段说明:\n  - check: 是否为检查模式\n  - published_count: 已发布的文件数\n  - drifted_count: 不一致（检查模式）或发布失败的文件数\n  - files: 每个文件的结果\n    * path: 清单中的路径\n    * document_id: 文档ID\n    * status: 发布前的一致性状态\n    * published: 是否已发布\n    * document_revision_id: 发布后的文档版本\n    * error: 错误信息（如果有）"
+    )]
+    Publish {
+        /// 项目清单路径（默认: .lark.toml）
+        #[arg(long)]
+        config: Option<String>,
+        /// 只检查不发布，不一致时以非零状态退出
+        #[arg(long)]
+        check: bool,
+        /// 发布所有文件，并覆盖飞书文档中的修改
+        #[arg(long)]
+        force: bool,
     },
     #[command(
         about = "获取文档的所有块内容",
-        long_about = "获取飞书文档中的所有块内容，支持分页获取\n\n示例:\n  lark get-blocks doccnz1abcdefg123456789\n  lark get-blocks doccnz1abcdefg123456789 --page-size 100\n  lark get-blocks doccnz1abcdefg123456789 --all\n\n参数说明:\n  - document_id: 文档ID\n  - page_size: 分页大小，默认500，最大500\n  - page_token: 分页标记，用于获取下一页\n  - document_revision_id: 文档版本ID，默认-1表示最新版本\n  - user_id_type: 用户ID类型，默认open_id\n  - all: 自动获取所有块（处理分页）\n\n返回结构体字段说明:\n  - items: 块内容数组\n    * block_id: 块唯一标识符\n    * block_type: 块类型代码\n    * parent_id: 父块ID\n    * children: 子块ID数组（可选）\n    * content: 块内容（JSON格式）\n  - page_token: 分页标记（可选）\n  - has_more: 是否还有更多结果"
+        long_about = "获取飞书文档中的所有块内容，支持分页获取\n\n示例:\n  lark get-blocks doccnz1abcdefg123456789\n  lark get-blocks doccnz1abcdefg123456789 --page-size 100\n  lark get-blocks doccnz1abcdefg123456789 --all\n  lark get-blocks doccnz1abcdefg123456789 --select 'callout:last'\n  lark get-blocks doccnz1abcdefg123456789 --select '#doxcnParent > :nth(3)'\n\n参数说明:\n  - document_id: 文档ID\n  - page_size: 分页大小，默认500，最大500\n  - page_token: 分页标记，用于获取下一页\n  - document_revision_id: 文档版本ID，默认-1表示最新版本\n  - user_id_type: 用户ID类型，默认open_id\n  - all: 自动获取所有块（处理分页）\n  - select: 块选择器，读取所有块后只输出选中的块（按文档顺序）\n\n选择器语法:\n  - 类型: heading2、todo、callout、*，或 type=todo\n  - ID: #doxcnAbCdEf\n  - 属性: [done=true]、[language!=1]、[align]（在块内容及其 style 中查找）\n  - 伪类: :contains(\"文本\")，:first、:last、:nth(n)（在当前匹配结果中按文档顺序取，n 从 1 开始）\n  - 组合: 空格（后代）、>（子块）、~（之后的所有同级块）、+（下一个同级块）\n  - 逗号分隔多个选择器，结果取并集\n\n返回结构体字段说明:\n  - items: 块内容数组\n    * block_id: 块唯一标识符\n    * block_type: 块类型代码\n    * parent_id: 父块ID\n    * children: 子块ID数组（可选）\n    * content: 块内容（JSON格式）\n  - page_token: 分页标记（可选）\n  - has_more: 是否还有更多结果"
     )]
     GetBlocks {
         /// 文档 ID
@@ -206,16 +524,22 @@ enum Commands {
         /// 获取所有块（自动处理分页）
         #[arg(long)]
         all: bool,
+        /// 块选择器，只输出选中的块
+        #[arg(long)]
+        select: Option<String>,
     },
     #[command(
         about = "批量更新文档块",
-        long_about = "批量更新飞书文档中的多个块内容\n\n示例:\n  lark batch-update-blocks doccnz1abcdefg123456789 '[{\"block_id\":\"block_123\",\"update_text\":{\"elements\":[{\"text_run\":{\"content\":\"新内容\"}}]}}]'\n\n参数说明:\n  - document_id: 文档ID\n  - requests: 更新请求列表（JSON格式）\n  - document_revision_id: 文档版本ID，默认-1表示最新版本\n  - client_token: 可选的UUIDv4，用于幂等更新\n  - user_id_type: 用户ID类型，默认open_id\n\n返回结构体字段说明:\n  - blocks: 更新后的块数组\n  - client_token: 客户端令牌（可选）\n  - document_revision_id: 文档版本号"
+        long_about = "批量更新飞书文档中的多个块内容\n\n示例:\n  lark batch-update-blocks doccnz1abcdefg123456789 '[{\"block_id\":\"block_123\",\"update_text\":{\"elements\":[{\"text_run\":{\"content\":\"新内容\"}}]}}]'\n\n  # 使用简写，从 YAML 文件读取\n  lark batch-update-blocks doccnz1abcdefg123456789 --file updates.yaml\n\n  updates.yaml:\n    - block_id: block_123\n      text: 新内容\n    - block_id: table_456\n      insert_row: 3\n\n参数说明:\n  - document_id: 文档ID\n  - requests: 更新请求列表（JSON 或 YAML 格式），与 --file 二选一\n  - file: 从 JSON/YAML 文件读取更新请求\n  - document_revision_id: 文档版本ID，默认-1表示最新版本\n  - client_token: 可选的UUIDv4，用于幂等更新\n  - user_id_type: 用户ID类型，默认open_id\n\n简写:\n  - text: \"纯文本\" → update_text_elements\n  - insert_row: 行号 → insert_table_row\n  - insert_column: 列号 → insert_table_column\n  - delete_rows: [起始, 结束) → delete_table_rows\n  - delete_columns: [起始, 结束) → delete_table_columns\n\n选择器:\n  - 请求中可以用 select: \"块选择器\" 代替 block_id，展开为每个选中块各一个请求\n  - 例如 {select: \"todo[done=false]\", text: \"待确认\"}；选择器语法见 get-blocks --help\n\n说明:\n  - 请求按类型逐一校验，错误信息指出出错的位置，如 requests[2].update_text_elements.elements[0]\n  - 每个请求只能包含一个更新操作\n  - 超过 200 个请求时自动拆分为多次调用，后续调用以上一次返回的文档版本为基准\n\n返回结构体字段说明:\n  - blocks: 更新后的块数组（多次调用时合并）\n  - client_token: 客户端令牌（可选）\n  - document_revision_id: 文档版本号"
     )]
     BatchUpdateBlocks {
         /// 文档 ID
         document_id: String,
-        /// 更新请求列表（JSON 格式）
-        requests: String,
+        /// 更新请求列表（JSON 或 YAML 格式）
+        requests: Option<String>,
+        /// 从 JSON/YAML 文件读取更新请求
+        #[arg(long)]
+        file: Option<String>,
         /// 文档版本 ID（默认: -1 表示最新版本）
         #[arg(long)]
         document_revision_id: Option<i32>,
@@ -228,17 +552,20 @@ enum Commands {
     },
     #[command(
         about = "删除文档块",
-        long_about = "删除飞书文档中的指定范围的块内容\n\n示例:\n  lark delete-blocks doccnz1abcdefg123456789 block_123 0 5\n  lark delete-blocks doccnz1abcdefg123456789 block_123 2 4 --document-revision-id 123\n\n参数说明:\n  - document_id: 文档ID\n  - block_id: 父块ID\n  - start_index: 删除起始索引（包含）\n  - end_index: 删除结束索引（不包含）\n  - document_revision_id: 文档版本ID，默认-1表示最新版本\n  - client_token: 可选的UUIDv4，用于幂等操作\n\n返回结构体字段说明:\n  - document_revision_id: 文档版本号\n  - client_token: 客户端令牌（可选）"
+        long_about = "删除飞书文档中的指定范围的块内容，或删除选择器选中的块\n\n示例:\n  lark delete-blocks doccnz1abcdefg123456789 block_123 0 5\n  lark delete-blocks doccnz1abcdefg123456789 block_123 2 4 --document-revision-id 123\n\n  # 删除 Changelog 标题之后的所有同级块\n  lark delete-blocks doccnz1abcdefg123456789 --select 'heading2:contains(\"Changelog\") ~ *'\n\n  # 删除所有已完成的待办\n  lark delete-blocks doccnz1abcdefg123456789 --select 'todo[done=true]'\n\n参数说明:\n  - document_id: 文档ID\n  - block_id: 父块ID（不使用 --select 时必填）\n  - start_index: 删除起始索引（包含）\n  - end_index: 删除结束索引（不包含）\n  - select: 块选择器，选中的块按父块分组、合并为连续区间后删除\n  - document_revision_id: 文档版本ID，默认-1表示最新版本\n  - client_token: 可选的UUIDv4，用于幂等操作\n\n选择器语法:\n  - 类型: heading2、todo、callout、*，或 type=todo\n  - ID: #doxcnAbCdEf\n  - 属性: [done=true]、[language!=1]、[align]（在块内容及其 style 中查找）\n  - 伪类: :contains(\"文本\")，:first、:last、:nth(n)（在当前匹配结果中按文档顺序取，n 从 1 开始）\n  - 组合: 空格（后代）、>（子块）、~（之后的所有同级块）、+（下一个同级块）\n  - 逗号分隔多个选择器，结果取并集\n\n返回结构体字段说明:\n  - document_revision_id: 文档版本号\n  - client_token: 客户端令牌（可选）\n\n使用 --select 时返回:\n  - selector: 选择器\n  - dry_run: 是否只规划\n  - block_count: 删除的块数（不含已被祖先块包含的块）\n  - ranges: 每个父块下删除的区间（parent_id、start_index、end_index、block_ids）\n  - document_revision_id: 删除后的文档版本"
     )]
     DeleteBlocks {
         /// 文档 ID
         document_id: String,
         /// 父块 ID
-        block_id: String,
+        block_id: Option<String>,
         /// 删除起始索引（包含）
-        start_index: i32,
+        start_index: Option<i32>,
         /// 删除结束索引（不包含）
-        end_index: i32,
+        end_index: Option<i32>,
+        /// 块选择器，代替父块 ID 和索引
+        #[arg(long)]
+        select: Option<String>,
         /// 文档版本 ID（默认: -1 表示最新版本）
         #[arg(long)]
         document_revision_id: Option<i32>,
@@ -287,7 +614,7 @@ enum Commands {
     },
     #[command(
         about = "下载素材文件",
-        long_about = "下载云文档中的素材文件，支持分片下载\n\n示例:\n  lark download-media boxcnrHpsg1QDqXAAAyachabcef ./downloaded_image.png\n  lark download-media boxcnrHpsg1QDqXAAAyachabcef ./downloads/\n  lark download-media boxcnrHpsg1QDqXAAAyachabcef ./file.png --range \"bytes=0-1024\"\n  lark download-media boxcnrHpsg1QDqXAAAyachabcef ./file.png --extra \"{\\\"drive_route_token\\\":\\\"doc_token\\\"}\"\n\n参数说明:\n  - file_token: 素材文件的token，可通过获取文档块、电子表格等接口获取\n  - output_path: 输出文件路径或目录\n    * 如果指定为文件路径，则保存到该文件\n    * 如果指定为目录（以/或\\结尾），则自动使用服务器返回的文件名\n  - extra: 额外扩展信息（可选），用于高级权限的多维表格鉴权\n    * 格式: JSON字符串，如 {\\\"drive_route_token\\\":\\\"文档token\\\"}\n  - range: 分片下载范围（可选），格式: bytes=start-end\n    * 示例: bytes=0-1024 表示下载前1024字节\n    * 支持 HTTP Range 请求标准\n\nfile_token 获取方式:\n  - 新版文档: 通过获取文档块接口获取图片块或文件块的token\n  - 电子表格: 通过读取范围接口获取附件的fileToken\n  - 多维表格: 通过查询记录接口获取附件的file_token\n\n返回结构体字段说明:\n  - file_path: 保存的文件路径\n  - file_size: 文件大小（字节）\n  - content_type: MIME类型\n  - file_name: 文件名（从服务器响应头获取，可选）\n\n注意事项:\n  - 需要确保应用拥有素材的下载权限\n  - 本接口仅支持下载云文档而非云空间中的资源文件\n  - 调用频率限制: 5 QPS，10000次/天\n  - HTTP状态码说明:\n    * 200: 下载成功\n    * 206: 部分内容下载成功（使用Range时）\n    * 400: 参数错误（高级权限多维表格需要extra参数）\n    * 403: 没有下载权限\n    * 404: 素材不存在或被删除\n    * 500: 服务端错误"
+        long_about = "下载云文档中的素材文件：内容流式写入临时文件，校验后重命名为目标文件，中断后再次运行自动续传\n\n示例:\n  lark download-media boxcnrHpsg1QDqXAAAyachabcef ./downloaded_image.png\n  lark download-media boxcnrHpsg1QDqXAAAyachabcef ./downloads/\n  lark download-media boxcnrHpsg1QDqXAAAyachabcef ./recording.mp4 --parallel 4\n  lark download-media boxcnrHpsg1QDqXAAAyachabcef ./file.png --range 0-1023\n  lark download-media boxcnrHpsg1QDqXAAAyachabcef ./file.png --extra \"{\\\"drive_route_token\\\":\\\"doc_token\\\"}\"\n\n参数说明:\n  - file_token: 素材文件的token，可通过获取文档块、电子表格等接口获取\n  - output_path: 输出文件路径或目录\n    * 如果指定为文件路径，则保存到该文件\n    * 如果指定为目录（以/或\\结尾），则自动使用服务器返回的文件名\n  - extra: 额外扩展信息（可选），用于高级权限的多维表格鉴权\n    * 格式: JSON字符串，如 {\\\"drive_route_token\\\":\\\"文档token\\\"}\n  - range: 只下载指定范围（可选），格式: start-end 或 start-，可带 bytes= 前缀\n    * 示例: 0-1023 表示下载前1024字节\n    * 指定范围时不续传、不并行\n  - parallel: 并行的范围请求数，默认1，最大16；大于 8MB 的文件按 8MB 分段并行下载\n  - no-resume: 删除上次留下的临时文件，重新下载\n\n说明:\n  - 下载过程中写入 <output_path>.part，完成后原子重命名；并行下载的分段进度和远端版本记录在 <output_path>.part.json，分段请求同样带 If-Range\n  - 单个请求续传时带 If-Range，素材或远端版本（ETag / Last-Modified，记录在 <output_path>.part.stream.json）不一致时从头下载\n  - 服务端返回 Digest 或 Repr-Digest 中的 SHA-256 摘要时校验文件内容，不一致时删除临时文件\n  - 在终端中运行时，进度输出到标准错误\n\nfile_token 获取方式:\n  - 新版文档: 通过获取文档块接口获取图片块或文件块的token\n  - 电子表格: 通过读取范围接口获取附件的fileToken\n  - 多维表格: 通过查询记录接口获取附件的file_token\n\n返回结构体字段说明:\n  - file_path: 保存的文件路径\n  - file_size: 文件大小（字节）\n  - content_type: MIME类型\n  - file_name: 文件名（从服务器响应头获取，可选）\n  - resumed_bytes: 续传时已存在的字节数\n  - parallel: 实际并行的范围请求数\n  - verified_checksum: 已校验的摘要算法（服务端提供摘要时）\n\n注意事项:\n  - 需要确保应用拥有素材的下载权限\n  - 本接口仅支持下载云文档而非云空间中的资源文件\n  - 调用频率限制: 5 QPS，10000次/天\n  - HTTP状态码说明:\n    * 200: 下载成功\n    * 206: 部分内容下载成功（使用Range时）\n    * 400: 参数错误（高级权限多维表格需要extra参数）\n    * 403: 没有下载权限\n    * 404: 素材不存在或被删除\n    * 416: 下载范围超出文件大小\n    * 500: 服务端错误"
     )]
     DownloadMedia {
         /// 素材文件 token
@@ -297,9 +624,15 @@ enum Commands {
         /// 额外扩展信息，格式: {"drive_route_token":"文档token"} (可选)
         #[arg(long)]
         extra: Option<String>,
-        /// 分片下载范围，格式: bytes=start-end (可选)
+        /// 只下载指定范围，格式: start-end 或 start- (可选)
         #[arg(long)]
         range: Option<String>,
+        /// 并行的范围请求数
+        #[arg(long, default_value = "1")]
+        parallel: usize,
+        /// 删除临时文件，重新下载
+        #[arg(long)]
+        no_resume: bool,
     },
     #[command(
         about = "发送消息",
@@ -431,6 +764,283 @@ enum Commands {
     },
 }
 
+#[derive(Subcommand)]
+enum TableCommands {
+    #[command(
+        about = "导出表格内容",
+        long_about = "导出表格的所有单元格文本\n\n示例:\n  lark table export doccnz1abcdefg123456789 doxcnTable123\n  lark table export doccnz1abcdefg123456789 doxcnTable123 --to json --no-header\n\n参数说明:\n  - document_id: 文档ID\n  - table_block_id: 表格块ID\n  - to: 导出格式 csv（默认）、markdown 或 json\n  - no_header: JSON 格式不以第一行为表头，输出二维数组\n  - output: 写入文件，不指定时输出到标准输出\n\n导出格式:\n  - csv: 包含逗号、引号或换行的字段加引号\n  - markdown: 第一行为表头，单元格中的换行转换为 <br>\n  - json: 以第一行为键的对象数组"
+    )]
+    Export {
+        /// 文档 ID
+        document_id: String,
+        /// 表格块 ID
+        table_block_id: String,
+        /// 导出格式 (csv, markdown, json)
+        #[arg(long, default_value = "csv")]
+        to: String,
+        /// JSON 格式不使用表头，输出二维数组
+        #[arg(long)]
+        no_header: bool,
+        /// 输出文件路径
+        #[arg(long)]
+        output: Option<String>,
+    },
+    #[command(
+        about = "从 CSV 创建表格",
+        long_about = "从 CSV 文件创建表格块\n\n示例:\n  lark table create doccnz1abcdefg123456789 --from data.csv\n  lark table create doccnz1abcdefg123456789 --from data.csv --parent-id doxcnParent --index 0 --no-header\n\n参数说明:\n  - document_id: 文档ID\n  - from: CSV 文件路径，- 表示从标准输入读取\n  - parent_id: 父块ID，默认为文档根\n  - index: 插入位置，-1 表示末尾\n  - no_header: 不把第一行设为表头\n\n说明:\n  - 各行列数不一致时按最大列数补齐空单元格\n  - 单次请求最多创建 1000 个块（每个单元格占 2 个），超出的行在表格创建后追加"
+    )]
+    Create {
+        /// 文档 ID
+        document_id: String,
+        /// CSV 文件路径，- 表示标准输入
+        #[arg(long)]
+        from: String,
+        /// 父块 ID（默认为文档根）
+        #[arg(long)]
+        parent_id: Option<String>,
+        /// 插入位置 (-1 表示末尾)
+        #[arg(long, default_value = "-1")]
+        index: i32,
+        /// 不把第一行设为表头
+        #[arg(long)]
+        no_header: bool,
+    },
+    #[command(
+        about = "在表格末尾追加行",
+        long_about = "从 CSV 文件读取数据，追加到表格末尾\n\n示例:\n  lark table append-rows doccnz1abcdefg123456789 doxcnTable123 --from new_rows.csv\n  cat new_rows.csv | lark table append-rows doccnz1abcdefg123456789 doxcnTable123 --from -\n\n参数说明:\n  - document_id: 文档ID\n  - table_block_id: 表格块ID\n  - from: CSV 文件路径（不含表头），- 表示从标准输入读取\n\n说明:\n  - 数据列数不能超过表格列数，不足的列留空\n  - 先插入空行，再写入新单元格的文本"
+    )]
+    AppendRows {
+        /// 文档 ID
+        document_id: String,
+        /// 表格块 ID
+        table_block_id: String,
+        /// CSV 文件路径，- 表示标准输入
+        #[arg(long)]
+        from: String,
+    },
+    #[command(
+        about = "修改单元格内容",
+        long_about = "修改表格中单个单元格的文本\n\n示例:\n  lark table set-cell doccnz1abcdefg123456789 doxcnTable123 0 1 \"负责人\"\n\n参数说明:\n  - document_id: 文档ID\n  - table_block_id: 表格块ID\n  - row: 行号，从 0 开始\n  - column: 列号，从 0 开始\n  - value: 单元格文本\n\n说明:\n  - 更新单元格中第一个文本块的内容，并删除单元格中的其余块"
+    )]
+    SetCell {
+        /// 文档 ID
+        document_id: String,
+        /// 表格块 ID
+        table_block_id: String,
+        /// 行号（从 0 开始）
+        row: usize,
+        /// 列号（从 0 开始）
+        column: usize,
+        /// 单元格文本
+        value: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum CommentsCommands {
+    #[command(
+        about = "列出评论",
+        long_about = "列出文档的全部评论，自动翻页，并补齐回复较多的评论的剩余回复\n\n示例:\n  lark comments list doccnz1abcdefg123456789\n  lark comments list doccnz1abcdefg123456789 --solved --format json\n\n参数说明:\n  - file_token: 文档 token\n  - type: 文档类型，默认 docx\n  - solved: 只列出已解决的评论\n  - unsolved: 只列出未解决的评论"
+    )]
+    List {
+        /// 文档 token
+        file_token: String,
+        /// 文档类型 (doc, docx, sheet, file, slides)
+        #[arg(long = "type", default_value = "docx")]
+        file_type: String,
+        /// 只列出已解决的评论
+        #[arg(long, conflicts_with = "unsolved")]
+        solved: bool,
+        /// 只列出未解决的评论
+        #[arg(long)]
+        unsolved: bool,
+    },
+    #[command(
+        about = "添加全文评论",
+        long_about = "在文档上添加一条全文评论\n\n示例:\n  lark comments add doccnz1abcdefg123456789 \"请补充接口说明\"\n\n参数说明:\n  - file_token: 文档 token\n  - content: 评论内容（纯文本）\n  - type: 文档类型，默认 docx"
+    )]
+    Add {
+        /// 文档 token
+        file_token: String,
+        /// 评论内容
+        content: String,
+        /// 文档类型 (doc, docx, sheet, file, slides)
+        #[arg(long = "type", default_value = "docx")]
+        file_type: String,
+    },
+    #[command(
+        about = "回复评论",
+        long_about = "回复一条评论\n\n示例:\n  lark comments reply doccnz1abcdefg123456789 6916106822734512356 \"已补充\"\n\n参数说明:\n  - file_token: 文档 token\n  - comment_id: 评论ID\n  - content: 回复内容（纯文本）\n  - type: 文档类型，默认 docx"
+    )]
+    Reply {
+        /// 文档 token
+        file_token: String,
+        /// 评论 ID
+        comment_id: String,
+        /// 回复内容
+        content: String,
+        /// 文档类型 (doc, docx, sheet, file, slides)
+        #[arg(long = "type", default_value = "docx")]
+        file_type: String,
+    },
+    #[command(
+        about = "解决评论",
+        long_about = "将评论标记为已解决\n\n示例:\n  lark comments resolve doccnz1abcdefg123456789 6916106822734512356\n\n参数说明:\n  - file_token: 文档 token\n  - comment_id: 评论ID\n  - type: 文档类型，默认 docx"
+    )]
+    Resolve {
+        /// 文档 token
+        file_token: String,
+        /// 评论 ID
+        comment_id: String,
+        /// 文档类型 (doc, docx, sheet, file, slides)
+        #[arg(long = "type", default_value = "docx")]
+        file_type: String,
+    },
+    #[command(
+        about = "重新打开评论",
+        long_about = "将已解决的评论重新打开\n\n示例:\n  lark comments unresolve doccnz1abcdefg123456789 6916106822734512356\n\n参数说明:\n  - file_token: 文档 token\n  - comment_id: 评论ID\n  - type: 文档类型，默认 docx"
+    )]
+    Unresolve {
+        /// 文档 token
+        file_token: String,
+        /// 评论 ID
+        comment_id: String,
+        /// 文档类型 (doc, docx, sheet, file, slides)
+        #[arg(long = "type", default_value = "docx")]
+        file_type: String,
+    },
+    #[command(
+        about = "导出评论",
+        long_about = "导出文档的全部评论和回复\n\n示例:\n  lark comments export doccnz1abcdefg123456789\n  lark comments export doccnz1abcdefg123456789 --unsolved --output review.md\n  lark comments export doccnz1abcdefg123456789 --to json --output comments.json\n\n参数说明:\n  - file_token: 文档 token\n  - type: 文档类型，默认 docx\n  - to: 导出格式 markdown（默认）或 json\n  - solved / unsolved: 只导出已解决或未解决的评论\n  - output: 写入文件，不指定时输出到标准输出\n\n导出格式:\n  - markdown: 每条评论一个小节，引用的文档内容作为引用块，回复按时间顺序列出\n  - json: file_token、file_type、comment_count 和 comments（comment_id、quote、is_whole、is_solved、author、author_id、created_at、solved_by、replies）\n\n说明:\n  - 评论者、解决者和被 @的用户通过通讯录查询名字，查询失败时保留 open_id\n  - 时间以 UTC 显示"
+    )]
+    Export {
+        /// 文档 token
+        file_token: String,
+        /// 文档类型 (doc, docx, sheet, file, slides)
+        #[arg(long = "type", default_value = "docx")]
+        file_type: String,
+        /// 导出格式 (markdown, json)
+        #[arg(long, default_value = "markdown")]
+        to: String,
+        /// 只导出已解决的评论
+        #[arg(long, conflicts_with = "unsolved")]
+        solved: bool,
+        /// 只导出未解决的评论
+        #[arg(long)]
+        unsolved: bool,
+        /// 输出文件路径
+        #[arg(long)]
+        output: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum DriveCommands {
+    #[command(
+        about = "列出文件夹内容",
+        long_about = "列出文件夹中的文件和子文件夹，自动翻页\n\n示例:\n  lark drive ls\n  lark drive ls fldcnAbCdEf --recursive\n  lark drive ls fldcnAbCdEf --depth 2 --type docx --format json\n\n参数说明:\n  - folder: 文件夹 token 或链接，默认为根文件夹\n  - recursive: 递归展开子文件夹，以目录树显示\n  - depth: 展开的层数（隐含 --recursive），1 表示只列出直接子项\n  - type: 只显示这些类型，可重复或逗号分隔；递归时保留包含匹配项的文件夹\n\n返回结构体字段说明（--format json）:\n  - folder_token: 列出的文件夹\n  - count: 项数\n  - entries: 按目录树先序排列的项\n    * depth: 层级，直接子项为 0\n    * path: 相对路径\n    * token / name / type / parent_token / url: 文件信息\n    * created_time / modified_time: 创建和修改时间（秒级时间戳）\n    * owner_id: 所有者 open_id\n    * shortcut_info: 快捷方式指向的文件（target_type、target_token）"
+    )]
+    Ls {
+        /// 文件夹 token 或链接（默认: 根文件夹）
+        folder: Option<String>,
+        /// 递归展开子文件夹
+        #[arg(long)]
+        recursive: bool,
+        /// 展开的层数
+        #[arg(long)]
+        depth: Option<usize>,
+        /// 只显示这些类型（可重复或逗号分隔）
+        #[arg(long = "type")]
+        types: Vec<String>,
+    },
+    #[command(
+        about = "新建文件夹",
+        long_about = "在指定文件夹下新建文件夹\n\n示例:\n  lark drive mkdir 周报\n  lark drive mkdir 周报 --parent https://example.feishu.cn/drive/folder/fldcnAbCdEf\n\n参数说明:\n  - name: 文件夹名称（1-256 个字符）\n  - parent: 父文件夹 token 或链接，默认为根文件夹\n\n返回结构体字段说明:\n  - token: 新建文件夹的 token\n  - url: 新建文件夹的链接"
+    )]
+    Mkdir {
+        /// 文件夹名称
+        name: String,
+        /// 父文件夹 token 或链接（默认: 根文件夹）
+        #[arg(long)]
+        parent: Option<String>,
+    },
+    #[command(
+        about = "移动文件或文件夹",
+        long_about = "将文件或文件夹移动到目标文件夹，移动文件夹时等待异步任务完成\n\n示例:\n  lark drive mv https://example.feishu.cn/docx/doxcnAbCdEf fldcnTarget\n  lark drive mv AbCdEf123 fldcnTarget --type sheet\n\n参数说明:\n  - source: 文件 token 或链接\n  - folder: 目标文件夹 token 或链接\n  - type: 文件类型，无法从 source 判断时必填\n\n返回结构体字段说明:\n  - action: move\n  - token / file_type: 移动的文件\n  - dry_run: 是否只规划、未执行\n  - folder_token: 目标文件夹\n  - task_id: 文件夹移动的异步任务ID"
+    )]
+    Mv {
+        /// 文件 token 或链接
+        source: String,
+        /// 目标文件夹 token 或链接
+        folder: String,
+        /// 文件类型（无法从 source 判断时必填）
+        #[arg(long = "type")]
+        file_type: Option<String>,
+    },
+    #[command(
+        about = "复制文件",
+        long_about = "将文件复制到目标文件夹\n\n示例:\n  lark drive cp https://example.feishu.cn/docx/doxcnAbCdEf fldcnTarget\n  lark drive cp https://example.feishu.cn/sheets/shtcnAbCdEf fldcnTarget --name \"数据备份\"\n\n参数说明:\n  - source: 文件 token 或链接（不支持文件夹）\n  - folder: 目标文件夹 token 或链接\n  - name: 副本名称，默认为原标题加“副本”\n  - type: 文件类型，无法从 source 判断时必填\n\n返回结构体字段说明:\n  - action: copy\n  - token / file_type: 源文件\n  - dry_run: 是否只规划、未执行\n  - folder_token: 目标文件夹\n  - name: 副本名称\n  - new_token / url: 副本的 token 和链接"
+    )]
+    Cp {
+        /// 文件 token 或链接
+        source: String,
+        /// 目标文件夹 token 或链接
+        folder: String,
+        /// 副本名称
+        #[arg(long)]
+        name: Option<String>,
+        /// 文件类型（无法从 source 判断时必填）
+        #[arg(long = "type")]
+        file_type: Option<String>,
+    },
+    #[command(
+        about = "删除文件或文件夹",
+        long_about = "删除文件或文件夹（移到回收站），执行前列出将删除的文件并要求确认\n\n示例:\n  lark drive rm https://example.feishu.cn/docx/doxcnAbCdEf\n  lark drive rm fldcnOld1 fldcnOld2 --yes\n\n参数说明:\n  - sources: 一个或多个文件 token 或链接\n  - type: 文件类型，无法从 token 判断时必填（对所有参数生效）\n  - yes: 跳过确认\n\n返回结构体字段说明:\n  - action: delete\n  - token / file_type: 删除的文件\n  - dry_run: 是否只规划、未执行\n  - task_id: 文件夹删除的异步任务ID"
+    )]
+    Rm {
+        /// 文件 token 或链接
+        #[arg(required = true)]
+        sources: Vec<String>,
+        /// 文件类型（无法从 token 判断时必填）
+        #[arg(long = "type")]
+        file_type: Option<String>,
+        /// 跳过确认
+        #[arg(long)]
+        yes: bool,
+    },
+    #[command(
+        about = "批量获取文件元数据",
+        long_about = "批量获取文件的标题、所有者、修改时间和链接，每次请求最多 200 个\n\n示例:\n  lark drive meta https://example.feishu.cn/docx/doxcnAbCdEf https://example.feishu.cn/sheets/shtcnAbCdEf\n\n参数说明:\n  - sources: 一个或多个文件 token 或链接\n  - type: 文件类型，无法从 token 判断时必填（对所有参数生效）\n\n返回结构体字段说明:\n  - metas: 元数据\n    * doc_token / doc_type: 文件 token 和类型\n    * title: 标题\n    * owner_id: 所有者 open_id\n    * create_time: 创建时间（秒级时间戳）\n    * latest_modify_user / latest_modify_time: 最后修改者和时间\n    * url: 文件链接\n  - failed_list: 获取失败的文件（token、code）"
+    )]
+    Meta {
+        /// 文件 token 或链接
+        #[arg(required = true)]
+        sources: Vec<String>,
+        /// 文件类型（无法从 token 判断时必填）
+        #[arg(long = "type")]
+        file_type: Option<String>,
+    },
+    #[command(
+        about = "上传本地文件到云空间",
+        long_about = "上传本地文件到云空间文件夹：20MB 以内的文件一次上传，更大的文件分片并发上传，中断后再次运行自动续传\n\n示例:\n  lark drive upload ./report.pdf\n  lark drive upload ./dataset.zip https://example.feishu.cn/drive/folder/fldcnAbCdEf --concurrency 8\n  lark drive upload ./backup.tar.gz fldcnAbCdEf --name \"备份-2024.tar.gz\"\n\n参数说明:\n  - path: 本地文件路径\n  - folder: 目标文件夹 token 或链接，默认为根文件夹\n  - name: 上传后的文件名，默认为本地文件名\n  - concurrency: 并发上传的分片数，默认4，最大16\n  - no-resume: 忽略断点记录，重新开始分片上传\n\n说明:\n  - 自动计算整个文件或每个分片的 Adler-32 校验和\n  - 分片从磁盘按需读取，不会一次读入整个文件\n  - 断点记录保存在用户配置目录的 lark-cli/uploads 下，upload_id 在 24 小时内有效；文件被修改后不再续传\n\n返回结构体字段说明:\n  - path / file_name: 本地路径和上传后的文件名\n  - folder_token: 目标文件夹\n  - size: 文件大小（字节）\n  - mode: upload_all（一次上传）或 multipart（分片上传）\n  - dry_run: 是否只规划、未上传\n  - checksum: 整个文件的 Adler-32 校验和（一次上传时）\n  - upload_id / block_size / block_num: 分片上传的 ID、分片大小和分片数\n  - resumed_parts: 续传时跳过的分片数\n  - uploaded_parts: 本次上传的分片数\n  - file_token: 上传后的文件 token"
+    )]
+    Upload {
+        /// 本地文件路径
+        path: std::path::PathBuf,
+        /// 目标文件夹 token 或链接（默认: 根文件夹）
+        folder: Option<String>,
+        /// 上传后的文件名
+        #[arg(long)]
+        name: Option<String>,
+        /// 并发上传的分片数
+        #[arg(long, default_value = "4")]
+        concurrency: usize,
+        /// 忽略断点记录，重新开始上传
+        #[arg(long)]
+        no_resume: bool,
+    },
+}
+
 #[tokio::main]
 async fn main() {
     let cli = Cli::parse();
@@ -457,13 +1067,13 @@ async fn main() {
     };
 
     // 运行命令
-    if let Err(e) = run_command(cli.command, output_format, cli.verbose).await {
+    if let Err(e) = run_command(cli.command, output_format, cli.verbose, cli.dry_run).await {
         eprintln!("错误: {}", e);
         std::process::exit(1);
     }
 }
 
-async fn run_command(command: Commands, output_format: OutputFormat, verbose: bool) -> Result<()> {
+async fn run_command(command: Commands, output_format: OutputFormat, verbose: bool, dry_run: bool) -> Result<()> {
     // 加载配置
     let config = Config::load()?;
 
@@ -471,7 +1081,7 @@ async fn run_command(command: Commands, output_format: OutputFormat, verbose: bo
     let auth_manager = AuthManager::new(config);
 
     // 创建 API 客户端
-    let api_client = ApiClient::new(auth_manager);
+    let api_client = ApiClient::new(auth_manager).with_dry_run(dry_run);
 
     match command {
         Commands::GetNode { token, obj_type } => {
@@ -503,8 +1113,25 @@ async fn run_command(command: Commands, output_format: OutputFormat, verbose: bo
                 output_format
             ).await
         }
-        Commands::CreateDocument { folder_token, title } => {
-            commands::document::handle_create_document(api_client, folder_token, title, output_format).await
+        Commands::CreateDocument {
+            folder_token,
+            title,
+            template,
+            vars,
+            vars_file,
+            collaborators,
+        } => {
+            commands::document::handle_create_document(
+                api_client,
+                folder_token,
+                title,
+                template,
+                vars,
+                vars_file,
+                collaborators,
+                output_format,
+            )
+            .await
         }
         Commands::AddCallout {
             document_id,
@@ -529,6 +1156,63 @@ async fn run_command(command: Commands, output_format: OutputFormat, verbose: bo
                 output_format
             ).await
         }
+        Commands::AddBlock {
+            document_id,
+            block_type,
+            text,
+            parent_id,
+            index,
+            language,
+            wrap,
+            done,
+            columns,
+            width_ratios,
+            emoji,
+            callout_type,
+            background_color,
+            border_color,
+            text_color,
+            url,
+            iframe_type,
+            spec,
+        } => {
+            // 块描述来自文件，或由命令行选项组成单个块
+            let specs = match (spec, block_type) {
+                (Some(path), None) => BlockSpec::load(std::path::Path::new(&path))?,
+                (None, Some(kind)) => vec![BlockSpec {
+                    kind,
+                    text,
+                    language,
+                    wrap: wrap.then_some(true),
+                    done: done.then_some(true),
+                    columns,
+                    width_ratios,
+                    emoji,
+                    callout_type,
+                    background_color,
+                    border_color,
+                    text_color,
+                    url,
+                    iframe_type,
+                    children: Vec::new(),
+                }],
+                (Some(_), Some(_)) => {
+                    return Err(error::LarkError::ValidationError("--spec 和 --type 不能同时使用".to_string()));
+                }
+                (None, None) => {
+                    return Err(error::LarkError::ValidationError("需要提供 --type 或 --spec".to_string()));
+                }
+            };
+
+            commands::add_block::handle_add_block(
+                api_client,
+                document_id,
+                specs,
+                parent_id,
+                index,
+                output_format
+            ).await
+        }
         Commands::AddBoard {
             document_id,
             parent_id,
@@ -564,7 +1248,20 @@ async fn run_command(command: Commands, output_format: OutputFormat, verbose: bo
             pattern,
             batch_size,
             skip_existing,
+            manifest,
+            replace_changed,
+            render_diagrams,
+            diagram_source,
         } => {
+            // 解析图表渲染选项
+            let diagrams = if render_diagrams {
+                let keep_source = diagram_source.parse()
+                    .map_err(|e: String| error::LarkError::ParseError(e))?;
+                Some(api::diagram_blocks::DiagramOptions { keep_source })
+            } else {
+                None
+            };
+
             commands::import::handle_add_content(
                 api_client,
                 document_id,
@@ -577,17 +1274,384 @@ async fn run_command(command: Commands, output_format: OutputFormat, verbose: bo
                 pattern,
                 batch_size,
                 skip_existing,
+                manifest,
+                replace_changed,
+                diagrams,
+                verbose,
+                output_format
+            ).await
+        }
+        Commands::ImportTree {
+            source,
+            folder_token,
+            wiki_space,
+            parent_node,
+            recursive,
+            pattern,
+            base_url,
+        } => {
+            commands::import_tree::handle_import_tree(
+                api_client,
+                source,
+                folder_token,
+                wiki_space,
+                parent_node,
+                recursive,
+                Some(pattern),
+                base_url,
                 verbose,
                 output_format
             ).await
         }
+        Commands::Sync { document_id, file } => {
+            commands::sync::handle_sync(api_client, document_id, file, verbose, output_format).await
+        }
+        Commands::Diff {
+            document_id,
+            from,
+            to,
+            unified,
+            context,
+        } => {
+            commands::diff::handle_diff(api_client, document_id, from, to, unified, context, verbose, output_format)
+                .await
+        }
+        Commands::EditSection {
+            document_id,
+            heading_path,
+            source,
+            source_type,
+            content_type,
+            mode,
+        } => {
+            commands::section::handle_edit_section(
+                api_client,
+                document_id,
+                heading_path,
+                source,
+                source_type,
+                content_type,
+                mode,
+                verbose,
+                output_format,
+            )
+            .await
+        }
+        Commands::Replace {
+            document_id,
+            pattern,
+            replacement,
+            regex,
+            ignore_case,
+            preview,
+        } => {
+            commands::replace::handle_replace(
+                api_client,
+                document_id,
+                pattern,
+                replacement,
+                regex,
+                ignore_case,
+                preview,
+                verbose,
+                output_format,
+            )
+            .await
+        }
+        Commands::Table { command } => match command {
+            TableCommands::Export {
+                document_id,
+                table_block_id,
+                to,
+                no_header,
+                output,
+            } => {
+                commands::table::handle_table_export(
+                    api_client,
+                    document_id,
+                    table_block_id,
+                    to,
+                    no_header,
+                    output,
+                    verbose,
+                )
+                .await
+            }
+            TableCommands::Create {
+                document_id,
+                from,
+                parent_id,
+                index,
+                no_header,
+            } => {
+                commands::table::handle_table_create(
+                    api_client,
+                    document_id,
+                    from,
+                    parent_id,
+                    index,
+                    no_header,
+                    verbose,
+                    output_format,
+                )
+                .await
+            }
+            TableCommands::AppendRows {
+                document_id,
+                table_block_id,
+                from,
+            } => {
+                commands::table::handle_table_append_rows(
+                    api_client,
+                    document_id,
+                    table_block_id,
+                    from,
+                    verbose,
+                    output_format,
+                )
+                .await
+            }
+            TableCommands::SetCell {
+                document_id,
+                table_block_id,
+                row,
+                column,
+                value,
+            } => {
+                commands::table::handle_table_set_cell(
+                    api_client,
+                    document_id,
+                    table_block_id,
+                    row,
+                    column,
+                    value,
+                    output_format,
+                )
+                .await
+            }
+        },
+        Commands::CopyBlocks {
+            source_document_id,
+            source,
+            document_id,
+            parent_block_id,
+            index,
+            move_blocks,
+            force,
+        } => {
+            commands::copy_blocks::handle_copy_blocks(
+                api_client,
+                source_document_id,
+                source,
+                document_id,
+                parent_block_id,
+                index,
+                move_blocks,
+                force,
+                verbose,
+                output_format,
+            )
+            .await
+        }
+        Commands::Lint {
+            document_id,
+            config,
+            rules,
+            skip,
+            offline,
+            fix,
+            fail_on,
+        } => {
+            commands::lint::handle_lint(
+                api_client,
+                document_id,
+                config,
+                rules,
+                skip,
+                offline,
+                fix,
+                fail_on,
+                output_format,
+            )
+            .await
+        }
+        Commands::Toc {
+            document_id,
+            title,
+            max_level,
+            index,
+            base_url,
+        } => {
+            commands::toc::handle_toc(api_client, document_id, title, max_level, index, base_url, output_format).await
+        }
+        Commands::Attach {
+            document_id,
+            paths,
+            parent_block_id,
+            index,
+        } => {
+            commands::attach::handle_attach(api_client, document_id, paths, parent_block_id, index, verbose, output_format)
+                .await
+        }
+        Commands::Comments { command } => {
+            let solved_filter = |solved: bool, unsolved: bool| {
+                if solved {
+                    Some(true)
+                } else if unsolved {
+                    Some(false)
+                } else {
+                    None
+                }
+            };
+            match command {
+                CommentsCommands::List {
+                    file_token,
+                    file_type,
+                    solved,
+                    unsolved,
+                } => {
+                    commands::comments::handle_comments_list(
+                        api_client,
+                        file_token,
+                        file_type,
+                        solved_filter(solved, unsolved),
+                        verbose,
+                        output_format,
+                    )
+                    .await
+                }
+                CommentsCommands::Add {
+                    file_token,
+                    content,
+                    file_type,
+                } => commands::comments::handle_comments_add(api_client, file_token, file_type, content, output_format).await,
+                CommentsCommands::Reply {
+                    file_token,
+                    comment_id,
+                    content,
+                    file_type,
+                } => {
+                    commands::comments::handle_comments_reply(
+                        api_client,
+                        file_token,
+                        file_type,
+                        comment_id,
+                        content,
+                        output_format,
+                    )
+                    .await
+                }
+                CommentsCommands::Resolve {
+                    file_token,
+                    comment_id,
+                    file_type,
+                } => {
+                    commands::comments::handle_comments_set_solved(
+                        api_client,
+                        file_token,
+                        file_type,
+                        comment_id,
+                        true,
+                        output_format,
+                    )
+                    .await
+                }
+                CommentsCommands::Unresolve {
+                    file_token,
+                    comment_id,
+                    file_type,
+                } => {
+                    commands::comments::handle_comments_set_solved(
+                        api_client,
+                        file_token,
+                        file_type,
+                        comment_id,
+                        false,
+                        output_format,
+                    )
+                    .await
+                }
+                CommentsCommands::Export {
+                    file_token,
+                    file_type,
+                    to,
+                    solved,
+                    unsolved,
+                    output,
+                } => {
+                    commands::comments::handle_comments_export(
+                        api_client,
+                        file_token,
+                        file_type,
+                        to,
+                        solved_filter(solved, unsolved),
+                        output,
+                        verbose,
+                    )
+                    .await
+                }
+            }
+        }
+        Commands::Drive { command } => match command {
+            DriveCommands::Ls {
+                folder,
+                recursive,
+                depth,
+                types,
+            } => commands::drive::handle_drive_ls(api_client, folder, recursive, depth, types, output_format).await,
+            DriveCommands::Mkdir { name, parent } => {
+                commands::drive::handle_drive_mkdir(api_client, name, parent, output_format).await
+            }
+            DriveCommands::Mv {
+                source,
+                folder,
+                file_type,
+            } => commands::drive::handle_drive_mv(api_client, source, folder, file_type, output_format).await,
+            DriveCommands::Cp {
+                source,
+                folder,
+                name,
+                file_type,
+            } => commands::drive::handle_drive_cp(api_client, source, folder, name, file_type, output_format).await,
+            DriveCommands::Rm {
+                sources,
+                file_type,
+                yes,
+            } => commands::drive::handle_drive_rm(api_client, sources, file_type, yes, output_format).await,
+            DriveCommands::Meta { sources, file_type } => {
+                commands::drive::handle_drive_meta(api_client, sources, file_type, output_format).await
+            }
+            DriveCommands::Upload {
+                path,
+                folder,
+                name,
+                concurrency,
+                no_resume,
+            } => {
+                commands::drive::handle_drive_upload(
+                    api_client,
+                    path,
+                    folder,
+                    name,
+                    concurrency,
+                    no_resume,
+                    verbose,
+                    output_format,
+                )
+                .await
+            }
+        },
+        Commands::Publish { config, check, force } => {
+            commands::publish::handle_publish(api_client, config, check, force, verbose, output_format).await
+        }
         Commands::GetBlocks {
             document_id,
             page_size,
             page_token,
             document_revision_id,
             user_id_type,
-            all
+            all,
+            select
         } => {
             commands::blocks::handle_get_blocks(
                 api_client,
@@ -597,12 +1661,14 @@ async fn run_command(command: Commands, output_format: OutputFormat, verbose: bo
                 document_revision_id,
                 user_id_type,
                 all,
+                select,
                 output_format
             ).await
         }
         Commands::BatchUpdateBlocks {
             document_id,
             requests,
+            file,
             document_revision_id,
             client_token,
             user_id_type
@@ -611,6 +1677,7 @@ async fn run_command(command: Commands, output_format: OutputFormat, verbose: bo
                 api_client,
                 document_id,
                 requests,
+                file,
                 document_revision_id,
                 client_token,
                 user_id_type,
@@ -622,6 +1689,7 @@ async fn run_command(command: Commands, output_format: OutputFormat, verbose: bo
             block_id,
             start_index,
             end_index,
+            select,
             document_revision_id,
             client_token
         } => {
@@ -631,6 +1699,7 @@ async fn run_command(command: Commands, output_format: OutputFormat, verbose: bo
                 block_id,
                 start_index,
                 end_index,
+                select,
                 document_revision_id,
                 client_token,
                 output_format
@@ -653,13 +1722,15 @@ async fn run_command(command: Commands, output_format: OutputFormat, verbose: bo
                 output_format
             ).await
         }
-        Commands::DownloadMedia { file_token, output_path, extra, range } => {
+        Commands::DownloadMedia { file_token, output_path, extra, range, parallel, no_resume } => {
             commands::download_media::handle_download_media(
                 api_client,
                 file_token,
                 output_path,
                 extra,
                 range,
+                parallel,
+                no_resume,
                 output_format
             ).await
         }
@@ -0,0 +1,292 @@
+use crate::api::DescendantBlock;
+use crate::utils::{content_hash, matching_pairs, BlockTree};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// 内容相似度不低于该值的同类型块视为同一块的修改，原地更新文本
+pub const UPDATE_SIMILARITY_THRESHOLD: f64 = 0.5;
+
+/// 比较时忽略的字段：块 ID 引用、评论和服务端生成的资源 token
+const IGNORED_KEYS: [&str; 5] = ["cells", "comment_ids", "token", "column_width", "merge_info"];
+
+/// 用于比较的块摘要，本地块和文档中的块统一转换为该结构
+#[derive(Debug, Clone)]
+pub struct BlockSummary {
+    /// 块 ID（本地块为临时 ID）
+    pub block_id: String,
+    /// 块类型
+    pub block_type: i32,
+    /// 文本片段，非文本块为 None
+    pub elements: Option<Value>,
+    /// 去掉文本片段和默认值后的块属性
+    pub properties: Value,
+    /// 子块摘要
+    pub children: Vec<BlockSummary>,
+}
+
+impl BlockSummary {
+    /// 根据转换得到的本地块生成摘要
+    pub fn from_local(block_id: &str, blocks: &HashMap<&str, &DescendantBlock>) -> Option<Self> {
+        let block = blocks.get(block_id)?;
+        let mut payload = serde_json::to_value(block).ok()?;
+        let children = block
+            .children
+            .iter()
+            .flatten()
+            .filter_map(|id| Self::from_local(id, blocks))
+            .collect();
+        Some(Self::build(block_id, block.block_type, &mut payload, children))
+    }
+
+    /// 根据文档中的块生成摘要
+    pub fn from_remote(block_id: &str, tree: &BlockTree) -> Option<Self> {
+        let block = tree.get(block_id)?;
+        let mut payload = block.content.clone();
+        let children = tree
+            .children(block_id)
+            .iter()
+            .filter_map(|id| Self::from_remote(id, tree))
+            .collect();
+        Some(Self::build(block_id, block.block_type, &mut payload, children))
+    }
+
+    fn build(block_id: &str, block_type: i32, payload: &mut Value, children: Vec<BlockSummary>) -> Self {
+        if let Some(object) = payload.as_object_mut() {
+            for key in ["block_id", "block_type", "parent_id", "children"] {
+                object.remove(key);
+            }
+        }
+
+        // 文本类块的内容位于 <类型>.elements
+        let elements = payload
+            .as_object_mut()
+            .and_then(|object| object.values_mut().find_map(|v| v.as_object_mut()?.remove("elements")))
+            .map(|mut elements| {
+                normalize(&mut elements);
+                elements
+            });
+        normalize(payload);
+
+        Self {
+            block_id: block_id.to_string(),
+            block_type,
+            elements,
+            properties: payload.clone(),
+            children,
+        }
+    }
+
+    /// 块的纯文本内容
+    pub fn plain_text(&self) -> String {
+        self.elements
+            .as_ref()
+            .and_then(|e| e.as_array())
+            .map(|elements| {
+                elements
+                    .iter()
+                    .filter_map(|e| e.pointer("/text_run/content").and_then(|c| c.as_str()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// 整棵子树的指纹，内容完全相同的子树指纹相同
+    pub fn fingerprint(&self) -> String {
+        let children: Vec<String> = self.children.iter().map(|c| c.fingerprint()).collect();
+        serde_json::json!([self.block_type, self.elements, self.properties, children]).to_string()
+    }
+
+    /// 能否通过只更新文本片段把 `self` 变为 `other`
+    pub fn can_update_to(&self, other: &BlockSummary) -> bool {
+        if self.block_type != other.block_type || self.elements.is_none() || other.elements.is_none() {
+            return false;
+        }
+        if self.properties != other.properties || self.children.len() != other.children.len() {
+            return false;
+        }
+        let same_children = self
+            .children
+            .iter()
+            .zip(&other.children)
+            .all(|(a, b)| a.fingerprint() == b.fingerprint());
+
+        same_children && similarity(&self.plain_text(), &other.plain_text()) >= UPDATE_SIMILARITY_THRESHOLD
+    }
+}
+
+/// 文档正文内容的哈希，基于一级块的子树指纹计算，不受块 ID 和服务端默认值影响
+pub fn document_content_hash(tree: &BlockTree, page_id: &str) -> String {
+    let fingerprints: Vec<String> = tree
+        .children(page_id)
+        .iter()
+        .filter_map(|id| BlockSummary::from_remote(id, tree))
+        .map(|summary| summary.fingerprint())
+        .collect();
+    content_hash(&fingerprints.join("\n"))
+}
+
+/// 去掉 null、false、空字符串、空对象和空数组，以及默认的左对齐和忽略的字段，
+/// 使转换结果与服务端返回的块（带有各种默认值）可以直接比较
+pub fn normalize(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            for key in IGNORED_KEYS {
+                object.remove(key);
+            }
+            if object.get("align").and_then(|a| a.as_i64()) == Some(1) {
+                object.remove("align");
+            }
+            for child in object.values_mut() {
+                normalize(child);
+            }
+            object.retain(|_, v| !is_empty(v));
+        }
+        Value::Array(items) => items.iter_mut().for_each(normalize),
+        _ => {}
+    }
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null | Value::Bool(false) => true,
+        Value::String(s) => s.is_empty(),
+        Value::Object(o) => o.is_empty(),
+        Value::Array(a) => a.is_empty(),
+        _ => false,
+    }
+}
+
+/// 基于字符二元组的 Dice 相似度，范围 [0, 1]
+pub fn similarity(a: &str, b: &str) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+
+    let bigrams = |s: &str| {
+        let chars: Vec<char> = s.chars().collect();
+        let mut counts: HashMap<(char, char), usize> = HashMap::new();
+        for pair in chars.windows(2) {
+            *counts.entry((pair[0], pair[1])).or_default() += 1;
+        }
+        counts
+    };
+
+    let (left, right) = (bigrams(a), bigrams(b));
+    let total: usize = left.values().sum::<usize>() + right.values().sum::<usize>();
+    if total == 0 {
+        return 0.0;
+    }
+
+    let common: usize = left
+        .iter()
+        .map(|(pair, count)| (*count).min(right.get(pair).copied().unwrap_or(0)))
+        .sum();
+    2.0 * common as f64 / total as f64
+}
+
+/// 原地更新文本的块
+#[derive(Debug, Clone, Serialize)]
+pub struct TextUpdate {
+    /// 文档中的块 ID
+    pub block_id: String,
+    /// 对应的本地块临时 ID
+    pub local_block_id: String,
+}
+
+/// 一段连续的修改：删除文档中 [index, index + delete_count) 的块，并在 index 处插入本地块
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiffHunk {
+    /// 在父块中的位置（基于修改前的文档）
+    pub index: i32,
+    /// 删除的块数
+    pub delete_count: usize,
+    /// 插入的本地一级块临时 ID
+    pub insert: Vec<String>,
+}
+
+/// 一级块列表的差异
+#[derive(Debug, Default, Serialize)]
+pub struct BlockDiff {
+    /// 内容相同、保持不变的块数
+    pub unchanged: usize,
+    /// 原地更新文本的块
+    pub updates: Vec<TextUpdate>,
+    /// 删除和插入，按位置升序排列
+    pub hunks: Vec<DiffHunk>,
+}
+
+impl BlockDiff {
+    /// 是否没有任何修改
+    pub fn is_empty(&self) -> bool {
+        self.updates.is_empty() && self.hunks.is_empty()
+    }
+}
+
+/// 计算把文档中的一级块 `remote` 变为本地一级块 `local` 所需的最少修改
+///
+/// 先按子树指纹求最长公共子序列（线性空间的 Myers 算法），未变化的块保持不动；两个不变块之间的块按位置配对，
+/// 同类型且内容相似的块原地更新文本，其余块删除后重新创建。
+pub fn diff_blocks(remote: &[BlockSummary], local: &[BlockSummary]) -> BlockDiff {
+    let remote_keys: Vec<String> = remote.iter().map(|b| b.fingerprint()).collect();
+    let local_keys: Vec<String> = local.iter().map(|b| b.fingerprint()).collect();
+
+    let (n, m) = (remote.len(), local.len());
+    let mut diff = BlockDiff::default();
+    let mut hunk: Option<DiffHunk> = None;
+    let (mut gap_remote, mut gap_local) = (0, 0);
+
+    for (i, j) in matching_pairs(&remote_keys, &local_keys).into_iter().chain(std::iter::once((n, m))) {
+        // 处理上一个不变块与当前不变块之间的块
+        diff_gap(remote, local, gap_remote..i, gap_local..j, &mut diff, &mut hunk);
+        diff.hunks.extend(hunk.take());
+        if i == n {
+            break;
+        }
+        diff.unchanged += 1;
+        gap_remote = i + 1;
+        gap_local = j + 1;
+    }
+
+    diff
+}
+
+fn diff_gap(
+    remote: &[BlockSummary],
+    local: &[BlockSummary],
+    remote_range: std::ops::Range<usize>,
+    local_range: std::ops::Range<usize>,
+    diff: &mut BlockDiff,
+    hunk: &mut Option<DiffHunk>,
+) {
+    let len = remote_range.len().max(local_range.len());
+    for k in 0..len {
+        let r = Some(remote_range.start + k).filter(|r| remote_range.contains(r));
+        let l = Some(local_range.start + k).filter(|l| local_range.contains(l));
+
+        if let (Some(r), Some(l)) = (r, l) {
+            if remote[r].can_update_to(&local[l]) {
+                diff.hunks.extend(hunk.take());
+                diff.updates.push(TextUpdate {
+                    block_id: remote[r].block_id.clone(),
+                    local_block_id: local[l].block_id.clone(),
+                });
+                continue;
+            }
+        }
+
+        // 删除的块总在插入位置之后，连续的修改合并为一段
+        let index = r.unwrap_or(remote_range.end);
+        let current = hunk.get_or_insert_with(|| DiffHunk {
+            index: index as i32,
+            delete_count: 0,
+            insert: Vec::new(),
+        });
+        if r.is_some() {
+            current.delete_count += 1;
+        }
+        if let Some(l) = l {
+            current.insert.push(local[l].block_id.clone());
+        }
+    }
+}
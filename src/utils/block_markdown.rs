@@ -0,0 +1,300 @@
+use crate::api::get_blocks::BlockItem;
+use crate::utils::BlockTree;
+use serde_json::Value;
+
+/// 块类型对应的内容字段名
+pub fn block_type_key(block_type: i32) -> Option<&'static str> {
+    let key = match block_type {
+        1 => "page",
+        2 => "text",
+        3 => "heading1",
+        4 => "heading2",
+        5 => "heading3",
+        6 => "heading4",
+        7 => "heading5",
+        8 => "heading6",
+        9 => "heading7",
+        10 => "heading8",
+        11 => "heading9",
+        12 => "bullet",
+        13 => "ordered",
+        14 => "code",
+        15 => "quote",
+        17 => "todo",
+        19 => "callout",
+        22 => "divider",
+        23 => "file",
+        24 => "grid",
+        25 => "grid_column",
+        27 => "image",
+        30 => "sheet",
+        31 => "table",
+        32 => "table_cell",
+        34 => "quote_container",
+        43 => "board",
+        _ => return None,
+    };
+    Some(key)
+}
+
+/// 标题块的级别（1-9），非标题块返回 None
+pub fn heading_level(block_type: i32) -> Option<usize> {
+    (3..=11).contains(&block_type).then(|| (block_type - 2) as usize)
+}
+
+/// 块的文本片段（文本类块才有）
+pub fn block_elements(block: &BlockItem) -> Option<&Value> {
+    let key = block_type_key(block.block_type)?;
+    block.content.get(key)?.get("elements")
+}
+
+/// 块的纯文本内容
+pub fn block_plain_text(block: &BlockItem) -> String {
+    block_elements(block).map(elements_plain_text).unwrap_or_default()
+}
+
+/// 文本片段拼接后的纯文本
+pub fn elements_plain_text(elements: &Value) -> String {
+    elements
+        .as_array()
+        .map(|elements| elements.iter().map(element_plain_text).collect())
+        .unwrap_or_default()
+}
+
+fn element_plain_text(element: &Value) -> String {
+    if let Some(content) = element.pointer("/text_run/content").and_then(|c| c.as_str()) {
+        content.to_string()
+    } else if let Some(user) = element.pointer("/mention_user/user_id").and_then(|u| u.as_str()) {
+        format!("@{}", user)
+    } else if let Some(title) = element.pointer("/mention_doc/title").and_then(|t| t.as_str()) {
+        title.to_string()
+    } else if let Some(equation) = element.pointer("/equation/content").and_then(|c| c.as_str()) {
+        equation.trim_end().to_string()
+    } else {
+        String::new()
+    }
+}
+
+/// 将文本片段渲染为行内 Markdown
+pub fn elements_to_markdown(elements: &Value) -> String {
+    let Some(elements) = elements.as_array() else {
+        return String::new();
+    };
+
+    elements
+        .iter()
+        .map(|element| {
+            if let Some(run) = element.get("text_run") {
+                let content = run.get("content").and_then(|c| c.as_str()).unwrap_or_default();
+                styled(content, run.get("text_element_style"))
+            } else if let Some(doc) = element.get("mention_doc") {
+                let title = doc.get("title").and_then(|t| t.as_str()).unwrap_or_default();
+                let url = doc.get("url").and_then(|u| u.as_str()).unwrap_or_default();
+                format!("[{}]({})", title, percent_decode(url))
+            } else if let Some(equation) = element.pointer("/equation/content").and_then(|c| c.as_str()) {
+                format!("${}$", equation.trim_end())
+            } else {
+                element_plain_text(element)
+            }
+        })
+        .collect()
+}
+
+fn styled(content: &str, style: Option<&Value>) -> String {
+    let flag = |name: &str| style.and_then(|s| s.get(name)).and_then(|v| v.as_bool()).unwrap_or(false);
+    if content.is_empty() {
+        return String::new();
+    }
+
+    let mut text = if flag("inline_code") {
+        format!("`{}`", content)
+    } else {
+        content.to_string()
+    };
+    if flag("bold") {
+        text = format!("**{}**", text);
+    }
+    if flag("italic") {
+        text = format!("*{}*", text);
+    }
+    if flag("strikethrough") {
+        text = format!("~~{}~~", text);
+    }
+    if let Some(url) = style.and_then(|s| s.pointer("/link/url")).and_then(|u| u.as_str()) {
+        text = format!("[{}]({})", text, percent_decode(url));
+    }
+    text
+}
+
+/// 解码链接中的百分号编码（飞书返回的链接地址经过 URL 编码）
+pub fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut output = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(byte) = hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                output.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        output.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&output).into_owned()
+}
+
+/// 对链接地址进行百分号编码（写入文本链接时飞书要求 URL 编码）
+pub fn percent_encode(input: &str) -> String {
+    input
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// 单个块（不含子块）的 Markdown 表示
+pub fn block_to_markdown(block: &BlockItem) -> String {
+    let text = || block_elements(block).map(elements_to_markdown).unwrap_or_default();
+
+    if let Some(level) = heading_level(block.block_type) {
+        return format!("{} {}", "#".repeat(level.min(6)), text());
+    }
+
+    match block.block_type {
+        1 | 2 => text(),
+        12 => format!("- {}", text()),
+        13 => format!("1. {}", text()),
+        14 => {
+            let code = block_elements(block).map(elements_plain_text).unwrap_or_default();
+            format!("```\n{}\n```", code)
+        }
+        15 => format!("> {}", text()),
+        17 => {
+            let done = block.content.pointer("/todo/style/done").and_then(|d| d.as_bool()).unwrap_or(false);
+            format!("- [{}] {}", if done { "x" } else { " " }, text())
+        }
+        22 => "---".to_string(),
+        27 => {
+            let token = block.content.pointer("/image/token").and_then(|t| t.as_str()).unwrap_or_default();
+            format!("![](image:{})", token)
+        }
+        43 => {
+            let token = block.content.pointer("/board/token").and_then(|t| t.as_str()).unwrap_or_default();
+            format!("<!-- board: {} -->", token)
+        }
+        block_type => match block_type_key(block_type) {
+            Some(key) => format!("<!-- {} -->", key),
+            None => format!("<!-- block_type {} -->", block_type),
+        },
+    }
+}
+
+/// 将文档渲染为 Markdown：标题写在开头的 Front Matter 中，正文按块顺序渲染
+pub fn render_document(tree: &BlockTree, document_id: &str) -> String {
+    let mut lines = Vec::new();
+    if let Some(page) = tree.get(document_id) {
+        lines.push("---".to_string());
+        lines.push(format!("title: {}", block_plain_text(page)));
+        lines.push("---".to_string());
+        lines.push(String::new());
+    }
+
+    lines.extend(render_children(tree, document_id, 0));
+    let mut markdown = lines.join("\n");
+    markdown.push('\n');
+    markdown
+}
+
+/// 渲染父块的所有子块，相邻的列表项之间不留空行
+pub fn render_children(tree: &BlockTree, parent_id: &str, depth: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut previous_list = false;
+
+    for child_id in tree.children(parent_id) {
+        let Some(block) = tree.get(child_id) else {
+            continue;
+        };
+        let is_list = matches!(block.block_type, 12 | 13 | 17);
+        if !(lines.is_empty() || is_list && previous_list) {
+            lines.push(String::new());
+        }
+        lines.extend(render_block(tree, block, depth));
+        previous_list = is_list;
+    }
+
+    lines
+}
+
+fn render_block(tree: &BlockTree, block: &BlockItem, depth: usize) -> Vec<String> {
+    let indent = "  ".repeat(depth);
+    match block.block_type {
+        // 高亮块、引用容器：子块整体作为引用
+        19 | 34 => render_children(tree, &block.block_id, 0)
+            .into_iter()
+            .map(|line| format!("{}>{}{}", indent, if line.is_empty() { "" } else { " " }, line))
+            .collect(),
+        // 分栏：按顺序渲染每一栏
+        24 | 25 => render_children(tree, &block.block_id, depth),
+        31 => render_table(tree, block)
+            .into_iter()
+            .map(|line| format!("{}{}", indent, line))
+            .collect(),
+        block_type => {
+            let mut lines: Vec<String> = block_to_markdown(block)
+                .lines()
+                .map(|line| format!("{}{}", indent, line))
+                .collect();
+            if lines.is_empty() {
+                lines.push(indent.clone());
+            }
+
+            // 列表项的子块缩进一级，其他块的子块与父块对齐
+            let child_depth = if matches!(block_type, 12 | 13 | 17) { depth + 1 } else { depth };
+            let children = render_children(tree, &block.block_id, child_depth);
+            if !children.is_empty() {
+                if !matches!(block_type, 12 | 13 | 17) {
+                    lines.push(String::new());
+                }
+                lines.extend(children);
+            }
+            lines
+        }
+    }
+}
+
+/// 表格渲染为 Markdown 表格，第一行作为表头，单元格内多个块以 <br> 连接
+fn render_table(tree: &BlockTree, table: &BlockItem) -> Vec<String> {
+    let columns = table
+        .content
+        .pointer("/table/property/column_size")
+        .and_then(|c| c.as_u64())
+        .unwrap_or(1)
+        .max(1) as usize;
+
+    let cells: Vec<String> = tree
+        .children(&table.block_id)
+        .iter()
+        .map(|cell_id| {
+            render_children(tree, cell_id, 0)
+                .into_iter()
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join("<br>")
+                .replace('|', "\\|")
+        })
+        .collect();
+
+    let mut lines = Vec::new();
+    for (i, row) in cells.chunks(columns).enumerate() {
+        lines.push(format!("| {} |", row.join(" | ")));
+        if i == 0 {
+            lines.push(format!("|{}", " --- |".repeat(columns)));
+        }
+    }
+    lines
+}
@@ -0,0 +1,501 @@
+use crate::api::get_blocks::BlockItem;
+use crate::error::{LarkError, Result};
+use crate::utils::{block_plain_text, block_type_key, document_order, index_ranges, BlockTree};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// 块选择器，语法类似 CSS 选择器
+///
+/// - 类型：`heading2`、`todo`、`callout`、`*`，也可以写作 `type=todo`
+/// - ID：`#doxcnAbCdEf`
+/// - 属性：`[done=true]`、`[language!=1]`、`[align]`，在块内容及其 style 中查找
+/// - 伪类：`:contains("文本")`，以及在当前匹配结果中取位置的 `:first`、`:last`、`:nth(n)`
+/// - 组合：空格（后代）、`>`（子块）、`~`（之后的所有同级块）、`+`（紧邻的下一个同级块）
+/// - 多个选择器以逗号分隔，结果取并集
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockSelector {
+    selectors: Vec<Vec<Step>>,
+}
+
+/// 选择器中的一步：与上一步的关系和本步的条件
+#[derive(Debug, Clone, PartialEq)]
+struct Step {
+    combinator: Combinator,
+    compound: Compound,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Combinator {
+    /// 第一步，从所有块中选择
+    Root,
+    Descendant,
+    Child,
+    FollowingSibling,
+    NextSibling,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Compound {
+    block_type: Option<i32>,
+    block_id: Option<String>,
+    attributes: Vec<Attribute>,
+    contains: Vec<String>,
+    position: Vec<Position>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Attribute {
+    name: String,
+    /// (是否取反, 值)，为空时只要求属性存在
+    condition: Option<(bool, String)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+    First,
+    Last,
+    /// 从 1 开始
+    Nth(usize),
+}
+
+/// 选中的块在父块中的一段连续子块
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SelectionRange {
+    /// 父块 ID
+    pub parent_id: String,
+    /// 起始位置（包含）
+    pub start_index: i32,
+    /// 结束位置（不包含）
+    pub end_index: i32,
+    /// 区间内的块 ID
+    pub block_ids: Vec<String>,
+}
+
+impl BlockSelector {
+    /// 解析选择器
+    pub fn parse(selector: &str) -> Result<Self> {
+        Parser::new(selector).parse()
+    }
+
+    /// 在以 `root_id` 为根的块树中求值，按文档顺序返回选中的块 ID
+    pub fn select(&self, tree: &BlockTree, root_id: &str) -> Vec<String> {
+        let order = document_order(tree, root_id);
+        let position: HashMap<&str, usize> = order.iter().enumerate().map(|(i, id)| (id.as_str(), i)).collect();
+
+        let mut selected: HashSet<String> = HashSet::new();
+        for steps in &self.selectors {
+            let mut current: Vec<String> = Vec::new();
+            for step in steps {
+                let candidates: HashSet<String> = match step.combinator {
+                    Combinator::Root => order.iter().cloned().collect(),
+                    combinator => current
+                        .iter()
+                        .flat_map(|id| related(tree, id, combinator))
+                        .collect(),
+                };
+                let mut matched: Vec<String> = candidates
+                    .into_iter()
+                    .filter(|id| position.contains_key(id.as_str()))
+                    .filter(|id| tree.get(id).is_some_and(|block| step.compound.matches(block)))
+                    .collect();
+                matched.sort_by_key(|id| position[id.as_str()]);
+                current = step.compound.apply_position(matched);
+            }
+            selected.extend(current);
+        }
+
+        let mut result: Vec<String> = selected.into_iter().collect();
+        result.sort_by_key(|id| position[id.as_str()]);
+        result
+    }
+}
+
+fn related(tree: &BlockTree, block_id: &str, combinator: Combinator) -> Vec<String> {
+    match combinator {
+        Combinator::Root => Vec::new(),
+        Combinator::Descendant => {
+            let mut descendants = document_order(tree, block_id);
+            descendants.remove(0);
+            descendants
+        }
+        Combinator::Child => tree.children(block_id).to_vec(),
+        Combinator::FollowingSibling | Combinator::NextSibling => {
+            let Some(parent_id) = tree.get(block_id).map(|b| b.parent_id.as_str()) else {
+                return Vec::new();
+            };
+            let siblings = tree.children(parent_id);
+            let Some(index) = siblings.iter().position(|id| id == block_id) else {
+                return Vec::new();
+            };
+            let following = &siblings[index + 1..];
+            if combinator == Combinator::NextSibling {
+                following.iter().take(1).cloned().collect()
+            } else {
+                following.to_vec()
+            }
+        }
+    }
+}
+
+impl Compound {
+    fn is_empty(&self) -> bool {
+        *self == Compound::default()
+    }
+
+    fn matches(&self, block: &BlockItem) -> bool {
+        if self.block_type.is_some_and(|t| t != block.block_type) {
+            return false;
+        }
+        if self.block_id.as_ref().is_some_and(|id| *id != block.block_id) {
+            return false;
+        }
+        if !self.contains.is_empty() {
+            let text = block_plain_text(block);
+            if !self.contains.iter().all(|c| text.contains(c.as_str())) {
+                return false;
+            }
+        }
+        self.attributes.iter().all(|attribute| attribute.matches(block))
+    }
+
+    fn apply_position(&self, mut matched: Vec<String>) -> Vec<String> {
+        for position in &self.position {
+            matched = match position {
+                Position::First => matched.into_iter().take(1).collect(),
+                Position::Last => matched.pop().into_iter().collect(),
+                Position::Nth(n) => matched.into_iter().nth(n - 1).into_iter().collect(),
+            };
+        }
+        matched
+    }
+}
+
+impl Attribute {
+    fn matches(&self, block: &BlockItem) -> bool {
+        let value = attribute_value(block, &self.name);
+        match (&self.condition, value) {
+            (None, value) => value.is_some(),
+            (Some((negate, expected)), value) => {
+                // 未设置的布尔属性视为 false
+                let equal = match value {
+                    Some(value) => value == *expected,
+                    None => expected == "false",
+                };
+                equal != *negate
+            }
+        }
+    }
+}
+
+/// 在块内容中查找属性值：先查找类型对应的内容，再查找其中的 style
+fn attribute_value(block: &BlockItem, name: &str) -> Option<String> {
+    let content = block_type_key(block.block_type).and_then(|key| block.content.get(key));
+    let value = content
+        .and_then(|c| c.get(name))
+        .or_else(|| content.and_then(|c| c.pointer(&format!("/style/{}", name))))
+        .or_else(|| block.content.get(name))?;
+    Some(match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    })
+}
+
+/// 根据名称或编号查找块类型
+fn block_type_from_name(name: &str) -> Option<i32> {
+    if let Ok(number) = name.parse::<i32>() {
+        return Some(number);
+    }
+    (1..=60).find(|&t| block_type_key(t) == Some(name))
+}
+
+/// 将选中的块转换为每个父块下连续的子块区间
+///
+/// 祖先块已被选中的块不再单独列出；区间按父块、起始位置排列。
+pub fn selection_ranges(tree: &BlockTree, block_ids: &[String]) -> Result<Vec<SelectionRange>> {
+    let selected: HashSet<&str> = block_ids.iter().map(String::as_str).collect();
+    let mut by_parent: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+
+    for block_id in block_ids {
+        let block = tree
+            .get(block_id)
+            .ok_or_else(|| LarkError::ValidationError(format!("找不到块: {}", block_id)))?;
+        if has_selected_ancestor(tree, block, &selected) {
+            continue;
+        }
+        if block.parent_id.is_empty() {
+            return Err(LarkError::ValidationError(format!("块 {} 是文档根块，没有父块", block_id)));
+        }
+        let index = tree.child_index(&block.parent_id, block_id).ok_or_else(|| {
+            LarkError::ValidationError(format!("块 {} 不在父块 {} 的子块中", block_id, block.parent_id))
+        })?;
+        by_parent.entry(block.parent_id.clone()).or_default().push(index);
+    }
+
+    Ok(by_parent
+        .into_iter()
+        .flat_map(|(parent_id, indices)| {
+            let children = tree.children(&parent_id).to_vec();
+            index_ranges(&indices)
+                .into_iter()
+                .map(move |(start, end)| SelectionRange {
+                    parent_id: parent_id.clone(),
+                    start_index: start,
+                    end_index: end,
+                    block_ids: children[start as usize..end as usize].to_vec(),
+                })
+        })
+        .collect())
+}
+
+fn has_selected_ancestor(tree: &BlockTree, block: &BlockItem, selected: &HashSet<&str>) -> bool {
+    let mut parent_id = block.parent_id.as_str();
+    while let Some(parent) = tree.get(parent_id) {
+        if selected.contains(parent_id) {
+            return true;
+        }
+        parent_id = parent.parent_id.as_str();
+    }
+    false
+}
+
+/// 选择器解析器
+struct Parser<'a> {
+    source: &'a str,
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            chars: source.chars().collect(),
+            pos: 0,
+        }
+    }
+
+    fn parse(mut self) -> Result<BlockSelector> {
+        let mut selectors = Vec::new();
+        loop {
+            selectors.push(self.parse_selector()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                None => break,
+                Some(c) => return Err(self.error(&format!("无法识别的字符 '{}'", c))),
+            }
+        }
+        Ok(BlockSelector { selectors })
+    }
+
+    fn parse_selector(&mut self) -> Result<Vec<Step>> {
+        self.skip_whitespace();
+        let mut steps = vec![Step {
+            combinator: Combinator::Root,
+            compound: self.parse_compound()?,
+        }];
+
+        loop {
+            let had_whitespace = self.skip_whitespace();
+            let combinator = match self.peek() {
+                Some('>') => Combinator::Child,
+                Some('~') => Combinator::FollowingSibling,
+                Some('+') => Combinator::NextSibling,
+                Some(',') | None => break,
+                Some(_) if had_whitespace => Combinator::Descendant,
+                Some(c) => return Err(self.error(&format!("无法识别的字符 '{}'", c))),
+            };
+            if combinator != Combinator::Descendant {
+                self.pos += 1;
+                self.skip_whitespace();
+            }
+            steps.push(Step {
+                combinator,
+                compound: self.parse_compound()?,
+            });
+        }
+        Ok(steps)
+    }
+
+    fn parse_compound(&mut self) -> Result<Compound> {
+        let mut compound = Compound::default();
+        let mut universal = false;
+
+        match self.peek() {
+            Some('*') => {
+                self.pos += 1;
+                universal = true;
+            }
+            Some(c) if c.is_ascii_alphanumeric() => {
+                let name = self.read_identifier();
+                let name = if name == "type" && self.peek() == Some('=') {
+                    self.pos += 1;
+                    self.read_identifier()
+                } else {
+                    name
+                };
+                compound.block_type = Some(
+                    block_type_from_name(&name).ok_or_else(|| self.error(&format!("未知的块类型 {}", name)))?,
+                );
+            }
+            _ => {}
+        }
+
+        loop {
+            match self.peek() {
+                Some('#') => {
+                    self.pos += 1;
+                    let id = self.read_identifier();
+                    if id.is_empty() {
+                        return Err(self.error("# 之后缺少块 ID"));
+                    }
+                    compound.block_id = Some(id);
+                }
+                Some('[') => {
+                    self.pos += 1;
+                    compound.attributes.push(self.parse_attribute()?);
+                }
+                Some(':') => {
+                    self.pos += 1;
+                    self.parse_pseudo(&mut compound)?;
+                }
+                _ => break,
+            }
+        }
+
+        if compound.is_empty() && !universal {
+            return Err(self.error("缺少选择条件"));
+        }
+        Ok(compound)
+    }
+
+    fn parse_attribute(&mut self) -> Result<Attribute> {
+        self.skip_whitespace();
+        let name = self.read_identifier();
+        if name.is_empty() {
+            return Err(self.error("缺少属性名"));
+        }
+        self.skip_whitespace();
+
+        let condition = match self.peek() {
+            Some(']') => None,
+            Some('=') => {
+                self.pos += 1;
+                Some((false, self.read_value()?))
+            }
+            Some('!') if self.chars.get(self.pos + 1) == Some(&'=') => {
+                self.pos += 2;
+                Some((true, self.read_value()?))
+            }
+            _ => return Err(self.error("属性条件只支持 = 和 !=")),
+        };
+
+        self.skip_whitespace();
+        self.expect(']')?;
+        Ok(Attribute { name, condition })
+    }
+
+    fn parse_pseudo(&mut self, compound: &mut Compound) -> Result<()> {
+        let name = self.read_identifier();
+        match name.as_str() {
+            "first" => compound.position.push(Position::First),
+            "last" => compound.position.push(Position::Last),
+            "nth" => {
+                self.expect('(')?;
+                self.skip_whitespace();
+                let number = self.read_identifier();
+                let n: usize = number
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or_else(|| self.error(&format!(":nth 需要大于 0 的整数，当前为 {}", number)))?;
+                self.skip_whitespace();
+                self.expect(')')?;
+                compound.position.push(Position::Nth(n));
+            }
+            "contains" => {
+                self.expect('(')?;
+                self.skip_whitespace();
+                let text = self.read_value()?;
+                self.skip_whitespace();
+                self.expect(')')?;
+                compound.contains.push(text);
+            }
+            _ => {
+                return Err(self.error(&format!(
+                    "未知的伪类 :{}。可选值: contains, first, last, nth",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// 读取带引号的字符串或不含空白和括号的值
+    fn read_value(&mut self) -> Result<String> {
+        self.skip_whitespace();
+        let Some(quote) = self.peek().filter(|c| *c == '"' || *c == '\'') else {
+            let start = self.pos;
+            while self.peek().is_some_and(|c| !c.is_whitespace() && !matches!(c, ']' | ')')) {
+                self.pos += 1;
+            }
+            return Ok(self.chars[start..self.pos].iter().collect());
+        };
+
+        self.pos += 1;
+        let mut value = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error("字符串缺少结束引号")),
+                Some('\\') => {
+                    self.pos += 1;
+                    if let Some(c) = self.peek() {
+                        value.push(c);
+                        self.pos += 1;
+                    }
+                }
+                Some(c) if c == quote => {
+                    self.pos += 1;
+                    return Ok(value);
+                }
+                Some(c) => {
+                    value.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    fn read_identifier(&mut self) -> String {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.pos;
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+        self.pos > start
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, expected: char) -> Result<()> {
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("缺少 '{}'", expected)))
+        }
+    }
+
+    fn error(&self, message: &str) -> LarkError {
+        LarkError::ValidationError(format!("无效的选择器 \"{}\"（位置 {}）: {}", self.source, self.pos, message))
+    }
+}
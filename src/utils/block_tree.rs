@@ -0,0 +1,75 @@
+use crate::api::get_blocks::BlockItem;
+use std::collections::HashMap;
+
+/// 文档块树，基于获取文档所有块接口返回的扁平列表建立索引
+#[derive(Debug)]
+pub struct BlockTree {
+    blocks: Vec<BlockItem>,
+    index: HashMap<String, usize>,
+}
+
+impl BlockTree {
+    /// 根据块列表创建块树
+    pub fn new(blocks: Vec<BlockItem>) -> Self {
+        let index = blocks
+            .iter()
+            .enumerate()
+            .map(|(i, block)| (block.block_id.clone(), i))
+            .collect();
+
+        Self { blocks, index }
+    }
+
+    /// 所有块，按接口返回的顺序排列
+    pub fn blocks(&self) -> &[BlockItem] {
+        &self.blocks
+    }
+
+    /// 根据块 ID 获取块
+    pub fn get(&self, block_id: &str) -> Option<&BlockItem> {
+        self.index.get(block_id).map(|&i| &self.blocks[i])
+    }
+
+    /// 获取块的子块 ID 列表
+    pub fn children(&self, block_id: &str) -> &[String] {
+        self.get(block_id)
+            .and_then(|block| block.children.as_deref())
+            .unwrap_or(&[])
+    }
+
+    /// 获取子块在父块中的位置
+    pub fn child_index(&self, parent_id: &str, child_id: &str) -> Option<usize> {
+        self.children(parent_id).iter().position(|id| id == child_id)
+    }
+}
+
+/// 按文档顺序（深度优先）列出根块及其所有后代
+pub fn document_order(tree: &BlockTree, root_id: &str) -> Vec<String> {
+    let mut order = Vec::new();
+    let mut stack = vec![root_id.to_string()];
+    while let Some(id) = stack.pop() {
+        if tree.get(&id).is_none() {
+            continue;
+        }
+        stack.extend(tree.children(&id).iter().rev().cloned());
+        order.push(id);
+    }
+    order
+}
+
+/// 将子块位置合并为连续的 [start, end) 区间，按起始位置升序排列
+pub fn index_ranges(indices: &[usize]) -> Vec<(i32, i32)> {
+    let mut sorted = indices.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+
+    let mut ranges: Vec<(i32, i32)> = Vec::new();
+    for index in sorted {
+        let index = index as i32;
+        match ranges.last_mut() {
+            Some((_, end)) if *end == index => *end += 1,
+            _ => ranges.push((index, index + 1)),
+        }
+    }
+    ranges
+}
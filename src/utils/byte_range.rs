@@ -0,0 +1,97 @@
+use base64::Engine;
+
+/// 下载范围，对应 HTTP Range 请求头 `bytes=start-end`，`end` 为空表示到文件末尾
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    pub start: u64,
+    /// 结束位置（包含）
+    pub end: Option<u64>,
+}
+
+impl ByteRange {
+    /// Range 请求头的值
+    pub fn header_value(&self) -> String {
+        match self.end {
+            Some(end) => format!("bytes={}-{}", self.start, end),
+            None => format!("bytes={}-", self.start),
+        }
+    }
+}
+
+impl std::str::FromStr for ByteRange {
+    type Err = String;
+
+    /// 解析 `start-end`、`start-`，可带 `bytes=` 前缀
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || format!("无效的下载范围: {}。格式: start-end 或 start-，如 0-1023", s);
+        let value = s.trim();
+        let value = value.strip_prefix("bytes=").unwrap_or(value);
+        let (start, end) = value.split_once('-').ok_or_else(invalid)?;
+        let start: u64 = start.trim().parse().map_err(|_| invalid())?;
+        let end = match end.trim() {
+            "" => None,
+            end => Some(end.parse::<u64>().map_err(|_| invalid())?),
+        };
+        if end.is_some_and(|end| end < start) {
+            return Err(invalid());
+        }
+        Ok(ByteRange { start, end })
+    }
+}
+
+/// Content-Range 响应头：`bytes start-end/total` 或 `bytes */total`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentRange {
+    /// 本次返回的范围（包含两端），范围无法满足时为 None
+    pub range: Option<(u64, u64)>,
+    /// 文件总大小，未知时为 None
+    pub total: Option<u64>,
+}
+
+/// 解析 Content-Range 响应头
+pub fn parse_content_range(value: &str) -> Option<ContentRange> {
+    let (range, total) = value.trim().strip_prefix("bytes")?.trim().split_once('/')?;
+    let total = match total.trim() {
+        "*" => None,
+        total => Some(total.parse().ok()?),
+    };
+    let range = match range.trim() {
+        "*" => None,
+        range => {
+            let (start, end) = range.split_once('-')?;
+            Some((start.trim().parse().ok()?, end.trim().parse().ok()?))
+        }
+    };
+    Some(ContentRange { range, total })
+}
+
+/// 从 `Digest: SHA-256=<base64>` 或 `Repr-Digest: sha-256=:<base64>:` 响应头中取出 SHA-256 摘要
+pub fn parse_sha256_digest(value: &str) -> Option<Vec<u8>> {
+    value.split(',').find_map(|item| {
+        let (algorithm, digest) = item.trim().split_once('=')?;
+        if !algorithm.trim().eq_ignore_ascii_case("sha-256") {
+            return None;
+        }
+        let digest = digest.trim().trim_matches(':');
+        base64::engine::general_purpose::STANDARD
+            .decode(digest)
+            .ok()
+            .filter(|d| d.len() == 32)
+    })
+}
+
+/// 可读的字节数，如 `1.5 MB`
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
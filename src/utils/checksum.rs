@@ -0,0 +1,19 @@
+/// Adler-32 的模数
+const ADLER_MOD: u32 = 65521;
+
+/// 在 u32 溢出前最多累加的字节数
+const ADLER_NMAX: usize = 5552;
+
+/// 计算 Adler-32 校验和，云空间上传接口用它校验文件和分片内容
+pub fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in data.chunks(ADLER_NMAX) {
+        for byte in chunk {
+            a += u32::from(*byte);
+            b += a;
+        }
+        a %= ADLER_MOD;
+        b %= ADLER_MOD;
+    }
+    (b << 16) | a
+}
@@ -0,0 +1,180 @@
+use crate::api::comments::{Comment, ReplyContent};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// 评论导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentExportFormat {
+    Markdown,
+    Json,
+}
+
+impl std::str::FromStr for CommentExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "markdown" | "md" => Ok(CommentExportFormat::Markdown),
+            "json" => Ok(CommentExportFormat::Json),
+            _ => Err(format!("无效的导出格式: {}。可选值: markdown, json", s)),
+        }
+    }
+}
+
+/// 导出的评论
+#[derive(Debug, Serialize)]
+pub struct CommentExport {
+    pub file_token: String,
+    pub file_type: String,
+    pub comment_count: usize,
+    pub comments: Vec<ExportedComment>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedComment {
+    pub comment_id: String,
+    /// 局部评论引用的文档内容，全文评论为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quote: Option<String>,
+    pub is_whole: bool,
+    pub is_solved: bool,
+    /// 评论者名字，查询失败时为 open_id
+    pub author: String,
+    pub author_id: String,
+    pub created_at: String,
+    /// 解决者名字
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub solved_by: Option<String>,
+    /// 回复列表，第一条为评论本身的内容
+    pub replies: Vec<ExportedReply>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedReply {
+    pub reply_id: String,
+    pub author: String,
+    pub author_id: String,
+    pub created_at: String,
+    pub text: String,
+}
+
+/// 评论中出现的所有用户（评论者、回复者、解决者和 @用户），用于查询名字
+pub fn comment_user_ids(comments: &[Comment]) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    let mut add = |id: &str| {
+        if !id.is_empty() && !ids.iter().any(|i| i == id) {
+            ids.push(id.to_string());
+        }
+    };
+    for comment in comments {
+        add(&comment.user_id);
+        if let Some(solver) = &comment.solver_user_id {
+            add(solver);
+        }
+        for reply in &comment.reply_list.replies {
+            add(&reply.user_id);
+            for person in reply.content.elements.iter().filter_map(|e| e.person.as_ref()) {
+                add(&person.user_id);
+            }
+        }
+    }
+    ids
+}
+
+/// 转换为导出结构，`names` 为 open_id 到名字的映射，缺少的用户保留 open_id
+pub fn export_comments(
+    file_token: &str,
+    file_type: &str,
+    comments: &[Comment],
+    names: &HashMap<String, String>,
+) -> CommentExport {
+    let name = |id: &str| names.get(id).cloned().unwrap_or_else(|| id.to_string());
+    let comments: Vec<ExportedComment> = comments
+        .iter()
+        .map(|comment| ExportedComment {
+            comment_id: comment.comment_id.clone(),
+            quote: comment.quote.clone().filter(|q| !q.is_empty()),
+            is_whole: comment.is_whole,
+            is_solved: comment.is_solved,
+            author: name(&comment.user_id),
+            author_id: comment.user_id.clone(),
+            created_at: format_timestamp(comment.create_time),
+            solved_by: comment
+                .solver_user_id
+                .as_deref()
+                .filter(|id| comment.is_solved && !id.is_empty())
+                .map(name),
+            replies: comment
+                .reply_list
+                .replies
+                .iter()
+                .map(|reply| ExportedReply {
+                    reply_id: reply.reply_id.clone(),
+                    author: name(&reply.user_id),
+                    author_id: reply.user_id.clone(),
+                    created_at: format_timestamp(reply.create_time),
+                    text: reply_text(&reply.content, names),
+                })
+                .collect(),
+        })
+        .collect();
+
+    CommentExport {
+        file_token: file_token.to_string(),
+        file_type: file_type.to_string(),
+        comment_count: comments.len(),
+        comments,
+    }
+}
+
+/// 回复内容的文本：@用户显示为名字，文档链接显示为 URL
+pub fn reply_text(content: &ReplyContent, names: &HashMap<String, String>) -> String {
+    content
+        .elements
+        .iter()
+        .map(|element| {
+            if let Some(run) = &element.text_run {
+                run.text.clone()
+            } else if let Some(link) = &element.docs_link {
+                link.url.clone()
+            } else if let Some(person) = &element.person {
+                format!("@{}", names.get(&person.user_id).unwrap_or(&person.user_id))
+            } else {
+                String::new()
+            }
+        })
+        .collect()
+}
+
+/// 渲染为 Markdown：每条评论一个小节，引用内容作为引用块，回复按时间顺序列出
+pub fn render_comments_markdown(export: &CommentExport) -> String {
+    let mut output = format!("# 评论：{}\n\n共 {} 条评论\n", export.file_token, export.comment_count);
+    for (i, comment) in export.comments.iter().enumerate() {
+        let status = match &comment.solved_by {
+            Some(solver) => format!("已解决，{}", solver),
+            None if comment.is_solved => "已解决".to_string(),
+            None => "未解决".to_string(),
+        };
+        output.push_str(&format!("\n## 评论 {}（{}）\n\n", i + 1, status));
+        match &comment.quote {
+            Some(quote) => {
+                for line in quote.lines() {
+                    output.push_str(&format!("> {}\n", line));
+                }
+            }
+            None => output.push_str("> （全文评论）\n"),
+        }
+        output.push('\n');
+        for reply in &comment.replies {
+            let text = reply.text.trim_end().replace('\n', "\n  ");
+            output.push_str(&format!("- **{}**（{}）：{}\n", reply.author, reply.created_at, text));
+        }
+    }
+    output
+}
+
+fn format_timestamp(seconds: i64) -> String {
+    chrono::DateTime::from_timestamp(seconds, 0)
+        .map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_default()
+}
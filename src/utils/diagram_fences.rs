@@ -0,0 +1,154 @@
+use crate::api::board::{DiagramSyntax, DiagramType};
+
+/// Markdown 中识别出的图表代码块
+#[derive(Debug, Clone)]
+pub struct DiagramFence {
+    /// 插入在代码块之前的标记段落内容，转换后用于定位图表位置
+    pub marker: String,
+    /// 图表语法
+    pub syntax: DiagramSyntax,
+    /// 根据代码推断的图表类型
+    pub diagram_type: DiagramType,
+    /// 图表源码
+    pub source: String,
+}
+
+/// 查找 Markdown 中的 mermaid / plantuml 代码块，在每个代码块之前插入唯一的标记段落
+///
+/// 代码块本身保留在原位置，转换后标记段落的下一个兄弟块就是该代码块。
+pub fn mark_diagram_fences(markdown: &str) -> (String, Vec<DiagramFence>) {
+    let prefix = format!("LARKDIAGRAM{}", uuid::Uuid::new_v4().simple());
+    let lines: Vec<&str> = markdown.split_inclusive('\n').collect();
+
+    let mut output = String::with_capacity(markdown.len());
+    let mut fences = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        // 列表项或引用中的代码块带有缩进或 `>` 前缀，标记段落也要带上，才能留在同一容器中
+        let container = container_prefix(lines[i]);
+        let Some((fence, info)) = fence_open(&lines[i][container.len()..]) else {
+            output.push_str(lines[i]);
+            i += 1;
+            continue;
+        };
+
+        // 找到对应的结束行
+        let close =
+            (i + 1..lines.len()).find(|&j| is_fence_close(strip_container(lines[j], container), fence));
+        let end = close.unwrap_or(lines.len());
+        let source: String = lines[i + 1..end].iter().map(|l| strip_container(l, container)).collect();
+
+        if let (Some(_), Some(syntax)) = (close, diagram_syntax(info, &source)) {
+            let marker = format!("{}N{}", prefix, fences.len());
+            output.push_str(container);
+            output.push_str(&marker);
+            output.push('\n');
+            output.push_str(container.trim_end());
+            output.push('\n');
+            fences.push(DiagramFence {
+                marker,
+                syntax,
+                diagram_type: detect_diagram_type(syntax, &source),
+                source: source.trim_end().to_string(),
+            });
+        }
+
+        let next = close.map(|j| j + 1).unwrap_or(lines.len());
+        for line in &lines[i..next] {
+            output.push_str(line);
+        }
+        i = next;
+    }
+
+    (output, fences)
+}
+
+/// 行首的容器前缀：列表项缩进和引用的 `>`
+fn container_prefix(line: &str) -> &str {
+    let end = line.find(|c: char| !matches!(c, ' ' | '\t' | '>')).unwrap_or(line.len());
+    &line[..end]
+}
+
+/// 去掉代码块内各行与开始行相同的容器前缀，缩进较少的行只去掉共同部分
+fn strip_container<'a>(line: &'a str, container: &str) -> &'a str {
+    let common = line.bytes().zip(container.bytes()).take_while(|(a, b)| a == b).count();
+    &line[common..]
+}
+
+/// 解析代码块开始行，返回围栏字符串和语言标识
+pub(crate) fn fence_open(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim_start();
+    let ch = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = trimmed.chars().take_while(|c| *c == ch).count();
+    if len < 3 {
+        return None;
+    }
+
+    Some((&trimmed[..len], trimmed[len..].trim()))
+}
+
+pub(crate) fn is_fence_close(line: &str, fence: &str) -> bool {
+    let trimmed = line.trim();
+    let ch = fence.chars().next().unwrap_or('`');
+    trimmed.len() >= fence.len() && trimmed.chars().all(|c| c == ch)
+}
+
+/// 根据代码块语言标识（或代码内容）判断图表语法
+pub fn diagram_syntax(info: &str, source: &str) -> Option<DiagramSyntax> {
+    let language = info.split_whitespace().next().unwrap_or("").to_lowercase();
+    match language.as_str() {
+        "mermaid" | "mmd" => Some(DiagramSyntax::Mermaid),
+        "plantuml" | "puml" | "uml" => Some(DiagramSyntax::PlantUml),
+        "" if source.trim_start().starts_with("@start") => Some(DiagramSyntax::PlantUml),
+        _ => None,
+    }
+}
+
+/// 根据图表源码推断图表类型，无法判断时返回 Auto 交由服务端识别
+pub fn detect_diagram_type(syntax: DiagramSyntax, source: &str) -> DiagramType {
+    let lines: Vec<&str> = source
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with("%%") && !l.starts_with('\''))
+        .collect();
+
+    match syntax {
+        DiagramSyntax::Mermaid => {
+            let keyword = lines.first().and_then(|l| l.split_whitespace().next()).unwrap_or("");
+            match keyword {
+                "sequenceDiagram" => DiagramType::Sequence,
+                "classDiagram" | "classDiagram-v2" => DiagramType::Class,
+                "erDiagram" => DiagramType::ER,
+                "flowchart" | "graph" => DiagramType::Flowchart,
+                "mindmap" => DiagramType::MindMap,
+                _ => DiagramType::Auto,
+            }
+        }
+        DiagramSyntax::PlantUml => {
+            let starts = |prefixes: &[&str]| {
+                lines.iter().any(|l| prefixes.iter().any(|p| l.to_lowercase().starts_with(p)))
+            };
+
+            if starts(&["@startmindmap"]) {
+                DiagramType::MindMap
+            } else if starts(&["usecase ", "("]) {
+                DiagramType::UseCase
+            } else if starts(&["class ", "interface ", "abstract ", "enum "]) {
+                DiagramType::Class
+            } else if starts(&["entity "]) {
+                DiagramType::ER
+            } else if starts(&["component ", "[", "package "]) {
+                DiagramType::Component
+            } else if starts(&["participant ", "actor ", "boundary ", "database "])
+                || lines.iter().any(|l| l.contains("->") && l.contains(':'))
+            {
+                DiagramType::Sequence
+            } else if starts(&["start", ":"]) {
+                DiagramType::Activity
+            } else {
+                DiagramType::Auto
+            }
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+/// 目录层级导入结果
+#[derive(Debug, Default, Serialize)]
+pub struct HierarchyImportResult {
+    /// 成功导入的文件数
+    pub success_count: usize,
+    /// 失败的文件数
+    pub failure_count: usize,
+    /// 创建的目录节点（云空间文件夹或知识库节点）
+    pub folders: Vec<HierarchyFolder>,
+    /// 每个文件的导入结果
+    pub documents: Vec<HierarchyDocument>,
+}
+
+/// 目录对应的云空间文件夹或知识库节点
+#[derive(Debug, Serialize)]
+pub struct HierarchyFolder {
+    /// 相对目录路径
+    pub relative_path: String,
+    /// 文件夹 token 或知识库节点 token
+    pub token: String,
+    /// 访问链接
+    pub url: String,
+}
+
+/// 单个文件对应的文档
+#[derive(Debug, Serialize)]
+pub struct HierarchyDocument {
+    /// 相对文件路径
+    pub relative_path: String,
+    /// 文档标题
+    pub title: String,
+    /// 文档 ID
+    pub document_id: Option<String>,
+    /// 知识库节点 token（仅知识库模式）
+    pub node_token: Option<String>,
+    /// 访问链接
+    pub url: Option<String>,
+    /// 是否成功
+    pub success: bool,
+    /// 错误信息（如果有）
+    pub error: Option<String>,
+}
+
+/// 提取 Markdown 中代码块之外的第一个一级标题，返回标题和去掉该标题行后的内容
+pub fn extract_h1_title(content: &str) -> Option<(String, String)> {
+    let mut in_fence = false;
+    let mut offset = 0;
+
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        } else if !in_fence {
+            if let Some(title) = trimmed.strip_prefix("# ") {
+                let title = title.trim().trim_end_matches('#').trim();
+                if !title.is_empty() {
+                    // 同时去掉标题后的空行
+                    let rest = content[offset + line.len()..].trim_start_matches(['\r', '\n']);
+                    let body = format!("{}{}", &content[..offset], rest);
+                    return Some((title.to_string(), body));
+                }
+            }
+        }
+        offset += line.len();
+    }
+
+    None
+}
+
+/// 确定文档标题：Markdown 优先使用第一个一级标题，否则使用文件名（不含扩展名）
+pub fn document_title(path: &Path, content: &str, content_type: &str) -> (String, String) {
+    if content_type == "markdown" {
+        if let Some(result) = extract_h1_title(content) {
+            return result;
+        }
+    }
+
+    let title = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("未命名文档")
+        .to_string();
+    (title, content.to_string())
+}
+
+/// 相对路径所在的目录（根目录为空字符串）
+pub fn parent_dir(relative_path: &str) -> &str {
+    relative_path.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("")
+}
+
+/// 收集所有文件所在的目录及其上级目录，父目录排在子目录之前
+pub fn collect_dirs<'a>(relative_paths: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut dirs = BTreeSet::new();
+    for path in relative_paths {
+        let mut dir = parent_dir(path);
+        while !dir.is_empty() {
+            dirs.insert(dir.to_string());
+            dir = parent_dir(dir);
+        }
+    }
+
+    // BTreeSet 按字典序排列，前缀（父目录）总是在前
+    dirs.into_iter().collect()
+}
+
+/// 将链接目标解析为相对于导入根目录的路径，外部链接和页内锚点返回 None
+pub fn resolve_link(source_path: &str, target: &str) -> Option<String> {
+    if target.is_empty() || target.starts_with('#') || target.starts_with('/') || target.contains(':') {
+        return None;
+    }
+
+    let path = target.split(['#', '?']).next().unwrap_or("").replace("%20", " ");
+    if path.is_empty() {
+        return None;
+    }
+
+    let mut parts: Vec<&str> = parent_dir(source_path)
+        .split('/')
+        .filter(|p| !p.is_empty())
+        .collect();
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                // 超出导入根目录的链接无法对应到导入的文档
+                parts.pop()?;
+            }
+            _ => parts.push(part),
+        }
+    }
+
+    Some(parts.join("/"))
+}
+
+/// 将 Markdown 中指向其他导入文件的相对链接替换为对应文档的链接
+///
+/// 支持行内链接 `[text](path.md)` 和引用式链接定义 `[id]: path.md`，代码块中的内容保持不变。
+pub fn rewrite_links(content: &str, source_path: &str, targets: &HashMap<String, String>) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut in_fence = false;
+
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            output.push_str(line);
+        } else if in_fence {
+            output.push_str(line);
+        } else if let Some(rewritten) = rewrite_reference_definition(line, source_path, targets) {
+            output.push_str(&rewritten);
+        } else {
+            output.push_str(&rewrite_inline_links(line, source_path, targets));
+        }
+    }
+
+    output
+}
+
+fn lookup<'a>(target: &str, source_path: &str, targets: &'a HashMap<String, String>) -> Option<&'a String> {
+    resolve_link(source_path, target).and_then(|key| targets.get(&key))
+}
+
+fn rewrite_inline_links(line: &str, source_path: &str, targets: &HashMap<String, String>) -> String {
+    let mut output = String::with_capacity(line.len());
+    let mut rest = line;
+
+    while let Some(pos) = rest.find("](") {
+        let (before, after) = rest.split_at(pos + 2);
+        output.push_str(before);
+
+        let Some(end) = after.find(')') else {
+            rest = after;
+            break;
+        };
+
+        // 链接目标后可能带有标题，如 [text](path.md "title")
+        let inner = &after[..end];
+        let target_len = inner.find(char::is_whitespace).unwrap_or(inner.len());
+        let target = inner[..target_len].trim_start_matches('<').trim_end_matches('>');
+
+        match lookup(target, source_path, targets) {
+            Some(url) => {
+                output.push_str(url);
+                output.push_str(&inner[target_len..]);
+            }
+            None => output.push_str(inner),
+        }
+        rest = &after[end..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+fn rewrite_reference_definition(
+    line: &str,
+    source_path: &str,
+    targets: &HashMap<String, String>,
+) -> Option<String> {
+    let trimmed = line.trim_start();
+    if !trimmed.starts_with('[') {
+        return None;
+    }
+
+    let label_end = trimmed.find("]:")?;
+    let after = &trimmed[label_end + 2..];
+    let target = after.split_whitespace().next()?;
+    let url = lookup(target.trim_start_matches('<').trim_end_matches('>'), source_path, targets)?;
+
+    let target_start = line.len() - after.len() + after.find(target)?;
+    Some(format!(
+        "{}{}{}",
+        &line[..target_start],
+        url,
+        &line[target_start + target.len()..]
+    ))
+}
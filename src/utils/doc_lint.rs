@@ -0,0 +1,470 @@
+use crate::api::get_blocks::BlockItem;
+use crate::error::{LarkError, Result};
+use crate::utils::{
+    block_elements, block_plain_text, document_from_url, document_order, heading_level, percent_decode, BlockTree,
+    DocumentRef,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// 文本块类型
+const TEXT_BLOCK_TYPE: i32 = 2;
+/// 图片块类型
+const IMAGE_BLOCK_TYPE: i32 = 27;
+/// 空段落作为唯一子块时不报告的父块类型：高亮块、分栏列、表格单元格、引用容器
+const CONTAINER_BLOCK_TYPES: [i32; 4] = [19, 25, 32, 34];
+
+/// 检查规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LintRule {
+    /// 标题级别跳级，如 H1 后直接出现 H3
+    HeadingLevelJump,
+    /// 空标题
+    EmptyHeading,
+    /// 多余的空段落：连续的空段落，或位于开头、结尾的空段落
+    EmptyParagraph,
+    /// 同级标题文本重复
+    DuplicateHeading,
+    /// 标题首尾有空白字符
+    HeadingWhitespace,
+    /// 无法解析的 @用户 或 @文档
+    UnresolvedMention,
+    /// 指向无法读取的飞书文档的链接
+    BrokenLink,
+    /// 图片缺少说明文字
+    ImageCaption,
+}
+
+impl LintRule {
+    /// 所有规则
+    pub const ALL: [LintRule; 8] = [
+        LintRule::HeadingLevelJump,
+        LintRule::EmptyHeading,
+        LintRule::EmptyParagraph,
+        LintRule::DuplicateHeading,
+        LintRule::HeadingWhitespace,
+        LintRule::UnresolvedMention,
+        LintRule::BrokenLink,
+        LintRule::ImageCaption,
+    ];
+
+    /// 规则名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            LintRule::HeadingLevelJump => "heading-level-jump",
+            LintRule::EmptyHeading => "empty-heading",
+            LintRule::EmptyParagraph => "empty-paragraph",
+            LintRule::DuplicateHeading => "duplicate-heading",
+            LintRule::HeadingWhitespace => "heading-whitespace",
+            LintRule::UnresolvedMention => "unresolved-mention",
+            LintRule::BrokenLink => "broken-link",
+            LintRule::ImageCaption => "image-caption",
+        }
+    }
+
+    /// 默认严重程度
+    pub fn default_severity(&self) -> Severity {
+        match self {
+            LintRule::HeadingLevelJump
+            | LintRule::EmptyHeading
+            | LintRule::UnresolvedMention
+            | LintRule::BrokenLink => Severity::Error,
+            LintRule::EmptyParagraph
+            | LintRule::DuplicateHeading
+            | LintRule::HeadingWhitespace
+            | LintRule::ImageCaption => Severity::Warning,
+        }
+    }
+
+    /// 是否需要调用接口检查引用
+    pub fn is_online(&self) -> bool {
+        matches!(self, LintRule::UnresolvedMention | LintRule::BrokenLink)
+    }
+}
+
+impl std::str::FromStr for LintRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        LintRule::ALL
+            .into_iter()
+            .find(|rule| rule.name() == s.trim().to_lowercase().replace('_', "-"))
+            .ok_or_else(|| {
+                format!(
+                    "未知的检查规则: {}。可选值: {}",
+                    s,
+                    LintRule::ALL.map(|r| r.name()).join(", ")
+                )
+            })
+    }
+}
+
+/// 问题的严重程度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// 警告
+    Warning,
+    /// 错误
+    Error,
+}
+
+impl std::str::FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "warning" => Ok(Severity::Warning),
+            "error" => Ok(Severity::Error),
+            _ => Err(format!("无效的严重程度: {}。可选值: warning, error", s)),
+        }
+    }
+}
+
+/// 检查配置
+///
+/// ```toml
+/// # 只启用这些规则（默认全部）
+/// rules = ["heading-level-jump", "broken-link"]
+/// # 跳过的规则
+/// skip = ["image-caption"]
+///
+/// [severity]
+/// empty-paragraph = "error"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintConfig {
+    /// 启用的规则，为空时启用全部
+    #[serde(default)]
+    pub rules: Vec<LintRule>,
+    /// 跳过的规则
+    #[serde(default)]
+    pub skip: Vec<LintRule>,
+    /// 覆盖规则的严重程度
+    #[serde(default)]
+    pub severity: BTreeMap<LintRule, Severity>,
+}
+
+impl LintConfig {
+    /// 从 TOML 文件加载配置
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| LarkError::IoError(format!("读取检查配置失败 {}: {}", path.display(), e)))?;
+        Self::parse(&content)
+    }
+
+    /// 解析 TOML 格式的配置
+    pub fn parse(content: &str) -> Result<Self> {
+        toml::from_str(content).map_err(|e| LarkError::ParseError(format!("检查配置格式错误: {}", e)))
+    }
+
+    /// 启用的规则
+    pub fn enabled_rules(&self) -> Vec<LintRule> {
+        LintRule::ALL
+            .into_iter()
+            .filter(|rule| self.rules.is_empty() || self.rules.contains(rule))
+            .filter(|rule| !self.skip.contains(rule))
+            .collect()
+    }
+
+    /// 规则的严重程度
+    pub fn severity(&self, rule: LintRule) -> Severity {
+        self.severity.get(&rule).copied().unwrap_or_else(|| rule.default_severity())
+    }
+}
+
+/// 自动修复方式
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum LintFix {
+    /// 删除块
+    Delete,
+    /// 用新的文本片段替换块内容
+    UpdateElements { elements: Value },
+}
+
+/// 检查发现的问题
+#[derive(Debug, Clone, Serialize)]
+pub struct LintFinding {
+    /// 规则
+    pub rule: LintRule,
+    /// 严重程度
+    pub severity: Severity,
+    /// 问题所在的块 ID
+    pub block_id: String,
+    /// 问题说明
+    pub message: String,
+    /// 自动修复方式（不能安全修复时为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<LintFix>,
+    /// 是否已自动修复
+    pub fixed: bool,
+}
+
+/// 文档中引用的飞书对象
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LintTarget {
+    /// 用户 open_id
+    User(String),
+    /// 新版文档
+    Docx(String),
+    /// 知识库节点
+    Wiki(String),
+}
+
+impl From<DocumentRef> for LintTarget {
+    fn from(document: DocumentRef) -> Self {
+        match document {
+            DocumentRef::Docx(token) => LintTarget::Docx(token),
+            DocumentRef::Wiki(token) => LintTarget::Wiki(token),
+        }
+    }
+}
+
+/// 需要调用接口检查的引用
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintReference {
+    /// 产生引用的规则
+    pub rule: LintRule,
+    /// 引用所在的块 ID
+    pub block_id: String,
+    /// 被引用的对象
+    pub target: LintTarget,
+    /// 在报告中展示的引用内容
+    pub label: String,
+}
+
+/// 离线检查文档块树，返回发现的问题（不含需要调用接口的规则）
+pub fn lint_blocks(tree: &BlockTree, root_id: &str, config: &LintConfig) -> Vec<LintFinding> {
+    let rules = config.enabled_rules();
+    let enabled = |rule: LintRule| rules.contains(&rule);
+    let finding = |rule: LintRule, block_id: &str, message: String, fix: Option<LintFix>| LintFinding {
+        rule,
+        severity: config.severity(rule),
+        block_id: block_id.to_string(),
+        message,
+        fix,
+        fixed: false,
+    };
+
+    let mut findings = Vec::new();
+    let mut previous_level: Option<usize> = None;
+    let mut headings: HashMap<(usize, String), String> = HashMap::new();
+
+    for block_id in document_order(tree, root_id) {
+        let Some(block) = tree.get(&block_id) else {
+            continue;
+        };
+
+        if let Some(level) = heading_level(block.block_type) {
+            let text = block_plain_text(block);
+            let trimmed = text.trim();
+
+            if enabled(LintRule::HeadingLevelJump) {
+                if let Some(previous) = previous_level.filter(|&p| level > p + 1) {
+                    findings.push(finding(
+                        LintRule::HeadingLevelJump,
+                        &block_id,
+                        format!("标题级别从 H{} 跳到 H{}", previous, level),
+                        None,
+                    ));
+                }
+            }
+            previous_level = Some(level);
+
+            if trimmed.is_empty() {
+                if enabled(LintRule::EmptyHeading) {
+                    findings.push(finding(
+                        LintRule::EmptyHeading,
+                        &block_id,
+                        format!("H{} 标题为空", level),
+                        Some(LintFix::Delete),
+                    ));
+                }
+                continue;
+            }
+
+            if enabled(LintRule::DuplicateHeading) {
+                match headings.get(&(level, trimmed.to_string())) {
+                    Some(first) => findings.push(finding(
+                        LintRule::DuplicateHeading,
+                        &block_id,
+                        format!("H{} 标题 \"{}\" 与块 {} 重复", level, trimmed, first),
+                        None,
+                    )),
+                    None => {
+                        headings.insert((level, trimmed.to_string()), block_id.clone());
+                    }
+                }
+            }
+
+            if enabled(LintRule::HeadingWhitespace) && trimmed != text {
+                let fix = block_elements(block)
+                    .and_then(trim_elements)
+                    .map(|elements| LintFix::UpdateElements { elements });
+                findings.push(finding(
+                    LintRule::HeadingWhitespace,
+                    &block_id,
+                    format!("标题 \"{}\" 首尾有空白字符", trimmed),
+                    fix,
+                ));
+            }
+        }
+
+        if enabled(LintRule::EmptyParagraph) && is_redundant_paragraph(tree, block) {
+            findings.push(finding(
+                LintRule::EmptyParagraph,
+                &block_id,
+                "多余的空段落".to_string(),
+                Some(LintFix::Delete),
+            ));
+        }
+
+        if enabled(LintRule::ImageCaption) && block.block_type == IMAGE_BLOCK_TYPE {
+            let caption = block
+                .content
+                .pointer("/image/caption/content")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            if caption.trim().is_empty() {
+                findings.push(finding(LintRule::ImageCaption, &block_id, "图片缺少说明文字".to_string(), None));
+            }
+        }
+    }
+
+    findings
+}
+
+/// 列出文档中需要调用接口检查的引用：@用户、@文档和指向飞书文档的链接
+pub fn lint_references(tree: &BlockTree, root_id: &str, config: &LintConfig) -> Vec<LintReference> {
+    let rules = config.enabled_rules();
+    let mut references = Vec::new();
+
+    for block_id in document_order(tree, root_id) {
+        let Some(elements) = tree.get(&block_id).and_then(block_elements).and_then(Value::as_array) else {
+            continue;
+        };
+        for element in elements {
+            if rules.contains(&LintRule::UnresolvedMention) {
+                if let Some(user) = element.get("mention_user") {
+                    let user_id = user.get("user_id").and_then(Value::as_str).unwrap_or_default();
+                    references.push(LintReference {
+                        rule: LintRule::UnresolvedMention,
+                        block_id: block_id.clone(),
+                        target: LintTarget::User(user_id.to_string()),
+                        label: format!("@{}", user_id),
+                    });
+                }
+                if let Some(doc) = element.get("mention_doc") {
+                    let token = doc.get("token").and_then(Value::as_str).unwrap_or_default();
+                    let url = doc.get("url").and_then(Value::as_str).map(percent_decode).unwrap_or_default();
+                    let target = match doc.get("obj_type").and_then(Value::as_i64) {
+                        Some(16) => Some(LintTarget::Wiki(token.to_string())),
+                        Some(22) => Some(LintTarget::Docx(token.to_string())),
+                        _ => document_from_url(&url).map(LintTarget::from),
+                    };
+                    if let Some(target) = target {
+                        let title = doc.get("title").and_then(Value::as_str).unwrap_or(token);
+                        references.push(LintReference {
+                            rule: LintRule::UnresolvedMention,
+                            block_id: block_id.clone(),
+                            target,
+                            label: title.to_string(),
+                        });
+                    }
+                }
+            }
+
+            if rules.contains(&LintRule::BrokenLink) {
+                let url = element
+                    .pointer("/text_run/text_element_style/link/url")
+                    .and_then(Value::as_str)
+                    .map(percent_decode);
+                if let Some((url, target)) = url.and_then(|url| document_from_url(&url).map(|d| (url, LintTarget::from(d)))) {
+                    references.push(LintReference {
+                        rule: LintRule::BrokenLink,
+                        block_id: block_id.clone(),
+                        target,
+                        label: url,
+                    });
+                }
+            }
+        }
+    }
+
+    references
+}
+
+/// 空段落：没有文本和子块的文本块
+fn is_empty_paragraph(block: &BlockItem) -> bool {
+    block.block_type == TEXT_BLOCK_TYPE
+        && block.children.as_ref().is_none_or(|c| c.is_empty())
+        && block_plain_text(block).trim().is_empty()
+        && block_elements(block)
+            .and_then(Value::as_array)
+            .is_none_or(|elements| elements.iter().all(|e| e.get("text_run").is_some()))
+}
+
+/// 多余的空段落：与另一个空段落相邻，或位于父块的开头、结尾（父块中只有它一个子块的容器除外）
+fn is_redundant_paragraph(tree: &BlockTree, block: &BlockItem) -> bool {
+    if !is_empty_paragraph(block) {
+        return false;
+    }
+    let siblings = tree.children(&block.parent_id);
+    let Some(index) = siblings.iter().position(|id| id == &block.block_id) else {
+        return false;
+    };
+    let is_empty_at = |i: usize| siblings.get(i).and_then(|id| tree.get(id)).is_some_and(is_empty_paragraph);
+
+    if index > 0 && is_empty_at(index - 1) {
+        return true;
+    }
+    if siblings.len() == 1 {
+        return !tree
+            .get(&block.parent_id)
+            .is_some_and(|parent| CONTAINER_BLOCK_TYPES.contains(&parent.block_type));
+    }
+    index == 0 || index + 1 == siblings.len()
+}
+
+/// 去掉文本片段首尾的空白字符，无需修改时返回 None
+fn trim_elements(elements: &Value) -> Option<Value> {
+    let mut elements = elements.as_array()?.clone();
+    let runs: Vec<usize> = elements
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| e.get("text_run").is_some())
+        .map(|(i, _)| i)
+        .collect();
+
+    let mut changed = false;
+    // 开头的空白可能跨多个片段，依次去掉直到遇到非空内容；结尾同理
+    for (order, trim) in [
+        (runs.clone(), str::trim_start as fn(&str) -> &str),
+        (runs.iter().rev().copied().collect(), str::trim_end),
+    ] {
+        for index in order {
+            let Some(content) = elements[index].pointer_mut("/text_run/content") else {
+                continue;
+            };
+            let text = content.as_str().unwrap_or_default();
+            let trimmed = trim(text).to_string();
+            if trimmed != text {
+                changed = true;
+            }
+            let done = !trimmed.is_empty();
+            *content = Value::String(trimmed);
+            if done {
+                break;
+            }
+        }
+    }
+
+    // 只剩空白的片段整体去掉
+    elements.retain(|e| e.pointer("/text_run/content").and_then(Value::as_str) != Some(""));
+    changed.then_some(Value::Array(elements))
+}
@@ -0,0 +1,132 @@
+use crate::error::{LarkError, Result};
+use crate::utils::{block_plain_text, heading_level, BlockTree};
+use serde::Serialize;
+
+/// 写入章节内容的方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SectionMode {
+    /// 替换标题下的全部内容
+    #[default]
+    Replace,
+    /// 追加到章节末尾
+    Append,
+    /// 插入到标题之后、原有内容之前
+    Prepend,
+}
+
+impl std::str::FromStr for SectionMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "replace" => Ok(SectionMode::Replace),
+            "append" => Ok(SectionMode::Append),
+            "prepend" => Ok(SectionMode::Prepend),
+            _ => Err(format!("无效的写入方式: {}。可选值: replace, append, prepend", s)),
+        }
+    }
+}
+
+/// 标题路径中的一级，如 `## Metrics` 解析为级别 2、文本 Metrics
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeadingSegment {
+    /// 标题文本
+    pub text: String,
+    /// 指定的标题级别（使用 `#` 前缀时）
+    pub level: Option<usize>,
+}
+
+/// 文档中的一个章节：标题块及其后直到下一个同级或更高级标题之前的块
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DocumentSection {
+    /// 标题块 ID
+    pub heading_block_id: String,
+    /// 标题级别
+    pub heading_level: usize,
+    /// 标题和内容所在的父块 ID
+    pub parent_id: String,
+    /// 章节内容在父块中的起始位置（标题之后）
+    pub start: usize,
+    /// 章节内容的结束位置（不含）
+    pub end: usize,
+}
+
+/// 解析以 `/` 分隔的标题路径，如 `Status / Metrics`
+pub fn parse_heading_path(path: &str) -> Result<Vec<HeadingSegment>> {
+    let segments: Vec<HeadingSegment> = path
+        .split('/')
+        .map(|segment| {
+            let segment = segment.trim();
+            let hashes = segment.chars().take_while(|&c| c == '#').count();
+            HeadingSegment {
+                text: segment[hashes..].trim().to_string(),
+                level: (hashes > 0).then_some(hashes),
+            }
+        })
+        .collect();
+
+    if segments.iter().any(|s| s.text.is_empty()) {
+        return Err(LarkError::ValidationError(format!("无效的标题路径: {}", path)));
+    }
+    Ok(segments)
+}
+
+/// 在父块的子块中按标题路径查找章节
+///
+/// 路径的每一级在上一级章节的范围内查找，级别必须低于上一级标题；
+/// 同一范围内有多个同名标题时返回错误，避免修改错误的位置。
+pub fn find_section(tree: &BlockTree, parent_id: &str, path: &[HeadingSegment]) -> Result<DocumentSection> {
+    let children = tree.children(parent_id);
+    let level_at = |i: usize| tree.get(&children[i]).and_then(|b| heading_level(b.block_type));
+
+    let (mut start, mut end) = (0, children.len());
+    let mut found: Option<DocumentSection> = None;
+
+    for segment in path {
+        let min_level = found.as_ref().map_or(1, |s| s.heading_level + 1);
+        let candidates: Vec<usize> = (start..end)
+            .filter(|&i| {
+                let Some(level) = level_at(i) else {
+                    return false;
+                };
+                let text_matches = tree
+                    .get(&children[i])
+                    .is_some_and(|b| block_plain_text(b).trim() == segment.text);
+                level >= min_level && segment.level.is_none_or(|l| l == level) && text_matches
+            })
+            .collect();
+
+        let index = match candidates.as_slice() {
+            [index] => *index,
+            [] => {
+                return Err(LarkError::ValidationError(format!("未找到标题: {}", segment.text)));
+            }
+            _ => {
+                return Err(LarkError::ValidationError(format!(
+                    "找到 {} 个标题 \"{}\"，请使用更完整的标题路径",
+                    candidates.len(),
+                    segment.text
+                )));
+            }
+        };
+
+        // 章节在下一个同级或更高级标题处结束
+        let level = level_at(index).unwrap_or(1);
+        let section_end = (index + 1..end)
+            .find(|&i| level_at(i).is_some_and(|l| l <= level))
+            .unwrap_or(end);
+
+        found = Some(DocumentSection {
+            heading_block_id: children[index].clone(),
+            heading_level: level,
+            parent_id: parent_id.to_string(),
+            start: index + 1,
+            end: section_end,
+        });
+        start = index + 1;
+        end = section_end;
+    }
+
+    found.ok_or_else(|| LarkError::ValidationError("标题路径不能为空".to_string()))
+}
@@ -0,0 +1,97 @@
+use crate::api::DescendantBlock;
+use crate::utils::{block_plain_text, document_order, heading_level, percent_encode, BlockTree};
+use serde_json::json;
+use std::collections::HashSet;
+
+/// 高亮块类型
+const CALLOUT_BLOCK_TYPE: i32 = 19;
+/// 文本块类型
+const TEXT_BLOCK_TYPE: i32 = 2;
+/// 无序列表块类型
+const BULLET_BLOCK_TYPE: i32 = 12;
+
+/// 目录中的一个标题
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TocEntry {
+    /// 标题块 ID
+    pub block_id: String,
+    /// 标题级别
+    pub level: usize,
+    /// 标题文本
+    pub text: String,
+}
+
+/// 查找目录标记：第一个子块为指定标题文本的高亮块
+pub fn find_toc_marker(tree: &BlockTree, root_id: &str, title: &str) -> Option<String> {
+    document_order(tree, root_id).into_iter().find(|id| {
+        tree.get(id).is_some_and(|block| block.block_type == CALLOUT_BLOCK_TYPE)
+            && tree
+                .children(id)
+                .first()
+                .and_then(|child| tree.get(child))
+                .is_some_and(|child| {
+                    child.block_type == TEXT_BLOCK_TYPE && block_plain_text(child).trim() == title.trim()
+                })
+    })
+}
+
+/// 按文档顺序列出不超过指定级别的非空标题，跳过目录标记块中的内容
+pub fn collect_headings(tree: &BlockTree, root_id: &str, max_level: usize, marker_id: Option<&str>) -> Vec<TocEntry> {
+    let excluded: HashSet<String> = marker_id
+        .map(|id| document_order(tree, id).into_iter().collect())
+        .unwrap_or_default();
+
+    document_order(tree, root_id)
+        .into_iter()
+        .filter(|id| !excluded.contains(id))
+        .filter_map(|id| {
+            let block = tree.get(&id)?;
+            let level = heading_level(block.block_type).filter(|&l| l <= max_level)?;
+            let text = block_plain_text(block).trim().to_string();
+            (!text.is_empty()).then_some(TocEntry { block_id: id, level, text })
+        })
+        .collect()
+}
+
+/// 构建目录的嵌套无序列表，返回一级块临时 ID 和所有待创建的块
+///
+/// 较深的标题嵌套在之前最近的较浅标题下，跳级的标题直接嵌套一层。
+/// 每一项链接到 `document_url#标题块 ID`。
+pub fn toc_blocks(entries: &[TocEntry], document_url: &str) -> (Vec<String>, Vec<DescendantBlock>) {
+    let mut descendants: Vec<DescendantBlock> = Vec::new();
+    let mut children_id = Vec::new();
+    // 当前路径上的 (标题级别, 块在 descendants 中的位置)
+    let mut stack: Vec<(usize, usize)> = Vec::new();
+
+    for (i, entry) in entries.iter().enumerate() {
+        let temp_id = format!("toc_{}", i + 1);
+        let url = format!("{}#{}", document_url, entry.block_id);
+        descendants.push(DescendantBlock {
+            block_id: temp_id.clone(),
+            block_type: BULLET_BLOCK_TYPE,
+            bullet: Some(json!({
+                "elements": [{
+                    "text_run": {
+                        "content": entry.text,
+                        "text_element_style": { "link": { "url": percent_encode(&url) } }
+                    }
+                }]
+            })),
+            ..Default::default()
+        });
+
+        while stack.last().is_some_and(|&(level, _)| level >= entry.level) {
+            stack.pop();
+        }
+        match stack.last() {
+            Some(&(_, parent)) => descendants[parent]
+                .children
+                .get_or_insert_with(Vec::new)
+                .push(temp_id),
+            None => children_id.push(temp_id),
+        }
+        stack.push((entry.level, descendants.len() - 1));
+    }
+
+    (children_id, descendants)
+}
@@ -0,0 +1,126 @@
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// 分段并行下载的断点记录，与临时文件放在一起
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadState {
+    /// 素材 token
+    pub file_token: String,
+    /// 文件总大小（字节）
+    pub total: u64,
+    /// 分段大小（字节）
+    pub segment_size: u64,
+    /// 已下载完成的分段序号
+    pub completed: BTreeSet<u64>,
+    /// 探测请求返回的远端版本，续传时据此确认文件未变化
+    #[serde(flatten)]
+    pub version: RemoteVersion,
+}
+
+impl DownloadState {
+    pub fn new(file_token: &str, total: u64, segment_size: u64, version: RemoteVersion) -> Self {
+        Self {
+            file_token: file_token.to_string(),
+            total,
+            segment_size,
+            completed: BTreeSet::new(),
+            version,
+        }
+    }
+
+    /// 加载断点记录，文件不存在或已损坏时返回 None
+    pub fn load(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// 保存断点记录（先写临时文件再重命名）
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// 分段数量
+    pub fn segment_count(&self) -> u64 {
+        self.total.div_ceil(self.segment_size)
+    }
+
+    /// 分段的字节范围（包含两端）
+    pub fn segment_range(&self, seq: u64) -> (u64, u64) {
+        let start = seq * self.segment_size;
+        (start, (start + self.segment_size).min(self.total) - 1)
+    }
+
+    /// 尚未下载的分段序号
+    pub fn pending_segments(&self) -> Vec<u64> {
+        (0..self.segment_count()).filter(|seq| !self.completed.contains(seq)).collect()
+    }
+
+    /// 已下载的字节数
+    pub fn completed_bytes(&self) -> u64 {
+        self.completed
+            .iter()
+            .map(|seq| {
+                let (start, end) = self.segment_range(*seq);
+                end - start + 1
+            })
+            .sum()
+    }
+}
+
+/// 远端文件的版本：响应头中的 ETag 和 Last-Modified
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteVersion {
+    /// 响应头中的 ETag
+    #[serde(default)]
+    pub etag: Option<String>,
+    /// 响应头中的 Last-Modified
+    #[serde(default)]
+    pub last_modified: Option<String>,
+}
+
+impl RemoteVersion {
+    /// 续传时 If-Range 使用的校验值：优先强 ETag，弱 ETag 不能用于范围请求，改用 Last-Modified
+    pub fn validator(&self) -> Option<&str> {
+        self.etag
+            .as_deref()
+            .filter(|etag| !etag.starts_with("W/"))
+            .or(self.last_modified.as_deref())
+    }
+
+    /// 与另一个版本不一致；任一方没有对应的头时不判断该项
+    pub fn conflicts_with(&self, other: &RemoteVersion) -> bool {
+        let differs = |a: &Option<String>, b: &Option<String>| matches!((a, b), (Some(a), Some(b)) if a != b);
+        differs(&self.etag, &other.etag) || differs(&self.last_modified, &other.last_modified)
+    }
+}
+
+/// 单个请求流式下载的断点记录：临时文件来自哪个素材和远端版本
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamState {
+    /// 素材 token
+    pub file_token: String,
+    /// 写入临时文件的响应的远端版本
+    #[serde(flatten)]
+    pub version: RemoteVersion,
+}
+
+impl StreamState {
+    /// 加载断点记录，文件不存在或已损坏时返回 None
+    pub fn load(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// 保存断点记录（先写临时文件再重命名）
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use super::lark_url::lark_url_segments;
+
+/// 云空间文件类型
+pub const DRIVE_FILE_TYPES: [&str; 9] = [
+    "doc", "docx", "sheet", "bitable", "mindnote", "file", "slides", "folder", "shortcut",
+];
+
+/// 命令行中指定的云空间文件：token 或文档链接
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DriveRef {
+    /// 云空间文件，无法判断类型时 `file_type` 为 None
+    File { token: String, file_type: Option<String> },
+    /// 知识库节点，需要查询节点对应的文档
+    Wiki(String),
+}
+
+/// 解析 token 或飞书链接，如 `https://xxx.feishu.cn/drive/folder/<token>`、`https://xxx.feishu.cn/sheets/<token>`
+///
+/// 链接按路径判断类型；纯 token 只能按旧版 token 前缀（如 `fldcn`、`doxcn`）判断类型。
+pub fn parse_drive_ref(input: &str) -> Result<DriveRef, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("文件 token 或链接不能为空".to_string());
+    }
+
+    if input.starts_with("http://") || input.starts_with("https://") {
+        let segments: Vec<&str> = lark_url_segments(input)
+            .ok_or_else(|| format!("不是飞书链接: {}", input))?
+            .collect();
+        let (kind, token) = match segments.as_slice() {
+            ["drive", "folder", token, ..] => ("folder", *token),
+            [kind, token, ..] => (*kind, *token),
+            _ => return Err(format!("无法从链接中解析文件: {}", input)),
+        };
+        let file_type = match kind {
+            "wiki" => return Ok(DriveRef::Wiki(token.to_string())),
+            "folder" => "folder",
+            "docx" => "docx",
+            "docs" | "doc" => "doc",
+            "sheets" | "sheet" => "sheet",
+            "base" | "bitable" => "bitable",
+            "mindnotes" | "mindnote" => "mindnote",
+            "file" => "file",
+            "slides" => "slides",
+            _ => return Err(format!("不支持的链接类型 {}: {}", kind, input)),
+        };
+        return Ok(DriveRef::File {
+            token: token.to_string(),
+            file_type: Some(file_type.to_string()),
+        });
+    }
+
+    if input.contains('/') {
+        return Err(format!("无效的文件 token: {}", input));
+    }
+    Ok(DriveRef::File {
+        token: input.to_string(),
+        file_type: legacy_token_type(input).map(str::to_string),
+    })
+}
+
+/// 检查文件类型是否有效
+pub fn check_drive_file_type(file_type: &str) -> Result<(), String> {
+    if DRIVE_FILE_TYPES.contains(&file_type) {
+        Ok(())
+    } else {
+        Err(format!(
+            "无效的文件类型: {}。可选值: {}",
+            file_type,
+            DRIVE_FILE_TYPES.join(", ")
+        ))
+    }
+}
+
+/// 旧版 token 带有类型前缀，新版 token 无法判断
+fn legacy_token_type(token: &str) -> Option<&'static str> {
+    const PREFIXES: [(&str, &str); 7] = [
+        ("fldcn", "folder"),
+        ("doccn", "doc"),
+        ("doxcn", "docx"),
+        ("shtcn", "sheet"),
+        ("bascn", "bitable"),
+        ("bmncn", "mindnote"),
+        ("boxcn", "file"),
+    ];
+    PREFIXES
+        .iter()
+        .find(|(prefix, _)| token.starts_with(prefix))
+        .map(|(_, file_type)| *file_type)
+}
@@ -0,0 +1,58 @@
+use crate::api::drive::DriveFile;
+use serde::Serialize;
+
+/// 目录树中的一项，按先序排列
+#[derive(Debug, Clone, Serialize)]
+pub struct DriveEntry {
+    /// 层级，列出的文件夹的直接子项为 0
+    pub depth: usize,
+    /// 相对于列出的文件夹的路径
+    pub path: String,
+    #[serde(flatten)]
+    pub file: DriveFile,
+}
+
+/// 按类型过滤：保留类型匹配的项，以及包含匹配项的文件夹（保持树结构）
+pub fn filter_entries(entries: Vec<DriveEntry>, types: &[String]) -> Vec<DriveEntry> {
+    if types.is_empty() {
+        return entries;
+    }
+    let matched: Vec<bool> = entries.iter().map(|e| types.contains(&e.file.file_type)).collect();
+    let keep: Vec<bool> = (0..entries.len())
+        .map(|i| {
+            matched[i]
+                || entries[i + 1..]
+                    .iter()
+                    .zip(&matched[i + 1..])
+                    .take_while(|(e, _)| e.depth > entries[i].depth)
+                    .any(|(_, m)| *m)
+        })
+        .collect();
+    entries.into_iter().zip(keep).filter(|(_, k)| *k).map(|(e, _)| e).collect()
+}
+
+/// 渲染为目录树，文件夹名后加 `/`，每项后附类型和 token
+pub fn render_drive_tree(root: &str, entries: &[DriveEntry]) -> String {
+    let mut output = format!("{}\n", root);
+    // 各层祖先是否为同级中的最后一项，决定是否画竖线
+    let mut last_at_depth: Vec<bool> = Vec::new();
+    for (i, entry) in entries.iter().enumerate() {
+        let is_last = entries[i + 1..]
+            .iter()
+            .find(|e| e.depth <= entry.depth)
+            .is_none_or(|e| e.depth < entry.depth);
+        last_at_depth.truncate(entry.depth);
+
+        for ancestor_last in &last_at_depth {
+            output.push_str(if *ancestor_last { "    " } else { "│   " });
+        }
+        output.push_str(if is_last { "└── " } else { "├── " });
+        let suffix = if entry.file.file_type == "folder" { "/" } else { "" };
+        output.push_str(&format!(
+            "{}{}  [{}] {}\n",
+            entry.file.name, suffix, entry.file.file_type, entry.file.token
+        ));
+        last_at_depth.push(is_last);
+    }
+    output
+}
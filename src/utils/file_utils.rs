@@ -149,6 +149,10 @@ pub struct ImportResult {
     pub file_path: PathBuf,
     /// 是否成功
     pub success: bool,
+    /// 是否跳过（内容未变化，或内容已变化但未指定替换）
+    pub skipped: bool,
+    /// 内容自上次导入后已变化，未指定 --replace-changed 而跳过
+    pub changed: bool,
     /// 错误信息（如果有）
     pub error: Option<String>,
     /// 创建的块 ID（如果成功）
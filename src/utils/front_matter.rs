@@ -0,0 +1,226 @@
+use crate::error::{LarkError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Markdown 文件开头的 YAML Front Matter
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct FrontMatter {
+    /// 文档标题
+    #[serde(default)]
+    pub title: Option<String>,
+    /// 导入后添加的协作者
+    #[serde(default)]
+    pub collaborators: Vec<FrontMatterCollaborator>,
+    /// 在内容前插入的高亮块
+    #[serde(default)]
+    pub callout: Option<FrontMatterCallout>,
+    /// 是否在内容前生成标题目录
+    #[serde(default)]
+    pub toc: bool,
+    /// 其他未识别的字段，可在正文中以 {{key}} 引用
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+/// 协作者，可以只写邮箱，也可以写完整的成员信息
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum FrontMatterCollaborator {
+    /// 邮箱，默认授予阅读权限
+    Email(String),
+    /// 完整的成员信息
+    Member {
+        member_id: String,
+        #[serde(default = "default_member_type")]
+        member_type: String,
+        #[serde(default = "default_perm")]
+        perm: String,
+    },
+}
+
+/// 高亮块，可以只写内容，也可以指定类型和图标
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum FrontMatterCallout {
+    /// 高亮块内容，类型为 info
+    Text(String),
+    /// 完整的高亮块配置
+    Detailed {
+        content: String,
+        #[serde(default, rename = "type")]
+        callout_type: Option<String>,
+        #[serde(default)]
+        icon: Option<String>,
+    },
+}
+
+fn default_member_type() -> String {
+    "email".to_string()
+}
+
+fn default_perm() -> String {
+    "view".to_string()
+}
+
+impl FrontMatterCollaborator {
+    /// 返回 (member_type, member_id, perm)
+    pub fn member(&self) -> (&str, &str, &str) {
+        match self {
+            FrontMatterCollaborator::Email(email) => ("email", email, "view"),
+            FrontMatterCollaborator::Member { member_id, member_type, perm } => (member_type, member_id, perm),
+        }
+    }
+}
+
+impl FrontMatterCallout {
+    /// 返回 (内容, 类型, 图标)
+    pub fn parts(&self) -> (&str, &str, Option<&str>) {
+        match self {
+            FrontMatterCallout::Text(content) => (content, "info", None),
+            FrontMatterCallout::Detailed { content, callout_type, icon } => {
+                (content, callout_type.as_deref().unwrap_or("info"), icon.as_deref())
+            }
+        }
+    }
+}
+
+impl FrontMatter {
+    /// 可在正文中引用的变量：标题和所有未识别字段中的标量值（列表以逗号连接）
+    pub fn variables(&self) -> BTreeMap<String, String> {
+        let mut variables = BTreeMap::new();
+        if let Some(title) = &self.title {
+            variables.insert("title".to_string(), title.clone());
+        }
+
+        for (key, value) in &self.extra {
+            if let Some(text) = scalar_to_string(value) {
+                variables.insert(key.clone(), text);
+            } else if let serde_json::Value::Array(items) = value {
+                let items: Vec<String> = items.iter().filter_map(scalar_to_string).collect();
+                variables.insert(key.clone(), items.join(", "));
+            }
+        }
+
+        variables
+    }
+
+    /// 按 Front Matter 渲染正文：替换 {{key}} 占位符，并按需在开头生成标题目录
+    pub fn render_body(&self, body: &str) -> String {
+        let body = render_placeholders(body, &self.variables());
+        if !self.toc {
+            return body;
+        }
+
+        let toc = toc_markdown(&body);
+        if toc.is_empty() {
+            body
+        } else {
+            format!("{}\n{}", toc, body)
+        }
+    }
+}
+
+fn scalar_to_string(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        serde_json::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// 拆分 Front Matter 和正文，内容不以 `---` 开头或分隔符之间不是 YAML 映射时返回 None 和原内容
+pub fn split_front_matter(content: &str) -> Result<(Option<FrontMatter>, String)> {
+    let text = content.trim_start_matches('\u{feff}');
+    let mut lines = text.split_inclusive('\n');
+
+    match lines.next() {
+        Some(first) if first.trim_end() == "---" => {}
+        _ => return Ok((None, content.to_string())),
+    }
+
+    let mut offset = text.find('\n').map(|i| i + 1).unwrap_or(text.len());
+    let yaml_start = offset;
+    for line in lines {
+        let trimmed = line.trim_end();
+        if trimmed == "---" || trimmed == "..." {
+            // 以分隔线开头的普通文档也会出现成对的 `---`，只有解析为 YAML 映射时才视为 Front Matter
+            let yaml = match serde_yaml::from_str::<serde_yaml::Value>(&text[yaml_start..offset]) {
+                Ok(value @ serde_yaml::Value::Mapping(_)) => value,
+                _ => return Ok((None, content.to_string())),
+            };
+            let front_matter: FrontMatter = serde_yaml::from_value(yaml).map_err(|e| {
+                LarkError::ParseError(format!("Front Matter 格式错误: {}", e))
+            })?;
+
+            let body = text[offset + line.len()..].trim_start_matches(['\r', '\n']);
+            return Ok((Some(front_matter), body.to_string()));
+        }
+        offset += line.len();
+    }
+
+    // 没有结束标记，不视为 Front Matter
+    Ok((None, content.to_string()))
+}
+
+/// 将正文中的 {{key}} 占位符替换为变量值，未定义的占位符保持不变
+pub fn render_placeholders(body: &str, variables: &BTreeMap<String, String>) -> String {
+    let mut output = String::with_capacity(body.len());
+    let mut rest = body;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find("}}") {
+            Some(end) => {
+                let key = after[..end].trim();
+                match variables.get(key) {
+                    Some(value) => output.push_str(value),
+                    None => output.push_str(&rest[start..start + 2 + end + 2]),
+                }
+                rest = &after[end + 2..];
+            }
+            None => {
+                output.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// 根据 Markdown 中代码块之外的标题生成嵌套的无序列表目录
+pub fn toc_markdown(body: &str) -> String {
+    let mut headings = Vec::new();
+    let mut in_fence = false;
+
+    for line in body.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        let level = trimmed.chars().take_while(|&c| c == '#').count();
+        if (1..=6).contains(&level) && trimmed[level..].starts_with(' ') {
+            let text = trimmed[level..].trim().trim_end_matches('#').trim();
+            if !text.is_empty() {
+                headings.push((level, text.to_string()));
+            }
+        }
+    }
+
+    let Some(min_level) = headings.iter().map(|(level, _)| *level).min() else {
+        return String::new();
+    };
+
+    headings
+        .iter()
+        .map(|(level, text)| format!("{}- {}\n", "  ".repeat(level - min_level), text))
+        .collect()
+}
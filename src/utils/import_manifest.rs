@@ -0,0 +1,154 @@
+use crate::error::{LarkError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// 默认的导入状态清单文件名（位于导入目录下）
+pub const DEFAULT_MANIFEST_FILE: &str = ".lark-import.json";
+
+/// 当前清单格式版本
+const MANIFEST_VERSION: u32 = 1;
+
+/// 导入状态清单，记录每个源文件导入到文档后的状态，用于断点续传和增量导入
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportManifest {
+    /// 清单格式版本
+    pub version: u32,
+    /// 目标文档 ID
+    pub document_id: String,
+    /// 已导入的文件，键为相对路径
+    pub files: BTreeMap<String, ManifestEntry>,
+    /// 清单文件路径
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+/// 单个源文件的导入记录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// 源文件路径
+    pub source_path: String,
+    /// 文件内容的 SHA-256 哈希
+    pub content_hash: String,
+    /// 导入时创建的一级块 ID
+    pub root_block_ids: Vec<String>,
+    /// 导入完成后的文档版本号
+    pub document_revision_id: i32,
+    /// 导入时间（RFC 3339）
+    pub imported_at: String,
+}
+
+/// 源文件相对于清单记录的状态
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManifestStatus {
+    /// 清单中没有记录
+    New,
+    /// 内容与上次导入一致
+    Unchanged,
+    /// 内容自上次导入后发生了变化
+    Changed(ManifestEntry),
+}
+
+/// 批量导入时对已导入文件的处理策略
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ExistingFilePolicy {
+    /// 跳过内容未变化的文件
+    pub skip_unchanged: bool,
+    /// 删除内容已变化文件之前创建的块，并在原位置重新导入
+    pub replace_changed: bool,
+}
+
+impl ImportManifest {
+    /// 加载清单文件，文件不存在时创建空清单
+    pub fn load_or_create(path: &Path, document_id: &str) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self {
+                version: MANIFEST_VERSION,
+                document_id: document_id.to_string(),
+                files: BTreeMap::new(),
+                path: path.to_path_buf(),
+            });
+        }
+
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            LarkError::IoError(format!("读取导入清单失败 {}: {}", path.display(), e))
+        })?;
+        let mut manifest: ImportManifest = serde_json::from_str(&content).map_err(|e| {
+            LarkError::ParseError(format!("导入清单格式错误 {}: {}", path.display(), e))
+        })?;
+
+        if manifest.version != MANIFEST_VERSION {
+            return Err(LarkError::ValidationError(format!(
+                "不支持的导入清单版本 {}（当前版本 {}）",
+                manifest.version, MANIFEST_VERSION
+            )));
+        }
+
+        if manifest.document_id != document_id {
+            return Err(LarkError::ValidationError(format!(
+                "导入清单 {} 属于文档 {}，与目标文档 {} 不一致",
+                path.display(),
+                manifest.document_id,
+                document_id
+            )));
+        }
+
+        manifest.path = path.to_path_buf();
+        Ok(manifest)
+    }
+
+    /// 清单文件路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 保存清单（先写临时文件再重命名，避免中断时损坏清单）
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            if !parent.as_os_str().is_empty() && !parent.exists() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+
+        let tmp_path = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+
+    /// 判断源文件相对于清单记录的状态
+    pub fn status(&self, key: &str, content_hash: &str) -> ManifestStatus {
+        match self.files.get(key) {
+            None => ManifestStatus::New,
+            Some(entry) if entry.content_hash == content_hash => ManifestStatus::Unchanged,
+            Some(entry) => ManifestStatus::Changed(entry.clone()),
+        }
+    }
+
+    /// 记录一次成功的导入
+    pub fn record(
+        &mut self,
+        key: &str,
+        source_path: &Path,
+        content_hash: String,
+        root_block_ids: Vec<String>,
+        document_revision_id: i32,
+    ) {
+        self.files.insert(
+            key.to_string(),
+            ManifestEntry {
+                source_path: source_path.display().to_string(),
+                content_hash,
+                root_block_ids,
+                document_revision_id,
+                imported_at: chrono::Utc::now().to_rfc3339(),
+            },
+        );
+    }
+}
+
+/// 计算内容的 SHA-256 哈希（十六进制）
+pub fn content_hash(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
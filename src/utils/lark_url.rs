@@ -0,0 +1,37 @@
+/// 飞书域名，链接的主机为这些域名或其子域名时视为飞书链接
+const LARK_DOMAINS: [&str; 4] = ["feishu.cn", "larksuite.com", "larkoffice.com", "feishu-pre.cn"];
+
+/// 飞书链接指向的文档
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DocumentRef {
+    /// 新版文档
+    Docx(String),
+    /// 知识库节点
+    Wiki(String),
+}
+
+/// 从飞书文档链接中解析文档，如 `https://xxx.feishu.cn/docx/<token>`、`https://xxx.larksuite.com/wiki/<token>`
+pub fn document_from_url(url: &str) -> Option<DocumentRef> {
+    let mut segments = lark_url_segments(url)?;
+    let kind = segments.next()?;
+    let token = segments.next()?.to_string();
+    match kind {
+        "docx" => Some(DocumentRef::Docx(token)),
+        "wiki" => Some(DocumentRef::Wiki(token)),
+        _ => None,
+    }
+}
+
+/// 飞书域名链接的路径段（不含查询参数和锚点），其他域名返回 None
+pub fn lark_url_segments(url: &str) -> Option<impl Iterator<Item = &str>> {
+    let rest = url.strip_prefix("https://").or_else(|| url.strip_prefix("http://"))?;
+    let (host, path) = rest.split_once('/')?;
+    let host = host.to_lowercase();
+    let is_lark = LARK_DOMAINS
+        .iter()
+        .any(|domain| host == *domain || host.ends_with(&format!(".{}", domain)));
+    if !is_lark {
+        return None;
+    }
+    Some(path.split(['?', '#']).next()?.split('/').filter(|s| !s.is_empty()))
+}
@@ -0,0 +1,125 @@
+use std::path::Path;
+
+/// 可以作为图片块插入的图片格式
+const IMAGE_MIME_TYPES: [&str; 7] = [
+    "image/png",
+    "image/jpeg",
+    "image/gif",
+    "image/bmp",
+    "image/webp",
+    "image/tiff",
+    "image/heic",
+];
+
+/// 识别文件的 MIME 类型：先按文件头识别常见格式，再按扩展名判断
+pub fn detect_mime_type(path: &Path, content: &[u8]) -> &'static str {
+    if content.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return "image/png";
+    }
+    if content.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        return "image/jpeg";
+    }
+    if content.starts_with(b"GIF87a") || content.starts_with(b"GIF89a") {
+        return "image/gif";
+    }
+    if content.starts_with(b"BM") && content.len() >= 26 {
+        return "image/bmp";
+    }
+    if content.len() >= 12 && &content[0..4] == b"RIFF" && &content[8..12] == b"WEBP" {
+        return "image/webp";
+    }
+    if content.starts_with(b"%PDF-") {
+        return "application/pdf";
+    }
+
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "bmp" => "image/bmp",
+        "webp" => "image/webp",
+        "tif" | "tiff" => "image/tiff",
+        "heic" => "image/heic",
+        "svg" => "image/svg+xml",
+        "pdf" => "application/pdf",
+        "txt" | "log" => "text/plain",
+        "md" => "text/markdown",
+        "csv" => "text/csv",
+        "json" => "application/json",
+        "zip" => "application/zip",
+        "doc" => "application/msword",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xls" => "application/vnd.ms-excel",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "ppt" => "application/vnd.ms-powerpoint",
+        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        "mp3" => "audio/mpeg",
+        "mp4" => "video/mp4",
+        "mov" => "video/quicktime",
+        _ => "application/octet-stream",
+    }
+}
+
+/// 该 MIME 类型的文件是否作为图片块插入，其他文件作为附件块插入
+pub fn is_image_mime_type(mime_type: &str) -> bool {
+    IMAGE_MIME_TYPES.contains(&mime_type)
+}
+
+/// 从文件头读取图片的宽高，支持 PNG、JPEG、GIF、BMP 和 WebP
+pub fn image_dimensions(content: &[u8]) -> Option<(u32, u32)> {
+    let u16_be = |at: usize| Some(u16::from_be_bytes([*content.get(at)?, *content.get(at + 1)?]) as u32);
+    let u16_le = |at: usize| Some(u16::from_le_bytes([*content.get(at)?, *content.get(at + 1)?]) as u32);
+    let u24_le = |at: usize| {
+        Some(u32::from_le_bytes([*content.get(at)?, *content.get(at + 1)?, *content.get(at + 2)?, 0]))
+    };
+    let i32_le = |at: usize| Some(i32::from_le_bytes(content.get(at..at + 4)?.try_into().ok()?));
+
+    let dimensions = match detect_mime_type(Path::new(""), content) {
+        "image/png" if content.get(12..16) == Some(b"IHDR") => {
+            let width = u32::from_be_bytes(content.get(16..20)?.try_into().ok()?);
+            let height = u32::from_be_bytes(content.get(20..24)?.try_into().ok()?);
+            (width, height)
+        }
+        "image/gif" => (u16_le(6)?, u16_le(8)?),
+        "image/bmp" => (i32_le(18)?.unsigned_abs(), i32_le(22)?.unsigned_abs()),
+        "image/webp" => match content.get(12..16)? {
+            b"VP8 " => (u16_le(26)? & 0x3FFF, u16_le(28)? & 0x3FFF),
+            b"VP8L" => {
+                let b = content.get(21..25)?;
+                let width = 1 + ((((b[1] & 0x3F) as u32) << 8) | b[0] as u32);
+                let height = 1 + ((((b[3] & 0x0F) as u32) << 10) | ((b[2] as u32) << 2) | ((b[1] & 0xC0) as u32 >> 6));
+                (width, height)
+            }
+            b"VP8X" => (1 + u24_le(24)?, 1 + u24_le(27)?),
+            _ => return None,
+        },
+        "image/jpeg" => {
+            // 依次跳过各段，直到帧头（SOF）段
+            let mut pos = 2;
+            loop {
+                while *content.get(pos)? != 0xFF {
+                    pos += 1;
+                }
+                while *content.get(pos)? == 0xFF {
+                    pos += 1;
+                }
+                let marker = *content.get(pos)?;
+                pos += 1;
+                match marker {
+                    0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                        break (u16_be(pos + 5)?, u16_be(pos + 3)?);
+                    }
+                    0xD0..=0xD9 | 0x01 => {}
+                    _ => pos += u16_be(pos)? as usize,
+                }
+            }
+        }
+        _ => return None,
+    };
+    (dimensions.0 > 0 && dimensions.1 > 0).then_some(dimensions)
+}
@@ -0,0 +1,274 @@
+use crate::api::DescendantBlock;
+use crate::utils::diagram_fences::{fence_open, is_fence_close};
+use crate::utils::{document_from_url, percent_decode, DocumentRef};
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// 代码块类型：其中的提及保留为原文
+const CODE_BLOCK_TYPE: i32 = 14;
+
+/// Markdown 中识别出的 @提及
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MentionMarker {
+    /// 替换原文的占位符，转换后据此定位提及
+    pub placeholder: String,
+    /// 原文，如 `@alice@corp.com`
+    pub source: String,
+    /// 提及的用户
+    pub target: MentionTarget,
+}
+
+/// 提及的用户
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MentionTarget {
+    /// `@[Bob](ou_xxx)`：直接指定 open_id
+    OpenId(String),
+    /// `@alice@corp.com`：需要通过通讯录解析为 open_id
+    Email(String),
+}
+
+/// 无法解析的提及
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MentionWarning {
+    /// 提及原文
+    pub mention: String,
+    /// 原因
+    pub message: String,
+}
+
+/// 查找 Markdown 中的 `@邮箱` 和 `@[名字](open_id)` 提及，替换为唯一的占位符
+///
+/// 代码块和行内代码中的内容保持不变。占位符只包含字母和数字，转换时不会被当作 Markdown 语法。
+pub fn mark_mentions(markdown: &str) -> (String, Vec<MentionMarker>) {
+    let prefix = format!("LARKMENTION{}", uuid::Uuid::new_v4().simple());
+    let pattern = Regex::new(
+        r"@\[([^\]\n]*)\]\((ou_[A-Za-z0-9_-]+)\)|@([A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,})",
+    )
+    .expect("提及正则表达式无效");
+
+    let mut output = String::with_capacity(markdown.len());
+    let mut markers = Vec::new();
+    let mut fence: Option<String> = None;
+
+    for line in markdown.split_inclusive('\n') {
+        match &fence {
+            Some(open) => {
+                if is_fence_close(line, open) {
+                    fence = None;
+                }
+                output.push_str(line);
+                continue;
+            }
+            None => {
+                if let Some((open, _)) = fence_open(line) {
+                    fence = Some(open.to_string());
+                    output.push_str(line);
+                    continue;
+                }
+            }
+        }
+
+        // 奇数段位于行内代码中
+        for (i, segment) in line.split('`').enumerate() {
+            if i > 0 {
+                output.push('`');
+            }
+            if i % 2 == 1 {
+                output.push_str(segment);
+                continue;
+            }
+
+            let mut last = 0;
+            for captures in pattern.captures_iter(segment) {
+                let whole = captures.get(0).expect("整体匹配总是存在");
+                // 前面紧跟字母数字时不是提及，如 `a@b@c.com`
+                if segment[..whole.start()]
+                    .chars()
+                    .next_back()
+                    .is_some_and(|c| c.is_alphanumeric() || c == '.')
+                {
+                    continue;
+                }
+                let target = match (captures.get(2), captures.get(3)) {
+                    (Some(open_id), _) => MentionTarget::OpenId(open_id.as_str().to_string()),
+                    (None, Some(email)) => MentionTarget::Email(email.as_str().to_string()),
+                    _ => continue,
+                };
+                let placeholder = format!("{}N{}E", prefix, markers.len());
+                output.push_str(&segment[last..whole.start()]);
+                output.push_str(&placeholder);
+                last = whole.end();
+                markers.push(MentionMarker {
+                    placeholder,
+                    source: whole.as_str().to_string(),
+                    target,
+                });
+            }
+            output.push_str(&segment[last..]);
+        }
+    }
+
+    (output, markers)
+}
+
+/// 需要解析的邮箱（去重，保持出现顺序）
+pub fn mention_emails(markers: &[MentionMarker]) -> Vec<String> {
+    let mut emails: Vec<String> = Vec::new();
+    for marker in markers {
+        if let MentionTarget::Email(email) = &marker.target {
+            if !emails.contains(email) {
+                emails.push(email.clone());
+            }
+        }
+    }
+    emails
+}
+
+/// 将转换后块中的占位符替换为 `mention_user` 文本元素，指向飞书文档的链接替换为 `mention_doc`
+///
+/// `open_ids` 为邮箱到 open_id 的映射。无法解析的邮箱保留原文，返回对应的警告。
+pub fn apply_mentions(
+    descendants: &mut [DescendantBlock],
+    markers: &[MentionMarker],
+    open_ids: &HashMap<String, String>,
+) -> Vec<MentionWarning> {
+    let mut warnings = Vec::new();
+    for block in descendants.iter_mut() {
+        let in_code = block.block_type == CODE_BLOCK_TYPE;
+        let Some(elements) = elements_mut(block).and_then(Value::as_array_mut) else {
+            continue;
+        };
+
+        let mut rewritten = Vec::with_capacity(elements.len());
+        for element in elements.drain(..) {
+            let Some(run) = element.get("text_run") else {
+                rewritten.push(element);
+                continue;
+            };
+            let content = run.get("content").and_then(Value::as_str).unwrap_or_default();
+            let style = run.get("text_element_style").cloned();
+
+            if !in_code {
+                if let Some(mention) = style.as_ref().and_then(document_mention) {
+                    rewritten.push(mention);
+                    continue;
+                }
+            }
+            if !markers.iter().any(|m| content.contains(&m.placeholder)) {
+                rewritten.push(element);
+                continue;
+            }
+
+            let mut pending = String::new();
+            for segment in split_placeholders(content, markers) {
+                let marker = match segment {
+                    Segment::Text(text) => {
+                        pending.push_str(text);
+                        continue;
+                    }
+                    Segment::Mention(marker) => marker,
+                };
+                let user_id = match &marker.target {
+                    _ if in_code => None,
+                    MentionTarget::OpenId(open_id) => Some(open_id.clone()),
+                    MentionTarget::Email(email) => {
+                        let user_id = open_ids.get(email).cloned();
+                        let reported = warnings.iter().any(|w: &MentionWarning| w.mention == marker.source);
+                        if user_id.is_none() && !reported {
+                            warnings.push(MentionWarning {
+                                mention: marker.source.clone(),
+                                message: "找不到该邮箱对应的用户，保留为文本".to_string(),
+                            });
+                        }
+                        user_id
+                    }
+                };
+                match user_id {
+                    Some(user_id) => {
+                        if !pending.is_empty() {
+                            rewritten.push(text_run(&std::mem::take(&mut pending), style.as_ref()));
+                        }
+                        rewritten.push(json!({ "mention_user": { "user_id": user_id } }));
+                    }
+                    None => pending.push_str(&marker.source),
+                }
+            }
+            if !pending.is_empty() {
+                rewritten.push(text_run(&pending, style.as_ref()));
+            }
+        }
+        *elements = rewritten;
+    }
+    warnings
+}
+
+enum Segment<'a> {
+    Text(&'a str),
+    Mention(&'a MentionMarker),
+}
+
+/// 按占位符拆分文本
+fn split_placeholders<'a>(content: &'a str, markers: &'a [MentionMarker]) -> Vec<Segment<'a>> {
+    let mut segments = Vec::new();
+    let mut rest = content;
+    loop {
+        let next = markers
+            .iter()
+            .filter_map(|m| rest.find(&m.placeholder).map(|pos| (pos, m)))
+            .min_by_key(|(pos, _)| *pos);
+        let Some((pos, marker)) = next else {
+            break;
+        };
+        if pos > 0 {
+            segments.push(Segment::Text(&rest[..pos]));
+        }
+        segments.push(Segment::Mention(marker));
+        rest = &rest[pos + marker.placeholder.len()..];
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Text(rest));
+    }
+    segments
+}
+
+/// 链接指向飞书文档时，返回对应的 `mention_doc` 元素
+fn document_mention(style: &Value) -> Option<Value> {
+    let url = style.pointer("/link/url").and_then(Value::as_str)?;
+    let (token, obj_type) = match document_from_url(&percent_decode(url))? {
+        DocumentRef::Docx(token) => (token, 22),
+        DocumentRef::Wiki(token) => (token, 16),
+    };
+    Some(json!({ "mention_doc": { "token": token, "obj_type": obj_type, "url": url } }))
+}
+
+fn text_run(content: &str, style: Option<&Value>) -> Value {
+    match style {
+        Some(style) => json!({ "text_run": { "content": content, "text_element_style": style } }),
+        None => json!({ "text_run": { "content": content } }),
+    }
+}
+
+/// 块内容中的文本元素列表
+fn elements_mut(block: &mut DescendantBlock) -> Option<&mut Value> {
+    let payload = match block.block_type {
+        2 => block.text.as_mut(),
+        3 => block.heading1.as_mut(),
+        4 => block.heading2.as_mut(),
+        5 => block.heading3.as_mut(),
+        6 => block.heading4.as_mut(),
+        7 => block.heading5.as_mut(),
+        8 => block.heading6.as_mut(),
+        9 => block.heading7.as_mut(),
+        10 => block.heading8.as_mut(),
+        11 => block.heading9.as_mut(),
+        12 => block.bullet.as_mut(),
+        13 => block.ordered.as_mut(),
+        14 => block.code.as_mut(),
+        15 => block.quote.as_mut(),
+        17 => block.todo.as_mut(),
+        _ => None,
+    }?;
+    payload.get_mut("elements")
+}
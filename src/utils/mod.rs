@@ -1,3 +1,59 @@
 pub mod file_utils;
+pub mod import_manifest;
+pub mod block_tree;
+pub mod doc_hierarchy;
+pub mod front_matter;
+pub mod diagram_fences;
+pub mod block_diff;
+pub mod project_manifest;
+pub mod block_markdown;
+pub mod text_diff;
+pub mod revision_diff;
+pub mod text_replace;
+pub mod doc_sections;
+pub mod template;
+pub mod table_data;
+pub mod block_selector;
+pub mod lark_url;
+pub mod doc_lint;
+pub mod doc_toc;
+pub mod mentions;
+pub mod media_info;
+pub mod comment_export;
+pub mod drive_ref;
+pub mod drive_tree;
+pub mod checksum;
+pub mod upload_state;
+pub mod byte_range;
+pub mod download_state;
+pub mod sequence_diff;
 
-pub use file_utils::*;
\ No newline at end of file
+pub use file_utils::*;
+pub use import_manifest::*;
+pub use block_tree::*;
+pub use doc_hierarchy::*;
+pub use front_matter::*;
+pub use diagram_fences::*;
+pub use block_diff::*;
+pub use project_manifest::*;
+pub use block_markdown::*;
+pub use text_diff::*;
+pub use revision_diff::*;
+pub use text_replace::*;
+pub use doc_sections::*;
+pub use template::*;
+pub use table_data::*;
+pub use block_selector::*;
+pub use lark_url::*;
+pub use doc_lint::*;
+pub use doc_toc::*;
+pub use mentions::*;
+pub use media_info::*;
+pub use comment_export::*;
+pub use drive_ref::*;
+pub use drive_tree::*;
+pub use checksum::*;
+pub use upload_state::*;
+pub use byte_range::*;
+pub use download_state::*;
+pub use sequence_diff::*;
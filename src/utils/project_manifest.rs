@@ -0,0 +1,269 @@
+use crate::error::{LarkError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// 默认的项目清单文件名
+pub const DEFAULT_PROJECT_FILE: &str = ".lark.toml";
+
+/// 默认的发布锁文件名（与项目清单位于同一目录）
+pub const DEFAULT_LOCK_FILE: &str = ".lark.lock";
+
+/// 当前锁文件格式版本
+const LOCK_VERSION: u32 = 1;
+
+/// 文档即代码的项目清单，描述本地 Markdown 文件与飞书文档的对应关系
+///
+/// ```toml
+/// lockfile = ".lark.lock"
+///
+/// [[documents]]
+/// path = "docs/design.md"
+/// document_id = "doxcnAbCdEf"
+///
+/// [[documents]]
+/// path = "docs/guide.md"
+/// wiki_node = "wikcnAbCdEf"
+/// ```
+#[derive(Debug, Deserialize)]
+pub struct ProjectManifest {
+    /// 锁文件路径（相对于清单所在目录）
+    #[serde(default)]
+    pub lockfile: Option<String>,
+    /// 文件与文档的映射
+    #[serde(default)]
+    pub documents: Vec<ProjectDocument>,
+    /// 清单所在目录，文件路径相对于该目录
+    #[serde(skip)]
+    root: PathBuf,
+}
+
+/// 一个本地文件与飞书文档的映射，`document_id` 和 `wiki_node` 必须且只能指定一个
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProjectDocument {
+    /// 本地文件路径（相对于清单所在目录）
+    pub path: String,
+    /// 文档 ID
+    #[serde(default)]
+    pub document_id: Option<String>,
+    /// 知识库节点 token
+    #[serde(default)]
+    pub wiki_node: Option<String>,
+}
+
+impl ProjectManifest {
+    /// 加载并校验项目清单
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            LarkError::IoError(format!("读取项目清单失败 {}: {}", path.display(), e))
+        })?;
+
+        let mut manifest = Self::parse(&content).map_err(|e| match e {
+            LarkError::ParseError(msg) => LarkError::ParseError(format!("{} ({})", msg, path.display())),
+            other => other,
+        })?;
+        manifest.root = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        Ok(manifest)
+    }
+
+    /// 解析并校验清单内容
+    pub fn parse(content: &str) -> Result<Self> {
+        let manifest: ProjectManifest = toml::from_str(content)
+            .map_err(|e| LarkError::ParseError(format!("项目清单格式错误: {}", e)))?;
+
+        let mut paths = HashSet::new();
+        for document in &manifest.documents {
+            if document.document_id.is_some() == document.wiki_node.is_some() {
+                return Err(LarkError::ValidationError(format!(
+                    "{}: document_id 和 wiki_node 必须且只能指定一个",
+                    document.path
+                )));
+            }
+            if !paths.insert(document.key()) {
+                return Err(LarkError::ValidationError(format!(
+                    "{}: 同一文件不能映射到多个文档",
+                    document.path
+                )));
+            }
+        }
+
+        Ok(manifest)
+    }
+
+    /// 本地文件的绝对路径
+    pub fn file_path(&self, document: &ProjectDocument) -> PathBuf {
+        self.root.join(&document.path)
+    }
+
+    /// 锁文件路径
+    pub fn lock_path(&self) -> PathBuf {
+        self.root.join(self.lockfile.as_deref().unwrap_or(DEFAULT_LOCK_FILE))
+    }
+}
+
+impl ProjectDocument {
+    /// 在锁文件中使用的键：统一分隔符并去掉开头的 `./`
+    pub fn key(&self) -> String {
+        let path = self.path.replace('\\', "/");
+        path.trim_start_matches("./").to_string()
+    }
+}
+
+/// 发布锁文件，记录每个文件最近一次发布时本地内容和飞书文档内容的哈希
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProjectLock {
+    /// 锁文件格式版本
+    pub version: u32,
+    /// 已发布的文件，键为清单中的相对路径
+    pub files: BTreeMap<String, LockEntry>,
+    /// 锁文件路径
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+/// 单个文件的发布记录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockEntry {
+    /// 发布到的文档 ID
+    pub document_id: String,
+    /// 发布时本地文件内容的哈希
+    pub content_hash: String,
+    /// 发布后飞书文档块内容的哈希
+    pub remote_hash: String,
+    /// 发布后的文档版本号
+    pub document_revision_id: i32,
+    /// 发布时间（RFC 3339）
+    pub published_at: String,
+}
+
+/// 文件与飞书文档的一致性状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftStatus {
+    /// 本地文件和飞书文档都与上次发布一致
+    InSync,
+    /// 从未发布过
+    NotPublished,
+    /// 本地文件有未发布的修改
+    LocalChanged,
+    /// 飞书文档在上次发布后被修改
+    RemoteChanged,
+    /// 本地文件和飞书文档都被修改
+    BothChanged,
+}
+
+impl DriftStatus {
+    /// 根据本地内容哈希和飞书文档内容哈希判断状态
+    pub fn detect(entry: Option<&LockEntry>, document_id: &str, content_hash: &str, remote_hash: &str) -> Self {
+        let Some(entry) = entry.filter(|e| e.document_id == document_id) else {
+            return DriftStatus::NotPublished;
+        };
+
+        match (entry.content_hash != content_hash, entry.remote_hash != remote_hash) {
+            (false, false) => DriftStatus::InSync,
+            (true, false) => DriftStatus::LocalChanged,
+            (false, true) => DriftStatus::RemoteChanged,
+            (true, true) => DriftStatus::BothChanged,
+        }
+    }
+
+    /// 状态说明
+    pub fn description(self) -> &'static str {
+        match self {
+            DriftStatus::InSync => "已同步",
+            DriftStatus::NotPublished => "尚未发布",
+            DriftStatus::LocalChanged => "本地有未发布的修改",
+            DriftStatus::RemoteChanged => "飞书文档在上次发布后被修改",
+            DriftStatus::BothChanged => "本地和飞书文档都有修改",
+        }
+    }
+}
+
+impl ProjectLock {
+    /// 加载锁文件，文件不存在时返回空锁
+    pub fn load_or_default(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self {
+                version: LOCK_VERSION,
+                files: BTreeMap::new(),
+                path: path.to_path_buf(),
+            });
+        }
+
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            LarkError::IoError(format!("读取锁文件失败 {}: {}", path.display(), e))
+        })?;
+        let mut lock: ProjectLock = serde_json::from_str(&content).map_err(|e| {
+            LarkError::ParseError(format!("锁文件格式错误 {}: {}", path.display(), e))
+        })?;
+
+        if lock.version != LOCK_VERSION {
+            return Err(LarkError::ValidationError(format!(
+                "不支持的锁文件版本 {}（当前版本 {}）",
+                lock.version, LOCK_VERSION
+            )));
+        }
+
+        lock.path = path.to_path_buf();
+        Ok(lock)
+    }
+
+    /// 保存锁文件（先写临时文件再重命名）
+    pub fn save(&self) -> Result<()> {
+        let tmp_path = self.path.with_extension("lock.tmp");
+        std::fs::write(&tmp_path, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+
+    /// 记录一次成功的发布
+    pub fn record(
+        &mut self,
+        key: &str,
+        document_id: &str,
+        content_hash: String,
+        remote_hash: String,
+        document_revision_id: i32,
+    ) {
+        self.files.insert(
+            key.to_string(),
+            LockEntry {
+                document_id: document_id.to_string(),
+                content_hash,
+                remote_hash,
+                document_revision_id,
+                published_at: chrono::Utc::now().to_rfc3339(),
+            },
+        );
+    }
+}
+
+/// 发布或检查的结果
+#[derive(Debug, Default, Serialize)]
+pub struct PublishReport {
+    /// 是否为检查模式（不修改文档）
+    pub check: bool,
+    /// 已发布的文件数
+    pub published_count: usize,
+    /// 与飞书文档不一致的文件数（检查模式）或未能发布的文件数
+    pub drifted_count: usize,
+    /// 每个文件的结果
+    pub files: Vec<PublishedFile>,
+}
+
+/// 单个文件的发布或检查结果
+#[derive(Debug, Serialize)]
+pub struct PublishedFile {
+    /// 清单中的相对路径
+    pub path: String,
+    /// 文档 ID
+    pub document_id: Option<String>,
+    /// 发布前的一致性状态
+    pub status: Option<DriftStatus>,
+    /// 是否已发布
+    pub published: bool,
+    /// 发布后的文档版本号
+    pub document_revision_id: Option<i32>,
+    /// 错误信息（如果有）
+    pub error: Option<String>,
+}
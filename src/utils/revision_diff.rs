@@ -0,0 +1,204 @@
+use crate::api::get_blocks::BlockItem;
+use crate::utils::{block_to_markdown, matching_pairs, normalize, BlockTree};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+/// 两个文档版本之间的结构差异，按块 ID 对应
+#[derive(Debug, Default, Serialize)]
+pub struct RevisionDiff {
+    /// 起始版本
+    pub from_revision: i32,
+    /// 目标版本
+    pub to_revision: i32,
+    /// 新增的块
+    pub added: Vec<BlockChange>,
+    /// 删除的块
+    pub removed: Vec<BlockChange>,
+    /// 移动了位置的块
+    pub moved: Vec<BlockMove>,
+    /// 内容或属性被修改的块
+    pub modified: Vec<BlockModification>,
+}
+
+/// 新增或删除的块
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockChange {
+    /// 块 ID
+    pub block_id: String,
+    /// 块类型
+    pub block_type: i32,
+    /// 父块 ID
+    pub parent_id: String,
+    /// 在父块中的位置
+    pub index: usize,
+    /// 块的 Markdown 表示（不含子块）
+    pub text: String,
+}
+
+/// 移动了位置的块
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockMove {
+    /// 块 ID
+    pub block_id: String,
+    /// 块类型
+    pub block_type: i32,
+    /// 原父块 ID
+    pub from_parent_id: String,
+    /// 原位置
+    pub from_index: usize,
+    /// 新父块 ID
+    pub to_parent_id: String,
+    /// 新位置
+    pub to_index: usize,
+    /// 块的 Markdown 表示（不含子块）
+    pub text: String,
+}
+
+/// 被修改的块
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockModification {
+    /// 块 ID
+    pub block_id: String,
+    /// 块类型
+    pub block_type: i32,
+    /// 修改前的 Markdown 表示
+    pub before: String,
+    /// 修改后的 Markdown 表示
+    pub after: String,
+}
+
+impl RevisionDiff {
+    /// 两个版本是否完全相同
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.moved.is_empty() && self.modified.is_empty()
+    }
+}
+
+/// 比较同一文档的两个版本
+///
+/// 块 ID 在版本之间保持不变：只在一个版本中出现的块为新增或删除；父块不同，
+/// 或在同一父块下不属于共同块的最长公共子序列的块为移动；去掉子块和默认值后
+/// 内容不同的块为修改，文档根块（页面块）的修改即标题的修改。
+pub fn diff_revisions(
+    from: &BlockTree,
+    to: &BlockTree,
+    document_id: &str,
+    from_revision: i32,
+    to_revision: i32,
+) -> RevisionDiff {
+    let mut diff = RevisionDiff {
+        from_revision,
+        to_revision,
+        ..Default::default()
+    };
+
+    for block in to.blocks() {
+        match from.get(&block.block_id) {
+            None => diff.added.push(block_change(to, block)),
+            Some(old) if comparable_content(old) != comparable_content(block) => {
+                diff.modified.push(BlockModification {
+                    block_id: block.block_id.clone(),
+                    block_type: block.block_type,
+                    before: block_to_markdown(old),
+                    after: block_to_markdown(block),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+
+    for block in from.blocks() {
+        if to.get(&block.block_id).is_none() {
+            diff.removed.push(block_change(from, block));
+        }
+    }
+
+    diff.moved = moved_blocks(from, to, document_id);
+    diff
+}
+
+fn block_change(tree: &BlockTree, block: &BlockItem) -> BlockChange {
+    let parent_id = block.parent_id.clone();
+    BlockChange {
+        index: tree.child_index(&parent_id, &block.block_id).unwrap_or(0),
+        block_id: block.block_id.clone(),
+        block_type: block.block_type,
+        parent_id,
+        text: block_to_markdown(block),
+    }
+}
+
+/// 去掉 ID、子块引用和默认值后的块内容
+fn comparable_content(block: &BlockItem) -> Value {
+    let mut content = block.content.clone();
+    if let Some(object) = content.as_object_mut() {
+        for key in ["block_id", "block_type", "parent_id", "children"] {
+            object.remove(key);
+        }
+    }
+    normalize(&mut content);
+    serde_json::json!([block.block_type, content])
+}
+
+fn moved_blocks(from: &BlockTree, to: &BlockTree, document_id: &str) -> Vec<BlockMove> {
+    let mut moved = Vec::new();
+    let mut moved_ids = HashSet::new();
+
+    // 父块发生变化的块
+    for block in to.blocks() {
+        if block.block_id == document_id {
+            continue;
+        }
+        let Some(old) = from.get(&block.block_id) else {
+            continue;
+        };
+        if old.parent_id != block.parent_id {
+            moved_ids.insert(block.block_id.clone());
+        }
+    }
+
+    // 父块未变化、但与兄弟块的相对顺序发生变化的块
+    for parent in to.blocks() {
+        let Some(old_parent) = from.get(&parent.block_id) else {
+            continue;
+        };
+        let stays = |id: &String| {
+            to.get(id).map(|b| &b.parent_id) == from.get(id).map(|b| &b.parent_id)
+                && from.get(id).is_some()
+        };
+        let old_order: Vec<&String> = old_parent.children.iter().flatten().filter(|id| stays(id)).collect();
+        let new_order: Vec<&String> = parent.children.iter().flatten().filter(|id| stays(id)).collect();
+
+        let kept: HashSet<&String> = matching_pairs(&old_order, &new_order).into_iter().map(|(i, _)| old_order[i]).collect();
+        moved_ids.extend(new_order.into_iter().filter(|id| !kept.contains(id)).cloned());
+    }
+
+    let positions: HashMap<&str, usize> = to
+        .blocks()
+        .iter()
+        .enumerate()
+        .map(|(i, b)| (b.block_id.as_str(), i))
+        .collect();
+    let mut ids: Vec<String> = moved_ids.into_iter().collect();
+    ids.sort_by_key(|id| positions.get(id.as_str()).copied());
+
+    for id in ids {
+        let (Some(old), Some(new)) = (from.get(&id), to.get(&id)) else {
+            continue;
+        };
+        let from_parent_id = old.parent_id.clone();
+        let to_parent_id = new.parent_id.clone();
+        moved.push(BlockMove {
+            from_index: from.child_index(&from_parent_id, &id).unwrap_or(0),
+            to_index: to.child_index(&to_parent_id, &id).unwrap_or(0),
+            block_id: id,
+            block_type: new.block_type,
+            from_parent_id,
+            to_parent_id,
+            text: block_to_markdown(new),
+        });
+    }
+
+    moved
+}
@@ -0,0 +1,143 @@
+/// 两个序列之间的逐项编辑操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffOp {
+    /// 旧序列第 i 项与新序列第 j 项相同
+    Equal(usize, usize),
+    /// 删除旧序列第 i 项
+    Delete(usize),
+    /// 插入新序列第 j 项
+    Insert(usize),
+}
+
+/// 计算把 `old` 变为 `new` 的最少编辑操作，同一段修改中删除排在插入之前
+pub fn diff_ops<T: PartialEq>(old: &[T], new: &[T]) -> Vec<DiffOp> {
+    let mut ops = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    for (mi, mj) in matching_pairs(old, new).into_iter().chain(std::iter::once((old.len(), new.len()))) {
+        ops.extend((i..mi).map(DiffOp::Delete));
+        ops.extend((j..mj).map(DiffOp::Insert));
+        if mi < old.len() {
+            ops.push(DiffOp::Equal(mi, mj));
+        }
+        i = mi + 1;
+        j = mj + 1;
+    }
+    ops
+}
+
+/// 最长公共子序列中相互对应的位置 `(旧序列下标, 新序列下标)`，按下标升序排列
+///
+/// 使用 Myers 差分算法的线性空间版本：先去掉公共前缀和后缀，再以中间蛇形分割递归求解，
+/// 内存只与两个序列的长度之和成正比，大文档比较时不会耗尽内存。
+pub fn matching_pairs<T: PartialEq>(old: &[T], new: &[T]) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    collect_pairs(old, new, 0, 0, &mut pairs);
+    pairs
+}
+
+fn collect_pairs<T: PartialEq>(old: &[T], new: &[T], old_offset: usize, new_offset: usize, pairs: &mut Vec<(usize, usize)>) {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    pairs.extend((0..prefix).map(|k| (old_offset + k, new_offset + k)));
+    let (old_rest, new_rest) = (&old[prefix..], &new[prefix..]);
+    let suffix = old_rest
+        .iter()
+        .rev()
+        .zip(new_rest.iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old_rest[..old_rest.len() - suffix];
+    let new_mid = &new_rest[..new_rest.len() - suffix];
+
+    if !old_mid.is_empty() && !new_mid.is_empty() {
+        if let Some((x, y)) = middle_split(old_mid, new_mid) {
+            let (old_offset, new_offset) = (old_offset + prefix, new_offset + prefix);
+            collect_pairs(&old_mid[..x], &new_mid[..y], old_offset, new_offset, pairs);
+            collect_pairs(&old_mid[x..], &new_mid[y..], old_offset + x, new_offset + y, pairs);
+        }
+    }
+
+    let old_end = old_offset + old.len();
+    let new_end = new_offset + new.len();
+    pairs.extend((0..suffix).rev().map(|k| (old_end - 1 - k, new_end - 1 - k)));
+}
+
+/// 同时从两端搜索最短编辑路径，返回两条路径相遇处的分割点；没有公共项时返回 `None`
+fn middle_split<T: PartialEq>(old: &[T], new: &[T]) -> Option<(usize, usize)> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max_d = (n + m + 1) / 2;
+    let offset = max_d;
+    let size = (2 * max_d + 2) as usize;
+    // forward[k]: 正向第 d 步在对角线 k 上到达的最远 x；backward 为从末尾反向搜索的结果
+    let mut forward = vec![-1isize; size];
+    let mut backward = vec![-1isize; size];
+    forward[(offset + 1) as usize] = 0;
+    backward[(offset + 1) as usize] = 0;
+    let delta = n - m;
+    // 差值为奇数时在正向搜索中检查相遇，否则在反向搜索中检查
+    let front = delta % 2 != 0;
+    let (mut k1_start, mut k1_end, mut k2_start, mut k2_end) = (0, 0, 0, 0);
+
+    for d in 0..max_d {
+        let mut k1 = -d + k1_start;
+        while k1 <= d - k1_end {
+            let k1_index = (offset + k1) as usize;
+            let mut x1 = if k1 == -d || (k1 != d && forward[k1_index - 1] < forward[k1_index + 1]) {
+                forward[k1_index + 1]
+            } else {
+                forward[k1_index - 1] + 1
+            };
+            let mut y1 = x1 - k1;
+            while x1 < n && y1 < m && old[x1 as usize] == new[y1 as usize] {
+                x1 += 1;
+                y1 += 1;
+            }
+            forward[k1_index] = x1;
+            if x1 > n {
+                k1_end += 2;
+            } else if y1 > m {
+                k1_start += 2;
+            } else if front {
+                let k2_index = offset + delta - k1;
+                if k2_index >= 0 && (k2_index as usize) < size && backward[k2_index as usize] != -1 {
+                    let x2 = n - backward[k2_index as usize];
+                    if x1 >= x2 {
+                        return Some((x1 as usize, y1 as usize));
+                    }
+                }
+            }
+            k1 += 2;
+        }
+
+        let mut k2 = -d + k2_start;
+        while k2 <= d - k2_end {
+            let k2_index = (offset + k2) as usize;
+            let mut x2 = if k2 == -d || (k2 != d && backward[k2_index - 1] < backward[k2_index + 1]) {
+                backward[k2_index + 1]
+            } else {
+                backward[k2_index - 1] + 1
+            };
+            let mut y2 = x2 - k2;
+            while x2 < n && y2 < m && old[(n - x2 - 1) as usize] == new[(m - y2 - 1) as usize] {
+                x2 += 1;
+                y2 += 1;
+            }
+            backward[k2_index] = x2;
+            if x2 > n {
+                k2_end += 2;
+            } else if y2 > m {
+                k2_start += 2;
+            } else if !front {
+                let k1_index = offset + delta - k2;
+                if k1_index >= 0 && (k1_index as usize) < size && forward[k1_index as usize] != -1 {
+                    let x1 = forward[k1_index as usize];
+                    let y1 = offset + x1 - k1_index;
+                    if x1 >= n - x2 {
+                        return Some((x1 as usize, y1 as usize));
+                    }
+                }
+            }
+            k2 += 2;
+        }
+    }
+    None
+}
@@ -0,0 +1,243 @@
+use crate::error::{LarkError, Result};
+use crate::utils::{block_plain_text, BlockTree};
+use serde_json::{Map, Value};
+
+/// 表格块类型
+const TABLE_BLOCK_TYPE: i32 = 31;
+
+/// 表格导出格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TableFormat {
+    #[default]
+    Csv,
+    Markdown,
+    Json,
+}
+
+impl std::str::FromStr for TableFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(TableFormat::Csv),
+            "markdown" | "md" => Ok(TableFormat::Markdown),
+            "json" => Ok(TableFormat::Json),
+            _ => Err(format!("无效的表格格式: {}。可选值: csv, markdown, json", s)),
+        }
+    }
+}
+
+/// 解析 CSV 文本（RFC 4180）：字段可以用双引号包围，引号内可以包含逗号、换行和转义的 `""`
+///
+/// 空行被忽略，各行的列数不要求一致。
+pub fn parse_csv(text: &str) -> Result<Vec<Vec<String>>> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut rows = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => row.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                row.push(std::mem::take(&mut field));
+                push_row(&mut rows, std::mem::take(&mut row));
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(LarkError::ParseError("CSV 格式错误: 引号未闭合".to_string()));
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        push_row(&mut rows, row);
+    }
+    Ok(rows)
+}
+
+fn push_row(rows: &mut Vec<Vec<String>>, row: Vec<String>) {
+    if !(row.len() == 1 && row[0].is_empty()) {
+        rows.push(row);
+    }
+}
+
+/// 将各行补齐为相同列数
+pub fn pad_rows(rows: &[Vec<String>], columns: usize) -> Vec<Vec<String>> {
+    rows.iter()
+        .map(|row| {
+            let mut row = row.clone();
+            row.resize(columns, String::new());
+            row
+        })
+        .collect()
+}
+
+/// 所有行中的最大列数
+pub fn column_count(rows: &[Vec<String>]) -> usize {
+    rows.iter().map(Vec::len).max().unwrap_or(0)
+}
+
+/// 按指定格式输出表格；Markdown 和 JSON 以第一行为表头
+///
+/// JSON 格式在 `header` 为 true 时输出以表头为键的对象数组，否则输出二维数组。
+pub fn format_table(rows: &[Vec<String>], format: TableFormat, header: bool) -> Result<String> {
+    match format {
+        TableFormat::Csv => Ok(to_csv(rows)),
+        TableFormat::Markdown => Ok(to_markdown(rows)),
+        TableFormat::Json => {
+            let value = if header { to_json_objects(rows) } else { serde_json::json!(rows) };
+            Ok(serde_json::to_string_pretty(&value)?)
+        }
+    }
+}
+
+/// 输出 CSV，包含逗号、引号或换行的字段加引号
+pub fn to_csv(rows: &[Vec<String>]) -> String {
+    let mut output = String::new();
+    for row in rows {
+        let fields: Vec<String> = row
+            .iter()
+            .map(|field| {
+                if field.contains([',', '"', '\n', '\r']) {
+                    format!("\"{}\"", field.replace('"', "\"\""))
+                } else {
+                    field.clone()
+                }
+            })
+            .collect();
+        output.push_str(&fields.join(","));
+        output.push('\n');
+    }
+    output
+}
+
+/// 输出 Markdown 表格，第一行为表头
+pub fn to_markdown(rows: &[Vec<String>]) -> String {
+    let columns = column_count(rows);
+    if columns == 0 {
+        return String::new();
+    }
+
+    let mut output = String::new();
+    for (i, row) in pad_rows(rows, columns).iter().enumerate() {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|cell| cell.replace('|', "\\|").replace('\n', "<br>"))
+            .collect();
+        output.push_str(&format!("| {} |\n", cells.join(" | ")));
+        if i == 0 {
+            output.push_str(&format!("|{}\n", " --- |".repeat(columns)));
+        }
+    }
+    output
+}
+
+fn to_json_objects(rows: &[Vec<String>]) -> Value {
+    let Some((header, body)) = rows.split_first() else {
+        return Value::Array(Vec::new());
+    };
+
+    let objects = body
+        .iter()
+        .map(|row| {
+            let mut object = Map::new();
+            for (i, key) in header.iter().enumerate() {
+                let value = row.get(i).cloned().unwrap_or_default();
+                object.insert(key.clone(), Value::String(value));
+            }
+            Value::Object(object)
+        })
+        .collect();
+    Value::Array(objects)
+}
+
+/// 表格块的行列数
+pub fn table_size(tree: &BlockTree, table_block_id: &str) -> Result<(usize, usize)> {
+    let table = tree
+        .get(table_block_id)
+        .ok_or_else(|| LarkError::ValidationError(format!("找不到块: {}", table_block_id)))?;
+    if table.block_type != TABLE_BLOCK_TYPE {
+        return Err(LarkError::ValidationError(format!(
+            "块 {} 不是表格（块类型 {}）",
+            table_block_id, table.block_type
+        )));
+    }
+
+    let size = |key: &str| {
+        table
+            .content
+            .pointer(&format!("/table/property/{}", key))
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0) as usize
+    };
+    Ok((size("row_size"), size("column_size")))
+}
+
+/// 表格的单元格块 ID，按行优先排列
+///
+/// 优先使用表格属性中的 cells 列表，没有时使用表格的子块。
+pub fn table_cell_ids(tree: &BlockTree, table_block_id: &str) -> Result<Vec<String>> {
+    table_size(tree, table_block_id)?;
+    let table = tree.get(table_block_id).map(|t| &t.content);
+    let cells: Option<Vec<String>> = table
+        .and_then(|c| c.pointer("/table/cells"))
+        .and_then(|c| c.as_array())
+        .map(|cells| cells.iter().filter_map(|c| c.as_str().map(str::to_string)).collect());
+
+    Ok(match cells {
+        Some(cells) if !cells.is_empty() => cells,
+        _ => tree.children(table_block_id).to_vec(),
+    })
+}
+
+/// 指定单元格的块 ID，行列从 0 开始
+pub fn table_cell_id(tree: &BlockTree, table_block_id: &str, row: usize, column: usize) -> Result<String> {
+    let (rows, columns) = table_size(tree, table_block_id)?;
+    if row >= rows || column >= columns {
+        return Err(LarkError::ValidationError(format!(
+            "单元格 ({}, {}) 超出表格范围，表格为 {} 行 {} 列",
+            row, column, rows, columns
+        )));
+    }
+
+    table_cell_ids(tree, table_block_id)?
+        .get(row * columns + column)
+        .cloned()
+        .ok_or_else(|| LarkError::ValidationError(format!("找不到单元格 ({}, {})", row, column)))
+}
+
+/// 读取表格的所有单元格文本；单元格内的多个块以换行连接
+pub fn table_rows(tree: &BlockTree, table_block_id: &str) -> Result<Vec<Vec<String>>> {
+    let (_, columns) = table_size(tree, table_block_id)?;
+    let cells: Vec<String> = table_cell_ids(tree, table_block_id)?
+        .iter()
+        .map(|cell_id| {
+            tree.children(cell_id)
+                .iter()
+                .filter_map(|id| tree.get(id))
+                .map(block_plain_text)
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect();
+
+    Ok(cells.chunks(columns.max(1)).map(<[String]>::to_vec).collect())
+}
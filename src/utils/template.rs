@@ -0,0 +1,159 @@
+use crate::error::{LarkError, Result};
+use crate::utils::render_placeholders;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// 模板变量，值可以是字符串、数字、布尔值，或用于循环的列表
+pub type TemplateVariables = BTreeMap<String, Value>;
+
+/// 解析命令行中的 `key=value` 变量
+pub fn parse_var_assignments(assignments: &[String]) -> Result<TemplateVariables> {
+    let mut variables = TemplateVariables::new();
+    for assignment in assignments {
+        let (key, value) = assignment.split_once('=').ok_or_else(|| {
+            LarkError::ValidationError(format!("无效的变量: {}，格式应为 key=value", assignment))
+        })?;
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(LarkError::ValidationError(format!("变量名不能为空: {}", assignment)));
+        }
+        variables.insert(key.to_string(), Value::String(value.to_string()));
+    }
+    Ok(variables)
+}
+
+/// 从 YAML（或 JSON）文件加载变量，顶层必须是映射
+pub fn load_vars_file(path: &Path) -> Result<TemplateVariables> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| LarkError::IoError(format!("读取变量文件失败 {}: {}", path.display(), e)))?;
+    serde_yaml::from_str(&content)
+        .map_err(|e| LarkError::ParseError(format!("变量文件格式错误 {}: {}", path.display(), e)))
+}
+
+/// 占位符替换使用的字符串变量：标量直接转换，列表中的标量以逗号连接
+pub fn scalar_variables(variables: &TemplateVariables) -> BTreeMap<String, String> {
+    let mut scalars = BTreeMap::new();
+    for (key, value) in variables {
+        if let Some(text) = scalar_text(value) {
+            scalars.insert(key.clone(), text);
+        } else if let Value::Array(items) = value {
+            let items: Vec<String> = items.iter().filter_map(scalar_text).collect();
+            scalars.insert(key.clone(), items.join(", "));
+        }
+    }
+    scalars
+}
+
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// 循环中单个列表项的变量：`{{this}}` 为标量项本身，`{{this.key}}` 为对象项的字段
+pub fn item_variables(variables: &TemplateVariables, item: &Value) -> TemplateVariables {
+    let mut scoped = variables.clone();
+    match item {
+        Value::Object(fields) => {
+            for (key, value) in fields {
+                scoped.insert(format!("this.{}", key), value.clone());
+            }
+        }
+        other => {
+            scoped.insert("this".to_string(), other.clone());
+        }
+    }
+    scoped
+}
+
+/// 循环变量对应的列表
+pub fn loop_items<'a>(variables: &'a TemplateVariables, key: &str) -> Result<&'a [Value]> {
+    match variables.get(key) {
+        Some(Value::Array(items)) => Ok(items),
+        Some(_) => Err(LarkError::ValidationError(format!("循环变量 {} 不是列表", key))),
+        None => Err(LarkError::ValidationError(format!("未定义循环变量: {}", key))),
+    }
+}
+
+/// 循环开始标记 `{{#each key}}` 中的变量名
+pub fn loop_start(text: &str) -> Option<&str> {
+    let inner = text.trim().strip_prefix("{{")?.strip_suffix("}}")?.trim();
+    let key = inner.strip_prefix("#each")?.trim();
+    (!key.is_empty() && !key.contains(char::is_whitespace)).then_some(key)
+}
+
+/// 是否为循环结束标记 `{{/each}}`
+pub fn is_loop_end(text: &str) -> bool {
+    text.trim()
+        .strip_prefix("{{")
+        .and_then(|t| t.strip_suffix("}}"))
+        .is_some_and(|inner| inner.trim() == "/each")
+}
+
+/// 渲染模板文本：先展开 `{{#each key}} ... {{/each}}` 循环，再替换 `{{key}}` 占位符
+///
+/// 循环不支持嵌套，未定义的占位符保持不变。
+pub fn render_template(text: &str, variables: &TemplateVariables) -> Result<String> {
+    const END: &str = "{{/each}}";
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some((marker_start, marker_end, key)) = find_loop_start(rest) {
+        output.push_str(&rest[..marker_start]);
+
+        let body_and_rest = &rest[marker_end..];
+        let end = body_and_rest.find(END).ok_or_else(|| {
+            LarkError::ValidationError(format!("循环 {} 缺少结束标记 {{{{/each}}}}", key))
+        })?;
+        let body = trim_marker_line(&body_and_rest[..end]);
+
+        for item in loop_items(variables, key)? {
+            let scoped = scalar_variables(&item_variables(variables, item));
+            output.push_str(&render_placeholders(body, &scoped));
+        }
+
+        // 结束标记独占一行时连同换行一起去掉
+        rest = &body_and_rest[end + END.len()..];
+        if output.ends_with('\n') || output.is_empty() {
+            rest = rest.strip_prefix("\r\n").or_else(|| rest.strip_prefix('\n')).unwrap_or(rest);
+        }
+    }
+
+    output.push_str(rest);
+    Ok(render_placeholders(&output, &scalar_variables(variables)))
+}
+
+/// 查找下一个循环开始标记，返回 (标记起始位置, 标记结束位置, 变量名)
+fn find_loop_start(text: &str) -> Option<(usize, usize, &str)> {
+    let mut offset = 0;
+    while let Some(pos) = text[offset..].find("{{#each") {
+        let start = offset + pos;
+        let close = text[start..].find("}}")? + start + 2;
+        if let Some(key) = loop_start(&text[start..close]) {
+            // 标记独占一行时连同换行一起去掉
+            let after = &text[close..];
+            let skip = if after.starts_with("\r\n") {
+                2
+            } else if after.starts_with('\n') {
+                1
+            } else {
+                0
+            };
+            return Some((start, close + skip, key));
+        }
+        offset = close;
+    }
+    None
+}
+
+/// 循环体末尾、结束标记之前的缩进
+fn trim_marker_line(body: &str) -> &str {
+    match body.rfind('\n') {
+        Some(pos) if body[pos + 1..].trim().is_empty() => &body[..pos + 1],
+        _ => body,
+    }
+}
@@ -0,0 +1,81 @@
+use crate::utils::{diff_ops, DiffOp};
+
+/// 生成统一格式（unified diff）的文本差异，内容相同时返回空字符串
+///
+/// `context` 为每段修改前后保留的上下文行数。
+pub fn unified_diff(old: &str, new: &str, old_label: &str, new_label: &str, context: usize) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_ops(&old_lines, &new_lines);
+    if ops.iter().all(|op| matches!(op, DiffOp::Equal(..))) {
+        return String::new();
+    }
+
+    let mut output = format!("--- {}\n+++ {}\n", old_label, new_label);
+    for (start, end) in hunk_ranges(&ops, context) {
+        let hunk = &ops[start..end];
+        // 段头中的起始行号从 1 开始，没有对应行时沿用前一行的行号
+        let (old_start, new_start) = match hunk[0] {
+            DiffOp::Equal(i, j) => (i + 1, j + 1),
+            DiffOp::Delete(i) => (i + 1, position_before(&ops[..start], false) + 1),
+            DiffOp::Insert(j) => (position_before(&ops[..start], true) + 1, j + 1),
+        };
+        let old_count = hunk.iter().filter(|op| !matches!(op, DiffOp::Insert(_))).count();
+        let new_count = hunk.iter().filter(|op| !matches!(op, DiffOp::Delete(_))).count();
+
+        output.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(old_start, old_count),
+            hunk_range(new_start, new_count)
+        ));
+        for op in hunk {
+            let line = match *op {
+                DiffOp::Equal(i, _) => format!(" {}", old_lines[i]),
+                DiffOp::Delete(i) => format!("-{}", old_lines[i]),
+                DiffOp::Insert(j) => format!("+{}", new_lines[j]),
+            };
+            output.push_str(&line);
+            output.push('\n');
+        }
+    }
+
+    output
+}
+
+/// 包含修改及其上下文的区间 [start, end)，相邻区间的上下文重叠时合并
+fn hunk_ranges(ops: &[DiffOp], context: usize) -> Vec<(usize, usize)> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for (k, op) in ops.iter().enumerate() {
+        if matches!(op, DiffOp::Equal(..)) {
+            continue;
+        }
+        let start = k.saturating_sub(context);
+        let end = (k + 1 + context).min(ops.len());
+        match ranges.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+    ranges
+}
+
+/// 前面操作中最后一个旧行（`old` 为 true）或新行的行号，没有时为 0
+fn position_before(ops: &[DiffOp], old: bool) -> usize {
+    ops.iter()
+        .rev()
+        .find_map(|op| match (*op, old) {
+            (DiffOp::Equal(i, _), true) | (DiffOp::Delete(i), true) => Some(i + 1),
+            (DiffOp::Equal(_, j), false) | (DiffOp::Insert(j), false) => Some(j + 1),
+            _ => None,
+        })
+        .unwrap_or(0)
+}
+
+fn hunk_range(start: usize, count: usize) -> String {
+    match count {
+        // 没有行时按惯例显示前一行的行号
+        0 => format!("{},0", start - 1),
+        1 => start.to_string(),
+        _ => format!("{},{}", start, count),
+    }
+}
@@ -0,0 +1,174 @@
+use crate::error::{LarkError, Result};
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+use serde_json::Value;
+
+/// 预览中匹配内容前后显示的字符数
+const CONTEXT_CHARS: usize = 20;
+
+/// 查找替换的匹配规则，字面量模式会转义为正则表达式
+#[derive(Debug, Clone)]
+pub struct TextMatcher {
+    regex: Regex,
+    /// 替换内容中的 `$1`、`${name}` 是否展开为捕获组
+    expand: bool,
+}
+
+/// 一处匹配
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TextMatch {
+    /// 匹配到的文本
+    pub matched: String,
+    /// 替换后的文本
+    pub replacement: String,
+    /// 匹配内容及其前后的文本，匹配内容以【】标出
+    pub context: String,
+}
+
+impl TextMatcher {
+    /// 创建匹配规则，`regex` 为 false 时按字面量匹配
+    pub fn new(pattern: &str, regex: bool, ignore_case: bool) -> Result<Self> {
+        if pattern.is_empty() {
+            return Err(LarkError::ValidationError("查找内容不能为空".to_string()));
+        }
+
+        let source = if regex { pattern.to_string() } else { regex::escape(pattern) };
+        let compiled = RegexBuilder::new(&source)
+            .case_insensitive(ignore_case)
+            .build()
+            .map_err(|e| LarkError::ValidationError(format!("无效的正则表达式 {}: {}", pattern, e)))?;
+
+        Ok(Self {
+            regex: compiled,
+            expand: regex,
+        })
+    }
+
+    /// 文本中所有非空匹配的 (起始, 结束, 替换后的文本)
+    fn find(&self, text: &str, replacement: &str) -> Vec<(usize, usize, String)> {
+        self.regex
+            .captures_iter(text)
+            .filter_map(|captures| {
+                let m = captures.get(0)?;
+                if m.is_empty() {
+                    return None;
+                }
+                let replaced = if self.expand {
+                    let mut expanded = String::new();
+                    captures.expand(replacement, &mut expanded);
+                    expanded
+                } else {
+                    replacement.to_string()
+                };
+                Some((m.start(), m.end(), replaced))
+            })
+            .collect()
+    }
+}
+
+/// 替换文本片段中的匹配内容
+///
+/// 相邻的文本片段（text_run）拼接后整体匹配，因此跨越多个样式片段的内容也能匹配到；
+/// 替换后的文本使用匹配开始处片段的样式，提及、公式等非文本片段保持不变。
+/// 没有匹配时返回 None。
+pub fn replace_in_elements(elements: &Value, matcher: &TextMatcher, replacement: &str) -> Option<(Value, Vec<TextMatch>)> {
+    let items = elements.as_array()?;
+    let mut output: Vec<Value> = Vec::with_capacity(items.len());
+    let mut matches = Vec::new();
+
+    let mut i = 0;
+    while i < items.len() {
+        if text_run_content(&items[i]).is_none() {
+            output.push(items[i].clone());
+            i += 1;
+            continue;
+        }
+
+        // 一段连续的文本片段
+        let start = i;
+        while i < items.len() && text_run_content(&items[i]).is_some() {
+            i += 1;
+        }
+        let runs = &items[start..i];
+        let joined: String = runs.iter().filter_map(text_run_content).collect();
+        let found = matcher.find(&joined, replacement);
+        if found.is_empty() {
+            output.extend(runs.iter().cloned());
+            continue;
+        }
+
+        matches.extend(found.iter().map(|(s, e, replaced)| TextMatch {
+            matched: joined[*s..*e].to_string(),
+            replacement: replaced.clone(),
+            context: match_context(&joined, *s, *e),
+        }));
+
+        let mut offset = 0;
+        for run in runs {
+            let content = text_run_content(run).unwrap_or_default();
+            let (run_start, run_end) = (offset, offset + content.len());
+            offset = run_end;
+
+            let mut text = String::new();
+            let mut pos = run_start;
+            for (s, e, replaced) in found.iter().filter(|(s, e, _)| *s < run_end && *e > run_start) {
+                if *s > pos {
+                    text.push_str(&joined[pos..*s]);
+                }
+                if *s >= run_start {
+                    text.push_str(replaced);
+                }
+                pos = pos.max((*e).min(run_end));
+            }
+            if pos < run_end {
+                text.push_str(&joined[pos..run_end]);
+            }
+
+            // 内容被完全替换掉的片段直接去掉
+            if !text.is_empty() {
+                output.push(with_content(run, text));
+            }
+        }
+    }
+
+    if matches.is_empty() {
+        return None;
+    }
+    // 整段内容被替换为空时保留一个空片段
+    if output.is_empty() {
+        output.push(serde_json::json!({ "text_run": { "content": "" } }));
+    }
+    Some((Value::Array(output), matches))
+}
+
+fn text_run_content(element: &Value) -> Option<&str> {
+    element.pointer("/text_run/content")?.as_str()
+}
+
+fn with_content(run: &Value, content: String) -> Value {
+    let mut run = run.clone();
+    if let Some(text_run) = run.get_mut("text_run").and_then(|t| t.as_object_mut()) {
+        text_run.insert("content".to_string(), Value::String(content));
+    }
+    run
+}
+
+/// 匹配内容前后各取若干字符作为上下文
+fn match_context(text: &str, start: usize, end: usize) -> String {
+    let before: String = {
+        let chars: Vec<char> = text[..start].chars().collect();
+        let skip = chars.len().saturating_sub(CONTEXT_CHARS);
+        let prefix = if skip > 0 { "…" } else { "" };
+        format!("{}{}", prefix, chars[skip..].iter().collect::<String>())
+    };
+    let after: String = {
+        let rest = &text[end..];
+        let mut taken: String = rest.chars().take(CONTEXT_CHARS).collect();
+        if taken.len() < rest.len() {
+            taken.push('…');
+        }
+        taken
+    };
+
+    format!("{}【{}】{}", before, &text[start..end], after).replace('\n', " ")
+}
@@ -0,0 +1,118 @@
+use crate::error::{LarkError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// upload_id 的有效期为 24 小时，留出余量避免续传时过期
+const UPLOAD_ID_TTL_SECS: i64 = 23 * 60 * 60;
+
+/// 分片上传的源文件和目标，续传时必须完全一致
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UploadSource {
+    /// 规范化后的本地路径
+    pub file_path: String,
+    /// 文件大小（字节）
+    pub size: u64,
+    /// 修改时间（秒级时间戳）
+    pub modified: i64,
+    /// 上传后的文件名
+    pub file_name: String,
+    /// 目标文件夹 token
+    pub parent_node: String,
+}
+
+impl UploadSource {
+    /// 读取本地文件信息
+    pub fn from_file(path: &Path, file_name: &str, parent_node: &str) -> Result<Self> {
+        let canonical = path
+            .canonicalize()
+            .map_err(|e| LarkError::IoError(format!("读取文件失败 {}: {}", path.display(), e)))?;
+        let metadata = std::fs::metadata(&canonical)
+            .map_err(|e| LarkError::IoError(format!("读取文件失败 {}: {}", path.display(), e)))?;
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or_default();
+
+        Ok(Self {
+            file_path: canonical.display().to_string(),
+            size: metadata.len(),
+            modified,
+            file_name: file_name.to_string(),
+            parent_node: parent_node.to_string(),
+        })
+    }
+}
+
+/// 分片上传的断点记录：upload_id 和已完成的分片
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadState {
+    #[serde(flatten)]
+    pub source: UploadSource,
+    pub upload_id: String,
+    /// 分片大小（字节）
+    pub block_size: u64,
+    /// 分片数量
+    pub block_num: u32,
+    /// 已上传的分片序号
+    pub completed: BTreeSet<u32>,
+    /// upload_id 的创建时间（秒级时间戳）
+    pub created_at: i64,
+}
+
+impl UploadState {
+    pub fn new(source: UploadSource, upload_id: String, block_size: u64, block_num: u32) -> Self {
+        Self {
+            source,
+            upload_id,
+            block_size,
+            block_num,
+            completed: BTreeSet::new(),
+            created_at: chrono::Utc::now().timestamp(),
+        }
+    }
+
+    /// 默认的断点记录目录：用户配置目录下的 lark-cli/uploads
+    pub fn default_dir() -> Result<PathBuf> {
+        dirs::config_dir()
+            .map(|dir| dir.join("lark-cli").join("uploads"))
+            .ok_or_else(|| LarkError::IoError("无法获取用户配置目录".to_string()))
+    }
+
+    /// 源文件和目标对应的断点记录文件
+    pub fn state_path(dir: &Path, source: &UploadSource) -> PathBuf {
+        let key = format!("{}\n{}", source.file_path, source.parent_node);
+        let hash = format!("{:x}", Sha256::digest(key.as_bytes()));
+        dir.join(format!("{}.json", &hash[..16]))
+    }
+
+    /// 加载断点记录，文件不存在或已损坏时返回 None
+    pub fn load(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// 保存断点记录（先写临时文件再重命名）
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// 源文件未变化且 upload_id 未过期时可以续传
+    pub fn can_resume(&self, source: &UploadSource, now: i64) -> bool {
+        self.source == *source && now - self.created_at < UPLOAD_ID_TTL_SECS
+    }
+
+    /// 尚未上传的分片序号
+    pub fn pending_parts(&self) -> Vec<u32> {
+        (0..self.block_num).filter(|seq| !self.completed.contains(seq)).collect()
+    }
+}
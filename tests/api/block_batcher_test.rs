@@ -0,0 +1,248 @@
+use lark_cli::api::block_batcher::{BatchLimits, BatchParent, BlockBatcher, CreateBatch};
+use lark_cli::api::DescendantBlock;
+use std::collections::{HashMap, HashSet};
+
+fn block(id: &str, block_type: i32, children: Vec<String>) -> DescendantBlock {
+    DescendantBlock {
+        block_id: id.to_string(),
+        block_type,
+        children: Some(children),
+        text: Some(serde_json::json!({"elements": [{"text_run": {"content": id}}]})),
+        ..Default::default()
+    }
+}
+
+/// 模拟按顺序执行所有请求，返回每个父块最终的子块顺序
+fn replay(batches: &[CreateBatch]) -> HashMap<String, Vec<String>> {
+    let mut tree: HashMap<String, Vec<String>> = HashMap::new();
+    for batch in batches {
+        let parent = match &batch.parent {
+            BatchParent::Target => "TARGET".to_string(),
+            BatchParent::Created(id) => id.clone(),
+        };
+        let siblings = tree.entry(parent).or_default();
+        let position = if batch.index < 0 {
+            siblings.len()
+        } else {
+            (batch.index as usize).min(siblings.len())
+        };
+        for (offset, id) in batch.children_id.iter().enumerate() {
+            siblings.insert(position + offset, id.clone());
+        }
+        for descendant in &batch.descendants {
+            tree.entry(descendant.block_id.clone())
+                .or_default()
+                .extend(descendant.children.clone().unwrap_or_default());
+        }
+    }
+    tree
+}
+
+/// 校验每个请求满足限制，且块只被创建一次、子块都在同一请求中
+fn verify(batches: &[CreateBatch], limits: BatchLimits) {
+    let mut created: HashSet<String> = HashSet::new();
+    for batch in batches {
+        assert!(!batch.children_id.is_empty());
+        assert!(batch.descendants.len() <= limits.max_blocks);
+
+        if let BatchParent::Created(parent) = &batch.parent {
+            assert!(created.contains(parent), "父块 {} 尚未创建", parent);
+        }
+
+        let in_batch: HashMap<&str, &DescendantBlock> = batch
+            .descendants
+            .iter()
+            .map(|b| (b.block_id.as_str(), b))
+            .collect();
+        for id in &batch.children_id {
+            assert!(in_batch.contains_key(id.as_str()));
+        }
+        for descendant in &batch.descendants {
+            for child in descendant.children.as_deref().unwrap_or(&[]) {
+                assert!(in_batch.contains_key(child.as_str()), "子块 {} 不在同一请求中", child);
+            }
+            assert!(created.insert(descendant.block_id.clone()), "块 {} 被重复创建", descendant.block_id);
+        }
+
+        // 计算请求内的嵌套层数
+        fn depth(id: &str, blocks: &HashMap<&str, &DescendantBlock>) -> usize {
+            1 + blocks[id]
+                .children
+                .as_deref()
+                .unwrap_or(&[])
+                .iter()
+                .map(|c| depth(c, blocks))
+                .max()
+                .unwrap_or(0)
+        }
+        for id in &batch.children_id {
+            assert!(depth(id, &in_batch) <= limits.max_depth);
+        }
+    }
+}
+
+#[test]
+fn test_flat_document_5000_blocks() {
+    // 测试 5000 个平铺段落按 1000 块一批拆分，索引按一级块数后移
+    let ids: Vec<String> = (0..5000).map(|i| format!("b{}", i)).collect();
+    let blocks: Vec<DescendantBlock> = ids.iter().map(|id| block(id, 2, vec![])).collect();
+
+    let limits = BatchLimits::default();
+    let batches = BlockBatcher::new(blocks, limits).unwrap().plan(&ids, 3).unwrap();
+
+    assert_eq!(batches.len(), 5);
+    assert_eq!(
+        batches.iter().map(|b| b.index).collect::<Vec<_>>(),
+        vec![3, 1003, 2003, 3003, 4003]
+    );
+    verify(&batches, limits);
+    assert_eq!(replay(&batches)["TARGET"], ids);
+}
+
+#[test]
+fn test_append_index_is_kept() {
+    // 测试追加到末尾（-1）时每批索引保持不变
+    let ids: Vec<String> = (0..2500).map(|i| format!("b{}", i)).collect();
+    let blocks: Vec<DescendantBlock> = ids.iter().map(|id| block(id, 2, vec![])).collect();
+
+    let batches = BlockBatcher::new(blocks, BatchLimits::default()).unwrap().plan(&ids, -1).unwrap();
+
+    assert_eq!(batches.len(), 3);
+    assert!(batches.iter().all(|b| b.index == -1));
+    assert_eq!(replay(&batches)["TARGET"], ids);
+}
+
+#[test]
+fn test_subtrees_never_straddle_batches() {
+    // 测试 500 棵各 10 个块的子树（共 5000 块）不会跨请求拆分
+    let mut blocks = Vec::new();
+    let mut roots = Vec::new();
+    for i in 0..500 {
+        let root = format!("list{}", i);
+        let children: Vec<String> = (0..9).map(|j| format!("list{}-{}", i, j)).collect();
+        for child in &children {
+            blocks.push(block(child, 12, vec![]));
+        }
+        blocks.push(block(&root, 12, children));
+        roots.push(root);
+    }
+
+    let limits = BatchLimits::default();
+    let batches = BlockBatcher::new(blocks, limits).unwrap().plan(&roots, 0).unwrap();
+
+    verify(&batches, limits);
+    assert_eq!(batches.len(), 5);
+    assert!(batches.iter().all(|b| b.parent == BatchParent::Target));
+    let tree = replay(&batches);
+    assert_eq!(tree["TARGET"], roots);
+    assert_eq!(tree["list42"].len(), 9);
+}
+
+#[test]
+fn test_oversized_subtree_is_split_under_created_parent() {
+    // 测试一棵 5000 块的子树：先创建父块和部分子块，剩余子块追加到已创建的父块下
+    let children: Vec<String> = (0..4999).map(|i| format!("c{}", i)).collect();
+    let mut blocks: Vec<DescendantBlock> = children.iter().map(|id| block(id, 12, vec![])).collect();
+    blocks.push(block("root", 12, children.clone()));
+    blocks.push(block("after", 2, vec![]));
+
+    let limits = BatchLimits::default();
+    let roots = vec!["root".to_string(), "after".to_string()];
+    let batches = BlockBatcher::new(blocks, limits).unwrap().plan(&roots, 0).unwrap();
+
+    verify(&batches, limits);
+    assert_eq!(batches[0].children_id, vec!["root".to_string()]);
+    assert_eq!(batches[0].descendants.len(), 1000);
+    assert!(batches[1..5]
+        .iter()
+        .all(|b| b.parent == BatchParent::Created("root".to_string()) && b.index == -1));
+    // 后面的一级块在根块之后插入
+    let last = batches.last().unwrap();
+    assert_eq!(last.children_id, vec!["after".to_string()]);
+    assert_eq!(last.index, 1);
+
+    let tree = replay(&batches);
+    assert_eq!(tree["TARGET"], roots);
+    assert_eq!(tree["root"], children);
+}
+
+#[test]
+fn test_deep_nesting_is_split_by_depth() {
+    // 测试嵌套层数超过限制时按层拆分
+    let depth = 25;
+    let mut blocks = Vec::new();
+    for i in 0..depth {
+        let children = if i + 1 < depth { vec![format!("d{}", i + 1)] } else { vec![] };
+        blocks.push(block(&format!("d{}", i), 12, children));
+    }
+
+    let limits = BatchLimits { max_blocks: 1000, max_depth: 10 };
+    let batches = BlockBatcher::new(blocks, limits)
+        .unwrap()
+        .plan(&["d0".to_string()], -1)
+        .unwrap();
+
+    verify(&batches, limits);
+    assert_eq!(batches.len(), 3);
+    let tree = replay(&batches);
+    for i in 0..depth - 1 {
+        assert_eq!(tree[&format!("d{}", i)], vec![format!("d{}", i + 1)]);
+    }
+}
+
+#[test]
+fn test_table_cells_stay_with_table() {
+    // 测试表格的单元格始终与表格一起创建，单元格内容可以延后追加
+    let mut blocks = Vec::new();
+    let mut cells = Vec::new();
+    for i in 0..9 {
+        let cell = format!("cell{}", i);
+        let texts: Vec<String> = (0..2).map(|j| format!("cell{}-t{}", i, j)).collect();
+        for text in &texts {
+            blocks.push(block(text, 2, vec![]));
+        }
+        blocks.push(block(&cell, 32, texts));
+        cells.push(cell);
+    }
+    blocks.push(block("table", 31, cells.clone()));
+
+    let limits = BatchLimits { max_blocks: 12, max_depth: 10 };
+    let batches = BlockBatcher::new(blocks, limits)
+        .unwrap()
+        .plan(&["table".to_string()], 0)
+        .unwrap();
+
+    verify(&batches, limits);
+    let first_ids: Vec<&str> = batches[0].descendants.iter().map(|b| b.block_id.as_str()).collect();
+    for cell in &cells {
+        assert!(first_ids.contains(&cell.as_str()));
+    }
+    let tree = replay(&batches);
+    assert_eq!(tree["table"], cells);
+    for i in 0..9 {
+        assert_eq!(tree[&format!("cell{}", i)].len(), 2);
+    }
+}
+
+#[test]
+fn test_table_with_too_many_cells_fails() {
+    // 测试表格直接子块超过单次请求上限时报错
+    let cells: Vec<String> = (0..20).map(|i| format!("cell{}", i)).collect();
+    let mut blocks: Vec<DescendantBlock> = cells.iter().map(|id| block(id, 32, vec![])).collect();
+    blocks.push(block("table", 31, cells));
+
+    let limits = BatchLimits { max_blocks: 10, max_depth: 10 };
+    let result = BlockBatcher::new(blocks, limits).unwrap().plan(&["table".to_string()], 0);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_unknown_root_and_cycle_fail() {
+    // 测试一级块不存在和子块循环引用时报错
+    let blocks = vec![block("a", 2, vec![])];
+    let batcher = BlockBatcher::new(blocks, BatchLimits::default()).unwrap();
+    assert!(batcher.plan(&["missing".to_string()], 0).is_err());
+
+    let cyclic = vec![block("x", 12, vec!["y".to_string()]), block("y", 12, vec!["x".to_string()])];
+    assert!(BlockBatcher::new(cyclic, BatchLimits::default()).is_err());
+}
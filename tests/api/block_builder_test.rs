@@ -0,0 +1,98 @@
+use lark_cli::api::block_builder::{callout_blocks, spec_blocks, BlockSpec, CalloutType};
+use serde_json::json;
+
+// 测试从 YAML 构建嵌套的分栏块：分栏宽度写在各栏上，子块排在父块之后
+#[test]
+fn test_spec_blocks_grid_from_yaml() {
+    let specs = BlockSpec::parse(
+        r#"
+type: grid
+width_ratios: [30, 70]
+children:
+  - type: column
+    text: 左栏
+  - type: column
+    children:
+      - type: code
+        language: py
+        text: print(1)
+      - type: todo
+        text: 检查
+        done: true
+"#,
+    )
+    .unwrap();
+
+    let (children_id, blocks) = spec_blocks("doc", &specs).unwrap();
+
+    assert_eq!(children_id.len(), 1);
+    let grid = &blocks[0];
+    assert_eq!(grid.block_id, children_id[0]);
+    assert_eq!(grid.block_type, 24);
+    assert_eq!(grid.grid, Some(json!({ "column_size": 2 })));
+    let columns: Vec<_> = grid.children.clone().unwrap();
+    let left = blocks.iter().find(|b| b.block_id == columns[0]).unwrap();
+    assert_eq!(left.grid_column, Some(json!({ "width_ratio": 30 })));
+    let right = blocks.iter().find(|b| b.block_id == columns[1]).unwrap();
+    let right_children = right.children.clone().unwrap();
+    assert_eq!(right_children.len(), 2);
+    let code = blocks.iter().find(|b| b.block_id == right_children[0]).unwrap();
+    assert_eq!(code.code.as_ref().unwrap()["style"]["language"], json!(49));
+    let todo = blocks.iter().find(|b| b.block_id == right_children[1]).unwrap();
+    assert_eq!(todo.todo.as_ref().unwrap()["style"]["done"], json!(true));
+    assert_eq!(blocks.len(), 6);
+}
+
+// 测试不适用的选项、错误的宽度比例和不能包含子块的块类型都会报错
+#[test]
+fn test_spec_blocks_validation() {
+    let spec = |yaml: &str| BlockSpec::parse(yaml).unwrap();
+
+    assert!(spec_blocks("doc", &spec("type: text\nlanguage: rust")).is_err());
+    assert!(spec_blocks("doc", &spec("type: grid\ncolumns: 3\nwidth_ratios: [50, 50]")).is_err());
+    assert!(spec_blocks("doc", &spec("type: divider\nchildren:\n  - type: text")).is_err());
+    assert!(spec_blocks("doc", &spec("type: column")).is_err());
+    assert!(spec_blocks("doc", &spec("type: iframe\nurl: https://figma.com/file/1")).is_err());
+    assert!(BlockSpec::parse("type: text\nunknown: 1").is_err());
+}
+
+// 测试高亮块颜色、公式块和内嵌网页的内容
+#[test]
+fn test_spec_blocks_payloads() {
+    let specs = BlockSpec::parse(
+        r#"
+- type: callout
+  callout_type: warning
+  border_color: blue
+  text_color: red
+- type: equation
+  text: E = mc^2
+- type: iframe
+  iframe_type: figma
+  url: https://www.figma.com/file/abc
+"#,
+    )
+    .unwrap();
+
+    let (children_id, blocks) = spec_blocks("doc", &specs).unwrap();
+
+    assert_eq!(children_id.len(), 3);
+    assert_eq!(
+        blocks[0].callout,
+        Some(json!({ "background_color": 3, "border_color": 5, "text_color": 1, "emoji_id": "o" }))
+    );
+    assert_eq!(blocks[1].block_type, 2);
+    let equation = blocks.iter().find(|b| b.block_id == children_id[1]).unwrap();
+    assert_eq!(equation.text.as_ref().unwrap()["elements"][0]["equation"]["content"], "E = mc^2");
+    let iframe = blocks.iter().find(|b| b.block_id == children_id[2]).unwrap();
+    assert_eq!(
+        iframe.iframe,
+        Some(json!({ "component": { "type": 8, "url": "https%3A%2F%2Fwww.figma.com%2Ffile%2Fabc" } }))
+    );
+
+    let (_, callout) = callout_blocks("doc", "提示", CalloutType::Success, None);
+    assert_eq!(
+        callout[0].callout,
+        Some(json!({ "background_color": 4, "border_color": 4, "emoji_id": "white_check_mark" }))
+    );
+}
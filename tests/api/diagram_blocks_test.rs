@@ -0,0 +1,107 @@
+use lark_cli::api::diagram_blocks::{plan_diagram_blocks, DiagramSourceMode};
+use lark_cli::api::DescendantBlock;
+use lark_cli::utils::mark_diagram_fences;
+
+/// 模拟转换接口的结果：标记段落、图表代码块和一个普通段落
+fn converted(marker: &str) -> (Vec<String>, Vec<DescendantBlock>) {
+    let text = |id: &str, content: &str| DescendantBlock {
+        block_id: id.to_string(),
+        block_type: 2,
+        children: Some(vec![]),
+        text: Some(serde_json::json!({"elements": [{"text_run": {"content": content}}]})),
+        ..Default::default()
+    };
+    let code = DescendantBlock {
+        block_id: "code".to_string(),
+        block_type: 14,
+        children: Some(vec![]),
+        code: Some(serde_json::json!({"elements": [{"text_run": {"content": "graph TD"}}]})),
+        ..Default::default()
+    };
+    let quote = DescendantBlock {
+        block_id: "quote".to_string(),
+        block_type: 34,
+        children: Some(vec!["marker".to_string(), "code".to_string()]),
+        ..Default::default()
+    };
+
+    (
+        vec!["quote".to_string(), "after".to_string()],
+        vec![quote, text("marker", marker), code, text("after", "after")],
+    )
+}
+
+fn fences() -> Vec<lark_cli::utils::DiagramFence> {
+    mark_diagram_fences("```mermaid\ngraph TD\n```\n").1
+}
+
+#[test]
+fn test_replace_marker_with_board_and_drop_code() {
+    // 测试标记段落替换为画板块，默认不保留代码块但记录用于恢复
+    let fences = fences();
+    let (mut children_id, mut descendants) = converted(&fences[0].marker);
+
+    let planned = plan_diagram_blocks(&mut children_id, &mut descendants, fences, DiagramSourceMode::None);
+
+    assert_eq!(planned.len(), 1);
+    assert_eq!(planned[0].board_block_id, "marker");
+    assert!(planned[0].source_block_id.is_none());
+    assert_eq!(planned[0].fallback_block.as_ref().unwrap().block_id, "code");
+
+    let quote = descendants.iter().find(|b| b.block_id == "quote").unwrap();
+    assert_eq!(quote.children, Some(vec!["marker".to_string()]));
+    let board = descendants.iter().find(|b| b.block_id == "marker").unwrap();
+    assert_eq!(board.block_type, 43);
+    assert!(board.text.is_none());
+    assert!(board.board.is_some());
+    assert!(descendants.iter().all(|b| b.block_id != "code"));
+    assert_eq!(children_id, vec!["quote".to_string(), "after".to_string()]);
+}
+
+#[test]
+fn test_keep_source_as_code_or_caption() {
+    // 测试保留源码为代码块或行内代码说明
+    let fences = fences();
+    let (mut children_id, mut descendants) = converted(&fences[0].marker);
+    let planned = plan_diagram_blocks(&mut children_id, &mut descendants, fences, DiagramSourceMode::Code);
+    assert_eq!(planned[0].source_block_id.as_deref(), Some("code"));
+    assert!(planned[0].fallback_block.is_none());
+    assert_eq!(descendants.iter().find(|b| b.block_id == "code").unwrap().block_type, 14);
+
+    let fences = self::fences();
+    let (mut children_id, mut descendants) = converted(&fences[0].marker);
+    let planned = plan_diagram_blocks(&mut children_id, &mut descendants, fences, DiagramSourceMode::Caption);
+    assert_eq!(planned[0].source_block_id.as_deref(), Some("code"));
+    assert_eq!(planned[0].fallback_block.as_ref().unwrap().block_type, 14);
+    let caption = descendants.iter().find(|b| b.block_id == "code").unwrap();
+    assert_eq!(caption.block_type, 2);
+    assert!(caption.code.is_none());
+    assert_eq!(caption.text.as_ref().unwrap()["elements"][0]["text_run"]["content"], "graph TD");
+}
+
+#[test]
+fn test_keep_source_collapsed() {
+    // 测试保留源码为折叠列表项下的代码块
+    let fences = fences();
+    let (mut children_id, mut descendants) = converted(&fences[0].marker);
+    let planned = plan_diagram_blocks(&mut children_id, &mut descendants, fences, DiagramSourceMode::Collapsed);
+
+    assert_eq!(planned[0].source_block_id.as_deref(), Some("code_source"));
+    assert!(planned[0].fallback_block.is_none());
+    let quote = descendants.iter().find(|b| b.block_id == "quote").unwrap();
+    assert_eq!(quote.children, Some(vec!["marker".to_string(), "code_source".to_string()]));
+    let toggle = descendants.iter().find(|b| b.block_id == "code_source").unwrap();
+    assert_eq!(toggle.block_type, 12);
+    assert_eq!(toggle.children, Some(vec!["code".to_string()]));
+    assert_eq!(toggle.bullet.as_ref().unwrap()["style"]["folded"], true);
+    assert_eq!(descendants.iter().find(|b| b.block_id == "code").unwrap().block_type, 14);
+}
+
+#[test]
+fn test_missing_marker_keeps_code_block() {
+    // 测试找不到标记段落时保持为普通代码块
+    let (mut children_id, mut descendants) = converted("other text");
+    let planned = plan_diagram_blocks(&mut children_id, &mut descendants, fences(), DiagramSourceMode::None);
+    assert!(planned.is_empty());
+    assert_eq!(descendants.len(), 4);
+}
@@ -0,0 +1,82 @@
+use crate::utils::block;
+use lark_cli::api::block_copy::copy_blocks_with_media;
+use lark_cli::api::document_copy::{check_move_skipped, check_move_target, resolve_source};
+use lark_cli::utils::BlockTree;
+use serde_json::json;
+
+fn document() -> BlockTree {
+    BlockTree::new(vec![
+        block("doc", 1, "", &["h2", "para", "image", "view"], json!({ "page": {} })),
+        block("h2", 4, "doc", &[], json!({ "heading2": { "elements": [{ "text_run": { "content": "接口设计" } }] } })),
+        block("para", 2, "doc", &["nested"], json!({ "text": { "elements": [{ "text_run": { "content": "正文" } }] } })),
+        block("nested", 2, "para", &[], json!({ "text": { "elements": [{ "text_run": { "content": "子块" } }] } })),
+        block("image", 27, "doc", &[], json!({ "image": { "token": "boxImg", "width": 800, "height": 600, "align": 2 } })),
+        block("view", 33, "doc", &["file"], json!({ "view": { "view_type": 1 } })),
+        block("file", 23, "view", &[], json!({ "file": { "token": "boxFile", "name": "spec.pdf" } })),
+    ])
+}
+
+// 测试图片和附件以空块复制并记录原素材，附件外层的视图块被展开
+#[test]
+fn test_copy_blocks_with_media() {
+    let tree = document();
+    let roots: Vec<String> = ["para", "image", "view"].iter().map(|s| s.to_string()).collect();
+    let copied = copy_blocks_with_media(&tree, &roots).unwrap();
+
+    assert_eq!(copied.children_id, vec!["copy_1", "copy_3", "copy_4"]);
+    assert!(copied.skipped.is_empty());
+    assert_eq!(copied.descendants[0].children.as_deref().unwrap(), ["copy_2"]);
+
+    let image = copied.descendants.iter().find(|b| b.block_id == "copy_3").unwrap();
+    assert_eq!(image.block_type, 27);
+    assert_eq!(image.image, Some(json!({})));
+    let file = copied.descendants.iter().find(|b| b.block_id == "copy_4").unwrap();
+    assert_eq!(file.block_type, 23);
+    assert_eq!(file.file, Some(json!({ "token": "" })));
+
+    assert_eq!(copied.media.len(), 2);
+    assert_eq!(copied.media[0].token, "boxImg");
+    assert_eq!(copied.media[0].width, Some(800));
+    assert_eq!(copied.media[0].align, Some(2));
+    assert_eq!(copied.media[1].source_block_id, "file");
+    assert_eq!(copied.media[1].name.as_deref(), Some("spec.pdf"));
+}
+
+// 测试源参数：存在的块 ID 直接使用，否则按选择器求值
+#[test]
+fn test_resolve_source() {
+    let tree = document();
+    assert_eq!(resolve_source(&tree, "doc", "para").unwrap(), vec!["para"]);
+    assert_eq!(resolve_source(&tree, "doc", "heading2 ~ text").unwrap(), vec!["para"]);
+    assert!(resolve_source(&tree, "doc", "callout").is_err());
+}
+
+// 测试同一文档内移动时，目标父块不能位于被移动的块中
+#[test]
+fn test_check_move_target() {
+    let tree = document();
+    let roots = vec!["para".to_string()];
+    assert!(check_move_target(&tree, &roots, "doc").is_ok());
+    assert!(check_move_target(&tree, &roots, "para").is_err());
+    assert!(check_move_target(&tree, &roots, "nested").is_err());
+}
+
+// 测试移动时有无法复制的块（画板）则拒绝，加 --force 后允许
+#[test]
+fn test_move_with_skipped_block() {
+    let tree = BlockTree::new(vec![
+        block("doc", 1, "", &["callout"], json!({ "page": {} })),
+        block("callout", 19, "doc", &["text", "board"], json!({ "callout": {} })),
+        block("text", 2, "callout", &[], json!({ "text": { "elements": [{ "text_run": { "content": "说明" } }] } })),
+        block("board", 43, "callout", &[], json!({ "board": { "token": "boardToken" } })),
+    ]);
+    let copied = copy_blocks_with_media(&tree, &["callout".to_string()]).unwrap();
+    assert_eq!(copied.skipped.len(), 1);
+    assert_eq!(copied.skipped[0].block_id, "board");
+
+    let error = check_move_skipped(&copied.skipped, false).unwrap_err().to_string();
+    assert!(error.contains("board（类型 43）"));
+    assert!(error.contains("--force"));
+    assert!(check_move_skipped(&copied.skipped, true).is_ok());
+    assert!(check_move_skipped(&[], false).is_ok());
+}
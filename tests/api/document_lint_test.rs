@@ -0,0 +1,21 @@
+use lark_cli::api::document_lint::is_broken_reference;
+use lark_cli::error::LarkError;
+
+// 测试只有不存在和无权访问的错误被视为失效引用，限流和服务端异常不算
+#[test]
+fn test_is_broken_reference() {
+    let api_error = |code| LarkError::ApiError { code, message: String::new() };
+    assert!(is_broken_reference(&api_error(1770002)));
+    assert!(is_broken_reference(&api_error(131006)));
+    assert!(!is_broken_reference(&api_error(99991400)));
+    assert!(is_broken_reference(&LarkError::ValidationError("提及缺少用户 ID".to_string())));
+
+    let http_error = |status: &str, body: &str| {
+        LarkError::NetworkError(format!("HTTP request failed, status code: {}, response body: {}", status, body))
+    };
+    assert!(is_broken_reference(&http_error("404 Not Found", "")));
+    assert!(is_broken_reference(&http_error("400 Bad Request", r#"{"code":1770002,"msg":"not found"}"#)));
+    assert!(!is_broken_reference(&http_error("429 Too Many Requests", r#"{"code":99991400,"msg":"rate limited"}"#)));
+    assert!(!is_broken_reference(&http_error("503 Service Unavailable", "")));
+    assert!(!is_broken_reference(&LarkError::NetworkError("connection reset".to_string())));
+}
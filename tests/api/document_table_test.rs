@@ -0,0 +1,30 @@
+use lark_cli::api::document_table::table_blocks;
+
+// 测试构建创建表格的块：补齐列数，每个单元格包含一个文本块
+#[test]
+fn test_table_blocks() {
+    let rows = vec![
+        vec!["服务".to_string(), "可用性".to_string()],
+        vec!["payment".to_string()],
+    ];
+    let (children_id, descendants) = table_blocks(&rows, 2, true);
+    assert_eq!(children_id, vec!["table"]);
+    assert_eq!(descendants.len(), 1 + 2 * 4);
+
+    let table = &descendants[0];
+    assert_eq!(table.block_type, 31);
+    assert_eq!(
+        table.children.as_deref().unwrap(),
+        ["cell_0_0", "cell_0_1", "cell_1_0", "cell_1_1"]
+    );
+    let property = table.table.as_ref().unwrap().get("property").unwrap();
+    assert_eq!(property["row_size"], 2);
+    assert_eq!(property["column_size"], 2);
+    assert_eq!(property["header_row"], true);
+
+    let padded = descendants.iter().find(|b| b.block_id == "text_1_1").unwrap();
+    assert_eq!(
+        padded.text.as_ref().unwrap().pointer("/elements/0/text_run/content"),
+        Some(&serde_json::json!(""))
+    );
+}
@@ -0,0 +1,67 @@
+use crate::utils::{block, flat_document, text, text_content};
+use lark_cli::api::block_copy::copy_blocks;
+use lark_cli::api::document_template::{render_block_template, TemplateSource};
+use lark_cli::api::get_blocks::BlockItem;
+use lark_cli::utils::{block_plain_text, BlockTree, TemplateVariables};
+use serde_json::json;
+use std::path::PathBuf;
+
+fn template(blocks: Vec<BlockItem>) -> BlockTree {
+    flat_document("tpl", text_content("page", "{{service}} 复盘"), blocks)
+}
+
+// 测试模板来源：Markdown 路径视为本地文件，其他视为文档 ID
+#[test]
+fn test_template_source_parse() {
+    assert_eq!(
+        TemplateSource::parse("templates/incident.md"),
+        TemplateSource::File(PathBuf::from("templates/incident.md"))
+    );
+    assert_eq!(
+        TemplateSource::parse("doccnTemplate123"),
+        TemplateSource::Document("doccnTemplate123".to_string())
+    );
+}
+
+// 测试渲染模板文档：替换占位符并按列表重复循环标记之间的块
+#[test]
+fn test_render_block_template_loops() {
+    let tree = template(vec![
+        text("intro", "tpl", "服务: {{service}}"),
+        text("start", "tpl", "{{#each owners}}"),
+        text("owner", "tpl", "负责人 {{this}}"),
+        text("end", "tpl", "{{/each}}"),
+        text("outro", "tpl", "完"),
+    ]);
+    let variables: TemplateVariables =
+        serde_json::from_value(json!({ "service": "payment", "owners": ["alice", "bob"] })).unwrap();
+
+    let (rendered, roots) = render_block_template(&tree, "tpl", &variables).unwrap();
+    assert_eq!(roots, vec!["intro", "owner_1", "owner_2", "outro"]);
+
+    let texts: Vec<String> = roots
+        .iter()
+        .map(|id| block_plain_text(rendered.get(id).unwrap()))
+        .collect();
+    assert_eq!(texts, vec!["服务: payment", "负责人 alice", "负责人 bob", "完"]);
+}
+
+// 测试复制块时跳过图片等无法复制的块，并去掉评论信息
+#[test]
+fn test_copy_blocks_skips_uncopyable() {
+    let mut commented = text("text", "tpl", "正文");
+    commented.content = json!({
+        "text": { "elements": [{ "text_run": { "content": "正文", "text_element_style": { "comment_ids": ["c1"] } } }] }
+    });
+    let image = block("image", 27, "tpl", &[], json!({ "image": { "token": "boxcn123" } }));
+    let tree = template(vec![commented, image]);
+
+    let copied = copy_blocks(&tree, &["text".to_string(), "image".to_string()]).unwrap();
+    assert_eq!(copied.children_id, vec!["copy_1"]);
+    assert_eq!(copied.skipped.len(), 1);
+    assert_eq!(copied.skipped[0].block_id, "image");
+
+    let text = copied.descendants[0].text.as_ref().unwrap();
+    assert!(text.pointer("/elements/0/text_run/text_element_style/comment_ids").is_none());
+    assert_eq!(text.pointer("/elements/0/text_run/content"), Some(&json!("正文")));
+}
@@ -0,0 +1,92 @@
+use lark_cli::api::block_batcher::BatchParent;
+use lark_cli::api::import_plan::ImportPlan;
+use lark_cli::api::{ApiClient, DescendantBlock};
+use lark_cli::auth::AuthManager;
+use lark_cli::config::Config;
+use lark_cli::error::LarkError;
+use lark_cli::utils::split_front_matter;
+
+fn text_block(id: &str) -> DescendantBlock {
+    DescendantBlock {
+        block_id: id.to_string(),
+        block_type: 2,
+        children: Some(vec![]),
+        text: Some(serde_json::json!({"elements": [{"text_run": {"content": id}}]})),
+        ..Default::default()
+    }
+}
+
+fn dry_run_client() -> ApiClient {
+    let config = Config {
+        app_id: "test_app_id".to_string(),
+        app_secret: "test_app_secret".to_string(),
+    };
+    ApiClient::new(AuthManager::new(config)).with_dry_run(true)
+}
+
+// 测试计划包含完整块树和与实际导入一致的创建请求
+#[test]
+fn test_plan_contains_tree_and_create_calls() {
+    let children_id = vec!["a".to_string(), "b".to_string()];
+    let descendants = vec![text_block("a"), text_block("b")];
+
+    let plan = ImportPlan::new("doc", "doc", 3, children_id.clone(), descendants, &[], None, true).unwrap();
+
+    assert_eq!(plan.parent_block_id, "doc");
+    assert_eq!(plan.descendants.len(), 2);
+    assert_eq!(plan.create_calls.len(), 1);
+    assert_eq!(plan.create_calls[0].parent, BatchParent::Target);
+    assert_eq!(plan.create_calls[0].index, 3);
+    assert_eq!(plan.create_calls[0].children_id, children_id);
+    assert_eq!(plan.create_calls[0].block_count, 2);
+    assert!(plan.title.is_none());
+    assert!(plan.permissions.is_empty());
+}
+
+// 测试 Front Matter 的标题和协作者计入计划
+#[test]
+fn test_plan_includes_front_matter_changes() {
+    let content = "---\ntitle: 周报\ncollaborators:\n  - a@example.com\n  - member_id: ou_1\n    member_type: openid\n    perm: edit\n---\n正文";
+    let (front_matter, _) = split_front_matter(content).unwrap();
+    let front_matter = front_matter.unwrap();
+
+    let plan = ImportPlan::new(
+        "doc", "doc", -1, vec!["a".to_string()], vec![text_block("a")], &[], Some(&front_matter), true,
+    )
+    .unwrap();
+    assert_eq!(plan.title.as_deref(), Some("周报"));
+    assert_eq!(plan.permissions.len(), 2);
+    assert_eq!(plan.permissions[0].member_id, "a@example.com");
+    assert_eq!(plan.permissions[0].perm, "view");
+    assert_eq!(plan.permissions[1].member_type, "openid");
+    assert_eq!(plan.permissions[1].perm, "edit");
+
+    // 不设置标题时只保留权限变更
+    let plan = ImportPlan::new(
+        "doc", "doc", -1, vec!["a".to_string()], vec![text_block("a")], &[], Some(&front_matter), false,
+    )
+    .unwrap();
+    assert!(plan.title.is_none());
+    assert_eq!(plan.permissions.len(), 2);
+}
+
+// 测试 dry-run 模式下写请求在发送前被拦截
+#[tokio::test]
+async fn test_dry_run_blocks_mutating_requests() {
+    let client = dry_run_client();
+    assert!(client.is_dry_run());
+
+    let result: Result<serde_json::Value, LarkError> = client
+        .post("https://open.larkoffice.com/open-apis/docx/v1/documents", &serde_json::json!({}))
+        .await;
+    assert!(matches!(result, Err(LarkError::DryRunError(_))));
+
+    let result: Result<serde_json::Value, LarkError> = client
+        .delete_with_params(
+            "https://open.larkoffice.com/open-apis/docx/v1/documents/doc/blocks/doc/children/batch_delete",
+            None,
+            &serde_json::json!({}),
+        )
+        .await;
+    assert!(matches!(result, Err(LarkError::DryRunError(_))));
+}
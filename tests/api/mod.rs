@@ -0,0 +1,10 @@
+pub mod block_batcher_test;
+pub mod diagram_blocks_test;
+pub mod import_plan_test;
+pub mod document_template_test;
+pub mod document_table_test;
+pub mod update_request_builder_test;
+pub mod document_copy_test;
+pub mod block_builder_test;
+pub mod multipart_test;
+pub mod document_lint_test;
@@ -0,0 +1,25 @@
+use lark_cli::api::multipart::MultipartForm;
+
+// 测试请求体格式：文本字段、文件字段和结束分隔符，每个请求的分隔符不同
+#[test]
+fn test_multipart_form() {
+    let (body, headers) = MultipartForm::new()
+        .text("upload_id", "u1")
+        .file("file", "a\"b.bin", b"\x00\x01")
+        .finish();
+    let content_type = &headers["Content-Type"];
+    let boundary = content_type.strip_prefix("multipart/form-data; boundary=").unwrap();
+
+    let mut expected = format!(
+        "--{b}\r\nContent-Disposition: form-data; name=\"upload_id\"\r\n\r\nu1\r\n\
+--{b}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"a%22b.bin\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+        b = boundary
+    )
+    .into_bytes();
+    expected.extend_from_slice(b"\x00\x01\r\n");
+    expected.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+    assert_eq!(body, expected);
+
+    let (_, other) = MultipartForm::new().finish();
+    assert_ne!(other["Content-Type"], *content_type);
+}
@@ -0,0 +1,100 @@
+use lark_cli::api::update_request_builder::{build_update_requests, parse_update_value};
+use serde_json::json;
+
+fn error_message(result: lark_cli::error::Result<Vec<lark_cli::api::batch_update_blocks::UpdateBlockRequest>>) -> String {
+    result.err().map(|e| e.to_string()).unwrap_or_default()
+}
+
+// 测试从 YAML 解析请求并展开简写
+#[test]
+fn test_parse_yaml_shorthand() {
+    let yaml = r#"
+requests:
+  - block_id: blk_text
+    text: 新内容
+  - block_id: blk_table
+    insert_row: 3
+  - block_id: blk_table
+    delete_columns: [1, 2]
+"#;
+    let requests = build_update_requests(&parse_update_value(yaml).unwrap()).unwrap();
+    assert_eq!(requests.len(), 3);
+
+    let value = serde_json::to_value(&requests).unwrap();
+    assert_eq!(
+        value[0],
+        json!({ "block_id": "blk_text", "update_text_elements": { "elements": [{ "text_run": { "content": "新内容" } }] } })
+    );
+    assert_eq!(value[1], json!({ "block_id": "blk_table", "insert_table_row": { "row_index": 3 } }));
+    assert_eq!(
+        value[2],
+        json!({ "block_id": "blk_table", "delete_table_columns": { "column_start_index": 1, "column_end_index": 2 } })
+    );
+}
+
+// 测试完整写法的 JSON 请求与原来的格式兼容
+#[test]
+fn test_parse_json_full_form() {
+    let value = parse_update_value(
+        r#"[{"block_id":"blk","update_text":{"elements":[{"text_run":{"content":"x","text_element_style":{"bold":true}}}]}}]"#,
+    )
+    .unwrap();
+    let requests = build_update_requests(&value).unwrap();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].update_text.is_some());
+}
+
+// 测试错误信息指出出错字段的路径
+#[test]
+fn test_errors_point_at_path() {
+    let message = error_message(build_update_requests(&json!([
+        { "block_id": "a", "text": "ok" },
+        { "block_id": "b", "update_text_elements": { "elements": [{ "text_run": { "content": "x", "text_element_style": { "bold": true, "itallic": true } } }] } }
+    ])));
+    assert!(
+        message.contains("requests[1].update_text_elements.elements[0].text_run.text_element_style.itallic"),
+        "{}",
+        message
+    );
+
+    let message = error_message(build_update_requests(&json!([{ "block_id": "a", "insert_table_row": {} }])));
+    assert!(message.contains("requests[0].insert_table_row"), "{}", message);
+    assert!(message.contains("row_index"), "{}", message);
+
+    let message = error_message(build_update_requests(&json!([{ "block_id": "a", "update_txt": {} }])));
+    assert!(message.contains("requests[0].update_txt"), "{}", message);
+}
+
+// 测试每个请求必须包含 block_id 且只能包含一个更新操作
+#[test]
+fn test_request_shape_errors() {
+    assert!(build_update_requests(&json!([{ "text": "x" }])).is_err());
+    assert!(build_update_requests(&json!([{ "block_id": "a" }])).is_err());
+    assert!(build_update_requests(&json!([{ "block_id": "a", "text": "x", "insert_row": 1 }])).is_err());
+    assert!(build_update_requests(&json!([{ "block_id": "a", "delete_rows": [3, 1] }])).is_err());
+    assert!(build_update_requests(&json!([])).is_err());
+    assert!(build_update_requests(&json!([{ "block_id": "a", "update_text": { "elements": [{}] } }])).is_err());
+}
+
+// 测试使用 select 的请求展开为每个选中块各一个请求
+#[test]
+fn test_expand_selectors() {
+    use crate::utils::{block, flat_document, text_content};
+    use lark_cli::api::update_request_builder::{expand_selectors, uses_selectors};
+
+    let todo = |id: &str| block(id, 17, "doc", &[], text_content("todo", id));
+    let tree = flat_document("doc", json!({}), vec![todo("a"), todo("b")]);
+
+    let value = json!([{ "select": "todo", "text": "待确认" }, { "block_id": "doc", "text": "标题" }]);
+    assert!(uses_selectors(&value));
+    let expanded = expand_selectors(&value, &tree, "doc").unwrap();
+    let requests = build_update_requests(&expanded).unwrap();
+    let ids: Vec<&str> = requests.iter().map(|r| r.block_id.as_str()).collect();
+    assert_eq!(ids, vec!["a", "b", "doc"]);
+
+    let message = expand_selectors(&json!([{ "select": "callout", "text": "x" }]), &tree, "doc")
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(message.contains("requests[0].select"), "{}", message);
+}
@@ -168,4 +168,16 @@ fn test_long_strings_in_config() {
     assert_eq!(config.app_secret.len(), 2000);
     assert_eq!(config.app_id, long_id);
     assert_eq!(config.app_secret, long_secret);
-}
\ No newline at end of file
+}
+#[test]
+fn test_resolve_base_url_from_argument() {
+    // 测试命令行指定的域名优先，去掉末尾的斜杠，不带协议时报错
+    assert_eq!(
+        Config::resolve_base_url(Some("https://example.feishu.cn/".to_string())).unwrap(),
+        "https://example.feishu.cn"
+    );
+    assert!(matches!(
+        Config::resolve_base_url(Some("example.feishu.cn".to_string())),
+        Err(LarkError::ValidationError(_))
+    ));
+}
@@ -8,4 +8,7 @@ pub mod config;
 pub mod output;
 
 // Auth module tests
-pub mod auth;
\ No newline at end of file
+pub mod auth;
+
+// API module tests
+pub mod api;
\ No newline at end of file
@@ -0,0 +1,129 @@
+use lark_cli::utils::{diff_blocks, similarity, BlockSummary, DiffHunk};
+use serde_json::json;
+
+fn text(id: &str, content: &str) -> BlockSummary {
+    BlockSummary {
+        block_id: id.to_string(),
+        block_type: 2,
+        elements: Some(json!([{ "text_run": { "content": content } }])),
+        properties: json!({}),
+        children: Vec::new(),
+    }
+}
+
+fn heading(id: &str, content: &str) -> BlockSummary {
+    BlockSummary { block_type: 3, ..text(id, content) }
+}
+
+// 测试内容相同时没有任何修改
+#[test]
+fn test_identical_blocks_unchanged() {
+    let remote = vec![text("r1", "第一段"), text("r2", "第二段")];
+    let local = vec![text("l1", "第一段"), text("l2", "第二段")];
+
+    let diff = diff_blocks(&remote, &local);
+    assert!(diff.is_empty());
+    assert_eq!(diff.unchanged, 2);
+}
+
+// 测试相似的同类型块原地更新文本
+#[test]
+fn test_similar_block_updated_in_place() {
+    let remote = vec![text("r1", "标题"), text("r2", "支付服务负责处理所有订单的扣款")];
+    let local = vec![text("l1", "标题"), text("l2", "支付服务负责处理所有订单的扣款和退款")];
+
+    let diff = diff_blocks(&remote, &local);
+    assert_eq!(diff.unchanged, 1);
+    assert_eq!(diff.updates.len(), 1);
+    assert_eq!(diff.updates[0].block_id, "r2");
+    assert_eq!(diff.updates[0].local_block_id, "l2");
+    assert!(diff.hunks.is_empty());
+}
+
+// 测试类型不同或内容差异大的块删除后重新创建
+#[test]
+fn test_dissimilar_block_replaced() {
+    let remote = vec![text("r1", "保持"), text("r2", "完全不同的旧内容")];
+    let local = vec![text("l1", "保持"), heading("l2", "完全不同的旧内容")];
+
+    let diff = diff_blocks(&remote, &local);
+    assert!(diff.updates.is_empty());
+    assert_eq!(
+        diff.hunks,
+        vec![DiffHunk { index: 1, delete_count: 1, insert: vec!["l2".to_string()] }]
+    );
+}
+
+// 测试插入和删除的位置基于修改前的文档
+#[test]
+fn test_insert_and_delete_positions() {
+    let remote = vec![text("r1", "一"), text("r2", "删除我吧"), text("r3", "三")];
+    let local = vec![text("l0", "新的开头段落"), text("l1", "一"), text("l3", "三"), text("l4", "新的结尾段落")];
+
+    let diff = diff_blocks(&remote, &local);
+    assert_eq!(diff.unchanged, 2);
+    assert_eq!(
+        diff.hunks,
+        vec![
+            DiffHunk { index: 0, delete_count: 0, insert: vec!["l0".to_string()] },
+            DiffHunk { index: 1, delete_count: 1, insert: vec![] },
+            DiffHunk { index: 3, delete_count: 0, insert: vec!["l4".to_string()] },
+        ]
+    );
+}
+
+// 测试子块不同的块不会只更新文本
+#[test]
+fn test_children_changes_force_replace() {
+    let mut remote = text("r1", "列表项");
+    remote.children.push(text("r2", "子项"));
+    let mut local = text("l1", "列表项！");
+    local.children.push(text("l2", "另一个子项"));
+
+    let diff = diff_blocks(&[remote], &[local]);
+    assert!(diff.updates.is_empty());
+    assert_eq!(diff.hunks.len(), 1);
+}
+
+// 测试服务端返回的默认值不影响比较
+#[test]
+fn test_remote_defaults_ignored() {
+    use lark_cli::api::get_blocks::BlockItem;
+    use lark_cli::api::DescendantBlock;
+    use lark_cli::utils::BlockTree;
+    use std::collections::HashMap;
+
+    let local_block = DescendantBlock {
+        block_id: "l1".to_string(),
+        block_type: 2,
+        children: Some(vec![]),
+        text: Some(json!({ "elements": [{ "text_run": { "content": "你好" } }] })),
+        ..Default::default()
+    };
+    let blocks: HashMap<&str, &DescendantBlock> = [("l1", &local_block)].into_iter().collect();
+    let local = BlockSummary::from_local("l1", &blocks).unwrap();
+
+    let remote_block: BlockItem = serde_json::from_value(json!({
+        "block_id": "r1",
+        "block_type": 2,
+        "parent_id": "doc",
+        "text": {
+            "elements": [{ "text_run": { "content": "你好", "text_element_style": { "bold": false, "italic": false } } }],
+            "style": { "align": 1, "folded": false }
+        },
+        "comment_ids": ["c1"]
+    }))
+    .unwrap();
+    let tree = BlockTree::new(vec![remote_block]);
+    let remote = BlockSummary::from_remote("r1", &tree).unwrap();
+
+    assert_eq!(remote.fingerprint(), local.fingerprint());
+}
+
+// 测试相似度计算
+#[test]
+fn test_similarity() {
+    assert_eq!(similarity("abc", "abc"), 1.0);
+    assert_eq!(similarity("ab", "cd"), 0.0);
+    assert!(similarity("hello world", "hello world!") > 0.9);
+}
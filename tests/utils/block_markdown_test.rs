@@ -0,0 +1,38 @@
+use super::{block, text_content};
+use lark_cli::utils::{block_to_markdown, render_document, BlockTree};
+use serde_json::json;
+
+// 测试行内样式和链接渲染为 Markdown
+#[test]
+fn test_inline_styles_rendered() {
+    let text = block(
+        "b1",
+        2,
+        "doc",
+        &[],
+        json!({ "text": { "elements": [
+            { "text_run": { "content": "加粗", "text_element_style": { "bold": true } } },
+            { "text_run": { "content": "，" } },
+            { "text_run": { "content": "文档", "text_element_style": { "link": { "url": "https%3A%2F%2Fexample.com%2Fa" } } } }
+        ] } }),
+    );
+
+    assert_eq!(block_to_markdown(&text), "**加粗**，[文档](https://example.com/a)");
+}
+
+// 测试文档渲染：标题、列表嵌套和高亮块
+#[test]
+fn test_render_document() {
+    let tree = BlockTree::new(vec![
+        block("doc", 1, "", &["h1", "l1", "l2", "c1"], text_content("page", "设计文档")),
+        block("h1", 3, "doc", &[], text_content("heading1", "背景")),
+        block("l1", 12, "doc", &["l1a"], text_content("bullet", "第一项")),
+        block("l1a", 12, "l1", &[], text_content("bullet", "子项")),
+        block("l2", 17, "doc", &[], json!({ "todo": { "elements": [{ "text_run": { "content": "待办" } }], "style": { "done": true } } })),
+        block("c1", 19, "doc", &["c1t"], json!({ "callout": {} })),
+        block("c1t", 2, "c1", &[], text_content("text", "注意")),
+    ]);
+
+    let expected = "---\ntitle: 设计文档\n---\n\n# 背景\n\n- 第一项\n  - 子项\n- [x] 待办\n\n> 注意\n";
+    assert_eq!(render_document(&tree, "doc"), expected);
+}
@@ -0,0 +1,96 @@
+use super::{block, text_content};
+use lark_cli::utils::{selection_ranges, BlockSelector, BlockTree};
+use serde_json::json;
+
+/// doc
+/// ├── h_intro (heading2 "Intro")
+/// ├── t1
+/// ├── todo_a (done)
+/// ├── callout_1
+/// │   └── t2
+/// ├── h_log (heading2 "Changelog")
+/// ├── t3
+/// ├── todo_b
+/// └── callout_2
+fn document() -> BlockTree {
+    let mut todo_done = text_content("todo", "发布");
+    todo_done["todo"]["style"] = json!({ "done": true });
+
+    BlockTree::new(vec![
+        block(
+            "doc",
+            1,
+            "",
+            &["h_intro", "t1", "todo_a", "callout_1", "h_log", "t3", "todo_b", "callout_2"],
+            json!({}),
+        ),
+        block("h_intro", 4, "doc", &[], text_content("heading2", "Intro")),
+        block("t1", 2, "doc", &[], text_content("text", "第一段")),
+        block("todo_a", 17, "doc", &[], todo_done),
+        block("callout_1", 19, "doc", &["t2"], json!({ "callout": {} })),
+        block("t2", 2, "callout_1", &[], text_content("text", "提示")),
+        block("h_log", 4, "doc", &[], text_content("heading2", "Changelog")),
+        block("t3", 2, "doc", &[], text_content("text", "v1.1")),
+        block("todo_b", 17, "doc", &[], text_content("todo", "回滚")),
+        block("callout_2", 19, "doc", &[], json!({ "callout": {} })),
+    ])
+}
+
+fn select(selector: &str) -> Vec<String> {
+    BlockSelector::parse(selector).unwrap().select(&document(), "doc")
+}
+
+// 测试类型、ID、属性和文本条件
+#[test]
+fn test_simple_selectors() {
+    assert_eq!(select("heading2"), vec!["h_intro", "h_log"]);
+    assert_eq!(select("type=todo[done=true]"), vec!["todo_a"]);
+    assert_eq!(select("todo[done=false]"), vec!["todo_b"]);
+    assert_eq!(select("#t3"), vec!["t3"]);
+    assert_eq!(select("text:contains(\"段\")"), vec!["t1"]);
+    assert_eq!(select("todo, callout"), vec!["todo_a", "callout_1", "todo_b", "callout_2"]);
+}
+
+// 测试组合关系：后代、子块、之后的同级块和下一个同级块
+#[test]
+fn test_combinators() {
+    assert_eq!(select("heading2:contains(\"Changelog\") ~ *"), vec!["t3", "todo_b", "callout_2"]);
+    assert_eq!(select("heading2:contains('Intro') + *"), vec!["t1"]);
+    assert_eq!(select("callout text"), vec!["t2"]);
+    assert_eq!(select("#doc > :nth(3)"), vec!["todo_a"]);
+    assert_eq!(select("callout > *"), vec!["t2"]);
+}
+
+// 测试在匹配结果中按位置选择
+#[test]
+fn test_positions() {
+    assert_eq!(select("callout:last"), vec!["callout_2"]);
+    assert_eq!(select("heading2:first"), vec!["h_intro"]);
+    assert_eq!(select("text:nth(2)"), vec!["t2"]);
+    assert!(select("text:nth(9)").is_empty());
+}
+
+// 测试无效的选择器报错
+#[test]
+fn test_parse_errors() {
+    for selector in ["", "heading99", "todo[done", ":unknown", "#", "text:nth(0)", "text:contains(\"x)", "todo >"] {
+        assert!(BlockSelector::parse(selector).is_err(), "{}", selector);
+    }
+}
+
+// 测试将选中的块转换为每个父块下的连续区间，祖先已选中的块不单独列出
+#[test]
+fn test_selection_ranges() {
+    let tree = document();
+    let ids: Vec<String> = ["t1", "todo_a", "callout_1", "t2", "t3", "todo_b"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let ranges = selection_ranges(&tree, &ids).unwrap();
+    assert_eq!(ranges.len(), 2);
+    assert_eq!((ranges[0].parent_id.as_str(), ranges[0].start_index, ranges[0].end_index), ("doc", 1, 4));
+    assert_eq!(ranges[0].block_ids, vec!["t1", "todo_a", "callout_1"]);
+    assert_eq!((ranges[1].start_index, ranges[1].end_index), (5, 7));
+
+    assert!(selection_ranges(&tree, &["doc".to_string()]).is_err());
+}
@@ -0,0 +1,35 @@
+use super::block;
+use lark_cli::utils::{index_ranges, BlockTree};
+use serde_json::json;
+
+#[test]
+fn test_block_tree_children() {
+    // 测试获取子块和子块位置
+    let tree = BlockTree::new(vec![
+        block("doc", 1, "", &["a", "b", "c"], json!({})),
+        block("a", 2, "doc", &[], json!({})),
+        block("b", 2, "doc", &["b1"], json!({})),
+        block("b1", 2, "b", &[], json!({})),
+        block("c", 2, "doc", &[], json!({})),
+    ]);
+
+    assert_eq!(tree.children("doc").len(), 3);
+    assert_eq!(tree.children("b"), ["b1".to_string()]);
+    assert!(tree.children("missing").is_empty());
+    assert_eq!(tree.child_index("doc", "c"), Some(2));
+    assert_eq!(tree.child_index("doc", "b1"), None);
+    assert_eq!(tree.get("b1").unwrap().parent_id, "b");
+}
+
+#[test]
+fn test_index_ranges_merges_contiguous() {
+    // 测试连续位置合并为区间
+    assert_eq!(index_ranges(&[3, 1, 2, 7, 8, 10]), vec![(1, 4), (7, 9), (10, 11)]);
+}
+
+#[test]
+fn test_index_ranges_dedup_and_empty() {
+    // 测试重复位置和空输入
+    assert_eq!(index_ranges(&[4, 4, 5]), vec![(4, 6)]);
+    assert!(index_ranges(&[]).is_empty());
+}
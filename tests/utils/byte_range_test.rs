@@ -0,0 +1,57 @@
+use lark_cli::utils::{format_bytes, parse_content_range, parse_sha256_digest, ByteRange, ContentRange};
+
+// 测试解析下载范围，支持 bytes= 前缀和省略结束位置
+#[test]
+fn test_parse_byte_range() {
+    let range: ByteRange = "0-1023".parse().unwrap();
+    assert_eq!(range, ByteRange { start: 0, end: Some(1023) });
+    assert_eq!(range.header_value(), "bytes=0-1023");
+
+    let range: ByteRange = "bytes=1024-".parse().unwrap();
+    assert_eq!(range, ByteRange { start: 1024, end: None });
+    assert_eq!(range.header_value(), "bytes=1024-");
+
+    assert!("1024".parse::<ByteRange>().is_err());
+    assert!("10-5".parse::<ByteRange>().is_err());
+    assert!("a-b".parse::<ByteRange>().is_err());
+}
+
+// 测试解析 Content-Range 响应头，包括范围无法满足时的 `bytes */total`
+#[test]
+fn test_parse_content_range() {
+    assert_eq!(
+        parse_content_range("bytes 0-0/1048576"),
+        Some(ContentRange { range: Some((0, 0)), total: Some(1_048_576) })
+    );
+    assert_eq!(
+        parse_content_range("bytes 100-199/*"),
+        Some(ContentRange { range: Some((100, 199)), total: None })
+    );
+    assert_eq!(
+        parse_content_range("bytes */500"),
+        Some(ContentRange { range: None, total: Some(500) })
+    );
+    assert_eq!(parse_content_range("items 0-1/2"), None);
+}
+
+// 测试从 Digest 和 Repr-Digest 响应头中取出 SHA-256 摘要，忽略其他算法
+#[test]
+fn test_parse_sha256_digest() {
+    let expected = parse_sha256_digest("SHA-256=ungWv48Bz+pBQUDeXa4iI7ADYaOWF3qctBD/YfIAFa0=").unwrap();
+    assert_eq!(expected.len(), 32);
+    assert_eq!(expected[0], 0xba);
+    assert_eq!(
+        parse_sha256_digest("md5=kAFQmDzST7DWlj99KOF/cg==, sha-256=:ungWv48Bz+pBQUDeXa4iI7ADYaOWF3qctBD/YfIAFa0=:"),
+        Some(expected)
+    );
+    assert_eq!(parse_sha256_digest("md5=kAFQmDzST7DWlj99KOF/cg=="), None);
+    assert_eq!(parse_sha256_digest("sha-256=:bm90LWEtZGlnZXN0:"), None);
+}
+
+// 测试可读的字节数
+#[test]
+fn test_format_bytes() {
+    assert_eq!(format_bytes(512), "512 B");
+    assert_eq!(format_bytes(1536), "1.5 KB");
+    assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GB");
+}
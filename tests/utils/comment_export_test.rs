@@ -0,0 +1,84 @@
+use lark_cli::api::comments::Comment;
+use lark_cli::utils::{comment_user_ids, export_comments, render_comments_markdown, CommentExportFormat};
+use std::collections::HashMap;
+
+fn comments() -> Vec<Comment> {
+    serde_json::from_value(serde_json::json!([
+        {
+            "comment_id": "c1",
+            "user_id": "ou_alice",
+            "create_time": 1700000000,
+            "is_solved": true,
+            "solver_user_id": "ou_bob",
+            "is_whole": false,
+            "quote": "接口超时时间为 30 秒\n重试三次",
+            "reply_list": { "replies": [
+                { "reply_id": "r1", "user_id": "ou_alice", "create_time": 1700000000,
+                  "content": { "elements": [
+                      { "type": "text_run", "text_run": { "text": "请确认 " } },
+                      { "type": "person", "person": { "user_id": "ou_bob" } }
+                  ] } },
+                { "reply_id": "r2", "user_id": "ou_bob", "create_time": 1700003600,
+                  "content": { "elements": [
+                      { "type": "text_run", "text_run": { "text": "见 " } },
+                      { "type": "docs_link", "docs_link": { "url": "https://example.feishu.cn/docx/abc" } }
+                  ] } }
+            ] }
+        },
+        {
+            "comment_id": "c2",
+            "user_id": "ou_carol",
+            "create_time": 1700007200,
+            "is_whole": true,
+            "reply_list": { "replies": [
+                { "reply_id": "r3", "user_id": "ou_carol", "create_time": 1700007200,
+                  "content": { "elements": [{ "type": "text_run", "text_run": { "text": "整体不错" } }] } }
+            ] }
+        }
+    ]))
+    .unwrap()
+}
+
+// 测试收集评论者、解决者和被 @用户，去重并保持出现顺序
+#[test]
+fn test_comment_user_ids() {
+    assert_eq!(comment_user_ids(&comments()), vec!["ou_alice", "ou_bob", "ou_carol"]);
+}
+
+// 测试导出时解析名字，@用户显示为名字，查询不到的用户保留 open_id
+#[test]
+fn test_export_comments_names() {
+    let names = HashMap::from([
+        ("ou_alice".to_string(), "Alice".to_string()),
+        ("ou_bob".to_string(), "Bob".to_string()),
+    ]);
+    let export = export_comments("doccnTest", "docx", &comments(), &names);
+
+    assert_eq!(export.comment_count, 2);
+    let first = &export.comments[0];
+    assert_eq!(first.author, "Alice");
+    assert_eq!(first.solved_by.as_deref(), Some("Bob"));
+    assert_eq!(first.created_at, "2023-11-14 22:13 UTC");
+    assert_eq!(first.replies[0].text, "请确认 @Bob");
+    assert_eq!(first.replies[1].text, "见 https://example.feishu.cn/docx/abc");
+
+    let second = &export.comments[1];
+    assert_eq!(second.author, "ou_carol");
+    assert!(second.quote.is_none());
+    assert!(second.solved_by.is_none());
+}
+
+// 测试渲染 Markdown：引用内容为引用块，全文评论有标记，回复按顺序列出
+#[test]
+fn test_render_comments_markdown() {
+    let names = HashMap::from([("ou_bob".to_string(), "Bob".to_string())]);
+    let markdown = render_comments_markdown(&export_comments("doccnTest", "docx", &comments(), &names));
+
+    assert!(markdown.starts_with("# 评论：doccnTest\n\n共 2 条评论\n"));
+    assert!(markdown.contains("## 评论 1（已解决，Bob）\n\n> 接口超时时间为 30 秒\n> 重试三次\n\n"));
+    assert!(markdown.contains("- **ou_alice**（2023-11-14 22:13 UTC）：请确认 @Bob\n"));
+    assert!(markdown.contains("## 评论 2（未解决）\n\n> （全文评论）\n\n- **ou_carol**"));
+
+    assert_eq!("md".parse::<CommentExportFormat>(), Ok(CommentExportFormat::Markdown));
+    assert!("csv".parse::<CommentExportFormat>().is_err());
+}
@@ -0,0 +1,70 @@
+use lark_cli::api::board::{DiagramSyntax, DiagramType};
+use lark_cli::utils::{detect_diagram_type, diagram_syntax, mark_diagram_fences};
+
+#[test]
+fn test_mark_diagram_fences() {
+    // 测试只为图表代码块插入标记段落，其他代码块保持不变
+    let markdown = "# 设计\n\n```mermaid\nsequenceDiagram\n  A->>B: hi\n```\n\n```rust\nfn main() {}\n```\n\n~~~plantuml\n@startuml\nclass User\n@enduml\n~~~\n";
+    let (marked, fences) = mark_diagram_fences(markdown);
+
+    assert_eq!(fences.len(), 2);
+    assert_eq!(fences[0].syntax, DiagramSyntax::Mermaid);
+    assert_eq!(fences[0].diagram_type, DiagramType::Sequence);
+    assert_eq!(fences[0].source, "sequenceDiagram\n  A->>B: hi");
+    assert_eq!(fences[1].syntax, DiagramSyntax::PlantUml);
+    assert_eq!(fences[1].diagram_type, DiagramType::Class);
+    assert_ne!(fences[0].marker, fences[1].marker);
+
+    let expected = format!(
+        "# 设计\n\n{}\n\n```mermaid\nsequenceDiagram\n  A->>B: hi\n```\n\n```rust\nfn main() {{}}\n```\n\n{}\n\n~~~plantuml\n@startuml\nclass User\n@enduml\n~~~\n",
+        fences[0].marker, fences[1].marker
+    );
+    assert_eq!(marked, expected);
+}
+
+#[test]
+fn test_mark_diagram_fences_in_list_and_quote() {
+    // 测试列表项和引用中的图表，标记段落带上相同的缩进或引用前缀，留在原容器中
+    let markdown = "- 步骤\n\n  ```mermaid\n  graph TD\n    A-->B\n  ```\n\n> 说明\n>\n> ```mermaid\n> sequenceDiagram\n> ```\n";
+    let (marked, fences) = mark_diagram_fences(markdown);
+
+    assert_eq!(fences.len(), 2);
+    assert_eq!(fences[0].source, "graph TD\n  A-->B");
+    assert_eq!(fences[1].diagram_type, DiagramType::Sequence);
+
+    let expected = format!(
+        "- 步骤\n\n  {}\n\n  ```mermaid\n  graph TD\n    A-->B\n  ```\n\n> 说明\n>\n> {}\n>\n> ```mermaid\n> sequenceDiagram\n> ```\n",
+        fences[0].marker, fences[1].marker
+    );
+    assert_eq!(marked, expected);
+}
+
+#[test]
+fn test_unclosed_and_nested_fences_are_ignored() {
+    // 测试未闭合的代码块和嵌套在其他代码块中的图表不被替换
+    let markdown = "````markdown\n```mermaid\ngraph TD\n```\n````\n\n```mermaid\ngraph TD\n";
+    let (marked, fences) = mark_diagram_fences(markdown);
+    assert!(fences.is_empty());
+    assert_eq!(marked, markdown);
+}
+
+#[test]
+fn test_diagram_syntax_detection() {
+    // 测试根据语言标识和内容识别图表语法
+    assert_eq!(diagram_syntax("mermaid", ""), Some(DiagramSyntax::Mermaid));
+    assert_eq!(diagram_syntax("puml {title}", ""), Some(DiagramSyntax::PlantUml));
+    assert_eq!(diagram_syntax("", "@startuml\nA -> B\n@enduml"), Some(DiagramSyntax::PlantUml));
+    assert_eq!(diagram_syntax("", "graph TD"), None);
+    assert_eq!(diagram_syntax("python", "@startuml"), None);
+}
+
+#[test]
+fn test_detect_diagram_type() {
+    // 测试根据源码推断图表类型
+    assert_eq!(detect_diagram_type(DiagramSyntax::Mermaid, "%% 注释\nflowchart LR\nA-->B"), DiagramType::Flowchart);
+    assert_eq!(detect_diagram_type(DiagramSyntax::Mermaid, "erDiagram\nA ||--o{ B : has"), DiagramType::ER);
+    assert_eq!(detect_diagram_type(DiagramSyntax::Mermaid, "gantt\ntitle x"), DiagramType::Auto);
+    assert_eq!(detect_diagram_type(DiagramSyntax::PlantUml, "@startmindmap\n* root\n@endmindmap"), DiagramType::MindMap);
+    assert_eq!(detect_diagram_type(DiagramSyntax::PlantUml, "@startuml\nAlice -> Bob: hi\n@enduml"), DiagramType::Sequence);
+    assert_eq!(detect_diagram_type(DiagramSyntax::PlantUml, "@startuml\nstart\n:step;\nstop\n@enduml"), DiagramType::Activity);
+}
@@ -0,0 +1,65 @@
+use lark_cli::utils::{collect_dirs, document_title, extract_h1_title, resolve_link, rewrite_links};
+use std::collections::HashMap;
+use std::path::Path;
+
+#[test]
+fn test_extract_h1_title() {
+    // 测试提取第一个一级标题并从正文中移除
+    let content = "```\n# not a title\n```\n\n# Getting Started\n\nIntro text\n";
+    let (title, body) = extract_h1_title(content).unwrap();
+    assert_eq!(title, "Getting Started");
+    assert_eq!(body, "```\n# not a title\n```\n\nIntro text\n");
+
+    assert!(extract_h1_title("## Only H2\n\ntext").is_none());
+}
+
+#[test]
+fn test_document_title_falls_back_to_file_name() {
+    // 测试没有一级标题或非 Markdown 文件时使用文件名
+    let (title, body) = document_title(Path::new("docs/install-guide.md"), "no heading", "markdown");
+    assert_eq!(title, "install-guide");
+    assert_eq!(body, "no heading");
+
+    let (title, _) = document_title(Path::new("page.html"), "# not markdown", "html");
+    assert_eq!(title, "page");
+}
+
+#[test]
+fn test_collect_dirs_parents_first() {
+    // 测试收集目录时包含所有上级目录且父目录在前
+    let dirs = collect_dirs(["README.md", "guide/a/b/deep.md", "api/index.md", "guide/intro.md"]);
+    assert_eq!(dirs, vec!["api", "guide", "guide/a", "guide/a/b"]);
+}
+
+#[test]
+fn test_resolve_link() {
+    // 测试相对链接解析为导入根目录下的路径
+    assert_eq!(resolve_link("guide/intro.md", "install.md"), Some("guide/install.md".to_string()));
+    assert_eq!(resolve_link("guide/intro.md", "../api/index.md#auth"), Some("api/index.md".to_string()));
+    assert_eq!(resolve_link("guide/intro.md", "./my%20page.md"), Some("guide/my page.md".to_string()));
+    assert_eq!(resolve_link("intro.md", "../outside.md"), None);
+    assert_eq!(resolve_link("intro.md", "https://example.com/a.md"), None);
+    assert_eq!(resolve_link("intro.md", "#section"), None);
+}
+
+#[test]
+fn test_rewrite_links() {
+    // 测试只改写指向已导入文件的链接，代码块和外部链接保持不变
+    let mut targets = HashMap::new();
+    targets.insert("guide/install.md".to_string(), "https://example.com/docx/doc1".to_string());
+    targets.insert("api/index.md".to_string(), "https://example.com/docx/doc2".to_string());
+
+    let content = "See [install](install.md \"Install\") and [API](../api/index.md#auth).\n\
+                   Keep [site](https://lark.com) and [missing](missing.md).\n\
+                   ```\n[code](install.md)\n```\n\
+                   [ref]: ../api/index.md\n";
+    let rewritten = rewrite_links(content, "guide/intro.md", &targets);
+
+    assert_eq!(
+        rewritten,
+        "See [install](https://example.com/docx/doc1 \"Install\") and [API](https://example.com/docx/doc2).\n\
+         Keep [site](https://lark.com) and [missing](missing.md).\n\
+         ```\n[code](install.md)\n```\n\
+         [ref]: https://example.com/docx/doc2\n"
+    );
+}
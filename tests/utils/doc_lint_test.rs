@@ -0,0 +1,141 @@
+use super::{block, elements, flat_document, heading, text};
+use lark_cli::utils::{
+    lint_blocks, lint_references, BlockTree, LintConfig, LintFix, LintRule, LintTarget, Severity,
+};
+use serde_json::json;
+
+fn rules(findings: &[lark_cli::utils::LintFinding]) -> Vec<(LintRule, &str)> {
+    findings.iter().map(|f| (f.rule, f.block_id.as_str())).collect()
+}
+
+// 测试标题规则：跳级、空标题、重复标题和首尾空白
+#[test]
+fn test_lint_headings() {
+    let blocks = vec![
+        heading("h1", "doc", 1, "概述"),
+        heading("h3", "doc", 3, "细节"),
+        heading("empty", "doc", 2, "  "),
+        heading("dup", "doc", 3, "细节"),
+        heading("space", "doc", 2, " 附录 "),
+    ];
+    let tree = flat_document("doc", json!({ "page": {} }), blocks);
+    let findings = lint_blocks(&tree, "doc", &LintConfig::default());
+    assert_eq!(
+        rules(&findings),
+        vec![
+            (LintRule::HeadingLevelJump, "h3"),
+            (LintRule::EmptyHeading, "empty"),
+            (LintRule::DuplicateHeading, "dup"),
+            (LintRule::HeadingWhitespace, "space"),
+        ]
+    );
+    assert_eq!(findings[0].severity, Severity::Error);
+    assert_eq!(findings[1].fix, Some(LintFix::Delete));
+    assert_eq!(
+        findings[3].fix,
+        Some(LintFix::UpdateElements {
+            elements: json!([{ "text_run": { "content": "附录" } }])
+        })
+    );
+}
+
+// 测试高亮块和引用容器中唯一的空段落不被报告，避免修复时删空容器
+#[test]
+fn test_lint_empty_paragraph_in_containers() {
+    let container = |id: &str, block_type: i32, key: &str, child: &str| {
+        block(id, block_type, "doc", &[child], json!({ key: {} }))
+    };
+    let top = vec![
+        heading("h1", "doc", 1, "概述"),
+        container("callout", 19, "callout", "p1"),
+        container("quote", 34, "quote_container", "p2"),
+        heading("h2", "doc", 2, "结尾"),
+    ];
+    let mut blocks = flat_document("doc", json!({ "page": {} }), top).blocks().to_vec();
+    blocks.push(block("p1", 2, "callout", &[], elements("text", json!([]))));
+    blocks.push(block("p2", 2, "quote", &[], elements("text", json!([]))));
+    let tree = BlockTree::new(blocks);
+    let findings = lint_blocks(&tree, "doc", &LintConfig::default());
+    assert!(findings.iter().all(|f| f.rule != LintRule::EmptyParagraph), "{:?}", rules(&findings));
+}
+
+// 测试空段落：只报告连续的以及位于开头、结尾的空段落，并检查图片说明
+#[test]
+fn test_lint_empty_paragraphs_and_images() {
+    let empty = || json!([{ "text_run": { "content": "" } }]);
+    let blocks = vec![
+        block("lead", 2, "doc", &[], elements("text", empty())),
+        text("body", "doc", "正文"),
+        block("gap", 2, "doc", &[], elements("text", empty())),
+        block("gap2", 2, "doc", &[], elements("text", empty())),
+        block("img", 27, "doc", &[], json!({ "image": { "token": "box", "caption": { "content": "" } } })),
+        block("img2", 27, "doc", &[], json!({ "image": { "token": "box", "caption": { "content": "架构图" } } })),
+        block("tail", 2, "doc", &[], elements("text", empty())),
+    ];
+    let tree = flat_document("doc", json!({ "page": {} }), blocks);
+    let findings = lint_blocks(&tree, "doc", &LintConfig::default());
+    assert_eq!(
+        rules(&findings),
+        vec![
+            (LintRule::EmptyParagraph, "lead"),
+            (LintRule::EmptyParagraph, "gap2"),
+            (LintRule::ImageCaption, "img"),
+            (LintRule::EmptyParagraph, "tail"),
+        ]
+    );
+}
+
+// 测试配置：启用、跳过规则和覆盖严重程度
+#[test]
+fn test_lint_config() {
+    let config = LintConfig::parse(
+        r#"
+skip = ["image-caption", "broken-link"]
+
+[severity]
+empty-paragraph = "error"
+"#,
+    )
+    .unwrap();
+    assert!(!config.enabled_rules().contains(&LintRule::ImageCaption));
+    assert_eq!(config.enabled_rules().len(), LintRule::ALL.len() - 2);
+    assert_eq!(config.severity(LintRule::EmptyParagraph), Severity::Error);
+    assert_eq!(config.severity(LintRule::DuplicateHeading), Severity::Warning);
+
+    assert!(LintConfig::parse("skip = [\"no-such-rule\"]").is_err());
+    assert_eq!("broken_link".parse::<LintRule>(), Ok(LintRule::BrokenLink));
+}
+
+// 测试收集提及和文档链接，只有飞书文档链接需要检查
+#[test]
+fn test_lint_references() {
+    let tree = flat_document(
+        "doc",
+        json!({ "page": {} }),
+        vec![block(
+            "refs",
+            2,
+            "doc",
+            &[],
+            elements(
+                "text",
+                json!([
+                    { "mention_user": { "user_id": "ou_abc" } },
+                    { "mention_doc": { "token": "doxcnA", "obj_type": 22, "title": "设计文档" } },
+                    { "text_run": { "content": "链接", "text_element_style": { "link": { "url": "https%3A%2F%2Fcorp.feishu.cn%2Fwiki%2FwikcnB%3Ffrom%3Dx" } } } },
+                    { "text_run": { "content": "外链", "text_element_style": { "link": { "url": "https%3A%2F%2Fexample.com%2Fdocx%2Fx" } } } }
+                ]),
+            ),
+        )],
+    );
+    let references = lint_references(&tree, "doc", &LintConfig::default());
+    let targets: Vec<_> = references.iter().map(|r| (r.rule, r.target.clone())).collect();
+    assert_eq!(
+        targets,
+        vec![
+            (LintRule::UnresolvedMention, LintTarget::User("ou_abc".to_string())),
+            (LintRule::UnresolvedMention, LintTarget::Docx("doxcnA".to_string())),
+            (LintRule::BrokenLink, LintTarget::Wiki("wikcnB".to_string())),
+        ]
+    );
+}
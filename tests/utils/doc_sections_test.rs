@@ -0,0 +1,59 @@
+use super::{flat_document, heading, text};
+use lark_cli::utils::{find_section, parse_heading_path, BlockTree, HeadingSegment, SectionMode};
+use serde_json::json;
+
+fn report() -> BlockTree {
+    let blocks = vec![
+        heading("h_status", "doc", 1, "Status"),
+        text("t1", "doc", "概述"),
+        heading("h_metrics", "doc", 2, "Metrics"),
+        text("t2", "doc", "旧指标 1"),
+        heading("h_detail", "doc", 3, "Detail"),
+        text("t3", "doc", "旧指标 2"),
+        heading("h_risks", "doc", 2, "Risks"),
+        text("t4", "doc", "风险"),
+        heading("h_next", "doc", 1, "Next"),
+        heading("h_metrics2", "doc", 2, "Metrics"),
+    ];
+    flat_document("doc", json!({}), blocks)
+}
+
+// 测试解析标题路径和级别前缀
+#[test]
+fn test_parse_heading_path() {
+    let path = parse_heading_path("Status / ## Metrics").unwrap();
+    assert_eq!(
+        path,
+        vec![
+            HeadingSegment { text: "Status".to_string(), level: None },
+            HeadingSegment { text: "Metrics".to_string(), level: Some(2) },
+        ]
+    );
+    assert!(parse_heading_path("Status / ").is_err());
+    assert_eq!("append".parse::<SectionMode>().unwrap(), SectionMode::Append);
+}
+
+// 测试章节包含下级标题，在下一个同级标题处结束
+#[test]
+fn test_find_nested_section() {
+    let tree = report();
+    let section = find_section(&tree, "doc", &parse_heading_path("Status / Metrics").unwrap()).unwrap();
+
+    assert_eq!(section.heading_block_id, "h_metrics");
+    assert_eq!(section.heading_level, 2);
+    assert_eq!((section.start, section.end), (3, 6));
+}
+
+// 测试最后一个章节延伸到文档末尾，以及同名标题需要完整路径
+#[test]
+fn test_section_end_and_ambiguous_heading() {
+    let tree = report();
+
+    let section = find_section(&tree, "doc", &parse_heading_path("Next").unwrap()).unwrap();
+    assert_eq!((section.start, section.end), (9, 10));
+
+    assert!(find_section(&tree, "doc", &parse_heading_path("Metrics").unwrap()).is_err());
+    let section = find_section(&tree, "doc", &parse_heading_path("Next / Metrics").unwrap()).unwrap();
+    assert_eq!(section.heading_block_id, "h_metrics2");
+    assert!(find_section(&tree, "doc", &parse_heading_path("Missing").unwrap()).is_err());
+}
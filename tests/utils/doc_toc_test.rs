@@ -0,0 +1,63 @@
+use super::{block, heading};
+use lark_cli::utils::{collect_headings, find_toc_marker, percent_encode, toc_blocks, BlockTree, TocEntry};
+use serde_json::json;
+
+fn entry(id: &str, level: usize) -> TocEntry {
+    TocEntry {
+        block_id: id.to_string(),
+        level,
+        text: id.to_uppercase(),
+    }
+}
+
+// 测试查找目录标记，并在收集标题时跳过标记块中的内容
+#[test]
+fn test_find_marker_and_collect_headings() {
+    let tree = BlockTree::new(vec![
+        block("doc", 1, "", &["toc", "h1", "h2", "h4", "empty"], json!({ "page": {} })),
+        block("toc", 19, "doc", &["toc_title", "old"], json!({ "callout": {} })),
+        block("toc_title", 2, "toc", &[], json!({ "text": { "elements": [{ "text_run": { "content": "目录" } }] } })),
+        block("old", 12, "toc", &[], json!({ "bullet": { "elements": [{ "text_run": { "content": "旧" } }] } })),
+        heading("h1", "doc", 1, "概述"),
+        heading("h2", "doc", 2, "背景"),
+        heading("h4", "doc", 4, "细节"),
+        heading("empty", "doc", 2, " "),
+    ]);
+
+    let marker = find_toc_marker(&tree, "doc", "目录");
+    assert_eq!(marker.as_deref(), Some("toc"));
+    assert_eq!(find_toc_marker(&tree, "doc", "Contents"), None);
+
+    let headings = collect_headings(&tree, "doc", 3, marker.as_deref());
+    let ids: Vec<_> = headings.iter().map(|h| h.block_id.as_str()).collect();
+    assert_eq!(ids, vec!["h1", "h2"]);
+    assert_eq!(headings[1].text, "背景");
+}
+
+// 测试目录列表的嵌套关系和标题链接
+#[test]
+fn test_toc_blocks_nesting() {
+    let entries = vec![entry("a", 1), entry("b", 2), entry("c", 4), entry("d", 2), entry("e", 1)];
+    let (children_id, descendants) = toc_blocks(&entries, "https://example.feishu.cn/docx/doc1");
+
+    assert_eq!(children_id, vec!["toc_1", "toc_5"]);
+    let children = |id: &str| {
+        descendants
+            .iter()
+            .find(|b| b.block_id == id)
+            .and_then(|b| b.children.clone())
+            .unwrap_or_default()
+    };
+    assert_eq!(children("toc_1"), vec!["toc_2", "toc_4"]);
+    assert_eq!(children("toc_2"), vec!["toc_3"]);
+    assert!(children("toc_5").is_empty());
+
+    let link = descendants[0]
+        .bullet
+        .as_ref()
+        .unwrap()
+        .pointer("/elements/0/text_run/text_element_style/link/url")
+        .cloned();
+    assert_eq!(link, Some(json!(percent_encode("https://example.feishu.cn/docx/doc1#a"))));
+    assert_eq!(percent_encode("https://x.cn/a#b"), "https%3A%2F%2Fx.cn%2Fa%23b");
+}
@@ -0,0 +1,56 @@
+use lark_cli::utils::{DownloadState, RemoteVersion, StreamState};
+use tempfile::TempDir;
+
+// 测试分段范围、未完成分段和断点记录的保存加载
+#[test]
+fn test_download_state_segments() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("video.mp4.part.json");
+
+    let version = RemoteVersion {
+        etag: Some("\"v1\"".to_string()),
+        last_modified: None,
+    };
+    let mut state = DownloadState::new("boxcnToken", 25, 10, version.clone());
+    assert_eq!(state.segment_count(), 3);
+    assert_eq!(state.segment_range(0), (0, 9));
+    assert_eq!(state.segment_range(2), (20, 24));
+
+    state.completed.insert(2);
+    state.save(&path).unwrap();
+
+    let loaded = DownloadState::load(&path).unwrap();
+    assert_eq!(loaded.file_token, "boxcnToken");
+    assert_eq!(loaded.pending_segments(), vec![0, 1]);
+    assert_eq!(loaded.completed_bytes(), 5);
+    assert_eq!(loaded.version, version);
+
+    std::fs::write(&path, "not json").unwrap();
+    assert!(DownloadState::load(&path).is_none());
+}
+
+// 测试远端版本：If-Range 优先使用强 ETag，任一方缺少的头不参与比较
+#[test]
+fn test_remote_version_validator() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("video.mp4.part.stream.json");
+
+    let version = RemoteVersion {
+        etag: Some("\"v1\"".to_string()),
+        last_modified: Some("Wed, 21 Oct 2026 07:28:00 GMT".to_string()),
+    };
+    let state = StreamState {
+        file_token: "boxcnToken".to_string(),
+        version: version.clone(),
+    };
+    state.save(&path).unwrap();
+    assert_eq!(StreamState::load(&path).unwrap(), state);
+    assert_eq!(version.validator(), Some("\"v1\""));
+
+    let weak = RemoteVersion { etag: Some("W/\"v1\"".to_string()), ..version.clone() };
+    assert_eq!(weak.validator(), Some("Wed, 21 Oct 2026 07:28:00 GMT"));
+    assert_eq!(RemoteVersion::default().validator(), None);
+
+    assert!(!version.conflicts_with(&RemoteVersion::default()));
+    assert!(version.conflicts_with(&RemoteVersion { etag: Some("\"v2\"".to_string()), ..version.clone() }));
+}
@@ -0,0 +1,40 @@
+use lark_cli::utils::{check_drive_file_type, parse_drive_ref, DriveRef};
+
+fn file(token: &str, file_type: Option<&str>) -> DriveRef {
+    DriveRef::File {
+        token: token.to_string(),
+        file_type: file_type.map(str::to_string),
+    }
+}
+
+// 测试从各类飞书链接中解析 token 和文件类型
+#[test]
+fn test_parse_drive_ref_urls() {
+    let cases = [
+        ("https://example.feishu.cn/drive/folder/AbCdEf?from=space", file("AbCdEf", Some("folder"))),
+        ("https://example.feishu.cn/docx/Doc123#part", file("Doc123", Some("docx"))),
+        ("https://example.larksuite.com/sheets/Sht123", file("Sht123", Some("sheet"))),
+        ("https://example.feishu.cn/base/Bas123?table=tbl1", file("Bas123", Some("bitable"))),
+        ("https://example.feishu.cn/file/Box123", file("Box123", Some("file"))),
+        ("https://example.feishu.cn/wiki/Wik123", DriveRef::Wiki("Wik123".to_string())),
+    ];
+    for (url, expected) in cases {
+        assert_eq!(parse_drive_ref(url).unwrap(), expected, "{}", url);
+    }
+
+    assert!(parse_drive_ref("https://example.com/docx/Doc123").is_err());
+    assert!(parse_drive_ref("https://example.feishu.cn/calendar/Cal123").is_err());
+}
+
+// 测试纯 token 按旧版前缀判断类型，新版 token 类型未知
+#[test]
+fn test_parse_drive_ref_tokens() {
+    assert_eq!(parse_drive_ref("fldcnAbCdEf").unwrap(), file("fldcnAbCdEf", Some("folder")));
+    assert_eq!(parse_drive_ref(" doxcnAbCdEf ").unwrap(), file("doxcnAbCdEf", Some("docx")));
+    assert_eq!(parse_drive_ref("Nq3JwAbCdEf").unwrap(), file("Nq3JwAbCdEf", None));
+    assert!(parse_drive_ref("").is_err());
+    assert!(parse_drive_ref("folder/AbCdEf").is_err());
+
+    assert!(check_drive_file_type("bitable").is_ok());
+    assert!(check_drive_file_type("wiki").is_err());
+}
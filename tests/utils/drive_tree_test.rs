@@ -0,0 +1,51 @@
+use lark_cli::api::drive::DriveFile;
+use lark_cli::utils::{filter_entries, render_drive_tree, DriveEntry};
+
+fn entry(depth: usize, name: &str, file_type: &str) -> DriveEntry {
+    DriveEntry {
+        depth,
+        path: name.to_string(),
+        file: DriveFile {
+            token: format!("tok_{}", name),
+            name: name.to_string(),
+            file_type: file_type.to_string(),
+            ..Default::default()
+        },
+    }
+}
+
+fn tree() -> Vec<DriveEntry> {
+    vec![
+        entry(0, "设计", "folder"),
+        entry(1, "接口", "docx"),
+        entry(1, "归档", "folder"),
+        entry(2, "旧数据", "sheet"),
+        entry(0, "周报", "folder"),
+        entry(1, "第一周", "docx"),
+        entry(0, "预算", "sheet"),
+    ]
+}
+
+// 测试渲染目录树：最后一项使用 └──，祖先不是最后一项时画竖线
+#[test]
+fn test_render_drive_tree() {
+    let expected = "root\n\
+├── 设计/  [folder] tok_设计\n\
+│   ├── 接口  [docx] tok_接口\n\
+│   └── 归档/  [folder] tok_归档\n\
+│       └── 旧数据  [sheet] tok_旧数据\n\
+├── 周报/  [folder] tok_周报\n\
+│   └── 第一周  [docx] tok_第一周\n\
+└── 预算  [sheet] tok_预算\n";
+    assert_eq!(render_drive_tree("root", &tree()), expected);
+}
+
+// 测试按类型过滤时保留包含匹配项的文件夹，去掉空文件夹
+#[test]
+fn test_filter_entries() {
+    let names = |entries: Vec<DriveEntry>| entries.into_iter().map(|e| e.file.name).collect::<Vec<_>>();
+
+    assert_eq!(names(filter_entries(tree(), &["sheet".to_string()])), ["设计", "归档", "旧数据", "预算"]);
+    assert_eq!(names(filter_entries(tree(), &["docx".to_string()])), ["设计", "接口", "周报", "第一周"]);
+    assert_eq!(filter_entries(tree(), &[]).len(), 7);
+}
@@ -0,0 +1,84 @@
+use lark_cli::utils::{render_placeholders, split_front_matter, toc_markdown, FrontMatterCallout};
+use std::collections::BTreeMap;
+
+#[test]
+fn test_split_front_matter() {
+    // 测试解析并去掉 Front Matter，识别字段和未识别字段分开保存
+    let content = "---\n\
+                   title: 设计评审\n\
+                   owners: [alice, bob]\n\
+                   audience: internal\n\
+                   toc: true\n\
+                   collaborators:\n  - alice@example.com\n  - member_id: ou_123\n    member_type: openid\n    perm: edit\n\
+                   callout:\n  content: 草稿\n  type: warning\n\
+                   ---\n\n# 背景\n";
+    let (front_matter, body) = split_front_matter(content).unwrap();
+    let front_matter = front_matter.unwrap();
+
+    assert_eq!(body, "# 背景\n");
+    assert_eq!(front_matter.title.as_deref(), Some("设计评审"));
+    assert!(front_matter.toc);
+    assert_eq!(front_matter.collaborators[0].member(), ("email", "alice@example.com", "view"));
+    assert_eq!(front_matter.collaborators[1].member(), ("openid", "ou_123", "edit"));
+    assert!(matches!(
+        front_matter.callout,
+        Some(FrontMatterCallout::Detailed { ref callout_type, .. }) if callout_type.as_deref() == Some("warning")
+    ));
+    assert!(front_matter.extra.contains_key("owners"));
+    assert!(front_matter.extra.contains_key("audience"));
+    assert!(!front_matter.extra.contains_key("title"));
+}
+
+#[test]
+fn test_split_without_front_matter() {
+    // 测试没有 Front Matter 或缺少结束标记时内容保持不变
+    let (front_matter, body) = split_front_matter("# Title\n---\ntext").unwrap();
+    assert!(front_matter.is_none());
+    assert_eq!(body, "# Title\n---\ntext");
+
+    let (front_matter, body) = split_front_matter("---\ntitle: x\n").unwrap();
+    assert!(front_matter.is_none());
+    assert_eq!(body, "---\ntitle: x\n");
+
+    // 分隔符之间不是 YAML 映射时不视为 Front Matter，字段类型错误时报错
+    let (front_matter, body) = split_front_matter("---\ntitle: [unclosed\n---\n").unwrap();
+    assert!(front_matter.is_none());
+    assert_eq!(body, "---\ntitle: [unclosed\n---\n");
+    assert!(split_front_matter("---\ntitle: [a, b]\n---\n").is_err());
+}
+
+#[test]
+fn test_split_document_starting_with_horizontal_rule() {
+    // 测试以分隔线开头、后面还有分隔线的普通文档保持不变
+    let content = "---\n\n# 第一部分\n\n正文：包含冒号的句子\n\n---\n\n# 第二部分\n";
+    let (front_matter, body) = split_front_matter(content).unwrap();
+    assert!(front_matter.is_none());
+    assert_eq!(body, content);
+
+    let content = "---\n- 列表项\n---\n";
+    let (front_matter, body) = split_front_matter(content).unwrap();
+    assert!(front_matter.is_none());
+    assert_eq!(body, content);
+}
+
+#[test]
+fn test_render_body_with_variables_and_toc() {
+    // 测试未识别字段可在正文中引用，并按需生成目录
+    let content = "---\ntitle: 周报\nowners: [alice, bob]\nversion: 2\ntoc: true\n---\n\
+                   # {{title}}\n\n负责人: {{ owners }}，版本 {{version}}，{{unknown}}\n\n## 进展\n\n```\n# 注释\n```\n";
+    let (front_matter, body) = split_front_matter(content).unwrap();
+    let rendered = front_matter.unwrap().render_body(&body);
+
+    assert_eq!(
+        rendered,
+        "- 周报\n  - 进展\n\n# 周报\n\n负责人: alice, bob，版本 2，{{unknown}}\n\n## 进展\n\n```\n# 注释\n```\n"
+    );
+}
+
+#[test]
+fn test_render_placeholders_and_toc_edge_cases() {
+    // 测试未闭合的占位符和没有标题的内容
+    let variables = BTreeMap::from([("a".to_string(), "1".to_string())]);
+    assert_eq!(render_placeholders("{{a}} {{a", &variables), "1 {{a");
+    assert_eq!(toc_markdown("plain text\n#hashtag"), "");
+}
@@ -0,0 +1,92 @@
+use lark_cli::utils::{content_hash, ImportManifest, ManifestStatus};
+use std::path::Path;
+use tempfile::TempDir;
+
+#[test]
+fn test_content_hash_stable() {
+    // 测试内容哈希稳定且区分内容
+    assert_eq!(content_hash("# Title"), content_hash("# Title"));
+    assert_ne!(content_hash("# Title"), content_hash("# Title 2"));
+    assert_eq!(content_hash("").len(), 64);
+}
+
+#[test]
+fn test_load_or_create_new_manifest() {
+    // 测试清单文件不存在时创建空清单
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join(".lark-import.json");
+
+    let manifest = ImportManifest::load_or_create(&path, "doc123").unwrap();
+    assert_eq!(manifest.document_id, "doc123");
+    assert!(manifest.files.is_empty());
+    assert!(!path.exists());
+}
+
+#[test]
+fn test_manifest_status() {
+    // 测试新增、未变化、已变化三种状态
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join(".lark-import.json");
+    let mut manifest = ImportManifest::load_or_create(&path, "doc123").unwrap();
+
+    let hash = content_hash("# A");
+    assert_eq!(manifest.status("a.md", &hash), ManifestStatus::New);
+
+    manifest.record("a.md", Path::new("docs/a.md"), hash.clone(), vec!["blk1".to_string()], 7);
+    assert_eq!(manifest.status("a.md", &hash), ManifestStatus::Unchanged);
+
+    match manifest.status("a.md", &content_hash("# A changed")) {
+        ManifestStatus::Changed(entry) => {
+            assert_eq!(entry.root_block_ids, vec!["blk1".to_string()]);
+            assert_eq!(entry.document_revision_id, 7);
+        }
+        other => panic!("unexpected status: {:?}", other),
+    }
+}
+
+#[test]
+fn test_manifest_save_and_reload() {
+    // 测试保存后重新加载
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("state").join("manifest.json");
+    let mut manifest = ImportManifest::load_or_create(&path, "doc123").unwrap();
+    manifest.record(
+        "guide/intro.md",
+        Path::new("docs/guide/intro.md"),
+        content_hash("# Intro"),
+        vec!["blk1".to_string(), "blk2".to_string()],
+        12,
+    );
+    manifest.save().unwrap();
+
+    assert!(path.exists());
+    assert!(!path.with_extension("json.tmp").exists());
+
+    let reloaded = ImportManifest::load_or_create(&path, "doc123").unwrap();
+    let entry = reloaded.files.get("guide/intro.md").unwrap();
+    assert_eq!(entry.source_path, "docs/guide/intro.md");
+    assert_eq!(entry.root_block_ids.len(), 2);
+    assert_eq!(entry.document_revision_id, 12);
+    assert_eq!(reloaded.path(), path.as_path());
+}
+
+#[test]
+fn test_manifest_document_mismatch() {
+    // 测试清单属于其他文档时报错
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join(".lark-import.json");
+    ImportManifest::load_or_create(&path, "doc123").unwrap().save().unwrap();
+
+    let result = ImportManifest::load_or_create(&path, "doc456");
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_manifest_invalid_json() {
+    // 测试清单内容损坏时报错
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join(".lark-import.json");
+    std::fs::write(&path, "not json").unwrap();
+
+    assert!(ImportManifest::load_or_create(&path, "doc123").is_err());
+}
@@ -0,0 +1,25 @@
+use lark_cli::utils::{document_from_url, lark_url_segments, DocumentRef};
+
+// 测试从飞书链接中解析文档，忽略锚点，拒绝伪造的域名
+#[test]
+fn test_document_from_url() {
+    assert_eq!(
+        document_from_url("https://abc.larksuite.com/docx/doxcnC#heading"),
+        Some(DocumentRef::Docx("doxcnC".to_string()))
+    );
+    assert_eq!(
+        document_from_url("https://example.feishu.cn/wiki/wikcnB?from=space"),
+        Some(DocumentRef::Wiki("wikcnB".to_string()))
+    );
+    assert_eq!(document_from_url("https://example.feishu.cn/sheets/shtcnA"), None);
+    assert_eq!(document_from_url("https://feishu.cn.evil.com/docx/x"), None);
+}
+
+// 测试飞书链接的路径段
+#[test]
+fn test_lark_url_segments() {
+    let segments: Vec<&str> = lark_url_segments("https://Example.Feishu.cn/drive/folder/fldA/?x=1").unwrap().collect();
+    assert_eq!(segments, vec!["drive", "folder", "fldA"]);
+    assert!(lark_url_segments("https://example.com/docx/x").is_none());
+    assert!(lark_url_segments("doxcnA").is_none());
+}
@@ -0,0 +1,55 @@
+use lark_cli::utils::{detect_mime_type, image_dimensions, is_image_mime_type};
+use std::path::Path;
+
+fn png(width: u32, height: u32) -> Vec<u8> {
+    let mut data = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR".to_vec();
+    data.extend_from_slice(&width.to_be_bytes());
+    data.extend_from_slice(&height.to_be_bytes());
+    data.extend_from_slice(&[8, 6, 0, 0, 0]);
+    data
+}
+
+// 测试按文件头识别类型，无法识别时按扩展名判断
+#[test]
+fn test_detect_mime_type() {
+    assert_eq!(detect_mime_type(Path::new("shot.dat"), &png(1, 1)), "image/png");
+    assert_eq!(detect_mime_type(Path::new("a.pdf"), b"%PDF-1.7"), "application/pdf");
+    assert_eq!(detect_mime_type(Path::new("photo.JPG"), b"not really"), "image/jpeg");
+    assert_eq!(detect_mime_type(Path::new("notes.txt"), b"hello"), "text/plain");
+    assert_eq!(detect_mime_type(Path::new("archive.bin"), b"\x00\x01"), "application/octet-stream");
+
+    assert!(is_image_mime_type("image/webp"));
+    assert!(!is_image_mime_type("image/svg+xml"));
+    assert!(!is_image_mime_type("application/pdf"));
+}
+
+// 测试从 PNG、GIF、BMP、JPEG、WebP 文件头读取宽高
+#[test]
+fn test_image_dimensions() {
+    assert_eq!(image_dimensions(&png(1280, 720)), Some((1280, 720)));
+
+    let gif = [b"GIF89a".as_slice(), &[0x40, 0x01, 0xF0, 0x00]].concat();
+    assert_eq!(image_dimensions(&gif), Some((320, 240)));
+
+    let mut bmp = vec![0u8; 26];
+    bmp[0..2].copy_from_slice(b"BM");
+    bmp[18..22].copy_from_slice(&640i32.to_le_bytes());
+    bmp[22..26].copy_from_slice(&(-480i32).to_le_bytes());
+    assert_eq!(image_dimensions(&bmp), Some((640, 480)));
+
+    // SOI、APP0 段，然后是 SOF0 段：精度 8，高 600，宽 800
+    let jpeg = [
+        &[0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00][..],
+        &[0xFF, 0xC0, 0x00, 0x11, 0x08, 0x02, 0x58, 0x03, 0x20, 0x03],
+    ]
+    .concat();
+    assert_eq!(image_dimensions(&jpeg), Some((800, 600)));
+
+    let mut webp = b"RIFF\x00\x00\x00\x00WEBPVP8X".to_vec();
+    webp.extend_from_slice(&[0u8; 8]);
+    webp.extend_from_slice(&[0x3F, 0x01, 0x00, 0xC7, 0x00, 0x00]);
+    assert_eq!(image_dimensions(&webp), Some((320, 200)));
+
+    assert_eq!(image_dimensions(b"%PDF-1.7"), None);
+    assert_eq!(image_dimensions(&png(1, 1)[..12]), None);
+}
@@ -0,0 +1,85 @@
+use lark_cli::api::DescendantBlock;
+use lark_cli::utils::{apply_mentions, mark_mentions, mention_emails, MentionTarget};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+fn text_block(id: &str, elements: Value) -> DescendantBlock {
+    DescendantBlock {
+        block_id: id.to_string(),
+        block_type: 2,
+        children: Some(vec![]),
+        text: Some(json!({ "elements": elements })),
+        ..Default::default()
+    }
+}
+
+// 测试识别邮箱和 open_id 两种提及，并跳过代码块和行内代码
+#[test]
+fn test_mark_mentions() {
+    let markdown = "请 @alice@corp.com 和 @[Bob](ou_123) 评审，邮件 x@alice@corp.com 不算。\n\
+                    `@carol@corp.com`\n\
+                    ```\n@dave@corp.com\n```\n";
+    let (marked, markers) = mark_mentions(markdown);
+
+    assert_eq!(markers.len(), 2);
+    assert_eq!(markers[0].source, "@alice@corp.com");
+    assert_eq!(markers[0].target, MentionTarget::Email("alice@corp.com".to_string()));
+    assert_eq!(markers[1].source, "@[Bob](ou_123)");
+    assert_eq!(markers[1].target, MentionTarget::OpenId("ou_123".to_string()));
+    assert!(marked.contains(&markers[0].placeholder));
+    assert!(!marked.contains("@[Bob]"));
+    assert!(marked.contains("x@alice@corp.com"));
+    assert!(marked.contains("`@carol@corp.com`"));
+    assert!(marked.contains("\n@dave@corp.com\n"));
+    assert_eq!(mention_emails(&markers), vec!["alice@corp.com".to_string()]);
+}
+
+// 测试占位符替换为 mention_user，无法解析的邮箱保留原文并给出警告
+#[test]
+fn test_apply_mentions() {
+    let (marked, markers) = mark_mentions("@alice@corp.com 和 @bob@corp.com 以及 @[Carol](ou_c)");
+    let style = json!({ "bold": true });
+    let mut blocks = vec![text_block(
+        "t1",
+        json!([{ "text_run": { "content": marked, "text_element_style": style } }]),
+    )];
+    let open_ids = HashMap::from([("alice@corp.com".to_string(), "ou_a".to_string())]);
+
+    let warnings = apply_mentions(&mut blocks, &markers, &open_ids);
+
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].mention, "@bob@corp.com");
+    let elements = &blocks[0].text.as_ref().unwrap()["elements"];
+    assert_eq!(
+        elements,
+        &json!([
+            { "mention_user": { "user_id": "ou_a" } },
+            { "text_run": { "content": " 和 @bob@corp.com 以及 ", "text_element_style": style } },
+            { "mention_user": { "user_id": "ou_c" } },
+        ])
+    );
+}
+
+// 测试指向飞书文档的链接转换为 mention_doc
+#[test]
+fn test_apply_document_links() {
+    let url = "https%3A%2F%2Fcorp.feishu.cn%2Fwiki%2FWikiToken1";
+    let mut blocks = vec![text_block(
+        "t1",
+        json!([
+            { "text_run": { "content": "见 " } },
+            { "text_run": { "content": "设计文档", "text_element_style": { "link": { "url": url } } } },
+            { "text_run": { "content": "外链", "text_element_style": { "link": { "url": "https%3A%2F%2Fexample.com" } } } },
+        ]),
+    )];
+
+    let warnings = apply_mentions(&mut blocks, &[], &HashMap::new());
+
+    assert!(warnings.is_empty());
+    let elements = &blocks[0].text.as_ref().unwrap()["elements"];
+    assert_eq!(
+        elements[1],
+        json!({ "mention_doc": { "token": "WikiToken1", "obj_type": 16, "url": url } })
+    );
+    assert_eq!(elements[2]["text_run"]["content"], "外链");
+}
@@ -1 +1,71 @@
-pub mod file_utils_test;
\ No newline at end of file
+pub mod file_utils_test;
+pub mod import_manifest_test;
+pub mod block_tree_test;
+pub mod doc_hierarchy_test;
+pub mod front_matter_test;
+pub mod diagram_fences_test;
+pub mod block_diff_test;
+pub mod project_manifest_test;
+pub mod block_markdown_test;
+pub mod text_diff_test;
+pub mod revision_diff_test;
+pub mod text_replace_test;
+pub mod doc_sections_test;
+pub mod template_test;
+pub mod table_data_test;
+pub mod block_selector_test;
+pub mod lark_url_test;
+pub mod doc_lint_test;
+pub mod doc_toc_test;
+pub mod mentions_test;
+pub mod media_info_test;
+pub mod comment_export_test;
+pub mod drive_ref_test;
+pub mod drive_tree_test;
+pub mod upload_state_test;
+pub mod byte_range_test;
+pub mod download_state_test;
+pub mod sequence_diff_test;
+
+use lark_cli::api::get_blocks::BlockItem;
+use lark_cli::utils::BlockTree;
+use serde_json::{json, Value};
+
+/// 测试用的文档块，`children` 为空时不设置子块列表
+pub fn block(id: &str, block_type: i32, parent_id: &str, children: &[&str], content: Value) -> BlockItem {
+    BlockItem {
+        block_id: id.to_string(),
+        block_type,
+        parent_id: parent_id.to_string(),
+        children: (!children.is_empty()).then(|| children.iter().map(|c| c.to_string()).collect()),
+        content,
+    }
+}
+
+/// 块内容 `{ key: { "elements": elements } }`
+pub fn elements(key: &str, elements: Value) -> Value {
+    json!({ key: { "elements": elements } })
+}
+
+/// 只有一段纯文本的块内容，如 `{ "heading2": { "elements": [{ "text_run": ... }] } }`
+pub fn text_content(key: &str, text: &str) -> Value {
+    elements(key, json!([{ "text_run": { "content": text } }]))
+}
+
+/// 文本块
+pub fn text(id: &str, parent_id: &str, text: &str) -> BlockItem {
+    block(id, 2, parent_id, &[], text_content("text", text))
+}
+
+/// 标题块，`level` 为 1-9
+pub fn heading(id: &str, parent_id: &str, level: i32, text: &str) -> BlockItem {
+    block(id, level + 2, parent_id, &[], text_content(&format!("heading{}", level), text))
+}
+
+/// 根块 `root_id` 下依次排列 `blocks` 的文档，`content` 为根块内容
+pub fn flat_document(root_id: &str, content: Value, blocks: Vec<BlockItem>) -> BlockTree {
+    let children: Vec<&str> = blocks.iter().map(|b| b.block_id.as_str()).collect();
+    let mut all = vec![block(root_id, 1, "", &children, content)];
+    all.extend(blocks);
+    BlockTree::new(all)
+}
@@ -0,0 +1,86 @@
+use lark_cli::utils::{DriftStatus, LockEntry, ProjectLock, ProjectManifest};
+use std::fs;
+use tempfile::TempDir;
+
+fn entry(document_id: &str, content_hash: &str, remote_hash: &str) -> LockEntry {
+    LockEntry {
+        document_id: document_id.to_string(),
+        content_hash: content_hash.to_string(),
+        remote_hash: remote_hash.to_string(),
+        document_revision_id: 3,
+        published_at: "2024-01-01T00:00:00Z".to_string(),
+    }
+}
+
+// 测试解析项目清单，路径相对于清单所在目录
+#[test]
+fn test_load_manifest() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join(".lark.toml");
+    fs::write(
+        &path,
+        r#"
+lockfile = "state/lark.lock"
+
+[[documents]]
+path = "./docs/design.md"
+document_id = "doxcn1"
+
+[[documents]]
+path = "docs/guide.md"
+wiki_node = "wikcn1"
+"#,
+    )
+    .unwrap();
+
+    let manifest = ProjectManifest::load(&path).unwrap();
+    assert_eq!(manifest.documents.len(), 2);
+    assert_eq!(manifest.documents[0].key(), "docs/design.md");
+    assert_eq!(manifest.documents[1].wiki_node.as_deref(), Some("wikcn1"));
+    assert_eq!(manifest.file_path(&manifest.documents[1]), temp_dir.path().join("docs/guide.md"));
+    assert_eq!(manifest.lock_path(), temp_dir.path().join("state/lark.lock"));
+}
+
+// 测试每个映射必须且只能指定一个目标
+#[test]
+fn test_manifest_requires_single_target() {
+    let both = "[[documents]]\npath = \"a.md\"\ndocument_id = \"d\"\nwiki_node = \"w\"\n";
+    assert!(ProjectManifest::parse(both).is_err());
+
+    let neither = "[[documents]]\npath = \"a.md\"\n";
+    assert!(ProjectManifest::parse(neither).is_err());
+
+    let duplicate = "[[documents]]\npath = \"a.md\"\ndocument_id = \"d1\"\n[[documents]]\npath = \"./a.md\"\ndocument_id = \"d2\"\n";
+    assert!(ProjectManifest::parse(duplicate).is_err());
+}
+
+// 测试根据锁文件判断一致性状态
+#[test]
+fn test_drift_status() {
+    let locked = entry("doc", "local1", "remote1");
+
+    assert_eq!(DriftStatus::detect(None, "doc", "local1", "remote1"), DriftStatus::NotPublished);
+    assert_eq!(DriftStatus::detect(Some(&locked), "other", "local1", "remote1"), DriftStatus::NotPublished);
+    assert_eq!(DriftStatus::detect(Some(&locked), "doc", "local1", "remote1"), DriftStatus::InSync);
+    assert_eq!(DriftStatus::detect(Some(&locked), "doc", "local2", "remote1"), DriftStatus::LocalChanged);
+    assert_eq!(DriftStatus::detect(Some(&locked), "doc", "local1", "remote2"), DriftStatus::RemoteChanged);
+    assert_eq!(DriftStatus::detect(Some(&locked), "doc", "local2", "remote2"), DriftStatus::BothChanged);
+}
+
+// 测试锁文件保存后可以重新加载
+#[test]
+fn test_lock_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join(".lark.lock");
+
+    let mut lock = ProjectLock::load_or_default(&path).unwrap();
+    assert!(lock.files.is_empty());
+    lock.record("docs/a.md", "doc", "local".to_string(), "remote".to_string(), 7);
+    lock.save().unwrap();
+
+    let loaded = ProjectLock::load_or_default(&path).unwrap();
+    let saved = &loaded.files["docs/a.md"];
+    assert_eq!(saved.document_id, "doc");
+    assert_eq!(saved.remote_hash, "remote");
+    assert_eq!(saved.document_revision_id, 7);
+}
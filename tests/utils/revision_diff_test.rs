@@ -0,0 +1,73 @@
+use super::{block, text, text_content};
+use lark_cli::utils::{diff_revisions, BlockTree};
+
+// 测试新增、删除和修改的块
+#[test]
+fn test_added_removed_and_modified() {
+    let from = BlockTree::new(vec![
+        block("doc", 1, "", &["a", "b"], text_content("page", "标题")),
+        text("a", "doc", "保留"),
+        text("b", "doc", "删除"),
+    ]);
+    let to = BlockTree::new(vec![
+        block("doc", 1, "", &["a", "c"], text_content("page", "标题")),
+        text("a", "doc", "保留并修改"),
+        text("c", "doc", "新增"),
+    ]);
+
+    let diff = diff_revisions(&from, &to, "doc", 1, 2);
+    assert_eq!(diff.added.len(), 1);
+    assert_eq!(diff.added[0].block_id, "c");
+    assert_eq!(diff.added[0].index, 1);
+    assert_eq!(diff.removed.len(), 1);
+    assert_eq!(diff.removed[0].text, "删除");
+    assert_eq!(diff.modified.len(), 1);
+    assert_eq!(diff.modified[0].before, "保留");
+    assert_eq!(diff.modified[0].after, "保留并修改");
+    assert!(diff.moved.is_empty());
+}
+
+// 测试调整顺序时只有离开原相对位置的块记为移动
+#[test]
+fn test_reordered_block_is_moved() {
+    let from = BlockTree::new(vec![
+        block("doc", 1, "", &["a", "b", "c"], text_content("page", "标题")),
+        text("a", "doc", "一"),
+        text("b", "doc", "二"),
+        text("c", "doc", "三"),
+    ]);
+    let to = BlockTree::new(vec![
+        block("doc", 1, "", &["c", "a", "b"], text_content("page", "标题")),
+        text("c", "doc", "三"),
+        text("a", "doc", "一"),
+        text("b", "doc", "二"),
+    ]);
+
+    let diff = diff_revisions(&from, &to, "doc", 1, 2);
+    assert_eq!(diff.moved.len(), 1);
+    assert_eq!(diff.moved[0].block_id, "c");
+    assert_eq!(diff.moved[0].from_index, 2);
+    assert_eq!(diff.moved[0].to_index, 0);
+    assert!(diff.added.is_empty() && diff.removed.is_empty() && diff.modified.is_empty());
+}
+
+// 测试改变父块的块记为移动
+#[test]
+fn test_reparented_block_is_moved() {
+    let from = BlockTree::new(vec![
+        block("doc", 1, "", &["a", "b"], text_content("page", "标题")),
+        text("a", "doc", "父"),
+        text("b", "doc", "子"),
+    ]);
+    let to = BlockTree::new(vec![
+        block("doc", 1, "", &["a"], text_content("page", "标题")),
+        block("a", 2, "doc", &["b"], text_content("text", "父")),
+        text("b", "a", "子"),
+    ]);
+
+    let diff = diff_revisions(&from, &to, "doc", 1, 2);
+    assert_eq!(diff.moved.len(), 1);
+    assert_eq!(diff.moved[0].from_parent_id, "doc");
+    assert_eq!(diff.moved[0].to_parent_id, "a");
+    assert!(diff.modified.is_empty());
+}
@@ -0,0 +1,63 @@
+use lark_cli::utils::{diff_ops, matching_pairs, DiffOp};
+
+// 用动态规划求最长公共子序列长度，作为对照
+fn lcs_len(a: &[u8], b: &[u8]) -> usize {
+    let mut table = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            table[i][j] = if a[i] == b[j] { table[i + 1][j + 1] + 1 } else { table[i + 1][j].max(table[i][j + 1]) };
+        }
+    }
+    table[0][0]
+}
+
+// 确定性的伪随机序列，字母表较小以产生大量重复项
+fn sequence(seed: &mut u64, len: usize) -> Vec<u8> {
+    (0..len)
+        .map(|_| {
+            *seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            b"abcd"[(*seed >> 33) as usize % 4]
+        })
+        .collect()
+}
+
+// 测试配对结果是最长公共子序列：下标递增、对应项相同且长度最大
+#[test]
+fn test_matching_pairs_is_longest_common_subsequence() {
+    let mut seed = 7;
+    for round in 0..200 {
+        let a = sequence(&mut seed, round % 23);
+        let b = sequence(&mut seed, (round * 7) % 19);
+        let pairs = matching_pairs(&a, &b);
+
+        assert_eq!(pairs.len(), lcs_len(&a, &b), "{:?} {:?}", a, b);
+        assert!(pairs.iter().all(|&(i, j)| a[i] == b[j]));
+        assert!(pairs.windows(2).all(|w| w[0].0 < w[1].0 && w[0].1 < w[1].1));
+    }
+}
+
+// 测试编辑操作按顺序覆盖两个序列，且同一段修改中删除排在插入之前
+#[test]
+fn test_diff_ops_orders_deletes_before_inserts() {
+    let ops = diff_ops(&["a", "b", "c"], &["a", "x", "c", "d"]);
+    assert_eq!(
+        ops,
+        vec![DiffOp::Equal(0, 0), DiffOp::Delete(1), DiffOp::Insert(1), DiffOp::Equal(2, 2), DiffOp::Insert(3)]
+    );
+}
+
+// 测试没有公共项和空序列
+#[test]
+fn test_diff_ops_without_common_items() {
+    assert_eq!(diff_ops(&[1, 2], &[3]), vec![DiffOp::Delete(0), DiffOp::Delete(1), DiffOp::Insert(0)]);
+    assert_eq!(diff_ops::<i32>(&[], &[]), Vec::new());
+    assert!(matching_pairs(&[1, 2], &[3, 4]).is_empty());
+}
+
+// 测试大序列只需线性内存即可完成比较
+#[test]
+fn test_matching_pairs_on_large_input() {
+    let a: Vec<usize> = (0..20_000).collect();
+    let b: Vec<usize> = (0..20_000).filter(|i| i % 100 != 0).chain(20_000..20_050).collect();
+    assert_eq!(matching_pairs(&a, &b).len(), 19_800);
+}
@@ -0,0 +1,88 @@
+use super::{block, text};
+use lark_cli::utils::{
+    format_table, parse_csv, table_cell_id, table_rows, to_csv, to_markdown, BlockTree, TableFormat,
+};
+use serde_json::json;
+
+fn rows(data: &[&[&str]]) -> Vec<Vec<String>> {
+    data.iter().map(|row| row.iter().map(|s| s.to_string()).collect()).collect()
+}
+
+/// 2 行 2 列的表格，右下角单元格包含两个文本块
+fn table_tree() -> BlockTree {
+    BlockTree::new(vec![
+        block("doc", 1, "", &["table"], json!({})),
+        block(
+            "table",
+            31,
+            "doc",
+            &["c00", "c01", "c10", "c11"],
+            json!({ "table": {
+                "cells": ["c00", "c01", "c10", "c11"],
+                "property": { "row_size": 2, "column_size": 2 }
+            } }),
+        ),
+        block("c00", 32, "table", &["t00"], json!({ "table_cell": {} })),
+        block("c01", 32, "table", &["t01"], json!({ "table_cell": {} })),
+        block("c10", 32, "table", &["t10"], json!({ "table_cell": {} })),
+        block("c11", 32, "table", &["t11a", "t11b"], json!({ "table_cell": {} })),
+        text("t00", "c00", "服务"),
+        text("t01", "c01", "可用性"),
+        text("t10", "c10", "payment"),
+        text("t11a", "c11", "99.9%"),
+        text("t11b", "c11", "达标"),
+    ])
+}
+
+// 测试解析 CSV：引号内的逗号、换行和转义引号，忽略空行和 BOM
+#[test]
+fn test_parse_csv() {
+    let parsed = parse_csv("\u{feff}name,note\r\n\"a,b\",\"say \"\"hi\"\"\"\n\nc,\"line1\nline2\"\n").unwrap();
+    assert_eq!(
+        parsed,
+        rows(&[&["name", "note"], &["a,b", "say \"hi\""], &["c", "line1\nline2"]])
+    );
+
+    assert_eq!(parse_csv("a,,b").unwrap(), rows(&[&["a", "", "b"]]));
+    assert!(parse_csv("\"unterminated").is_err());
+}
+
+// 测试输出 CSV 和 Markdown 时的转义
+#[test]
+fn test_format_csv_and_markdown() {
+    let data = rows(&[&["name", "note"], &["a,b", "x|y"], &["c"]]);
+    assert_eq!(to_csv(&data), "name,note\n\"a,b\",x|y\nc\n");
+    assert_eq!(
+        to_markdown(&data),
+        "| name | note |\n| --- | --- |\n| a,b | x\\|y |\n| c |  |\n"
+    );
+}
+
+// 测试 JSON 格式：有表头时输出对象数组，否则输出二维数组
+#[test]
+fn test_format_json() {
+    let data = rows(&[&["service", "uptime"], &["payment", "99.9%"]]);
+    let objects: serde_json::Value =
+        serde_json::from_str(&format_table(&data, TableFormat::Json, true).unwrap()).unwrap();
+    assert_eq!(objects, json!([{ "service": "payment", "uptime": "99.9%" }]));
+
+    let arrays: serde_json::Value =
+        serde_json::from_str(&format_table(&data, TableFormat::Json, false).unwrap()).unwrap();
+    assert_eq!(arrays, json!([["service", "uptime"], ["payment", "99.9%"]]));
+
+    assert!("xlsx".parse::<TableFormat>().is_err());
+}
+
+// 测试从块树读取表格内容和定位单元格
+#[test]
+fn test_table_rows_and_cells() {
+    let tree = table_tree();
+    assert_eq!(
+        table_rows(&tree, "table").unwrap(),
+        rows(&[&["服务", "可用性"], &["payment", "99.9%\n达标"]])
+    );
+
+    assert_eq!(table_cell_id(&tree, "table", 1, 0).unwrap(), "c10");
+    assert!(table_cell_id(&tree, "table", 2, 0).is_err());
+    assert!(table_rows(&tree, "t00").is_err());
+}
@@ -0,0 +1,56 @@
+use lark_cli::utils::{loop_start, parse_var_assignments, render_template, TemplateVariables};
+use serde_json::json;
+
+fn vars(value: serde_json::Value) -> TemplateVariables {
+    serde_json::from_value(value).unwrap()
+}
+
+// 测试解析命令行变量，值中可以包含等号
+#[test]
+fn test_parse_var_assignments() {
+    let parsed = parse_var_assignments(&["service=payment".to_string(), "query=a=b".to_string()]).unwrap();
+    assert_eq!(parsed["service"], json!("payment"));
+    assert_eq!(parsed["query"], json!("a=b"));
+
+    assert!(parse_var_assignments(&["missing".to_string()]).is_err());
+    assert!(parse_var_assignments(&["=value".to_string()]).is_err());
+}
+
+// 测试替换占位符，未定义的占位符保持不变
+#[test]
+fn test_render_placeholders() {
+    let variables = vars(json!({ "service": "payment", "severity": 2 }));
+    let rendered = render_template("# {{service}} 故障 (P{{severity}})\n负责人: {{owner}}", &variables).unwrap();
+    assert_eq!(rendered, "# payment 故障 (P2)\n负责人: {{owner}}");
+}
+
+// 测试展开标量列表和对象列表的循环，标记行不保留
+#[test]
+fn test_render_loops() {
+    let variables = vars(json!({
+        "owners": ["alice", "bob"],
+        "actions": [
+            { "task": "回滚", "owner": "alice" },
+            { "task": "复盘", "owner": "bob" }
+        ]
+    }));
+
+    let text = "负责人:\n{{#each owners}}\n- {{this}}\n{{/each}}\n行动项:\n{{#each actions}}\n- [ ] {{this.task}} @{{this.owner}}\n{{/each}}\n";
+    let rendered = render_template(text, &variables).unwrap();
+    assert_eq!(
+        rendered,
+        "负责人:\n- alice\n- bob\n行动项:\n- [ ] 回滚 @alice\n- [ ] 复盘 @bob\n"
+    );
+}
+
+// 测试循环缺少结束标记或循环变量不是列表时报错
+#[test]
+fn test_render_loop_errors() {
+    let variables = vars(json!({ "owners": ["alice"], "service": "payment" }));
+    assert!(render_template("{{#each owners}}\n- {{this}}\n", &variables).is_err());
+    assert!(render_template("{{#each service}}\n{{/each}}\n", &variables).is_err());
+    assert!(render_template("{{#each unknown}}\n{{/each}}\n", &variables).is_err());
+
+    assert_eq!(loop_start("  {{#each owners}} "), Some("owners"));
+    assert_eq!(loop_start("{{#each}}"), None);
+}
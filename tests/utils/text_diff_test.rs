@@ -0,0 +1,27 @@
+use lark_cli::utils::unified_diff;
+
+// 测试内容相同时没有差异
+#[test]
+fn test_identical_text_has_no_diff() {
+    assert_eq!(unified_diff("a\nb\n", "a\nb\n", "old", "new", 3), "");
+}
+
+// 测试修改一行时输出带上下文的统一格式差异
+#[test]
+fn test_single_line_change() {
+    let old = "a\nb\nc\nd\ne\n";
+    let new = "a\nb\nC\nd\ne\n";
+
+    let diff = unified_diff(old, new, "doc@1", "doc@2", 1);
+    assert_eq!(diff, "--- doc@1\n+++ doc@2\n@@ -2,3 +2,3 @@\n b\n-c\n+C\n d\n");
+}
+
+// 测试相距较远的修改拆分为多段
+#[test]
+fn test_distant_changes_split_into_hunks() {
+    let old = "1\n2\n3\n4\n5\n6\n7\n8\n";
+    let new = "0\n1\n2\n3\n4\n5\n6\n7\n";
+
+    let diff = unified_diff(old, new, "a", "b", 1);
+    assert_eq!(diff, "--- a\n+++ b\n@@ -1 +1,2 @@\n+0\n 1\n@@ -7,2 +8 @@\n 7\n-8\n");
+}
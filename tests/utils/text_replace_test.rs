@@ -0,0 +1,69 @@
+use lark_cli::utils::{replace_in_elements, TextMatcher};
+use serde_json::json;
+
+// 测试字面量替换保留文字样式和链接
+#[test]
+fn test_literal_replace_preserves_style() {
+    let elements = json!([
+        { "text_run": { "content": "欢迎使用 Acme", "text_element_style": { "bold": true } } },
+        { "text_run": { "content": " 文档", "text_element_style": { "link": { "url": "https%3A%2F%2Facme.com" } } } }
+    ]);
+    let matcher = TextMatcher::new("Acme", false, false).unwrap();
+
+    let (replaced, matches) = replace_in_elements(&elements, &matcher, "Globex").unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].context, "欢迎使用 【Acme】 文档");
+    assert_eq!(
+        replaced,
+        json!([
+            { "text_run": { "content": "欢迎使用 Globex", "text_element_style": { "bold": true } } },
+            { "text_run": { "content": " 文档", "text_element_style": { "link": { "url": "https%3A%2F%2Facme.com" } } } }
+        ])
+    );
+}
+
+// 测试跨越多个样式片段的匹配，替换文本使用匹配开始处的样式
+#[test]
+fn test_match_across_runs() {
+    let elements = json!([
+        { "text_run": { "content": "产品 Ac" } },
+        { "text_run": { "content": "me 发布", "text_element_style": { "italic": true } } }
+    ]);
+    let matcher = TextMatcher::new("acme", false, true).unwrap();
+
+    let (replaced, _) = replace_in_elements(&elements, &matcher, "Globex").unwrap();
+    assert_eq!(
+        replaced,
+        json!([
+            { "text_run": { "content": "产品 Globex" } },
+            { "text_run": { "content": " 发布", "text_element_style": { "italic": true } } }
+        ])
+    );
+}
+
+// 测试提及不参与匹配且保持不变，正则替换展开捕获组
+#[test]
+fn test_regex_replace_keeps_mentions() {
+    let elements = json!([
+        { "text_run": { "content": "v1.0 由 " } },
+        { "mention_user": { "user_id": "ou_123" } },
+        { "text_run": { "content": " 发布，v2.0 计划中" } }
+    ]);
+    let matcher = TextMatcher::new(r"v(\d+)\.0", true, false).unwrap();
+
+    let (replaced, matches) = replace_in_elements(&elements, &matcher, "v$1.1").unwrap();
+    assert_eq!(matches.len(), 2);
+    assert_eq!(replaced[0]["text_run"]["content"], "v1.1 由 ");
+    assert_eq!(replaced[1], json!({ "mention_user": { "user_id": "ou_123" } }));
+    assert_eq!(replaced[2]["text_run"]["content"], " 发布，v2.1 计划中");
+}
+
+// 测试没有匹配时返回 None，无效的正则表达式返回错误
+#[test]
+fn test_no_match_and_invalid_regex() {
+    let elements = json!([{ "text_run": { "content": "无关内容" } }]);
+    let matcher = TextMatcher::new("Acme", false, false).unwrap();
+
+    assert!(replace_in_elements(&elements, &matcher, "Globex").is_none());
+    assert!(TextMatcher::new("(", true, false).is_err());
+}
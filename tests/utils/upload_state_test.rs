@@ -0,0 +1,46 @@
+use lark_cli::utils::{adler32, UploadSource, UploadState};
+use std::fs;
+use tempfile::TempDir;
+
+// 测试 Adler-32 校验和与标准值一致，跨越累加分块边界时仍然正确
+#[test]
+fn test_adler32() {
+    assert_eq!(adler32(b""), 1);
+    assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+    assert_eq!(adler32(b"abc"), 0x024D_0127);
+    assert_eq!(adler32(&vec![0xFF; 100_000]), 0x149A_302C);
+}
+
+// 测试断点记录的保存、加载和续传条件
+#[test]
+fn test_upload_state_resume() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("data.bin");
+    fs::write(&file, vec![7u8; 1024]).unwrap();
+    let source = UploadSource::from_file(&file, "data.bin", "fldTarget").unwrap();
+    assert_eq!(source.size, 1024);
+
+    let state_dir = temp_dir.path().join("uploads");
+    let state_path = UploadState::state_path(&state_dir, &source);
+    assert_eq!(state_path, UploadState::state_path(&state_dir, &source));
+    let other_folder = UploadSource { parent_node: "fldOther".to_string(), ..source.clone() };
+    assert_ne!(state_path, UploadState::state_path(&state_dir, &other_folder));
+
+    let mut state = UploadState::new(source.clone(), "upload-1".to_string(), 256, 4);
+    state.completed.extend([0, 2]);
+    state.save(&state_path).unwrap();
+
+    let loaded = UploadState::load(&state_path).unwrap();
+    assert_eq!(loaded.upload_id, "upload-1");
+    assert_eq!(loaded.pending_parts(), vec![1, 3]);
+
+    let now = loaded.created_at;
+    assert!(loaded.can_resume(&source, now + 60));
+    assert!(!loaded.can_resume(&source, now + 24 * 60 * 60));
+    assert!(!loaded.can_resume(&other_folder, now));
+    let changed = UploadSource { size: 2048, ..source };
+    assert!(!loaded.can_resume(&changed, now));
+
+    fs::write(&state_path, "not json").unwrap();
+    assert!(UploadState::load(&state_path).is_none());
+}